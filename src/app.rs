@@ -4,6 +4,8 @@ use crate::args::AppArgs;
 use crate::audio::music_player::{MusicPlayer, MusicPlayerEvent};
 use crate::network::handler::BehaviourEvent;
 use crate::network::handler::NetworkHandler;
+use crate::network::network_callback::NetworkCallback;
+use crate::network::types::NetworkData;
 use crate::{
     core::*,
     crossterm_event_handler,
@@ -15,8 +17,10 @@ use crate::{
         PopupMessage, {UiScreen, UiState},
     },
 };
+use chrono::{DateTime, Local};
 use libp2p::identity::Keypair;
 use libp2p::swarm::SwarmEvent;
+use libp2p::PeerId;
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
 use ratatui::crossterm;
@@ -25,6 +29,9 @@ use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
+// Autosaves write a snapshot at most this often.
+const SNAPSHOT_INTERVAL: Tick = HOURS;
+
 #[derive(Debug, PartialEq)]
 pub enum AppState {
     Running,
@@ -37,7 +44,12 @@ pub enum AppEvent {
     SlowTick(Tick),
     FastTick(Tick),
     TerminalEvent(TerminalEvent),
-    NetworkEvent(SwarmEvent<BehaviourEvent>),
+    NetworkEvent(Box<SwarmEvent<BehaviourEvent>>),
+    DirectRequestFailed {
+        peer_id: PeerId,
+        network_data: Box<NetworkData>,
+        error: String,
+    },
     #[cfg(feature = "audio")]
     AudioEvent(MusicPlayerEvent),
 }
@@ -55,6 +67,7 @@ pub struct App {
     pub network_handler: NetworkHandler,
     new_version_notified: bool,
     cancellation_token: CancellationToken,
+    last_snapshot: Tick,
 }
 
 impl App {
@@ -148,6 +161,23 @@ impl App {
         }
     }
 
+    // Advance a manual world clock by the given duration, ticking the world and applying
+    // its callbacks as the game loop would do. Used to run headless simulations faster than real time.
+    pub fn fast_forward(&mut self, duration: Tick) -> AppResult<()> {
+        let now = self.world.clock.advance(duration)?;
+        // Unlike is_simulating, we do not allow lagging behind: every tick up to now is handled.
+        while self.world.last_tick_short_interval + TickInterval::SHORT <= now {
+            let callbacks = self.world.handle_slow_tick_events(
+                self.world.last_tick_short_interval + TickInterval::SHORT,
+            )?;
+            for callback in callbacks.iter() {
+                callback.call(self)?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn test_default() -> AppResult<Self> {
         let mut app = App::new(AppArgs::test())?;
         app.new_world();
@@ -178,7 +208,7 @@ impl App {
     pub fn new(args: AppArgs) -> AppResult<Self> {
         // If the reset_world flag is set, reset the world.
         if args.reset_world {
-            reset_store().expect("Failed to reset world");
+            reset_store(args.store_prefix()).expect("Failed to reset world");
         }
 
         let ui = UiScreen::new(args.store_prefix(), args.is_network_disabled());
@@ -204,14 +234,21 @@ impl App {
             }
         };
 
-        let network_handler = NetworkHandler::new(args.seed_node_ip.as_ref())?;
-        let random_seed = args.random_seed;
+        let mut network_handler = NetworkHandler::new(args.seed_node_ip.as_ref())?;
+        if args.use_mdns() {
+            network_handler = network_handler.with_mdns();
+        }
+        if args.trust_relayer_bans() {
+            network_handler = network_handler.with_relayer_bans();
+        }
+        let mut world = World::new(args.random_seed);
+        world.allow_local_draws = args.allow_draws();
 
         Ok(Self {
             args,
             event_sender,
             event_receiver,
-            world: World::new(random_seed),
+            world,
             state: AppState::Running,
             ui,
             #[cfg(feature = "audio")]
@@ -219,6 +256,7 @@ impl App {
             network_handler,
             new_version_notified: false,
             cancellation_token: CancellationToken::new(),
+            last_snapshot: Tick::default(),
         })
     }
 
@@ -333,7 +371,19 @@ impl App {
                     }
 
                     AppEvent::NetworkEvent(swarm_event) => {
-                        self.handle_network_events(swarm_event)?;
+                        self.handle_network_events(*swarm_event)?;
+                    }
+
+                    AppEvent::DirectRequestFailed {
+                        peer_id,
+                        network_data,
+                        error,
+                    } => {
+                        self.handle_network_callback(NetworkCallback::HandleFailedDirectRequest {
+                            peer_id,
+                            network_data,
+                            error,
+                        });
                     }
 
                     #[cfg(feature = "audio")]
@@ -386,11 +436,26 @@ impl App {
 
     pub fn continue_game(&mut self) {
         // Try to load an existing world.
-        match load_world(self.args.store_prefix()) {
-            Ok(mut w) => {
+        match load_world(
+            self.args.store_prefix(),
+            self.args.save_passphrase().as_deref(),
+        ) {
+            Ok((mut w, snapshot)) => {
+                if let Some(timestamp) = snapshot {
+                    let date: DateTime<Local> = timestamp.as_system_time().into();
+                    self.ui.push_popup(PopupMessage::Warning {
+                        message: format!(
+                            "The save file could not be loaded.\nRestored the snapshot saved on {}.",
+                            date.format("%Y-%m-%d %H:%M:%S")
+                        ),
+                        timestamp: Tick::now(),
+                    });
+                }
+
                 w.dirty_network = true;
                 w.dirty_ui = true;
                 self.world = w;
+                self.world.allow_local_draws = self.args.allow_draws();
 
                 if self.args.reset_network_peers {
                     self.world.reset_network_store_peers();
@@ -418,8 +483,8 @@ impl App {
         if own_team.creation_time == Tick::default() {
             let mut creation_time = Tick::now();
             if let Ok(data) = world_file_data(self.args.store_prefix()) {
-                if let Ok(time) = data.created() {
-                    creation_time = Tick::from_system_time(time);
+                if let Some(time) = data.created_at {
+                    creation_time = time;
                 }
             }
             own_team.creation_time = creation_time;
@@ -436,7 +501,8 @@ impl App {
             save_world(
                 &self.world,
                 self.args.store_prefix(),
-                true,
+                self.args.save_passphrase().as_deref(),
+                Some(self.args.snapshot_retention),
                 self.args.store_uncompressed,
             )?;
         }
@@ -550,7 +616,22 @@ impl App {
 
         if self.world.dirty {
             self.world.dirty = false;
-            if let Err(e) = save_world(&self.world, self.args.store_prefix(), false, false) {
+            let now = Tick::now();
+            let snapshot_retention = if now.saturating_sub(self.last_snapshot) >= SNAPSHOT_INTERVAL
+            {
+                self.last_snapshot = now;
+                Some(self.args.snapshot_retention)
+            } else {
+                None
+            };
+
+            if let Err(e) = save_world(
+                &self.world,
+                self.args.store_prefix(),
+                self.args.save_passphrase().as_deref(),
+                snapshot_retention,
+                false,
+            ) {
                 log::error!("Failed to save world: {e}");
             }
             self.world.serialized_size =
@@ -679,23 +760,27 @@ impl App {
 
     fn handle_network_events(&mut self, swarm_event: SwarmEvent<BehaviourEvent>) -> AppResult<()> {
         if let Some(callback) = self.network_handler.handle_network_events(swarm_event) {
-            match callback.call(self) {
-                Ok(Some(message)) => {
-                    self.ui.push_popup(PopupMessage::Ok {
-                        message,
-                        is_skippable: true,
-                        timestamp: Tick::now(),
-                    });
-                }
-                Ok(None) => {}
-                Err(e) => {
-                    self.ui
-                        .push_log_event(Tick::now(), None, e.to_string(), log::Level::Error);
-                }
-            }
+            self.handle_network_callback(callback);
         }
         Ok(())
     }
+
+    fn handle_network_callback(&mut self, callback: NetworkCallback) {
+        match callback.call(self) {
+            Ok(Some(message)) => {
+                self.ui.push_popup(PopupMessage::Ok {
+                    message,
+                    is_skippable: true,
+                    timestamp: Tick::now(),
+                });
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.ui
+                    .push_log_event(Tick::now(), None, e.to_string(), log::Level::Error);
+            }
+        }
+    }
 }
 
 impl Drop for App {
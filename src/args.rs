@@ -1,5 +1,12 @@
 use crate::network::constants::DEFAULT_NETWORK_PORT;
-use clap::{ArgAction, Parser};
+use crate::store::DEFAULT_SNAPSHOT_RETENTION;
+use crate::types::{GameId, TeamId, Tick};
+use clap::{ArgAction, ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+// The save passphrase is read from the environment rather than from the command line,
+// where other users of a shared server could see it.
+pub const SAVE_PASSPHRASE_ENV: &str = "REBELS_SAVE_PASSPHRASE";
 
 #[derive(PartialEq)]
 pub enum AppMode {
@@ -10,6 +17,101 @@ pub enum AppMode {
     Relayer,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Args, Debug, Clone, PartialEq)]
+pub struct SimulateArgs {
+    #[clap(long, short = 'd', action=ArgAction::Set, default_value_t = 7, help = "Number of days to simulate")]
+    pub days: u64,
+    #[clap(long, action=ArgAction::Set, default_value_t = 0, help = "Random seed used to generate the world")]
+    pub seed: u64,
+    #[clap(long, value_enum, default_value_t = ReportFormat::Json, help = "Report format")]
+    pub format: ReportFormat,
+    #[clap(long, short = 'o', action=ArgAction::Set, help = "Write the report to file instead of stdout")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum StorageKind {
+    #[default]
+    Filesystem,
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+    Memory,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+    Markdown,
+    PlayByPlay,
+    Replay,
+}
+
+#[derive(Args, Debug, Clone, PartialEq)]
+pub struct ExportArgs {
+    #[clap(long, short = 'g', action=ArgAction::Set, help = "Id of a persisted game to export")]
+    pub game_id: GameId,
+    #[clap(long, value_enum, default_value_t = ExportFormat::Json, help = "Box score format, play-by-play log or replay file")]
+    pub format: ExportFormat,
+    #[clap(long, short = 'o', action=ArgAction::Set, help = "Write the export to file instead of stdout")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, Clone, PartialEq)]
+#[clap(group(ArgGroup::new("source").required(true).args(["game_id", "file"])))]
+pub struct ReplayArgs {
+    #[clap(long, short = 'g', action=ArgAction::Set, help = "Id of a persisted game to replay")]
+    pub game_id: Option<GameId>,
+    #[clap(long, short = 'f', action=ArgAction::Set, help = "Replay file to verify")]
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, Clone, PartialEq)]
+pub struct SnapshotsArgs {
+    #[clap(long, action=ArgAction::Set, help = "Restore the snapshot with the given timestamp")]
+    pub restore: Option<Tick>,
+}
+
+#[derive(Args, Debug, Clone, PartialEq)]
+pub struct HistoryArgs {
+    #[clap(long, action=ArgAction::SetTrue, help = "List past tournaments instead of past games")]
+    pub tournaments: bool,
+    #[clap(long, short = 't', action=ArgAction::Set, help = "Only list the games or tournaments of the given team")]
+    pub team_id: Option<TeamId>,
+    #[clap(long, action=ArgAction::Set, help = "Only list entries from the given timestamp")]
+    pub since: Option<Tick>,
+    #[clap(long, action=ArgAction::Set, help = "Only list entries up to the given timestamp")]
+    pub until: Option<Tick>,
+    #[clap(long, short = 'n', action=ArgAction::Set, help = "Maximum number of entries to list")]
+    pub limit: Option<usize>,
+    #[clap(long, value_enum, default_value_t = ReportFormat::Json, help = "History format")]
+    pub format: ReportFormat,
+    #[clap(long, short = 'o', action=ArgAction::Set, help = "Write the history to file instead of stdout")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum AppCommand {
+    #[clap(about = "Simulate a local world without UI and print a report")]
+    Simulate(SimulateArgs),
+    #[clap(about = "Replay a game and verify that every action matches the recorded one")]
+    Replay(ReplayArgs),
+    #[clap(about = "Export the box score, the play-by-play log or the replay of a finished game")]
+    Export(ExportArgs),
+    #[clap(about = "List the snapshots of the saved world, or restore one")]
+    Snapshots(SnapshotsArgs),
+    #[clap(about = "List the persisted past games or tournaments, from the most recent")]
+    History(HistoryArgs),
+}
+
 #[derive(Parser, Debug)]
 #[clap(name="Rebels in the sky", about = "P(lanet)2P(lanet) basketball", author, version, long_about = None)]
 pub struct AppArgs {
@@ -20,7 +122,7 @@ pub struct AppArgs {
     #[cfg(feature = "audio")]
     #[clap(long, short='a', action=ArgAction::SetTrue, help = "Disable audio")]
     disable_audio: bool,
-    #[clap(long, short='r', action=ArgAction::SetTrue, help = "Reset all save files")]
+    #[clap(long, short='r', action=ArgAction::SetTrue, help = "Reset the saved world")]
     pub reset_world: bool,
     #[clap(long="disable_local_world", short='f', action=ArgAction::SetFalse, help = "Disable generating local teams")]
     pub generate_local_world: bool,
@@ -38,14 +140,28 @@ pub struct AppArgs {
     network_port: Option<u16>,
     #[clap(long, short = 'i', action=ArgAction::SetTrue, help = "Use ipv6 instead of ipv4")]
     use_ipv6: bool,
+    #[clap(long, short = 'm', action=ArgAction::SetTrue, help = "Discover peers on the local network via mDNS")]
+    enable_mdns: bool,
+    #[clap(long, action=ArgAction::SetTrue, help = "Apply the ban lists published by relayers")]
+    trust_relayer_bans: bool,
     #[clap(long, action=ArgAction::Set, help = "Set store prefix")]
     store_prefix: Option<String>,
     #[clap(long, action=ArgAction::SetTrue, help = "Save game to uncompressed json")]
     pub store_uncompressed: bool,
+    #[clap(long, action=ArgAction::Set, default_value_t = DEFAULT_SNAPSHOT_RETENTION, help = "Set number of save snapshots to keep, 0 to disable them")]
+    pub snapshot_retention: usize,
+    #[clap(long, value_enum, default_value_t = StorageKind::Filesystem, help = "Set storage backend for save files")]
+    pub storage: StorageKind,
+    #[clap(skip)]
+    save_passphrase: Option<String>,
     #[clap(long, short = 'q', action=ArgAction::Set, help = "Set auto quit after value in seconds")]
     pub auto_quit_after: Option<u64>,
     #[clap(long, action=ArgAction::SetTrue, help = "Reset p2p netowrk data")]
     pub reset_network_peers: bool,
+    #[clap(long, action=ArgAction::SetTrue, help = "Let friendly local games end in a draw instead of going to overtime")]
+    allow_draws: bool,
+    #[clap(subcommand)]
+    pub command: Option<AppCommand>,
 }
 
 impl AppArgs {
@@ -54,6 +170,7 @@ impl AppArgs {
         store_prefix: Option<String>,
         network_port: Option<u16>,
         auto_quit_after: Option<u64>,
+        save_passphrase: Option<String>,
     ) -> Self {
         Self {
             random_seed: None,
@@ -69,10 +186,17 @@ impl AppArgs {
             seed_node_ip: None,
             network_port,
             use_ipv6: true,
+            enable_mdns: false,
+            trust_relayer_bans: false,
             store_prefix,
             store_uncompressed: false,
+            snapshot_retention: DEFAULT_SNAPSHOT_RETENTION,
+            storage: StorageKind::Filesystem,
+            save_passphrase,
             auto_quit_after,
             reset_network_peers: false,
+            allow_draws: false,
+            command: None,
         }
     }
     pub fn test() -> Self {
@@ -91,10 +215,25 @@ impl AppArgs {
             seed_node_ip: None,
             network_port: None,
             use_ipv6: true,
+            enable_mdns: false,
+            trust_relayer_bans: false,
             store_prefix: None,
             store_uncompressed: false,
+            snapshot_retention: DEFAULT_SNAPSHOT_RETENTION,
+            storage: StorageKind::Filesystem,
+            save_passphrase: None,
             auto_quit_after: None,
             reset_network_peers: false,
+            allow_draws: false,
+            command: None,
+        }
+    }
+
+    pub fn simulation(random_seed: u64) -> Self {
+        Self {
+            random_seed: Some(random_seed),
+            disable_ui: true,
+            ..Self::test()
         }
     }
 
@@ -139,6 +278,25 @@ impl AppArgs {
         self.use_ipv6
     }
 
+    pub fn allow_draws(&self) -> bool {
+        self.allow_draws
+    }
+
+    pub fn use_mdns(&self) -> bool {
+        self.enable_mdns
+    }
+
+    pub fn trust_relayer_bans(&self) -> bool {
+        self.trust_relayer_bans
+    }
+
+    pub fn save_passphrase(&self) -> Option<String> {
+        self.save_passphrase
+            .clone()
+            .or_else(|| std::env::var(SAVE_PASSPHRASE_ENV).ok())
+            .filter(|passphrase| !passphrase.is_empty())
+    }
+
     pub fn store_prefix(&self) -> &str {
         if let Some(prefix) = self.store_prefix.as_ref() {
             prefix
@@ -0,0 +1,195 @@
+use crate::{
+    core::{GameResult, WEEKS},
+    game_engine::types::GameStats,
+    types::{TeamId, Tick},
+};
+use serde::{Deserialize, Serialize};
+
+// Games are logged per team and per week. When the log grows too long,
+// the oldest consecutive entries with the same team are merged into a single spell.
+pub const CAREER_WINDOW: Tick = WEEKS;
+pub const MAX_CAREER_ENTRIES: usize = 16;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct CareerEntry {
+    pub team_id: TeamId,
+    pub team_name: String,
+    // Start of the first window and end of the last window covered by the entry.
+    pub from: Tick,
+    pub to: Tick,
+    pub stats: GameStats,
+}
+
+impl CareerEntry {
+    fn new(team_id: TeamId, team_name: &str, timestamp: Tick) -> Self {
+        let from = timestamp / CAREER_WINDOW * CAREER_WINDOW;
+        Self {
+            team_id,
+            team_name: team_name.to_string(),
+            from,
+            to: from + CAREER_WINDOW,
+            stats: GameStats::default(),
+        }
+    }
+
+    pub fn games(&self) -> u16 {
+        self.stats.games.iter().sum()
+    }
+
+    pub fn is_spell(&self) -> bool {
+        self.to - self.from > CAREER_WINDOW
+    }
+
+    pub fn per_game(&self, value: u16) -> f32 {
+        value as f32 / self.games().max(1) as f32
+    }
+
+    fn add_game(&mut self, stats: &GameStats, result: GameResult) {
+        self.stats.update(stats);
+        // Shots are only useful for the shot map of a single game.
+        self.stats.shots.clear();
+        self.stats.last_action_shot = None;
+        self.stats.plus_minus += stats.plus_minus;
+        match result {
+            GameResult::Win => self.stats.games[0] += 1,
+            GameResult::Loss => self.stats.games[1] += 1,
+            GameResult::Draw => self.stats.games[2] += 1,
+        }
+    }
+
+    fn merge(&mut self, other: &CareerEntry) {
+        self.stats.update(&other.stats);
+        self.stats.shots.clear();
+        self.stats.last_action_shot = None;
+        self.stats.plus_minus += other.stats.plus_minus;
+        for idx in 0..self.stats.games.len() {
+            self.stats.games[idx] += other.stats.games[idx];
+        }
+        self.from = self.from.min(other.from);
+        self.to = self.to.max(other.to);
+        self.team_name = other.team_name.clone();
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Career {
+    // Sorted from oldest to newest.
+    pub entries: Vec<CareerEntry>,
+}
+
+impl Career {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn record_game(
+        &mut self,
+        team_id: TeamId,
+        team_name: &str,
+        timestamp: Tick,
+        stats: &GameStats,
+        result: GameResult,
+    ) {
+        let window_start = timestamp / CAREER_WINDOW * CAREER_WINDOW;
+        let entry = match self
+            .entries
+            .iter_mut()
+            .rev()
+            .find(|entry| entry.team_id == team_id && entry.from == window_start)
+        {
+            Some(entry) => entry,
+            None => {
+                self.entries
+                    .push(CareerEntry::new(team_id, team_name, timestamp));
+                self.entries.last_mut().expect("Entry was just pushed")
+            }
+        };
+        entry.add_game(stats, result);
+
+        self.compact();
+    }
+
+    fn compact(&mut self) {
+        while self.entries.len() > MAX_CAREER_ENTRIES {
+            if let Some(idx) = self
+                .entries
+                .windows(2)
+                .position(|pair| pair[0].team_id == pair[1].team_id)
+            {
+                let newer = self.entries.remove(idx + 1);
+                self.entries[idx].merge(&newer);
+            } else {
+                self.entries.remove(0);
+            }
+        }
+    }
+
+    // Totals for each team the player has played for, in order of first appearance.
+    pub fn team_splits(&self) -> Vec<CareerEntry> {
+        let mut splits: Vec<CareerEntry> = vec![];
+        for entry in self.entries.iter() {
+            if let Some(split) = splits
+                .iter_mut()
+                .find(|split| split.team_id == entry.team_id)
+            {
+                split.merge(entry);
+            } else {
+                splits.push(entry.clone());
+            }
+        }
+        splits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Career, CAREER_WINDOW, MAX_CAREER_ENTRIES};
+    use crate::{core::GameResult, game_engine::types::GameStats, types::TeamId};
+
+    #[test]
+    fn test_career_is_bounded() {
+        let mut career = Career::default();
+        let team_a = TeamId::new_v4();
+        let team_b = TeamId::new_v4();
+        let stats = GameStats {
+            points: 10,
+            plus_minus: 2,
+            ..Default::default()
+        };
+
+        for week in 0..40 {
+            let team_id = if week < 30 { team_a } else { team_b };
+            career.record_game(
+                team_id,
+                "Team",
+                week * CAREER_WINDOW,
+                &stats,
+                GameResult::Win,
+            );
+            career.record_game(
+                team_id,
+                "Team",
+                week * CAREER_WINDOW + 1,
+                &stats,
+                GameResult::Loss,
+            );
+        }
+
+        assert_eq!(career.entries.len(), MAX_CAREER_ENTRIES);
+        let total_games: u16 = career.entries.iter().map(|e| e.games()).sum();
+        assert_eq!(total_games, 80);
+        assert!(career.entries[0].is_spell());
+        assert_eq!(career.entries[0].from, 0);
+        assert_eq!(career.entries.last().unwrap().games(), 2);
+        assert_eq!(career.entries.last().unwrap().per_game(20), 10.0);
+
+        let splits = career.team_splits();
+        assert_eq!(splits.len(), 2);
+        assert_eq!(splits[0].stats.points, 600);
+        assert_eq!(splits[1].stats.plus_minus, 40);
+    }
+}
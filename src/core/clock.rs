@@ -0,0 +1,113 @@
+use crate::types::{AppResult, SystemTimeTick, Tick};
+use anyhow::anyhow;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+// The Clock is the source of the current Tick for the World.
+// The real clock follows the system time and is the one used while playing.
+// The manual clock moves only when explicitly advanced, so that tests and headless runs
+// can drive the world through weeks of game time in a few seconds.
+// The accelerated clock runs `multiplier` times faster than the system time, starting from `origin`.
+// Clones of a manual clock share the same time.
+#[derive(Debug, Clone, Default)]
+pub enum Clock {
+    #[default]
+    Real,
+    Manual {
+        now: Arc<AtomicU64>,
+    },
+    Accelerated {
+        origin: Tick,
+        started_at: Tick,
+        multiplier: u64,
+    },
+}
+
+impl Clock {
+    pub fn manual(start: Tick) -> Self {
+        Self::Manual {
+            now: Arc::new(AtomicU64::new(start)),
+        }
+    }
+
+    pub fn accelerated(origin: Tick, multiplier: u64) -> Self {
+        Self::Accelerated {
+            origin,
+            started_at: Tick::now(),
+            multiplier: multiplier.max(1),
+        }
+    }
+
+    pub fn now(&self) -> Tick {
+        match self {
+            Self::Real => Tick::now(),
+            Self::Manual { now } => now.load(Ordering::SeqCst),
+            Self::Accelerated {
+                origin,
+                started_at,
+                multiplier,
+            } => origin + Tick::now().saturating_sub(*started_at) * multiplier,
+        }
+    }
+
+    pub fn is_real(&self) -> bool {
+        matches!(self, Self::Real)
+    }
+
+    pub fn set(&self, tick: Tick) -> AppResult<()> {
+        match self {
+            Self::Manual { now } => {
+                now.store(tick, Ordering::SeqCst);
+                Ok(())
+            }
+            _ => Err(anyhow!("Only a manual clock can be set.")),
+        }
+    }
+
+    pub fn advance(&self, duration: Tick) -> AppResult<Tick> {
+        match self {
+            Self::Manual { now } => Ok(now.fetch_add(duration, Ordering::SeqCst) + duration),
+            _ => Err(anyhow!("Only a manual clock can be advanced.")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Clock;
+    use crate::{
+        core::{HOURS, SECONDS},
+        types::{AppResult, SystemTimeTick, Tick},
+    };
+
+    #[test]
+    fn test_manual_clock() -> AppResult<()> {
+        let clock = Clock::manual(0);
+        let shared = clock.clone();
+        assert_eq!(clock.now(), 0);
+
+        assert_eq!(clock.advance(10 * SECONDS)?, 10 * SECONDS);
+        assert_eq!(shared.now(), 10 * SECONDS);
+
+        shared.set(HOURS)?;
+        assert_eq!(clock.now(), HOURS);
+        Ok(())
+    }
+
+    #[test]
+    fn test_real_clock_cannot_be_driven() {
+        let clock = Clock::Real;
+        assert!(clock.advance(SECONDS).is_err());
+        assert!(clock.set(0).is_err());
+        assert!(clock.now() >= Tick::now() - SECONDS);
+    }
+
+    #[test]
+    fn test_accelerated_clock() {
+        let clock = Clock::accelerated(0, 1000);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert!(clock.now() >= 5 * SECONDS);
+    }
+}
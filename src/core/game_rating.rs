@@ -3,9 +3,24 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
+use crate::{core::DAYS, types::Tick};
+
+// Glicko-2 rating system, see http://www.glicko.net/glicko/glicko2.pdf
 const DEFAULT_RATING: f32 = 1200.0;
 const FLOOR_RATING: f32 = 100.0;
-const K_FACTOR_REDUCTION_THRESHOLD: usize = 10;
+const DEFAULT_DEVIATION: f32 = 350.0;
+const MIN_DEVIATION: f32 = 30.0;
+// Ratings stored before the deviation was tracked had already been refined by Elo updates,
+// so they start with more confidence than brand new ones.
+const MIGRATED_DEVIATION: f32 = 200.0;
+const DEFAULT_VOLATILITY: f32 = 0.06;
+// Constrains the change in volatility over time.
+const TAU: f64 = 0.5;
+const GLICKO2_SCALE: f64 = 173.7178;
+const CONVERGENCE_TOLERANCE: f64 = 0.000001;
+const MAX_ITERATIONS: usize = 100;
+// Inactive teams see their deviation grow once per rating period.
+const RATING_PERIOD: Tick = DAYS;
 
 #[derive(Debug, Clone, Copy, Serialize_repr, Deserialize_repr, Eq, Hash, PartialEq)]
 #[repr(u8)]
@@ -15,11 +30,42 @@ pub enum GameResult {
     Loss,
 }
 
+impl GameResult {
+    fn score(&self) -> f64 {
+        match self {
+            Self::Win => 1.0,
+            Self::Draw => 0.5,
+            Self::Loss => 0.0,
+        }
+    }
+}
+
+fn default_deviation() -> f32 {
+    MIGRATED_DEVIATION
+}
+
+fn default_volatility() -> f32 {
+    DEFAULT_VOLATILITY
+}
+
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi.powi(2) / std::f64::consts::PI.powi(2)).sqrt()
+}
+
+fn expected(mu: f64, mu_j: f64, phi_j: f64) -> f64 {
+    1.0 / (1.0 + (-g(phi_j) * (mu - mu_j)).exp())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct GameRating {
     pub rating: f32,
     pub record: HashMap<GameResult, usize>,
-    has_been_above_2400: bool,
+    #[serde(default = "default_deviation")]
+    pub deviation: f32,
+    #[serde(default = "default_volatility")]
+    pub volatility: f32,
+    #[serde(default)]
+    pub last_update: Tick,
 }
 
 impl Default for GameRating {
@@ -27,78 +73,179 @@ impl Default for GameRating {
         Self {
             rating: DEFAULT_RATING,
             record: HashMap::default(),
-            has_been_above_2400: false,
+            deviation: DEFAULT_DEVIATION,
+            volatility: DEFAULT_VOLATILITY,
+            last_update: 0,
         }
     }
 }
 
 impl GameRating {
-    fn num_games(&self) -> usize {
+    pub fn num_games(&self) -> usize {
         self.record.values().sum()
     }
 
-    fn k_factor(&self) -> usize {
-        // K = 30: for a player new to the rating list until the completion of events with a total of 30 games.
-        // K = 15: for players who have always been rated under 2400.
-        // K = 10: for players with any published rating of at least 2400 and at least 30 games played in previous events. Thereafter it remains permanently at 10.
-        let n = self.num_games();
-        if n < K_FACTOR_REDUCTION_THRESHOLD {
-            30
-        } else if !self.has_been_above_2400 {
-            15
-        } else {
-            10
+    // Deviation of a rating refined by the given number of Elo updates: every game
+    // played makes the rating more reliable, down to the minimum deviation.
+    pub fn migrated_deviation(num_games: usize) -> f32 {
+        (DEFAULT_DEVIATION / (1.0 + num_games as f32).sqrt()).max(MIN_DEVIATION)
+    }
+
+    fn mu(&self) -> f64 {
+        (self.rating - DEFAULT_RATING) as f64 / GLICKO2_SCALE
+    }
+
+    fn phi(&self, now: Tick) -> f64 {
+        self.deviation_at(now) as f64 / GLICKO2_SCALE
+    }
+
+    // Deviation grows with every rating period the team has not played.
+    pub fn deviation_at(&self, now: Tick) -> f32 {
+        if self.last_update == 0 {
+            return self.deviation;
         }
+
+        let idle_periods = now.saturating_sub(self.last_update) / RATING_PERIOD;
+        (self.deviation.powi(2)
+            + idle_periods as f32 * (self.volatility * GLICKO2_SCALE as f32).powi(2))
+        .sqrt()
+        .min(DEFAULT_DEVIATION)
     }
 
-    fn expected_score(&self, other_rating: &GameRating) -> f32 {
-        1.0 / (1.0 + 10.0_f32.powf((other_rating.rating - self.rating) / 400.0))
+    // Lower bound of the rating with ~95% confidence, used to rank teams.
+    pub fn conservative_rating(&self, now: Tick) -> f32 {
+        (self.rating - 2.0 * self.deviation_at(now)).max(0.0)
     }
 
-    pub fn update(&mut self, result: GameResult, other_rating: &GameRating) {
-        self.record
-            .entry(result)
-            .and_modify(|e| *e += 1)
-            .or_insert(1);
+    pub fn expected_score(&self, other: &GameRating, now: Tick) -> f32 {
+        expected(self.mu(), other.mu(), other.phi(now)) as f32
+    }
 
-        let pa = self.expected_score(other_rating);
+    // Returns a value in [0, 1], higher for games whose outcome is more uncertain.
+    pub fn match_quality(&self, other: &GameRating, now: Tick) -> f32 {
+        let combined_phi = (self.phi(now).powi(2) + other.phi(now).powi(2)).sqrt();
+        let expected_score = expected(self.mu(), other.mu(), combined_phi);
+        (1.0 - 2.0 * (expected_score - 0.5).abs()) as f32
+    }
 
-        let outcome = match result {
-            GameResult::Win => 1.0,
-            GameResult::Draw => 0.5,
-            GameResult::Loss => -1.0,
+    fn new_volatility(&self, phi: f64, v: f64, delta: f64) -> f64 {
+        let a = (self.volatility as f64).powi(2).ln();
+        let f = |x: f64| {
+            x.exp() * (delta.powi(2) - phi.powi(2) - v - x.exp())
+                / (2.0 * (phi.powi(2) + v + x.exp()).powi(2))
+                - (x - a) / TAU.powi(2)
         };
 
-        let new_rating = self.rating + self.k_factor() as f32 * (outcome - pa);
-
-        self.rating = new_rating.max(FLOOR_RATING);
+        let mut big_a = a;
+        let mut big_b = if delta.powi(2) > phi.powi(2) + v {
+            (delta.powi(2) - phi.powi(2) - v).ln()
+        } else {
+            let mut k = 1.0;
+            while f(a - k * TAU) < 0.0 && k < MAX_ITERATIONS as f64 {
+                k += 1.0;
+            }
+            a - k * TAU
+        };
 
-        if !self.has_been_above_2400 && self.rating >= 2400.0 {
-            self.has_been_above_2400 = true;
+        let mut f_a = f(big_a);
+        let mut f_b = f(big_b);
+        let mut iterations = 0;
+        while (big_b - big_a).abs() > CONVERGENCE_TOLERANCE && iterations < MAX_ITERATIONS {
+            let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+            let f_c = f(big_c);
+            if f_c * f_b <= 0.0 {
+                big_a = big_b;
+                f_a = f_b;
+            } else {
+                f_a /= 2.0;
+            }
+            big_b = big_c;
+            f_b = f_c;
+            iterations += 1;
         }
+
+        (big_a / 2.0).exp()
+    }
+
+    // Each game is treated as its own rating period. The opponent rating
+    // should be the one from before the game.
+    pub fn update(&mut self, result: GameResult, other: &GameRating, now: Tick) {
+        self.record
+            .entry(result)
+            .and_modify(|e| *e += 1)
+            .or_insert(1);
+
+        let mu = self.mu();
+        let phi = self.phi(now);
+        let mu_j = other.mu();
+        let phi_j = other.phi(now);
+
+        let g_j = g(phi_j);
+        let e_j = expected(mu, mu_j, phi_j);
+        let v = 1.0 / (g_j.powi(2) * e_j * (1.0 - e_j));
+        let delta = v * g_j * (result.score() - e_j);
+
+        let volatility = self.new_volatility(phi, v, delta);
+        let phi_star = (phi.powi(2) + volatility.powi(2)).sqrt();
+        let new_phi = 1.0 / (1.0 / phi_star.powi(2) + 1.0 / v).sqrt();
+        let new_mu = mu + new_phi.powi(2) * g_j * (result.score() - e_j);
+
+        self.rating = (DEFAULT_RATING + (GLICKO2_SCALE * new_mu) as f32).max(FLOOR_RATING);
+        self.deviation = ((GLICKO2_SCALE * new_phi) as f32).clamp(MIN_DEVIATION, DEFAULT_DEVIATION);
+        self.volatility = volatility as f32;
+        self.last_update = now;
     }
 }
 
 #[cfg(test)]
 mod tests {
-
+    use super::{DEFAULT_DEVIATION, MIGRATED_DEVIATION, RATING_PERIOD};
     use crate::core::{GameRating, GameResult};
 
     #[test]
     fn test_rating_update() {
         let mut rating_a = GameRating::default();
         let mut rating_b = GameRating::default();
+        let mut now = RATING_PERIOD;
 
         for _ in 0..35 {
-            rating_a.update(GameResult::Win, &rating_b);
-            rating_b.update(GameResult::Loss, &rating_a);
+            let previous_a = rating_a.clone();
+            rating_a.update(GameResult::Win, &rating_b, now);
+            rating_b.update(GameResult::Loss, &previous_a, now);
 
             assert!(rating_a.rating > rating_b.rating);
-            print!("{rating_a:#?} vs {rating_b:#?}");
+            assert!(rating_a.deviation < DEFAULT_DEVIATION);
+            now += 1;
         }
 
-        rating_a.update(GameResult::Draw, &rating_b);
-        rating_b.update(GameResult::Draw, &rating_a);
-        print!("{rating_a:#?} vs {rating_b:#?}");
+        let deviation = rating_a.deviation;
+        let previous_a = rating_a.clone();
+        rating_a.update(GameResult::Draw, &rating_b, now);
+        rating_b.update(GameResult::Draw, &previous_a, now);
+        assert!(rating_a.deviation <= deviation);
+        assert!(rating_a.rating < previous_a.rating);
+        assert!(rating_a.match_quality(&rating_b, now) < 0.5);
+    }
+
+    #[test]
+    fn test_deviation_grows_with_inactivity() {
+        let mut rating = GameRating::default();
+        rating.update(GameResult::Win, &GameRating::default(), RATING_PERIOD);
+        let deviation = rating.deviation;
+
+        assert_eq!(rating.deviation_at(RATING_PERIOD), deviation);
+        assert!(rating.deviation_at(RATING_PERIOD * 60) > deviation);
+        assert!(rating.deviation_at(RATING_PERIOD * 1_000_000) <= DEFAULT_DEVIATION);
+    }
+
+    #[test]
+    fn test_elo_rating_migration() {
+        let stored = r#"{"rating":1500.0,"record":{"0":12,"2":3},"has_been_above_2400":false}"#;
+        let rating: GameRating = serde_json::from_str(stored).expect("Should deserialize");
+
+        assert_eq!(rating.rating, 1500.0);
+        assert_eq!(rating.num_games(), 15);
+        assert_eq!(rating.deviation, MIGRATED_DEVIATION);
+        assert!(rating.conservative_rating(0) > GameRating::default().conservative_rating(0));
     }
 }
@@ -7,7 +7,7 @@ use crate::{
         MAX_PLAYERS_PER_GAME, SATOSHI_PER_BITCOIN, WEEKS,
     },
     game_engine::game::GameSummary,
-    types::{GameId, PlanetId, PlayerId, SystemTimeTick, Tick},
+    types::{GameId, PlanetId, PlayerId, Tick},
 };
 use itertools::Itertools;
 use serde_repr::{Deserialize_repr, Serialize_repr};
@@ -34,6 +34,7 @@ impl Honour {
         past_games: &HashMap<GameId, GameSummary>,
         players: &HashMap<PlayerId, Player>,
         planets: &HashMap<PlanetId, Planet>,
+        now: Tick,
     ) -> bool {
         match self {
             Self::Defiant => {
@@ -98,7 +99,7 @@ impl Honour {
             Self::Traveller => team.total_travelled >= LIGHT_YEAR,
             Self::Veteran => {
                 team.creation_time != Tick::default()
-                    && (Tick::now() - team.creation_time) >= 52 * WEEKS
+                    && now.saturating_sub(team.creation_time) >= 52 * WEEKS
             }
         }
     }
@@ -153,7 +154,7 @@ mod tests {
     fn test_conditions_not_met_multikulti() -> AppResult<()> {
         let app = &mut App::test_default()?;
 
-        let mut team = Team::random(None);
+        let mut team = Team::random(None, app.world.clock.now());
         let team_id = team.id;
         team.add_resource(Resource::SATOSHI, 1_000_000)?;
         assert!(team.player_ids.len() == 0);
@@ -194,7 +195,8 @@ mod tests {
             &team,
             &app.world.past_games,
             &app.world.players,
-            &app.world.planets
+            &app.world.planets,
+            app.world.clock.now()
         ));
 
         Ok(())
@@ -204,7 +206,7 @@ mod tests {
     fn test_conditions_met_multikulti() -> AppResult<()> {
         let app = &mut App::test_default()?;
 
-        let mut team = Team::random(None);
+        let mut team = Team::random(None, app.world.clock.now());
         let team_id = team.id;
         team.add_resource(Resource::SATOSHI, 1_000_000)?;
         assert!(team.player_ids.len() == 0);
@@ -241,7 +243,8 @@ mod tests {
             &team,
             &app.world.past_games,
             &app.world.players,
-            &app.world.planets
+            &app.world.planets,
+            app.world.clock.now()
         ));
 
         Ok(())
@@ -251,7 +254,7 @@ mod tests {
     fn test_conditions_met_galactic() -> AppResult<()> {
         let app = &mut App::test_default()?;
 
-        let mut team = Team::random(None);
+        let mut team = Team::random(None, app.world.clock.now());
 
         let parent_planets = app
             .world
@@ -275,7 +278,8 @@ mod tests {
             &team,
             &app.world.past_games,
             &app.world.players,
-            &app.world.planets
+            &app.world.planets,
+            app.world.clock.now()
         ));
 
         Ok(())
@@ -0,0 +1,94 @@
+use crate::{
+    core::{DAYS, HOURS},
+    types::Tick,
+};
+use rand::RngExt;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum InjurySeverity {
+    #[default]
+    Minor,
+    Moderate,
+    Severe,
+}
+
+impl Display for InjurySeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Minor => write!(f, "Minor"),
+            Self::Moderate => write!(f, "Moderate"),
+            Self::Severe => write!(f, "Severe"),
+        }
+    }
+}
+
+impl InjurySeverity {
+    // Most injuries are just bruises, a few keep the pirate out for days.
+    pub fn random(rng: &mut ChaCha8Rng) -> Self {
+        match rng.random_range(0..20) {
+            0..=12 => Self::Minor,
+            13..=17 => Self::Moderate,
+            _ => Self::Severe,
+        }
+    }
+
+    pub fn recovery_time(&self) -> Tick {
+        match self {
+            Self::Minor => 6 * HOURS,
+            Self::Moderate => DAYS,
+            Self::Severe => 3 * DAYS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Injury {
+    pub severity: InjurySeverity,
+    // Recovery time left at the base recovery rate.
+    pub recovery_left: Tick,
+}
+
+impl Injury {
+    pub fn new(severity: InjurySeverity) -> Self {
+        Self {
+            severity,
+            recovery_left: severity.recovery_time(),
+        }
+    }
+
+    // A good doctor multiplies the recovery rate (see TeamBonus::TirednessRecovery).
+    pub fn recover(&mut self, elapsed: Tick, bonus: f32) {
+        self.recovery_left = self
+            .recovery_left
+            .saturating_sub((elapsed as f32 * bonus) as Tick);
+    }
+
+    pub fn has_healed(&self) -> bool {
+        self.recovery_left == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Injury, InjurySeverity};
+    use crate::core::{TeamBonus, HOURS};
+
+    #[test]
+    fn test_injury_recovery() {
+        let mut injury = Injury::new(InjurySeverity::Minor);
+        assert_eq!(injury.recovery_left, 6 * HOURS);
+
+        injury.recover(2 * HOURS, TeamBonus::BASE_BONUS);
+        assert_eq!(injury.recovery_left, 4 * HOURS);
+        assert!(!injury.has_healed());
+
+        // A doctor with maximum skill doubles the recovery rate.
+        injury.recover(2 * HOURS, 2.0 * TeamBonus::BASE_BONUS);
+        assert!(injury.has_healed());
+    }
+}
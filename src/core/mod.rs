@@ -1,7 +1,10 @@
 pub mod asteroid_upgrades;
+pub mod career;
+pub mod clock;
 pub mod constants;
 pub mod game_rating;
 pub mod honours;
+pub mod injury;
 pub mod jersey;
 pub mod kartoffel;
 pub mod planet;
@@ -20,9 +23,12 @@ pub mod utils;
 pub mod world;
 
 pub use asteroid_upgrades::*;
+pub use career::*;
+pub use clock::*;
 pub use constants::*;
 pub use game_rating::*;
 pub use honours::*;
+pub use injury::*;
 pub use jersey::*;
 pub use kartoffel::*;
 pub use planet::*;
@@ -3,12 +3,13 @@ use super::{resources::Resource, skill::MAX_SKILL, types::Population};
 use crate::core::skill::GameSkill;
 use crate::core::utils::is_default;
 use crate::core::{AsteroidUpgradeTarget, Upgrade, MIN_SKILL};
-use crate::types::{SystemTimeTick, Tick};
+use crate::types::Tick;
 use crate::{
     types::*,
     types::{PlanetId, TeamId},
 };
 use anyhow::anyhow;
+use itertools::Itertools;
 use libp2p::PeerId;
 use rand::prelude::Distribution;
 use rand::seq::IndexedRandom;
@@ -82,14 +83,13 @@ impl Planet {
     fn price_delta(&self, merchant_bonus: f32) -> f32 {
         (TRADE_DELTA_BUY_SELL + 1.0 / (10.0 + self.total_population() as f32)) / merchant_bonus
     }
-    fn resource_price(&self, resource: Resource) -> f32 {
+    fn resource_price(&self, resource: Resource, now: Tick) -> f32 {
         // Resource price follows a hyperbolic tangent curve
         let relative_amount = (self.resources.value(&resource) as f32).bound() / MAX_SKILL;
         let amount_modifier =
             relative_amount / TRADE_DELTA_SCARCITY + (1.0 - relative_amount) * TRADE_DELTA_SCARCITY;
 
-        let random_fluctuation =
-            0.2 * ((Tick::now() / RESOURCE_PRICE_REFRESH_RATE_MILLIS) as f32).sin();
+        let random_fluctuation = 0.2 * ((now / RESOURCE_PRICE_REFRESH_RATE_MILLIS) as f32).sin();
 
         let mut s = DefaultHasher::new();
         self.name.hash(&mut s);
@@ -110,8 +110,8 @@ impl Planet {
         price
     }
 
-    pub fn resource_buy_price(&self, resource: Resource, merchant_bonus: f32) -> u32 {
-        let price = self.resource_price(resource);
+    pub fn resource_buy_price(&self, resource: Resource, merchant_bonus: f32, now: Tick) -> u32 {
+        let price = self.resource_price(resource, now);
         let delta = self.price_delta(merchant_bonus);
         let buy_price = price * (1.0 + delta);
 
@@ -124,8 +124,8 @@ impl Planet {
         (buy_price as u32).max(1)
     }
 
-    pub fn resource_sell_price(&self, resource: Resource, merchant_bonus: f32) -> u32 {
-        let price = self.resource_price(resource);
+    pub fn resource_sell_price(&self, resource: Resource, merchant_bonus: f32, now: Tick) -> u32 {
+        let price = self.resource_price(resource, now);
         let delta = self.price_delta(merchant_bonus);
         let sell_price = price * (1.0 - delta);
 
@@ -147,10 +147,12 @@ impl Planet {
     }
 
     pub fn random_population(&self, rng: &mut ChaCha8Rng) -> Option<Population> {
+        // Sort to keep the rng sequence deterministic.
         let weights = self
             .populations
             .iter()
             .map(|(pop, n)| (*pop, *n))
+            .sorted()
             .collect::<Vec<(Population, u32)>>();
 
         let dist = WeightedIndex::new(weights.iter().map(|(_, w)| w)).ok()?;
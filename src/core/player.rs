@@ -1,5 +1,7 @@
 use super::{
+    career::Career,
     constants::*,
+    injury::{Injury, InjurySeverity},
     jersey::Jersey,
     position::{GamePosition, GamePositionUtils, MAX_GAME_POSITION},
     resources::Resource,
@@ -62,6 +64,8 @@ pub struct Player {
     pub tiredness: Skill,
     pub morale: Skill,
     pub historical_stats: GameStats,
+    pub career: Career,
+    pub injury: Option<Injury>,
     build_data: PlayerBuildData, // Intermediate state used to build the random player. Not serialized
 }
 
@@ -88,6 +92,8 @@ impl Default for Player {
             tiredness: Skill::default(),
             morale: Skill::default(),
             historical_stats: GameStats::default(),
+            career: Career::default(),
+            injury: None,
             build_data: PlayerBuildData::default(),
         }
     }
@@ -99,7 +105,7 @@ impl Serialize for Player {
         // and serialize them in a vector which is then deserialized
         // into the corresponding fields
         let compact_skills = self.current_skill_array().to_vec();
-        let mut state = serializer.serialize_struct("Player", 17)?;
+        let mut state = serializer.serialize_struct("Player", 19)?;
         state.serialize_field("id", &self.id)?;
 
         state.serialize_field("peer_id", &self.peer_id)?;
@@ -117,6 +123,16 @@ impl Serialize for Player {
         state.serialize_field("morale", &self.morale)?;
         state.serialize_field("compact_skills", &compact_skills)?;
         state.serialize_field("historical_stats", &self.historical_stats)?;
+        if self.career.is_empty() {
+            state.skip_field("career")?;
+        } else {
+            state.serialize_field("career", &self.career)?;
+        }
+        if self.injury.is_none() {
+            state.skip_field("injury")?;
+        } else {
+            state.serialize_field("injury", &self.injury)?;
+        }
         state.end()
     }
 }
@@ -143,6 +159,8 @@ impl<'de> Deserialize<'de> for Player {
             Morale,
             CompactSkills,
             HistoricalStats,
+            Career,
+            Injury,
         }
 
         impl<'de> Deserialize<'de> for Field {
@@ -177,6 +195,8 @@ impl<'de> Deserialize<'de> for Player {
                             "morale" => Ok(Field::Morale),
                             "compact_skills" => Ok(Field::CompactSkills),
                             "historical_stats" => Ok(Field::HistoricalStats),
+                            "career" => Ok(Field::Career),
+                            "injury" => Ok(Field::Injury),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -245,6 +265,8 @@ impl<'de> Deserialize<'de> for Player {
                     .next_element()?
                     .ok_or_else(|| serde::de::Error::invalid_length(14, &self))?;
                 let historical_stats = seq.next_element()?.unwrap_or_default();
+                let career = seq.next_element()?.unwrap_or_default();
+                let injury = seq.next_element()?.unwrap_or_default();
 
                 let mut player = Player {
                     id,
@@ -268,6 +290,8 @@ impl<'de> Deserialize<'de> for Player {
                     tiredness,
                     morale,
                     historical_stats,
+                    career,
+                    injury,
                     build_data: PlayerBuildData::default(),
                 };
 
@@ -325,6 +349,8 @@ impl<'de> Deserialize<'de> for Player {
                 let mut morale = None;
                 let mut compact_skills: Option<Vec<Skill>> = None;
                 let mut historical_stats = None;
+                let mut career = None;
+                let mut injury = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -425,6 +451,20 @@ impl<'de> Deserialize<'de> for Player {
                             }
                             historical_stats = Some(map.next_value()?);
                         }
+
+                        Field::Career => {
+                            if career.is_some() {
+                                return Err(serde::de::Error::duplicate_field("career"));
+                            }
+                            career = Some(map.next_value()?);
+                        }
+
+                        Field::Injury => {
+                            if injury.is_some() {
+                                return Err(serde::de::Error::duplicate_field("injury"));
+                            }
+                            injury = Some(map.next_value()?);
+                        }
                     }
                 }
 
@@ -452,6 +492,8 @@ impl<'de> Deserialize<'de> for Player {
                 let compact_skills = compact_skills
                     .ok_or_else(|| serde::de::Error::missing_field("compact_skills"))?;
                 let historical_stats = historical_stats.unwrap_or_default();
+                let career = career.unwrap_or_default();
+                let injury = injury.unwrap_or_default();
 
                 let mut player = Player {
                     id,
@@ -475,6 +517,8 @@ impl<'de> Deserialize<'de> for Player {
                     tiredness,
                     morale,
                     historical_stats,
+                    career,
+                    injury,
                     build_data: PlayerBuildData::default(),
                 };
 
@@ -530,6 +574,8 @@ impl<'de> Deserialize<'de> for Player {
             "morale",
             "compact_skills",
             "historical_stats",
+            "career",
+            "injury",
         ];
         deserializer.deserialize_struct("Player", FIELDS, PlayerVisitor)
     }
@@ -973,6 +1019,21 @@ impl Player {
         self.tiredness == MAX_SKILL
     }
 
+    pub fn is_injured(&self) -> bool {
+        self.injury.is_some()
+    }
+
+    // A new injury replaces the current one only if it takes longer to recover.
+    pub fn add_injury(&mut self, severity: InjurySeverity) {
+        let injury = Injury::new(severity);
+        if self
+            .injury
+            .is_none_or(|current| current.recovery_left < injury.recovery_left)
+        {
+            self.injury = Some(injury);
+        }
+    }
+
     pub fn add_tiredness(&mut self, tiredness: f32) {
         let max_tiredness = if self.special_trait == Some(Trait::Relentless) {
             0.8 * MAX_SKILL
@@ -1262,9 +1323,10 @@ impl Trait {
 
 #[cfg(test)]
 mod test {
+    use super::Player;
     use crate::{
         app::App,
-        core::skill::Rated,
+        core::{skill::Rated, Injury, InjurySeverity},
         types::{AppResult, HashMapWithResult},
     };
     use itertools::Itertools;
@@ -1300,6 +1362,27 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_player_injury() -> AppResult<()> {
+        let mut player = Player::default().randomize(None);
+        assert!(!player.is_injured());
+
+        player.add_injury(InjurySeverity::Moderate);
+        // A lighter injury does not shorten the recovery.
+        player.add_injury(InjurySeverity::Minor);
+        assert_eq!(player.injury, Some(Injury::new(InjurySeverity::Moderate)));
+
+        let serialized = serde_json::to_string(&player)?;
+        let deserialized: Player = serde_json::from_str(&serialized)?;
+        assert_eq!(deserialized.injury, player.injury);
+
+        player.injury = None;
+        let serialized = serde_json::to_string(&player)?;
+        assert!(!serialized.contains("injury"));
+
+        Ok(())
+    }
+
     #[ignore]
     #[test]
     fn test_players_generation() -> AppResult<()> {
@@ -461,7 +461,7 @@ mod tests {
         let planet_ids = world.planets.keys().collect_vec();
         let from = planet_ids[0].clone();
         let to = planet_ids[1].clone();
-        let mut team = Team::random(None).with_home_planet(from);
+        let mut team = Team::random(None, Tick::now()).with_home_planet(from);
         team.spaceship = spaceship;
         team.current_location = TeamLocation::Travelling {
             from,
@@ -1,7 +1,11 @@
 use super::*;
 use crate::{
     core::{constants::MAX_CREW_SIZE, utils::is_default},
-    game_engine::{tactic::Tactic, types::EnginePlayer, Tournament, TournamentId, TournamentState},
+    game_engine::{
+        tactic::{CustomTactic, DefensiveScheme, Tactic},
+        types::EnginePlayer,
+        Tournament, TournamentId, TournamentState,
+    },
     network::{challenge::Challenge, trade::Trade},
     types::*,
 };
@@ -83,6 +87,15 @@ pub struct Team {
     #[serde(default)]
     pub network_game_rating: GameRating,
     pub game_tactic: Tactic,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub custom_tactics: Vec<CustomTactic>,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub active_custom_tactic: Option<usize>,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub defensive_scheme: DefensiveScheme,
     pub training_focus: Option<TrainingFocus>,
     #[serde(skip)]
     pub sent_trades: HashMap<(PlayerId, PlayerId), Trade>,
@@ -113,7 +126,7 @@ pub struct Team {
 }
 
 impl Team {
-    pub fn random(rng: Option<&mut ChaCha8Rng>) -> Self {
+    pub fn random(rng: Option<&mut ChaCha8Rng>, now: Tick) -> Self {
         let rng = if let Some(r) = rng {
             r
         } else {
@@ -125,10 +138,11 @@ impl Team {
         resources.insert(Resource::SATOSHI, INITIAL_TEAM_BALANCE);
         Self {
             id: TeamId::new_v4(),
-            creation_time: Tick::now(),
+            creation_time: now,
             jersey,
             spaceship: Spaceship::random(rng).with_color_map(ship_color),
             game_tactic: Tactic::random(rng),
+            defensive_scheme: DefensiveScheme::random(rng),
             resources,
             ..Default::default()
         }
@@ -840,6 +854,11 @@ impl Team {
         self.can_change_team_settings()
     }
 
+    pub fn active_custom_tactic(&self) -> Option<&CustomTactic> {
+        self.active_custom_tactic
+            .and_then(|index| self.custom_tactics.get(index))
+    }
+
     pub fn can_change_training_focus(&self) -> AppResult<()> {
         self.can_change_team_settings()
     }
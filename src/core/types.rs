@@ -7,7 +7,7 @@ use super::{
 use crate::{
     core::{Resource, Trait},
     image::color_map::SkinColorMap,
-    types::{AppResult, HashMapWithResult, PlanetId, SystemTimeTick, TeamId, Tick},
+    types::{AppResult, HashMapWithResult, PlanetId, TeamId, Tick},
 };
 use rand::{seq::IteratorRandom, SeedableRng};
 use rand_chacha::ChaCha8Rng;
@@ -20,7 +20,18 @@ use strum::{Display, FromRepr};
 use strum_macros::EnumIter;
 
 #[derive(
-    Debug, Default, PartialEq, Eq, Clone, Copy, EnumIter, Serialize_repr, Deserialize_repr, Hash,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Copy,
+    EnumIter,
+    Serialize_repr,
+    Deserialize_repr,
+    Hash,
 )]
 #[repr(u8)]
 pub enum Region {
@@ -55,7 +66,7 @@ impl From<u8> for Region {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, EnumIter, Hash)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, EnumIter, Hash)]
 pub enum Population {
     Human { region: Region },
     Yardalaim,
@@ -586,10 +597,10 @@ impl<U> Upgrade<U>
 where
     U: UpgradeableElement,
 {
-    pub fn new(target: U, bonus: f32) -> Self {
+    pub fn new(target: U, bonus: f32, started: Tick) -> Self {
         let duration = (target.upgrade_duration() as f32 / bonus) as Tick;
         Self {
-            started: Tick::now(),
+            started,
             duration,
             target,
         }
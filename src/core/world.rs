@@ -11,9 +11,10 @@ use super::team::Team;
 use super::types::{PlayerLocation, TeamBonus, TeamLocation};
 use super::utils::{is_default, PLANET_DATA, TEAM_DATA};
 use crate::core::{
-    AsteroidUpgradeTarget, AutonomousStrategy, GameResult, Honour, Rated, RatedPlayers, Skill,
-    TournamentRegistrationState, Upgrade, MIN_SKILL,
+    AsteroidUpgradeTarget, AutonomousStrategy, Clock, GameResult, Honour, Rated, RatedPlayers,
+    Skill, TournamentRegistrationState, Upgrade, MIN_SKILL,
 };
+use crate::game_engine::adjustment::{GameAdjustment, GameAdjustmentKind, ADJUSTMENT_DELAY};
 use crate::game_engine::game::{Game, GameSummary};
 use crate::game_engine::tactic::Tactic;
 use crate::game_engine::types::{Possession, TeamInGame};
@@ -22,6 +23,7 @@ use crate::game_engine::{
 };
 use crate::image::color_map::ColorMap;
 use crate::network::network_store_data::NetworkStoreData;
+use crate::network::plausibility::{check_team, check_version};
 use crate::network::types::{NetworkGame, NetworkTeam};
 use crate::space_adventure::ControllableSpaceship;
 use crate::space_adventure::SpaceAdventure;
@@ -48,6 +50,12 @@ pub struct World {
     pub dirty_network: bool, // Whether anything relevant for the entwork has changed and thus should be sent over.
     #[serde(skip)]
     pub dirty_ui: bool, // Whether anything relevant for UI has changed and thus should be drawn.
+    #[serde(skip)]
+    pub clock: Clock, // Source of the current tick. Defaults to the system time.
+    #[serde(skip)]
+    pub allow_local_draws: bool, // Whether friendly local games can end in a draw instead of going to overtime.
+    #[serde(skip)]
+    pub headless: bool, // Whether the world runs without persistence, e.g. in simulations.
     pub serialized_size: u64,
     pub seed: u64,
     #[serde(skip_serializing_if = "is_default")]
@@ -113,6 +121,11 @@ impl World {
         }
     }
 
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     pub fn initialize(&mut self, generate_local_world: bool) -> AppResult<()> {
         let rng = &mut ChaCha8Rng::seed_from_u64(self.seed);
         for planet in PLANET_DATA.iter() {
@@ -123,7 +136,7 @@ impl World {
             self.generate_local_world(rng)?;
         }
 
-        let now = Tick::now();
+        let now = self.clock.now();
 
         self.last_tick_min_interval = now;
         self.last_tick_short_interval = now;
@@ -167,6 +180,7 @@ impl World {
             .values()
             .filter(|planet| planet.total_population() > 0)
             .map(|p| p.id)
+            .sorted()
             .collect::<Vec<PlanetId>>();
 
         for idx in 0..team_data.len() {
@@ -186,10 +200,12 @@ impl World {
         ship_name: String,
         team_base_level: Option<f32>,
     ) -> AppResult<TeamId> {
-        let team = Team::random(Some(rng))
+        let mut team = Team::random(Some(rng), self.clock.now())
             .with_name(team_name)
             .with_spaceship_name(ship_name)
             .with_home_planet(home_planet_id);
+        // Draw the id from the rng, so that a seeded world is reproducible.
+        team.id = uuid::Builder::from_random_bytes(rng.random()).into_uuid();
         let team_id = team.id;
 
         let mut planet = self.planets.get_or_err(&team.home_planet_id)?.clone();
@@ -244,7 +260,7 @@ impl World {
         let team = Team {
             id: team_id,
             name,
-            creation_time: Tick::now(),
+            creation_time: self.clock.now(),
             jersey: Jersey {
                 style: jersey_style,
                 color: jersey_colors,
@@ -300,11 +316,12 @@ impl World {
         home_planet: &Planet,
         base_level: f32,
     ) -> AppResult<PlayerId> {
-        let player = Player::default()
+        let mut player = Player::default()
             .with_position(position)
             .with_home_planet(home_planet.id)
             .with_base_level(base_level)
             .randomize(Some(rng));
+        player.id = uuid::Builder::from_random_bytes(rng.random()).into_uuid();
 
         // random(rng, position, home_planet, base_level);
         let player_id = player.id;
@@ -467,7 +484,7 @@ impl World {
                 distance,
             } = team.current_location
             {
-                let new_start = Tick::now();
+                let new_start = self.clock.now();
                 let time_elapsed = new_start - started;
                 let bonus = TeamBonus::SpaceshipSpeed.current_team_bonus(self, &team_id)?;
 
@@ -492,7 +509,7 @@ impl World {
             let mut team = self.teams.get_or_err(&team_id)?.clone();
             // If spaceship or any asteroid has a pending upgrade and engineer was updated recalculate upgrade duration.
             if let Some(upgrade) = team.spaceship.pending_upgrade {
-                let new_start = Tick::now();
+                let new_start = self.clock.now();
                 let time_elapsed = new_start - upgrade.started;
                 let bonus = TeamBonus::Upgrades.current_team_bonus(self, &team_id)?;
 
@@ -502,15 +519,15 @@ impl World {
                 log::debug!(
                     "Update {role}: old upgrade {previous_upgrade_bonus}, new upgrade {bonus}"
                 );
-                let new_upgrade =
-                    Upgrade::new(upgrade.target, bonus).with_duration(new_duration as Tick);
+                let new_upgrade = Upgrade::new(upgrade.target, bonus, new_start)
+                    .with_duration(new_duration as Tick);
                 team.spaceship.pending_upgrade = Some(new_upgrade);
             }
 
             for asteroid_id in team.asteroid_ids.iter() {
                 let asteroid = self.planets.get_or_err(asteroid_id)?;
                 if let Some(upgrade) = asteroid.pending_upgrade {
-                    let new_start = Tick::now();
+                    let new_start = self.clock.now();
                     let time_elapsed = new_start - upgrade.started;
                     let bonus = TeamBonus::Upgrades.current_team_bonus(self, &team_id)?;
 
@@ -523,8 +540,8 @@ impl World {
 
                     let mut asteroid = asteroid.clone();
 
-                    let new_upgrade =
-                        Upgrade::new(upgrade.target, bonus).with_duration(new_duration as Tick);
+                    let new_upgrade = Upgrade::new(upgrade.target, bonus, new_start)
+                        .with_duration(new_duration as Tick);
 
                     asteroid.pending_upgrade = Some(new_upgrade);
                     self.planets.insert(asteroid.id, asteroid);
@@ -647,7 +664,7 @@ impl World {
                 distance,
             } = team.current_location
             {
-                let new_start = Tick::now();
+                let new_start = self.clock.now();
                 let time_elapsed = new_start - started;
                 let bonus = TeamBonus::SpaceshipSpeed.current_team_bonus(self, &team_id)?;
 
@@ -673,7 +690,7 @@ impl World {
             let mut team = self.teams.get_or_err(&team_id)?.clone();
             // If spaceship or any asteroid has a pending upgrade and engineer was updated recalculate upgrade duration.
             if let Some(upgrade) = team.spaceship.pending_upgrade {
-                let new_start = Tick::now();
+                let new_start = self.clock.now();
                 let time_elapsed = new_start - upgrade.started;
                 let bonus = TeamBonus::Upgrades.current_team_bonus(self, &team_id)?;
 
@@ -684,15 +701,15 @@ impl World {
                     "Update {role}: old upgrade {previous_upgrade_bonus}, new upgrade {bonus}"
                 );
 
-                let new_upgrade =
-                    Upgrade::new(upgrade.target, bonus).with_duration(new_duration as Tick);
+                let new_upgrade = Upgrade::new(upgrade.target, bonus, new_start)
+                    .with_duration(new_duration as Tick);
                 team.spaceship.pending_upgrade = Some(new_upgrade);
             }
 
             for asteroid_id in team.asteroid_ids.iter() {
                 let asteroid = self.planets.get_or_err(asteroid_id)?;
                 if let Some(upgrade) = asteroid.pending_upgrade {
-                    let new_start = Tick::now();
+                    let new_start = self.clock.now();
                     let time_elapsed = new_start - upgrade.started;
                     let bonus = TeamBonus::Upgrades.current_team_bonus(self, &team_id)?;
 
@@ -704,8 +721,8 @@ impl World {
                     );
 
                     let mut asteroid = asteroid.clone();
-                    let new_upgrade =
-                        Upgrade::new(upgrade.target, bonus).with_duration(new_duration as Tick);
+                    let new_upgrade = Upgrade::new(upgrade.target, bonus, new_start)
+                        .with_duration(new_duration as Tick);
                     asteroid.pending_upgrade = Some(new_upgrade);
                     self.planets.insert(asteroid.id, asteroid);
                 }
@@ -1038,6 +1055,7 @@ impl World {
         starting_at: Tick,
         planet_id: PlanetId,
         part_of_tournament: Option<TournamentId>,
+        allow_draw: bool,
     ) -> AppResult<GameId> {
         // Generate deterministic game id from team IDs and starting time.
         // Two games starting at u64::MAX milliseconds apart ~ 584_942_417 years
@@ -1073,6 +1091,20 @@ impl World {
             player.add_morale(morale_bonus);
         }
 
+        // The bonus is part of the morale players enter the game with.
+        for team_in_game in [&mut home_team_in_game, &mut away_team_in_game] {
+            team_in_game.initial_morale = team_in_game
+                .initial_positions
+                .iter()
+                .map(|id| {
+                    team_in_game
+                        .players
+                        .get_or_err(id)
+                        .map(|player| player.morale)
+                })
+                .collect::<AppResult<_>>()?;
+        }
+
         let game = Game::new(
             game_id,
             home_team_in_game,
@@ -1082,7 +1114,8 @@ impl World {
             planet.total_population(),
             planet.name.as_str(),
             part_of_tournament,
-        );
+        )
+        .with_draw_allowed(allow_draw);
         self.games.insert(game.id, game);
 
         Ok(game_id)
@@ -1111,6 +1144,12 @@ impl World {
 
         home_team.can_accept_network_challenge(&away_team)?;
 
+        for team_in_game in [&home_team_in_game, &away_team_in_game] {
+            if let Some(custom_tactic) = team_in_game.custom_tactic.as_ref() {
+                custom_tactic.validate()?;
+            }
+        }
+
         let location = match home_team.current_location {
             TeamLocation::OnPlanet { planet_id } => planet_id,
             _ => {
@@ -1124,6 +1163,7 @@ impl World {
             starting_at,
             location,
             None,
+            false,
         )?;
 
         if let Some(previous_game_id) = home_team.current_game {
@@ -1183,6 +1223,7 @@ impl World {
             starting_at,
             location,
             None,
+            self.allow_local_draws,
         )?;
 
         for team_id in team_ids.iter() {
@@ -1219,6 +1260,15 @@ impl World {
             ));
         }
 
+        for team_in_game in [
+            &network_game.home_team_in_game,
+            &network_game.away_team_in_game,
+        ] {
+            if let Some(custom_tactic) = team_in_game.custom_tactic.as_ref() {
+                custom_tactic.validate()?;
+            }
+        }
+
         if !self.games.contains_key(&network_game.id) {
             let planet = self.planets.get_or_err(&network_game.location)?;
             let mut game = Game::new(
@@ -1232,8 +1282,12 @@ impl World {
                 network_game.part_of_tournament,
             );
 
-            while game.timer.value < network_game.timer.value && !game.timer.has_ended() {
-                game.tick(Tick::now());
+            for adjustment in network_game.adjustments {
+                game.add_adjustment(adjustment)?;
+            }
+
+            while game.timer.value < network_game.timer.value && !game.has_ended() {
+                game.tick(self.clock.now());
             }
 
             self.games.insert(game.id, game);
@@ -1243,6 +1297,59 @@ impl World {
         Ok(())
     }
 
+    // Schedule a decision of the own team in its current game.
+    pub fn add_own_game_adjustment(
+        &mut self,
+        game_id: GameId,
+        kind: GameAdjustmentKind,
+    ) -> AppResult<GameAdjustment> {
+        let own_team_id = self.own_team_id;
+        let game = self
+            .games
+            .get_mut(&game_id)
+            .ok_or_else(|| anyhow!("Game {game_id} not found"))?;
+
+        if !game.has_started(self.clock.now()) {
+            return Err(anyhow!("Game has not started yet"));
+        }
+
+        let adjustment = GameAdjustment::new(own_team_id, game.timer.plus(ADJUSTMENT_DELAY), kind);
+        game.add_adjustment(adjustment.clone())?;
+        self.dirty_ui = true;
+
+        Ok(adjustment)
+    }
+
+    pub fn add_network_game_adjustment(
+        &mut self,
+        peer_id: Option<PeerId>,
+        game_id: GameId,
+        adjustment: GameAdjustment,
+    ) -> AppResult<()> {
+        if adjustment.team_id == self.own_team_id {
+            return Err(anyhow!(
+                "Cannot receive own team game adjustment over the network."
+            ));
+        }
+
+        let game = self
+            .games
+            .get_mut(&game_id)
+            .ok_or_else(|| anyhow!("Game {game_id} not found"))?;
+
+        let team = game
+            .team_in_game(adjustment.team_id)
+            .ok_or_else(|| anyhow!("Team is not playing in game {game_id}"))?;
+        if team.peer_id.is_none() || team.peer_id != peer_id {
+            return Err(anyhow!("Game adjustment was not sent by the team peer."));
+        }
+
+        game.add_adjustment(adjustment)?;
+        self.dirty_ui = true;
+
+        Ok(())
+    }
+
     pub fn add_network_team(&mut self, network_team: NetworkTeam) -> AppResult<bool> {
         let NetworkTeam {
             team,
@@ -1258,6 +1365,32 @@ impl World {
             return Err(anyhow!("Cannot receive own team over the network."));
         }
 
+        check_team(&team, &players)?;
+        check_version(
+            "team",
+            self.teams.get(&team.id).map(|db_team| db_team.version),
+            team.version,
+        )?;
+        for player in players.values() {
+            check_version(
+                "player",
+                self.players
+                    .get(&player.id)
+                    .map(|db_player| db_player.version),
+                player.version,
+            )?;
+        }
+
+        // Custom tactics are simulated as received, so they must respect the same bounds as ours.
+        for custom_tactic in team.custom_tactics.iter() {
+            custom_tactic.validate()?;
+        }
+        if let Some(index) = team.active_custom_tactic {
+            if index >= team.custom_tactics.len() {
+                return Err(anyhow!("Invalid active custom tactic."));
+            }
+        }
+
         // Check if we are receiving a team with which we have an open challenge.
         // Note: there could be a race condition where we receive a team over the network right after
         //       accepting the challenge but before the challenge has been finalized on our side.
@@ -1390,7 +1523,7 @@ impl World {
         // This works if we assume that we can't lag behind more than a SHORT interval (1 second).
         // DEBUG_TIME_MULTIPLIER than cannot be too large or due to finite FPS this condition
         // would always return true.
-        Tick::now() > self.last_tick_short_interval + TickInterval::SHORT
+        self.clock.now() > self.last_tick_short_interval + TickInterval::SHORT
     }
 
     fn resources_found_after_exploration(
@@ -1466,6 +1599,9 @@ impl World {
         // ROund up to keep it in sync across network.
         current_tick -= current_tick % TickInterval::SHORT;
 
+        // Derive the rng from the world seed, so that a seeded world always evolves in the same way.
+        let rng = &mut ChaCha8Rng::seed_from_u64(self.seed.wrapping_add(current_tick));
+
         if current_tick >= self.last_tick_short_interval + TickInterval::SHORT {
             self.tick_games(current_tick)?;
             callbacks.append(&mut self.tick_tournaments(current_tick)?);
@@ -1498,17 +1634,18 @@ impl World {
 
         if current_tick >= self.last_tick_medium_interval + TickInterval::MEDIUM {
             self.tick_tiredness_recovery()?;
+            self.tick_injury_recovery()?;
 
-            for cb in self.tick_player_leaving_team_for_low_morale(current_tick)? {
+            for cb in self.tick_player_leaving_team_for_low_morale(rng, current_tick)? {
                 callbacks.push(cb);
             }
 
             if !is_simulating {
-                self.tick_team_position_assignment()?;
+                self.tick_team_position_assignment(rng)?;
             }
 
             if self.games.len() < AUTO_GENERATE_GAMES_NUMBER {
-                self.generate_random_games()?;
+                self.generate_random_games(rng)?;
             }
 
             // Once every MEDIUM interval, set dirty_network flag,
@@ -1521,7 +1658,7 @@ impl World {
         if current_tick >= self.last_tick_long_interval + TickInterval::LONG {
             self.tick_players_update();
 
-            for cb in self.tick_player_retirement(current_tick)? {
+            for cb in self.tick_player_retirement(rng, current_tick)? {
                 callbacks.push(cb);
             }
 
@@ -1533,8 +1670,8 @@ impl World {
 
             // Create free pirates only if this is the last time window to do so.
             // This will run also during a simulation, but only once.
-            if Tick::now() < current_tick + TickInterval::LONG {
-                callbacks.push(self.tick_free_pirates(current_tick)?);
+            if self.clock.now() < current_tick + TickInterval::LONG {
+                callbacks.push(self.tick_free_pirates(rng, current_tick)?);
             }
 
             self.last_tick_long_interval += TickInterval::LONG;
@@ -1597,6 +1734,7 @@ impl World {
 
                     player.tiredness = game_player.tiredness;
                     player.morale = game_player.morale;
+                    player.injury = game_player.injury;
 
                     player.version += 1;
                     player.add_morale(MORALE_INCREASE_PER_GAME);
@@ -1637,6 +1775,19 @@ impl World {
                     // Plus/minus is not updated automatically and must be updated by hand
                     player.historical_stats.plus_minus += stats.plus_minus;
 
+                    let result = match game.winner {
+                        Some(winner) if winner == team.team_id => GameResult::Win,
+                        Some(_) => GameResult::Loss,
+                        None => GameResult::Draw,
+                    };
+                    player.career.record_game(
+                        team.team_id,
+                        &team.name,
+                        current_tick,
+                        stats,
+                        result,
+                    );
+
                     player.reputation = (player.reputation
                         + REPUTATION_PER_EXPERIENCE
                             * stats.seconds_played as f32
@@ -1671,7 +1822,9 @@ impl World {
             if game.home_team_in_game.team_id == self.own_team_id
                 || game.away_team_in_game.team_id == self.own_team_id
             {
-                save_game(game)?;
+                if !self.headless {
+                    save_game(game)?;
+                }
                 // Update network that game has ended.
                 self.dirty_network = true;
 
@@ -1768,22 +1921,31 @@ impl World {
                     match game.winner {
                         Some(winner) => {
                             if winner == *team_id {
-                                team.network_game_rating
-                                    .update(GameResult::Win, other_rating);
+                                team.network_game_rating.update(
+                                    GameResult::Win,
+                                    other_rating,
+                                    current_tick,
+                                );
                                 team.reputation = (team.reputation
                                     + ReputationModifier::HIGH_BONUS
                                     + ReputationModifier::MEDIUM_BONUS)
                                     .bound();
                             } else {
-                                team.network_game_rating
-                                    .update(GameResult::Loss, other_rating);
+                                team.network_game_rating.update(
+                                    GameResult::Loss,
+                                    other_rating,
+                                    current_tick,
+                                );
                                 team.reputation =
                                     (team.reputation + ReputationModifier::MEDIUM_MALUS).bound();
                             }
                         }
                         None => {
-                            team.network_game_rating
-                                .update(GameResult::Draw, other_rating);
+                            team.network_game_rating.update(
+                                GameResult::Draw,
+                                other_rating,
+                                current_tick,
+                            );
                             team.reputation =
                                 (team.reputation + ReputationModifier::MEDIUM_BONUS).bound()
                         }
@@ -1807,37 +1969,48 @@ impl World {
                     self.teams.insert(team.id, team);
                 }
             } else {
+                // Both teams are rated against the opponent rating from before the game.
+                let local_game_ratings = [
+                    self.teams
+                        .get_or_err(&game.home_team_in_game.team_id)?
+                        .local_game_rating
+                        .clone(),
+                    self.teams
+                        .get_or_err(&game.away_team_in_game.team_id)?
+                        .local_game_rating
+                        .clone(),
+                ];
                 for (idx, team_id) in team_ids.iter().enumerate() {
                     let mut team = self.teams.get_or_err(team_id)?.clone();
 
-                    let other_rating = if idx == 0 {
-                        &self
-                            .teams
-                            .get_or_err(&game.away_team_in_game.team_id)?
-                            .local_game_rating
-                    } else {
-                        &self
-                            .teams
-                            .get_or_err(&game.home_team_in_game.team_id)?
-                            .local_game_rating
-                    };
+                    let other_rating = &local_game_ratings[1 - idx];
 
                     match game.winner {
                         Some(winner) => {
                             if winner == *team_id {
-                                team.local_game_rating.update(GameResult::Win, other_rating);
+                                team.local_game_rating.update(
+                                    GameResult::Win,
+                                    other_rating,
+                                    current_tick,
+                                );
                                 team.reputation =
                                     (team.reputation + ReputationModifier::HIGH_BONUS).bound();
                             } else {
-                                team.local_game_rating
-                                    .update(GameResult::Loss, other_rating);
+                                team.local_game_rating.update(
+                                    GameResult::Loss,
+                                    other_rating,
+                                    current_tick,
+                                );
                                 team.reputation =
                                     (team.reputation + ReputationModifier::MEDIUM_MALUS).bound();
                             }
                         }
                         None => {
-                            team.local_game_rating
-                                .update(GameResult::Draw, other_rating);
+                            team.local_game_rating.update(
+                                GameResult::Draw,
+                                other_rating,
+                                current_tick,
+                            );
                             team.reputation =
                                 (team.reputation + ReputationModifier::MEDIUM_BONUS).bound()
                         }
@@ -2071,7 +2244,9 @@ impl World {
                     self.recently_finished_tournaments
                         .insert(tournament.id, tournament.clone());
 
-                    save_tournament(tournament)?;
+                    if !self.headless {
+                        save_tournament(tournament)?;
+                    }
                 }
 
                 for team_id in tournament.participants.keys() {
@@ -2338,9 +2513,36 @@ impl World {
         Ok(())
     }
 
-    fn tick_team_position_assignment(&mut self) -> AppResult<()> {
+    fn tick_injury_recovery(&mut self) -> AppResult<()> {
+        let teams = self
+            .teams
+            .values()
+            .filter(|team| team.current_game.is_none() && team.peer_id.is_none())
+            .collect::<Vec<&Team>>();
+
+        for team in teams {
+            let bonus = TeamBonus::TirednessRecovery.current_team_bonus(self, &team.id)?;
+            for player_id in team.player_ids.iter() {
+                let player = if let Some(player) = self.players.get_mut(player_id) {
+                    player
+                } else {
+                    continue;
+                };
+                if let Some(injury) = player.injury.as_mut() {
+                    injury.recover(TickInterval::MEDIUM, bonus);
+                    if injury.has_healed() {
+                        player.injury = None;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn tick_team_position_assignment(&mut self, rng: &mut ChaCha8Rng) -> AppResult<()> {
         //TODO: once we remove local teams, we can completely remove this function
-        for team in self.teams.values_mut() {
+        for team in self.teams.values_mut().sorted_by_key(|team| team.id) {
             if team.peer_id.is_some() {
                 continue;
             }
@@ -2361,17 +2563,19 @@ impl World {
                 team.player_ids = Team::best_position_assignment(pirates);
             }
 
-            let rng = &mut ChaCha8Rng::from_rng(&mut rand::rng());
             team.game_tactic = Tactic::random(rng);
         }
 
         Ok(())
     }
 
-    fn tick_free_pirates(&mut self, current_tick: Tick) -> AppResult<UiCallback> {
+    fn tick_free_pirates(
+        &mut self,
+        rng: &mut ChaCha8Rng,
+        current_tick: Tick,
+    ) -> AppResult<UiCallback> {
         self.players.retain(|_, player| player.team.is_some());
 
-        let rng = &mut ChaCha8Rng::seed_from_u64(rand::random());
         for planet in PLANET_DATA.iter() {
             self.populate_planet(rng, planet)?;
         }
@@ -2396,7 +2600,7 @@ impl World {
         let mut hired_player_ids: Vec<PlayerId> = vec![];
         let mut hiring_team_ids: Vec<TeamId> = vec![];
 
-        for (&team_id, team) in self.teams.iter() {
+        for (&team_id, team) in self.teams.iter().sorted_by_key(|(&id, _)| id) {
             if team_id == self.own_team_id {
                 continue;
             }
@@ -2586,13 +2790,14 @@ impl World {
 
     fn tick_player_leaving_team_for_low_morale(
         &mut self,
+        rng: &mut ChaCha8Rng,
         current_tick: Tick,
     ) -> AppResult<Vec<UiCallback>> {
         let mut messages = vec![];
 
         let mut releasing_player_ids = vec![];
 
-        for &player_id in self.players.keys() {
+        for &player_id in self.players.keys().sorted() {
             let player = self.players.get_or_err(&player_id)?;
             if player.team.is_none() {
                 continue;
@@ -2610,7 +2815,6 @@ impl World {
                 continue;
             }
 
-            let rng = &mut ChaCha8Rng::from_rng(&mut rand::rng());
             if player.morale < MORALE_THRESHOLD_FOR_LEAVING
                 && rng.random_bool(
                     (1.0 - player.morale / MAX_SKILL) as f64 * LEAVING_PROBABILITY_MORALE_MODIFIER,
@@ -2648,12 +2852,16 @@ impl World {
         Ok(messages)
     }
 
-    fn tick_player_retirement(&mut self, current_tick: Tick) -> AppResult<Vec<UiCallback>> {
+    fn tick_player_retirement(
+        &mut self,
+        rng: &mut ChaCha8Rng,
+        current_tick: Tick,
+    ) -> AppResult<Vec<UiCallback>> {
         let mut messages = vec![];
 
         let mut releasing_player_ids = vec![];
 
-        for &player_id in self.players.keys() {
+        for &player_id in self.players.keys().sorted() {
             let player = self.players.get_or_err(&player_id)?;
             if player.team.is_none() {
                 continue;
@@ -2671,7 +2879,6 @@ impl World {
                 continue;
             }
 
-            let rng = &mut ChaCha8Rng::from_rng(&mut rand::rng());
             if player.info.relative_age() > MIN_RELATIVE_RETIREMENT_AGE {
                 // Add extra check to avoid running rng call unnecessarily.
                 if player.info.relative_age() > rng.random_range(MIN_RELATIVE_RETIREMENT_AGE..1.0) {
@@ -2710,6 +2917,7 @@ impl World {
     }
 
     fn update_own_team_honours(&mut self) -> AppResult<()> {
+        let now = self.clock.now();
         let own_team = self
             .teams
             .get_mut(&self.own_team_id)
@@ -2717,7 +2925,13 @@ impl World {
 
         for honour in Honour::iter() {
             if !own_team.honours.contains(&honour)
-                && honour.conditions_met(own_team, &self.past_games, &self.players, &self.planets)
+                && honour.conditions_met(
+                    own_team,
+                    &self.past_games,
+                    &self.players,
+                    &self.planets,
+                    now,
+                )
             {
                 own_team.honours.insert(honour);
             }
@@ -2726,9 +2940,8 @@ impl World {
         Ok(())
     }
 
-    fn generate_random_games(&mut self) -> AppResult<()> {
-        let rng = &mut ChaCha8Rng::from_rng(&mut rand::rng());
-        for planet in self.planets.values() {
+    fn generate_random_games(&mut self, rng: &mut ChaCha8Rng) -> AppResult<()> {
+        for planet in self.planets.values().sorted_by_key(|planet| planet.id) {
             if planet.team_ids.len() < 2 {
                 continue;
             }
@@ -2739,7 +2952,12 @@ impl World {
                 .map(|&id| self.teams.get_or_err(&id))
                 .filter(|team_res| {
                     if let Ok(team) = team_res {
-                        if team.player_ids.len() < MIN_PLAYERS_PER_GAME {
+                        let healthy_players = team
+                            .player_ids
+                            .iter()
+                            .filter(|id| self.players.get(id).is_some_and(|p| !p.is_injured()))
+                            .count();
+                        if healthy_players < MIN_PLAYERS_PER_GAME {
                             return false;
                         }
 
@@ -2796,7 +3014,7 @@ impl World {
             });
 
             self.tournaments.retain(|_, t| {
-                (t.is_team_registered(&self.own_team_id) && !t.has_started(Tick::now()))
+                (t.is_team_registered(&self.own_team_id) && !t.has_started(self.clock.now()))
                     || t.is_team_participating(&self.own_team_id)
                     || t.organizer_id == self.own_team_id
             });
@@ -2834,7 +3052,7 @@ impl World {
                     || matches!(own_team_current_location, Some(id) if id == planet.id )
             });
             self.tournaments.retain(|_, t| {
-                (t.is_team_registered(&self.own_team_id) && !t.has_started(Tick::now()))
+                (t.is_team_registered(&self.own_team_id) && !t.has_started(self.clock.now()))
                     || t.is_team_participating(&self.own_team_id)
                     || t.organizer_id == self.own_team_id
             });
@@ -3079,6 +3297,8 @@ mod test {
             world::{TickInterval, AU, EXPLORATION_DURATION},
             RatedPlayers, DEFAULT_PLANET_ID, MIN_PLAYERS_PER_GAME,
         },
+        game_engine::types::TeamInGame,
+        store::load_game,
         types::{HashMapWithResult, StorableResourceMap, SystemTimeTick, Tick},
         ui::UiCallback,
     };
@@ -3480,7 +3700,8 @@ mod test {
 
         player.info.age = player.info.population.max_age();
         world.players.insert(player_id, player);
-        world.tick_player_retirement(Tick::now())?;
+        let rng = &mut ChaCha8Rng::seed_from_u64(0);
+        world.tick_player_retirement(rng, Tick::now())?;
 
         let player = world.players.get_or_err(&player_id)?;
         assert!(player.team.is_none());
@@ -3503,9 +3724,10 @@ mod test {
         world.players.insert(player_id, player);
 
         // Players with low morale quit a team randomly
+        let rng = &mut ChaCha8Rng::seed_from_u64(0);
         let mut idx = 0;
         loop {
-            world.tick_player_leaving_team_for_low_morale(Tick::now())?;
+            world.tick_player_leaving_team_for_low_morale(rng, Tick::now())?;
             let player: &crate::core::player::Player = world.players.get_or_err(&player_id)?;
             if player.team.is_none() {
                 break;
@@ -3643,9 +3865,10 @@ mod test {
 
         let world = &mut app.world;
 
+        let rng = &mut ChaCha8Rng::seed_from_u64(0);
         for i in 0..11 {
             assert!(world.games.len() == i);
-            world.generate_random_games()?;
+            world.generate_random_games(rng)?;
         }
 
         for game in world.games.values() {
@@ -3657,4 +3880,49 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_headless_world_does_not_persist_games() -> AppResult<()> {
+        let mut app = App::test_default()?;
+
+        let world = &mut app.world;
+        world.headless = true;
+
+        let own_team_id = world.own_team_id;
+        let opponent_id = *world
+            .teams
+            .keys()
+            .find(|&&team_id| team_id != own_team_id)
+            .expect("There should be at least one other team");
+        let planet_id = world
+            .teams
+            .get_or_err(&own_team_id)?
+            .is_on_planet()
+            .expect("Own team should be on a planet");
+
+        let home_team_in_game =
+            TeamInGame::from_team_id(&own_team_id, &world.teams, &world.players)?;
+        let away_team_in_game =
+            TeamInGame::from_team_id(&opponent_id, &world.teams, &world.players)?;
+        let mut current_tick = world.last_tick_short_interval;
+        let game_id = world.generate_game_no_checks(
+            home_team_in_game,
+            away_team_in_game,
+            current_tick,
+            planet_id,
+            None,
+            false,
+        )?;
+
+        while !world.games.get_or_err(&game_id)?.has_ended() {
+            current_tick += TickInterval::SHORT;
+            world.tick_games(current_tick)?;
+        }
+        world.cleanup_games(current_tick)?;
+
+        assert!(world.past_games.contains_key(&game_id));
+        assert!(load_game(&game_id).is_err());
+
+        Ok(())
+    }
 }
@@ -6,8 +6,8 @@ use super::{
 use crate::{
     core::{utils::is_default, GamePosition, Player, MAX_GAME_POSITION},
     game_engine::{
-        brawl, end_of_quarter, fastbreak, isolation, jump_ball, off_the_screen, pick_and_roll,
-        post, rebound, shot, start_of_quarter, total_brawl,
+        brawl, end_of_quarter, fastbreak, foul, isolation, jump_ball, off_the_screen,
+        pick_and_roll, post, rebound, shot, start_of_quarter, total_brawl,
     },
 };
 use core::fmt::Debug;
@@ -46,6 +46,7 @@ pub enum ActionSituation {
     Fastbreak,
     Dunk,
     ForcedOffTheScreenAction, // FIXME: would be better to use an interal enum property action: Action
+    FreeThrow,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
@@ -79,6 +80,10 @@ pub struct ActionOutput {
     #[serde(skip_serializing_if = "is_default")]
     #[serde(default)]
     pub foul_on: Option<usize>,
+    // Free throws left to shoot when the situation is FreeThrow.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub free_throws: u8,
     #[serde(skip_serializing_if = "is_default")]
     #[serde(default)]
     pub home_score: u16,
@@ -112,6 +117,8 @@ pub enum Action {
     Dunk,
     Fastbreak,
     TotalBrawl,
+    Foul,
+    FreeThrow,
 }
 
 impl Action {
@@ -146,6 +153,8 @@ impl Action {
             Action::Brawl => brawl::execute(input, game, action_rng, description_rng),
             Action::Fastbreak => fastbreak::execute(input, game, action_rng, description_rng),
             Action::TotalBrawl => total_brawl::execute(input, game, action_rng, description_rng),
+            Action::Foul => foul::execute(input, game, action_rng, description_rng),
+            Action::FreeThrow => foul::execute_free_throw(input, game, action_rng, description_rng),
         };
         output.random_seed = action_rng.get_seed();
         output
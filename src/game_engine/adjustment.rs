@@ -0,0 +1,507 @@
+use super::{
+    action::{ActionOutput, ActionSituation},
+    game::Game,
+    tactic::{CustomTactic, Tactic},
+    timer::Timer,
+    types::{EnginePlayer, TeamInGame},
+};
+use crate::{
+    core::skill::GameSkill,
+    types::{AppResult, PlayerId, TeamId},
+};
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_TIMEOUTS_PER_GAME: usize = 3;
+// Game seconds without actions after a timeout is called. The game clock keeps running.
+pub const TIMEOUT_DURATION: u16 = 30;
+// Game seconds between a decision and the earliest moment it can be applied,
+// so that network peers receive it before reaching that point in the game.
+pub const ADJUSTMENT_DELAY: u16 = 5;
+const TIMEOUT_TIREDNESS_RECOVERY: f32 = 6.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum GameAdjustmentKind {
+    Timeout,
+    ChangeTactic {
+        tactic: Tactic,
+        custom_tactic: Option<CustomTactic>,
+    },
+    Substitution {
+        player_out: PlayerId,
+        player_in: PlayerId,
+    },
+}
+
+// A decision of a team during a live game. It is applied at the first dead ball
+// after timer where it is allowed: timeouts during quarters, tactic changes during
+// breaks and timeouts, substitutions at any dead ball.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameAdjustment {
+    pub team_id: TeamId,
+    pub timer: Timer,
+    pub kind: GameAdjustmentKind,
+}
+
+impl GameAdjustment {
+    pub fn new(team_id: TeamId, timer: Timer, kind: GameAdjustmentKind) -> Self {
+        Self {
+            team_id,
+            timer,
+            kind,
+        }
+    }
+
+    // Checks that do not depend on the game state at the moment of application.
+    pub fn validate(&self, team: &TeamInGame) -> AppResult<()> {
+        if self.team_id != team.team_id {
+            return Err(anyhow!("Adjustment is not for team {}", team.name));
+        }
+
+        match &self.kind {
+            GameAdjustmentKind::Timeout => {}
+            GameAdjustmentKind::ChangeTactic { custom_tactic, .. } => {
+                if let Some(custom_tactic) = custom_tactic {
+                    custom_tactic.validate()?;
+                }
+            }
+            GameAdjustmentKind::Substitution {
+                player_out,
+                player_in,
+            } => {
+                if player_out == player_in {
+                    return Err(anyhow!("Cannot substitute a player with themselves"));
+                }
+                if !team.players.contains_key(player_out) || !team.players.contains_key(player_in) {
+                    return Err(anyhow!("Substituted players should be part of the team"));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// The most tired player on court and the best rested replacement for their position.
+pub fn suggested_substitution(team: &TeamInGame) -> Option<(PlayerId, PlayerId)> {
+    let (out_id, out_position) = team
+        .players
+        .values()
+        .filter(|p| !p.is_knocked_out())
+        .filter_map(|p| {
+            let position = team.stats.get(&p.id)?.position?;
+            Some((p, position))
+        })
+        .max_by(|(a, _), (b, _)| a.tiredness.total_cmp(&b.tiredness))
+        .map(|(p, position)| (p.id, position))?;
+
+    let in_id = team
+        .players
+        .values()
+        .filter(|p| !p.is_knocked_out() && !p.is_injured())
+        .filter(|p| {
+            team.stats
+                .get(&p.id)
+                .map(|s| !s.is_playing() && !s.is_fouled_out())
+                .unwrap_or(false)
+        })
+        .max_by(|a, b| {
+            a.in_game_rating_at_position(out_position)
+                .total_cmp(&b.in_game_rating_at_position(out_position))
+        })
+        .map(|p| p.id)?;
+
+    Some((out_id, in_id))
+}
+
+enum Application {
+    Applied(Box<ActionOutput>),
+    Postponed,
+    Dropped,
+}
+
+fn later(a: Timer, b: Timer) -> Timer {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+fn info_output(last: &ActionOutput, game: &Game, description: String) -> ActionOutput {
+    // Keep the last action as base so that the next action sees the same situation.
+    ActionOutput {
+        description,
+        start_at: game.timer,
+        end_at: later(last.end_at, game.timer),
+        attack_stats_update: None,
+        defense_stats_update: None,
+        score_change: 0,
+        ..last.clone()
+    }
+}
+
+fn apply_timeout(game: &mut Game, team_id: TeamId, last: &ActionOutput) -> Application {
+    if game.timer.is_break() || game.is_in_timeout() {
+        return Application::Postponed;
+    }
+
+    if game.timeouts_used(team_id) >= MAX_TIMEOUTS_PER_GAME {
+        return Application::Dropped;
+    }
+
+    let end_at = game
+        .timer
+        .plus(TIMEOUT_DURATION)
+        .value
+        .min(game.timer.period().next().start());
+    let team = game
+        .team_in_game_mut(team_id)
+        .expect("Adjustment team should be in game");
+    for (id, player) in team.players.iter_mut() {
+        let is_playing = team.stats.get(id).map(|s| s.is_playing()).unwrap_or(false);
+        if is_playing && !player.is_knocked_out() {
+            player.tiredness = (player.tiredness - TIMEOUT_TIREDNESS_RECOVERY).bound();
+        }
+    }
+    let team_name = team.name.clone();
+
+    game.start_timeout(end_at);
+
+    let mut output = info_output(
+        last,
+        game,
+        format!("Timeout {team_name}! The crew gathers around the captain to catch some breath."),
+    );
+    output.end_at = later(Timer::from(end_at), output.end_at);
+    Application::Applied(Box::new(output))
+}
+
+fn apply_change_tactic(
+    game: &mut Game,
+    team_id: TeamId,
+    tactic: Tactic,
+    custom_tactic: Option<CustomTactic>,
+    last: &ActionOutput,
+) -> Application {
+    if !game.timer.is_break() && !game.is_in_timeout() {
+        return Application::Postponed;
+    }
+
+    let team = game
+        .team_in_game_mut(team_id)
+        .expect("Adjustment team should be in game");
+    team.tactic = custom_tactic.as_ref().map(|c| c.base).unwrap_or(tactic);
+    team.custom_tactic = custom_tactic;
+    let description = format!("{} switches to {}.", team.name, team.tactic_name());
+
+    Application::Applied(Box::new(info_output(last, game, description)))
+}
+
+fn apply_substitution(
+    game: &mut Game,
+    team_id: TeamId,
+    player_out: PlayerId,
+    player_in: PlayerId,
+    last: &ActionOutput,
+) -> Application {
+    let team = game
+        .team_in_game_mut(team_id)
+        .expect("Adjustment team should be in game");
+
+    let (out_position, in_is_playing) =
+        match (team.stats.get(&player_out), team.stats.get(&player_in)) {
+            // Fouled out players cannot get back in.
+            (Some(out_stats), Some(in_stats)) if !in_stats.is_fouled_out() => {
+                (out_stats.position, in_stats.is_playing())
+            }
+            _ => return Application::Dropped,
+        };
+
+    let out_position = match out_position {
+        Some(position) if !in_is_playing => position,
+        _ => return Application::Dropped,
+    };
+
+    let (out_name, in_name) = match (team.players.get(&player_out), team.players.get(&player_in)) {
+        (Some(out), Some(player)) if !player.is_knocked_out() && !player.is_injured() => {
+            (out.info.short_name(), player.info.short_name())
+        }
+        _ => return Application::Dropped,
+    };
+
+    if let Some(stats) = team.stats.get_mut(&player_out) {
+        stats.position = None;
+    }
+    if let Some(stats) = team.stats.get_mut(&player_in) {
+        stats.position = Some(out_position);
+    }
+
+    let description = format!(
+        "{} calls {} to the bench, {} gets in.",
+        team.name, out_name, in_name
+    );
+
+    Application::Applied(Box::new(info_output(last, game, description)))
+}
+
+// Apply the pending adjustments whose time has come, if the ball is dead.
+// Applied adjustments are moved to game.adjustments, invalid ones are discarded.
+pub(crate) fn apply_pending(game: &mut Game) {
+    if game.pending_adjustments.is_empty() {
+        return;
+    }
+
+    let last = game
+        .action_results
+        .last()
+        .cloned()
+        .expect("There should be at least an action");
+
+    let is_dead_ball = game.is_in_timeout()
+        || (game.timer.is_break() && last.situation == ActionSituation::EndOfQuarter)
+        || (game.has_reached_next_step()
+            && matches!(
+                last.situation,
+                ActionSituation::BallInBackcourt | ActionSituation::AfterSubstitution
+            ));
+
+    if !is_dead_ball {
+        return;
+    }
+
+    let pending = std::mem::take(&mut game.pending_adjustments);
+    let mut still_pending = vec![];
+    for adjustment in pending {
+        if adjustment.timer > game.timer {
+            still_pending.push(adjustment);
+            continue;
+        }
+
+        let last = game
+            .action_results
+            .last()
+            .cloned()
+            .expect("There should be at least an action");
+
+        let application = match adjustment.kind.clone() {
+            GameAdjustmentKind::Timeout => apply_timeout(game, adjustment.team_id, &last),
+            GameAdjustmentKind::ChangeTactic {
+                tactic,
+                custom_tactic,
+            } => apply_change_tactic(game, adjustment.team_id, tactic, custom_tactic, &last),
+            GameAdjustmentKind::Substitution {
+                player_out,
+                player_in,
+            } => apply_substitution(game, adjustment.team_id, player_out, player_in, &last),
+        };
+
+        match application {
+            Application::Applied(output) => {
+                game.action_results.push(*output);
+                game.adjustments.push(adjustment);
+            }
+            Application::Postponed => still_pending.push(adjustment),
+            Application::Dropped => {}
+        }
+    }
+
+    game.pending_adjustments = still_pending;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        suggested_substitution, GameAdjustment, GameAdjustmentKind, MAX_TIMEOUTS_PER_GAME,
+        TIMEOUT_DURATION,
+    };
+    use crate::{
+        core::injury::InjurySeverity,
+        game_engine::{
+            constants::MAX_PERSONAL_FOULS, game::Game, replay::GameReplay, tactic::Tactic,
+            timer::Timer, types::TeamInGame,
+        },
+        types::{AppResult, SystemTimeTick, Tick},
+    };
+
+    fn play_until(game: &mut Game, value: u16) {
+        while game.timer.value < value && !game.has_ended() {
+            game.tick(Tick::now());
+        }
+    }
+
+    #[test]
+    fn test_timeouts_and_tactic_changes() -> AppResult<()> {
+        let mut game = Game::test(TeamInGame::test(), TeamInGame::test());
+        let team_id = game.home_team_in_game.team_id;
+
+        play_until(&mut game, 100);
+        for _ in 0..MAX_TIMEOUTS_PER_GAME {
+            game.add_adjustment(GameAdjustment::new(
+                team_id,
+                game.timer.plus(1),
+                GameAdjustmentKind::Timeout,
+            ))?;
+        }
+        assert!(game
+            .add_adjustment(GameAdjustment::new(
+                team_id,
+                game.timer.plus(1),
+                GameAdjustmentKind::Timeout
+            ))
+            .is_err());
+
+        let next_tactic = game.home_team_in_game.tactic.next();
+        game.add_adjustment(GameAdjustment::new(
+            team_id,
+            game.timer.plus(1),
+            GameAdjustmentKind::ChangeTactic {
+                tactic: next_tactic,
+                custom_tactic: None,
+            },
+        ))?;
+
+        // The first timeout is applied at the next dead ball, together with the tactic change.
+        while !game.is_in_timeout() && !game.has_ended() {
+            game.tick(Tick::now());
+        }
+        let timeout_at = game
+            .action_results
+            .iter()
+            .rev()
+            .find(|action| action.description.starts_with("Timeout"))
+            .expect("Timeout should have been called")
+            .start_at
+            .value;
+        assert_eq!(game.home_team_in_game.tactic, next_tactic);
+        assert_eq!(game.timeouts_used(team_id), 1);
+
+        // No action is played during the timeout.
+        let actions = game.action_results.len();
+        play_until(&mut game, timeout_at + TIMEOUT_DURATION - 1);
+        assert_eq!(game.action_results.len(), actions);
+
+        play_until(&mut game, u16::MAX);
+        assert!(game.timeouts_used(team_id) <= MAX_TIMEOUTS_PER_GAME);
+        assert!(game.pending_adjustments.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fouled_out_player_cannot_come_in() -> AppResult<()> {
+        let mut game = Game::test(TeamInGame::test(), TeamInGame::test());
+        let team = &mut game.home_team_in_game;
+        let team_id = team.team_id;
+
+        let player_out = team
+            .initial_positions
+            .first()
+            .copied()
+            .expect("There should be a player on court");
+        for stats in team.stats.values_mut().filter(|stats| !stats.is_playing()) {
+            stats.fouls = MAX_PERSONAL_FOULS;
+        }
+        assert!(suggested_substitution(team).is_none());
+
+        let player_in = team
+            .stats
+            .iter()
+            .find(|(_, stats)| stats.is_fouled_out())
+            .map(|(id, _)| *id)
+            .expect("There should be a player on the bench");
+        game.add_adjustment(GameAdjustment::new(
+            team_id,
+            game.timer.plus(1),
+            GameAdjustmentKind::Substitution {
+                player_out,
+                player_in,
+            },
+        ))?;
+
+        play_until(&mut game, 600);
+        assert!(game.pending_adjustments.is_empty());
+        assert!(game.adjustments.is_empty());
+        assert!(!game.home_team_in_game.stats[&player_in].is_playing());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_injured_player_cannot_come_in() -> AppResult<()> {
+        let mut game = Game::test(TeamInGame::test(), TeamInGame::test());
+        let team = &mut game.home_team_in_game;
+        let team_id = team.team_id;
+
+        let player_out = team
+            .initial_positions
+            .first()
+            .copied()
+            .expect("There should be a player on court");
+        let bench = team
+            .stats
+            .iter()
+            .filter(|(_, stats)| !stats.is_playing())
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for player_id in bench.iter() {
+            if let Some(player) = team.players.get_mut(player_id) {
+                player.add_injury(InjurySeverity::Minor);
+            }
+        }
+        assert!(suggested_substitution(team).is_none());
+
+        let player_in = *bench
+            .first()
+            .expect("There should be a player on the bench");
+        game.add_adjustment(GameAdjustment::new(
+            team_id,
+            game.timer.plus(1),
+            GameAdjustmentKind::Substitution {
+                player_out,
+                player_in,
+            },
+        ))?;
+
+        play_until(&mut game, 600);
+        assert!(game.pending_adjustments.is_empty());
+        assert!(game.adjustments.is_empty());
+        assert!(!game.home_team_in_game.stats[&player_in].is_playing());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_late_adjustment_is_applied_deterministically() -> AppResult<()> {
+        let home = TeamInGame::test();
+        let away = TeamInGame::test();
+        let mut game = Game::test(home, away);
+        let team_id = game.away_team_in_game.team_id;
+
+        let mut peer_game = game.clone();
+        let adjustment = GameAdjustment::new(
+            team_id,
+            Timer::from(200),
+            GameAdjustmentKind::ChangeTactic {
+                tactic: Tactic::Arrembaggio,
+                custom_tactic: None,
+            },
+        );
+
+        // One peer knows about the adjustment in advance, the other receives it late.
+        game.add_adjustment(adjustment.clone())?;
+        play_until(&mut game, 1500);
+        play_until(&mut peer_game, 1500);
+        peer_game.add_adjustment(adjustment)?;
+
+        assert_eq!(game.action_results, peer_game.action_results);
+        assert_eq!(game.home_team_in_game, peer_game.home_team_in_game);
+        assert_eq!(game.away_team_in_game, peer_game.away_team_in_game);
+
+        play_until(&mut game, u16::MAX);
+        assert_eq!(game.away_team_in_game.tactic, Tactic::Arrembaggio);
+        GameReplay::from_game(&game)?.verify()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,441 @@
+use super::{
+    game::{Game, GameSummary},
+    replay::GameReplay,
+    types::{GameStats, TeamInGame},
+};
+use crate::{
+    args::{ExportArgs, ExportFormat},
+    core::{csv_field, utils::is_default, GamePosition, GamePositionUtils, MAX_GAME_POSITION},
+    store::load_game,
+    types::{AppResult, GameId, PlayerId, SystemTimeTick, TeamId, Tick},
+};
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct BoxScoreLine {
+    pub seconds_played: u32,
+    pub points: u16,
+    pub made_2pt: u16,
+    pub attempted_2pt: u16,
+    pub made_3pt: u16,
+    pub attempted_3pt: u16,
+    pub made_ft: u16,
+    pub attempted_ft: u16,
+    pub offensive_rebounds: u16,
+    pub defensive_rebounds: u16,
+    pub assists: u16,
+    pub steals: u16,
+    pub blocks: u16,
+    pub turnovers: u16,
+    pub fouls: u16,
+    pub plus_minus: i32,
+    pub brawls: [u16; 3],
+}
+
+impl BoxScoreLine {
+    fn from_stats(stats: &GameStats) -> Self {
+        Self {
+            seconds_played: stats.seconds_played,
+            points: stats.points,
+            made_2pt: stats.made_2pt,
+            attempted_2pt: stats.attempted_2pt,
+            made_3pt: stats.made_3pt,
+            attempted_3pt: stats.attempted_3pt,
+            made_ft: stats.made_ft,
+            attempted_ft: stats.attempted_ft,
+            offensive_rebounds: stats.offensive_rebounds,
+            defensive_rebounds: stats.defensive_rebounds,
+            assists: stats.assists,
+            steals: stats.steals,
+            blocks: stats.blocks,
+            turnovers: stats.turnovers,
+            fouls: stats.fouls,
+            plus_minus: stats.plus_minus,
+            brawls: stats.brawls,
+        }
+    }
+
+    fn add(&mut self, other: &Self) {
+        self.seconds_played += other.seconds_played;
+        self.points += other.points;
+        self.made_2pt += other.made_2pt;
+        self.attempted_2pt += other.attempted_2pt;
+        self.made_3pt += other.made_3pt;
+        self.attempted_3pt += other.attempted_3pt;
+        self.made_ft += other.made_ft;
+        self.attempted_ft += other.attempted_ft;
+        self.offensive_rebounds += other.offensive_rebounds;
+        self.defensive_rebounds += other.defensive_rebounds;
+        self.assists += other.assists;
+        self.steals += other.steals;
+        self.blocks += other.blocks;
+        self.turnovers += other.turnovers;
+        self.fouls += other.fouls;
+        self.plus_minus += other.plus_minus;
+        for idx in 0..self.brawls.len() {
+            self.brawls[idx] += other.brawls[idx];
+        }
+    }
+
+    fn minutes(&self) -> String {
+        format!(
+            "{:02}:{:02}",
+            self.seconds_played / 60,
+            self.seconds_played % 60
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlayerBoxScore {
+    pub player_id: PlayerId,
+    pub name: String,
+    // Starting position, only for the starting five.
+    pub position: Option<String>,
+    #[serde(flatten)]
+    pub line: BoxScoreLine,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamBoxScore {
+    pub team_id: TeamId,
+    pub name: String,
+    pub score: u16,
+    pub quarters_score: [u16; 4],
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub overtime_score: u16,
+    pub players: Vec<PlayerBoxScore>,
+    pub totals: BoxScoreLine,
+}
+
+impl TeamBoxScore {
+    fn new(team: &TeamInGame, quarters_score: [u16; 4], overtime_score: u16) -> Self {
+        let mut totals = BoxScoreLine::default();
+        let players = team
+            .initial_positions
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, player_id)| {
+                let player = team.players.get(player_id)?;
+                let line = team
+                    .stats
+                    .get(player_id)
+                    .map(BoxScoreLine::from_stats)
+                    .unwrap_or_default();
+                totals.add(&line);
+                Some(PlayerBoxScore {
+                    player_id: *player_id,
+                    name: player.info.full_name(),
+                    position: (idx < MAX_GAME_POSITION as usize)
+                        .then(|| (idx as GamePosition).as_str().to_string()),
+                    line,
+                })
+            })
+            .collect();
+
+        Self {
+            team_id: team.team_id,
+            name: team.name.clone(),
+            score: quarters_score.iter().sum::<u16>() + overtime_score,
+            quarters_score,
+            overtime_score,
+            players,
+            totals,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BoxScore {
+    pub game_id: GameId,
+    pub planet_name: String,
+    pub attendance: u32,
+    pub starting_at: Tick,
+    pub ended_at: Option<Tick>,
+    pub winner: Option<TeamId>,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub overtimes: u8,
+    pub home: TeamBoxScore,
+    pub away: TeamBoxScore,
+}
+
+impl BoxScore {
+    pub fn from_game(game: &Game) -> AppResult<Self> {
+        if !game.has_ended() {
+            return Err(anyhow!("Game {} has not ended yet", game.id));
+        }
+
+        let (home_quarters_score, away_quarters_score) = game.get_score_by_quarter();
+        let (home_overtime_score, away_overtime_score) = game.get_overtime_score();
+        Ok(Self {
+            game_id: game.id,
+            planet_name: game.planet_name.clone(),
+            attendance: game.attendance,
+            starting_at: game.starting_at,
+            ended_at: game.ended_at,
+            winner: game.winner,
+            overtimes: game.overtimes(),
+            home: TeamBoxScore::new(
+                &game.home_team_in_game,
+                home_quarters_score,
+                home_overtime_score,
+            ),
+            away: TeamBoxScore::new(
+                &game.away_team_in_game,
+                away_quarters_score,
+                away_overtime_score,
+            ),
+        })
+    }
+
+    // Summaries only keep the score, so the full game is loaded from the store.
+    pub fn from_game_summary(summary: &GameSummary) -> AppResult<Self> {
+        Self::from_game(&load_game(&summary.id)?)
+    }
+
+    pub fn to_json(&self) -> AppResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    // One row per player, followed by a totals row for each team.
+    pub fn to_csv(&self) -> String {
+        let mut csv = "team,player_id,name,position,seconds_played,points,made_2pt,attempted_2pt,made_3pt,attempted_3pt,made_ft,attempted_ft,offensive_rebounds,defensive_rebounds,assists,steals,blocks,turnovers,fouls,plus_minus,brawls_won,brawls_lost,brawls_drawn\n".to_string();
+        let mut push_row = |team: &str,
+                            id: &str,
+                            name: &str,
+                            position: &str,
+                            line: &BoxScoreLine| {
+            let _ = writeln!(
+                csv,
+                "{},{id},{},{position},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                csv_field(team),
+                csv_field(name),
+                line.seconds_played,
+                line.points,
+                line.made_2pt,
+                line.attempted_2pt,
+                line.made_3pt,
+                line.attempted_3pt,
+                line.made_ft,
+                line.attempted_ft,
+                line.offensive_rebounds,
+                line.defensive_rebounds,
+                line.assists,
+                line.steals,
+                line.blocks,
+                line.turnovers,
+                line.fouls,
+                line.plus_minus,
+                line.brawls[0],
+                line.brawls[1],
+                line.brawls[2],
+            );
+        };
+
+        for team in [&self.home, &self.away] {
+            for player in team.players.iter() {
+                push_row(
+                    &team.name,
+                    &player.player_id.to_string(),
+                    &player.name,
+                    player.position.as_deref().unwrap_or_default(),
+                    &player.line,
+                );
+            }
+            push_row(&team.name, "", "Totals", "", &team.totals);
+        }
+
+        csv
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        let _ = writeln!(
+            md,
+            "# {} {} - {} {}\n",
+            self.home.name, self.home.score, self.away.score, self.away.name
+        );
+        let _ = writeln!(
+            md,
+            "{} on {}, attendance {}\n",
+            self.planet_name,
+            self.starting_at.formatted_as_date(),
+            self.attendance
+        );
+
+        // The overtime column is shown only for games that went to overtime.
+        let overtime = self.overtimes > 0;
+        if overtime {
+            let _ = writeln!(md, "| Team | Q1 | Q2 | Q3 | Q4 | OT | Total |");
+            let _ = writeln!(md, "|---|---:|---:|---:|---:|---:|---:|");
+        } else {
+            let _ = writeln!(md, "| Team | Q1 | Q2 | Q3 | Q4 | Total |");
+            let _ = writeln!(md, "|---|---:|---:|---:|---:|---:|");
+        }
+        for team in [&self.home, &self.away] {
+            let mut quarters = team
+                .quarters_score
+                .iter()
+                .map(|score| score.to_string())
+                .collect::<Vec<String>>();
+            if overtime {
+                quarters.push(team.overtime_score.to_string());
+            }
+            let _ = writeln!(
+                md,
+                "| {} | {} | {} |",
+                team.name,
+                quarters.join(" | "),
+                team.score
+            );
+        }
+
+        for team in [&self.home, &self.away] {
+            let _ = writeln!(md, "\n## {}\n", team.name);
+            let _ = writeln!(
+                md,
+                "| Player | Pos | MIN | PTS | 2PT | 3PT | FT | OREB | DREB | AST | STL | BLK | TOV | PF | +/- |"
+            );
+            let _ = writeln!(
+                md,
+                "|---|---|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|"
+            );
+            let mut push_row = |name: &str, position: &str, line: &BoxScoreLine| {
+                let _ = writeln!(
+                    md,
+                    "| {name} | {position} | {} | {} | {}/{} | {}/{} | {}/{} | {} | {} | {} | {} | {} | {} | {} | {:+} |",
+                    line.minutes(),
+                    line.points,
+                    line.made_2pt,
+                    line.attempted_2pt,
+                    line.made_3pt,
+                    line.attempted_3pt,
+                    line.made_ft,
+                    line.attempted_ft,
+                    line.offensive_rebounds,
+                    line.defensive_rebounds,
+                    line.assists,
+                    line.steals,
+                    line.blocks,
+                    line.turnovers,
+                    line.fouls,
+                    line.plus_minus,
+                );
+            };
+            for player in team.players.iter() {
+                push_row(
+                    &player.name,
+                    player.position.as_deref().unwrap_or_default(),
+                    &player.line,
+                );
+            }
+            push_row("**Totals**", "", &team.totals);
+        }
+
+        md
+    }
+}
+
+// Text log of the game, one action per entry with the game clock and the score.
+pub fn play_by_play(game: &Game) -> String {
+    let mut log = format!(
+        "{} vs {}\n",
+        game.home_team_in_game.name, game.away_team_in_game.name
+    );
+    for action in game.action_results.iter() {
+        let mut lines = action.description.lines();
+        let _ = writeln!(
+            log,
+            "[{}] {:>3}-{:<3} {}",
+            action.start_at.format(),
+            action.home_score,
+            action.away_score,
+            lines.next().unwrap_or_default()
+        );
+        for line in lines {
+            let _ = writeln!(log, "{:19}{line}", "");
+        }
+    }
+    log
+}
+
+pub fn export_game(game: &Game, format: ExportFormat) -> AppResult<String> {
+    match format {
+        ExportFormat::Json => BoxScore::from_game(game)?.to_json(),
+        ExportFormat::Csv => Ok(BoxScore::from_game(game)?.to_csv()),
+        ExportFormat::Markdown => Ok(BoxScore::from_game(game)?.to_markdown()),
+        ExportFormat::PlayByPlay => Ok(play_by_play(game)),
+        ExportFormat::Replay => Ok(serde_json::to_string_pretty(&GameReplay::from_game(game)?)?),
+    }
+}
+
+pub fn run_export(args: &ExportArgs) -> AppResult<()> {
+    let game = load_game(&args.game_id)?;
+    let formatted = export_game(&game, args.format)?;
+
+    if let Some(path) = args.output.as_ref() {
+        std::fs::write(path, formatted)?;
+    } else {
+        println!("{formatted}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{play_by_play, BoxScore};
+    use crate::{
+        core::TickInterval,
+        game_engine::{game::Game, types::TeamInGame},
+        types::AppResult,
+    };
+
+    #[test]
+    fn test_box_score_export() -> AppResult<()> {
+        let mut game = Game::test(TeamInGame::test(), TeamInGame::test());
+        assert!(BoxScore::from_game(&game).is_err());
+
+        let mut current_tick = game.starting_at;
+        while !game.has_ended() {
+            game.tick(current_tick);
+            current_tick += TickInterval::SHORT;
+        }
+
+        let box_score = BoxScore::from_game(&game)?;
+        let (home_score, away_score) = game.get_score();
+        assert_eq!(box_score.home.score, home_score);
+        assert_eq!(box_score.away.score, away_score);
+        assert_eq!(box_score.home.totals.points, home_score);
+        assert_eq!(box_score.away.totals.points, away_score);
+        assert_eq!(box_score.overtimes, game.overtimes());
+        assert_eq!(
+            box_score.to_markdown().contains("| OT |"),
+            game.overtimes() > 0
+        );
+
+        let csv = box_score.to_csv();
+        assert_eq!(
+            csv.lines().count(),
+            1 + box_score.home.players.len() + box_score.away.players.len() + 2
+        );
+        assert!(box_score.to_markdown().contains("| Player |"));
+        assert!(box_score.to_json()?.contains("\"quarters_score\""));
+        assert_eq!(
+            play_by_play(&game).lines().next(),
+            Some(
+                format!(
+                    "{} vs {}",
+                    game.home_team_in_game.name, game.away_team_in_game.name
+                )
+                .as_str()
+            )
+        );
+
+        Ok(())
+    }
+}
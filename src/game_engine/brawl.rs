@@ -5,7 +5,7 @@ use crate::{
         player::Trait,
         skill::GameSkill,
     },
-    game_engine::constants::ADV_NEUTRAL_LIMIT,
+    game_engine::constants::{ADV_NEUTRAL_LIMIT, BRAWL_INJURY_PROBABILITY},
 };
 use rand::{seq::IndexedRandom, RngExt};
 use rand_chacha::ChaCha8Rng;
@@ -101,10 +101,7 @@ pub(crate) fn execute(
     let mut atk_result = attacker.roll(action_rng)
         + (0.5 * attacker.athletics.strength + 0.5 * attacker.mental.aggression).game_value()
         + attacker.offense.brawl.game_value()
-        + game
-            .attacking_team()
-            .tactic
-            .attack_roll_bonus(&Action::Brawl);
+        + game.attacking_team().attack_roll_bonus(&Action::Brawl);
 
     if attacker.special_trait == Some(Trait::Killer) {
         atk_result += attacker.reputation.game_value();
@@ -113,16 +110,13 @@ pub(crate) fn execute(
     let mut def_result = defender.roll(action_rng)
         + (0.5 * defender.athletics.strength + 0.5 * defender.mental.aggression).game_value()
         + defender.offense.brawl.game_value()
-        + game
-            .defending_team()
-            .tactic
-            .defense_roll_bonus(&Action::Brawl);
+        + game.defending_team().defense_roll_bonus(&Action::Brawl);
 
     if defender.special_trait == Some(Trait::Killer) {
         def_result += defender.reputation.game_value();
     }
 
-    let description = match atk_result - def_result {
+    let mut description = match atk_result - def_result {
         x if x > ADV_NEUTRAL_LIMIT => {
             defender_update.extra_morale += MoraleModifier::SEVERE_MALUS;
             attacker_update.extra_morale += MoraleModifier::SEVERE_BONUS;
@@ -298,6 +292,22 @@ pub(crate) fn execute(
         }
     };
 
+    // The loser of the brawl risks an injury, in an even fight both do.
+    for (player, update) in [
+        (attacker, &mut attacker_update),
+        (defender, &mut defender_update),
+    ] {
+        let probability = match update.brawls {
+            [0, 1, 0] => BRAWL_INJURY_PROBABILITY,
+            [0, 0, 1] => BRAWL_INJURY_PROBABILITY / 2.0,
+            _ => continue,
+        };
+        if let Some(severity) = player.roll_injury(probability, action_rng) {
+            update.injury = Some(severity);
+            description.push_str(&injury_description(player, severity));
+        }
+    }
+
     let timer_increase = 7 + action_rng.random_range(0..=5);
 
     let mut result = ActionOutput {
@@ -11,6 +11,22 @@ pub(crate) const SUBSTITUTION_ACTION_PROBABILITY: f64 = 1.1;
 
 pub(crate) const DUNK_PROBABILITY: f64 = 0.45;
 
+// Fouls: shooting fouls are checked on every contested shot, other fouls are committed
+// by the defense while the attack is setting up.
+pub(crate) const FOUL_ACTION_PROBABILITY: f64 = 0.05;
+pub(crate) const SHOOTING_FOUL_PROBABILITY: f64 = 0.1;
+// From this number of team fouls in a period, every foul is sanctioned with free throws.
+pub(crate) const TEAM_FOULS_FOR_BONUS: usize = 5;
+pub(crate) const MAX_PERSONAL_FOULS: u16 = 6;
+// Free throws compare the shooter roll + 2 medium range skill against this difficulty.
+pub(crate) const FREE_THROW_DIFFICULTY: i16 = 65;
+
+// Injuries: brawl losers and dunkers risk an injury, as do exhausted players in every action they take part in.
+pub(crate) const BRAWL_INJURY_PROBABILITY: f64 = 0.05;
+pub(crate) const DUNK_INJURY_PROBABILITY: f64 = 0.005;
+pub(crate) const TIREDNESS_INJURY_PROBABILITY: f64 = 0.002;
+pub(crate) const MIN_TIREDNESS_FOR_INJURY: f32 = 1.5 * MIN_TIREDNESS_FOR_ROLL_DECLINE;
+
 // Action checks compare attacker and defender as
 // NUMBER_OF_ROLLS + 2 player skill + 1 tactic skill
 // The higher the number of rolls, the less relevant skills and tactics are.
@@ -16,7 +16,13 @@ pub(crate) fn execute(
         Period::B1 => "It's the end of the first quarter.".to_string(),
         Period::B2 => "It's the end of the second quarter. Halftime!".to_string(),
         Period::B3 => "It's the end of the third quarter.".to_string(),
-        Period::B4 => "It's the end of the game.".to_string(),
+        // The game ends at these breaks unless it's tied.
+        Period::B4 => {
+            "It's the end of regulation and the score is tied! We're going to overtime.".to_string()
+        }
+        Period::BOT1 | Period::BOT2 | Period::BOT3 => {
+            "It's the end of the overtime and it's still tied! One more overtime to go.".to_string()
+        }
         _ => panic!("Invalid period {}", game.timer.period()),
     };
 
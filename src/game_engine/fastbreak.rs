@@ -4,7 +4,7 @@ use crate::core::{
     skill::GameSkill,
     Pronoun,
 };
-use rand::{RngExt};
+use rand::RngExt;
 use rand_chacha::ChaCha8Rng;
 use std::collections::HashMap;
 
@@ -42,10 +42,7 @@ pub(crate) fn execute(
         + playmaker.athletics.quickness.game_value()
         + (0.5 * playmaker.technical.ball_handling + 0.5 * playmaker.mental.aggression)
             .game_value()
-        + game
-            .attacking_team()
-            .tactic
-            .attack_roll_bonus(&Action::Fastbreak);
+        + game.attacking_team().attack_roll_bonus(&Action::Fastbreak);
 
     let def_result = playmaker_defender.roll(action_rng)
         + playmaker_defender.athletics.quickness.game_value()
@@ -53,10 +50,7 @@ pub(crate) fn execute(
             + 0.25 * playmaker_defender.mental.intuition
             + 0.25 * playmaker_defender.defense.steal)
             .game_value()
-        + game
-            .defending_team()
-            .tactic
-            .defense_roll_bonus(&Action::Fastbreak);
+        + game.defending_team().defense_roll_bonus(&Action::Fastbreak);
 
     let mut playmaker_update = GameStats {
         extra_tiredness: TirednessCost::MEDIUM,
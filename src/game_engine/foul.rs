@@ -0,0 +1,474 @@
+use super::{
+    action::{sample_player_index, ActionOutput, ActionSituation, Advantage},
+    constants::*,
+    game::Game,
+    types::*,
+};
+use crate::core::{
+    constants::{MoraleModifier, TirednessCost},
+    player::Player,
+    skill::GameSkill,
+    MAX_SKILL,
+};
+use rand::{seq::IndexedRandom, RngExt};
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashMap;
+
+/// Number of fouls committed in the current period by the team defending against `possession`.
+/// Fouls always leave the ball to the fouled team, so the fouling team is the one without possession.
+pub(crate) fn team_fouls_in_period(game: &Game, possession: Possession) -> usize {
+    let period = game.timer.period();
+    game.action_results
+        .iter()
+        .filter(|action| {
+            action.foul_from.is_some()
+                && action.possession == possession
+                && action.start_at.period() == period
+        })
+        .count()
+}
+
+/// Whether the defender fouls the shooter. Shots closer to the rim draw more contact.
+pub(crate) fn is_shooting_foul(
+    defender: &Player,
+    shot_difficulty: ShotDifficulty,
+    action_rng: &mut ChaCha8Rng,
+) -> bool {
+    if defender.is_knocked_out() {
+        return false;
+    }
+
+    let difficulty_modifier = match shot_difficulty {
+        ShotDifficulty::Dunk | ShotDifficulty::Close => 1.5,
+        ShotDifficulty::Medium => 0.75,
+        ShotDifficulty::Long => 0.25,
+    };
+
+    action_rng.random_bool(
+        (SHOOTING_FOUL_PROBABILITY
+            * difficulty_modifier
+            * (0.5 + defender.mental.aggression / MAX_SKILL) as f64)
+            .clamp(0.0, 1.0),
+    )
+}
+
+fn fouled_out_description(defender: &Player, personal_fouls: u16) -> String {
+    if personal_fouls >= MAX_PERSONAL_FOULS {
+        format!(
+            " That's foul number {} for {}, {} fouled out!",
+            personal_fouls,
+            defender.info.short_name(),
+            defender.info.pronouns.as_subject().to_lowercase()
+        )
+    } else if personal_fouls == MAX_PERSONAL_FOULS - 1 {
+        format!(" {} is in foul trouble now.", defender.info.short_name())
+    } else {
+        String::new()
+    }
+}
+
+/// Commentary appended to a shot description when the shooter is fouled.
+pub(crate) fn shooting_foul_description(
+    description_rng: &mut ChaCha8Rng,
+    shooter: &Player,
+    defender: &Player,
+    personal_fouls: u16,
+    made: bool,
+    free_throws: u8,
+) -> String {
+    let text = if made {
+        [
+            format!(
+                " And one! {} fouled {} on the way up.",
+                defender.info.short_name(),
+                shooter.info.short_name()
+            ),
+            format!(
+                " {} scores through the contact from {}, the and-one is coming.",
+                shooter.info.short_name(),
+                defender.info.short_name()
+            ),
+            format!(
+                " The referee calls the foul on {}! {} can complete the three-point play.",
+                defender.info.short_name(),
+                shooter.info.short_name()
+            ),
+        ]
+    } else {
+        [
+            format!(
+                " {} was fouled by {} on the shot and goes to the line for {}.",
+                shooter.info.short_name(),
+                defender.info.short_name(),
+                free_throws
+            ),
+            format!(
+                " Foul on {}! {} will shoot {} free throws.",
+                defender.info.short_name(),
+                shooter.info.short_name(),
+                free_throws
+            ),
+            format!(
+                " {} hacked {} on the arm, {} free throws coming.",
+                defender.info.short_name(),
+                shooter.info.short_name(),
+                free_throws
+            ),
+        ]
+    };
+
+    let mut description = text
+        .choose(description_rng)
+        .expect("There should be a description")
+        .clone();
+    description.push_str(&fouled_out_description(defender, personal_fouls));
+    description
+}
+
+pub(crate) fn execute(
+    input: &ActionOutput,
+    game: &Game,
+    action_rng: &mut ChaCha8Rng,
+    description_rng: &mut ChaCha8Rng,
+) -> ActionOutput {
+    let attacking_players_array = game.attacking_players_array();
+    let defending_players_array = game.defending_players_array();
+
+    // Aggressive defenders are more likely to foul, ball handlers are more likely to be fouled.
+    let weights = defending_players_array
+        .iter()
+        .map(|p| p.mental.aggression.value() + 1)
+        .collect::<Vec<u8>>()
+        .try_into()
+        .expect("There should be players");
+    let defender_idx = sample_player_index(action_rng, weights, defending_players_array);
+
+    let weights = attacking_players_array
+        .iter()
+        .map(|p| p.technical.ball_handling.value() + 1)
+        .collect::<Vec<u8>>()
+        .try_into()
+        .expect("There should be players");
+    let attacker_idx = sample_player_index(action_rng, weights, attacking_players_array);
+
+    let (defender_idx, attacker_idx) = match (defender_idx, attacker_idx) {
+        (Some(defender_idx), Some(attacker_idx)) => (defender_idx, attacker_idx),
+        _ => {
+            return ActionOutput {
+                situation: ActionSituation::BallInMidcourt,
+                possession: input.possession,
+                description: format!(
+                    "{} moves the ball over the half court line.",
+                    game.attacking_team().name
+                ),
+                start_at: input.end_at,
+                end_at: input.end_at.plus(4 + action_rng.random_range(0..=3)),
+                home_score: input.home_score,
+                away_score: input.away_score,
+                ..Default::default()
+            }
+        }
+    };
+
+    let attacker = attacking_players_array[attacker_idx];
+    let defender = defending_players_array[defender_idx];
+
+    let personal_fouls = game
+        .defending_team()
+        .stats
+        .get(&defender.id)
+        .map(|stats| stats.fouls)
+        .unwrap_or_default()
+        + 1;
+    let in_bonus = team_fouls_in_period(game, input.possession) + 1 >= TEAM_FOULS_FOR_BONUS;
+
+    let mut description = [
+        format!(
+            "{} fouls {} away from the ball.",
+            defender.info.short_name(),
+            attacker.info.short_name()
+        ),
+        format!(
+            "{} reaches in and the referee calls the foul on {}.",
+            defender.info.short_name(),
+            defender.info.pronouns.as_object()
+        ),
+        format!(
+            "{} gets tangled with {}, the whistle goes against the defense.",
+            attacker.info.short_name(),
+            defender.info.short_name()
+        ),
+    ]
+    .choose(description_rng)
+    .expect("There should be a description")
+    .clone();
+
+    if in_bonus {
+        description.push_str(&format!(
+            " {} reached the team fouls limit, {} goes to the line for two.",
+            game.defending_team().name,
+            attacker.info.short_name()
+        ));
+    }
+    description.push_str(&fouled_out_description(defender, personal_fouls));
+
+    let mut defense_stats_update = HashMap::new();
+    defense_stats_update.insert(
+        defender.id,
+        GameStats {
+            fouls: 1,
+            extra_tiredness: TirednessCost::LOW,
+            extra_morale: if personal_fouls >= MAX_PERSONAL_FOULS {
+                MoraleModifier::MEDIUM_MALUS
+            } else {
+                0.0
+            },
+            ..Default::default()
+        },
+    );
+
+    let (situation, attackers, free_throws, end_at) = if in_bonus {
+        (
+            ActionSituation::FreeThrow,
+            vec![attacker_idx],
+            2,
+            input.end_at.plus(3 + action_rng.random_range(0..=2)),
+        )
+    } else {
+        (
+            ActionSituation::BallInBackcourt,
+            vec![],
+            0,
+            input.end_at.plus(4 + action_rng.random_range(0..=3)),
+        )
+    };
+
+    ActionOutput {
+        situation,
+        possession: input.possession,
+        attackers,
+        free_throws,
+        foul_from: Some(defender_idx),
+        foul_on: Some(attacker_idx),
+        description,
+        start_at: input.end_at,
+        end_at,
+        home_score: input.home_score,
+        away_score: input.away_score,
+        defense_stats_update: Some(defense_stats_update),
+        ..Default::default()
+    }
+}
+
+pub(crate) fn execute_free_throw(
+    input: &ActionOutput,
+    game: &Game,
+    action_rng: &mut ChaCha8Rng,
+    description_rng: &mut ChaCha8Rng,
+) -> ActionOutput {
+    let attacking_players_array = game.attacking_players_array();
+
+    assert!(input.attackers.len() == 1);
+    let shooter_idx = input.attackers[0];
+    let shooter = attacking_players_array[shooter_idx];
+    let free_throws_left = input.free_throws.saturating_sub(1);
+
+    let roll = shooter.roll(action_rng) + 2 * shooter.offense.medium_range.game_value()
+        - FREE_THROW_DIFFICULTY;
+    let success = roll > 0;
+
+    let description = if success {
+        [
+            format!("{} makes the free throw.", shooter.info.short_name()),
+            format!("{} sinks it from the line.", shooter.info.short_name()),
+            format!(
+                "Nothing but net from the line for {}.",
+                shooter.info.short_name()
+            ),
+        ]
+    } else {
+        [
+            format!("{} misses the free throw.", shooter.info.short_name()),
+            format!(
+                "{} clanks it off the rim from the line.",
+                shooter.info.short_name()
+            ),
+            format!(
+                "{} can't convert from the charity stripe.",
+                shooter.info.short_name()
+            ),
+        ]
+    }
+    .choose(description_rng)
+    .expect("There should be a description")
+    .clone();
+
+    let score_change = if success { 1 } else { 0 };
+    let (home_score, away_score) = match input.possession {
+        Possession::Home => (input.home_score + score_change, input.away_score),
+        Possession::Away => (input.home_score, input.away_score + score_change),
+    };
+
+    let mut result = if free_throws_left > 0 {
+        ActionOutput {
+            situation: ActionSituation::FreeThrow,
+            possession: input.possession,
+            attackers: vec![shooter_idx],
+            free_throws: free_throws_left,
+            description,
+            start_at: input.end_at,
+            end_at: input.end_at.plus(2 + action_rng.random_range(0..=1)),
+            ..Default::default()
+        }
+    } else if success {
+        ActionOutput {
+            situation: ActionSituation::BallInBackcourt,
+            possession: !input.possession,
+            description,
+            start_at: input.end_at,
+            end_at: input.end_at.plus(8 + action_rng.random_range(0..=4)),
+            ..Default::default()
+        }
+    } else {
+        ActionOutput {
+            situation: ActionSituation::MissedShot,
+            advantage: Advantage::Defense,
+            possession: input.possession,
+            attackers: vec![shooter_idx],
+            description,
+            start_at: input.end_at,
+            end_at: input.end_at.plus(1 + action_rng.random_range(0..=2)),
+            ..Default::default()
+        }
+    };
+
+    result.score_change = score_change;
+    result.home_score = home_score;
+    result.away_score = away_score;
+
+    let mut attack_stats_update = HashMap::new();
+    attack_stats_update.insert(
+        shooter.id,
+        GameStats {
+            attempted_ft: 1,
+            made_ft: score_change,
+            points: score_change,
+            extra_morale: if success {
+                MoraleModifier::SMALL_BONUS
+            } else {
+                MoraleModifier::SMALL_MALUS
+            },
+            ..Default::default()
+        },
+    );
+    result.attack_stats_update = Some(attack_stats_update);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::execute_free_throw;
+    use crate::{
+        game_engine::{
+            action::{ActionOutput, ActionSituation},
+            constants::MAX_PERSONAL_FOULS,
+            game::Game,
+            substitution,
+            types::{Possession, TeamInGame},
+        },
+        types::AppResult,
+    };
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_free_throws() -> AppResult<()> {
+        let game = Game::test(TeamInGame::test(), TeamInGame::test());
+        let action_rng = &mut ChaCha8Rng::seed_from_u64(0);
+        let description_rng = &mut ChaCha8Rng::seed_from_u64(1);
+
+        for _ in 0..100 {
+            let input = ActionOutput {
+                situation: ActionSituation::FreeThrow,
+                possession: game.possession,
+                attackers: vec![0],
+                free_throws: 2,
+                ..Default::default()
+            };
+            let result = execute_free_throw(&input, &game, action_rng, description_rng);
+            assert_eq!(result.situation, ActionSituation::FreeThrow);
+            assert_eq!(result.free_throws, 1);
+            assert_eq!(result.possession, game.possession);
+            assert!(result.score_change <= 1);
+
+            let result = execute_free_throw(&result, &game, action_rng, description_rng);
+            assert_eq!(result.free_throws, 0);
+            if result.score_change > 0 {
+                assert_eq!(result.situation, ActionSituation::BallInBackcourt);
+                assert_eq!(result.possession, !game.possession);
+            } else {
+                assert_eq!(result.situation, ActionSituation::MissedShot);
+                assert_eq!(result.possession, game.possession);
+                assert_eq!(result.attackers, vec![0]);
+            }
+
+            let shooter_stats = result
+                .attack_stats_update
+                .as_ref()
+                .and_then(|updates| updates.values().next())
+                .expect("Shooter should have a stats update");
+            assert_eq!(shooter_stats.attempted_ft, 1);
+            assert_eq!(shooter_stats.made_ft, result.score_change);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fouled_out_player_is_substituted() -> AppResult<()> {
+        let mut game = Game::test(TeamInGame::test(), TeamInGame::test());
+        let action_rng = &mut ChaCha8Rng::seed_from_u64(0);
+        let description_rng = &mut ChaCha8Rng::seed_from_u64(1);
+
+        let defending_team = match game.possession {
+            Possession::Home => &mut game.away_team_in_game,
+            Possession::Away => &mut game.home_team_in_game,
+        };
+        let fouled_out_id = defending_team.initial_positions[0];
+        defending_team
+            .stats
+            .get_mut(&fouled_out_id)
+            .expect("Player should have stats")
+            .fouls = MAX_PERSONAL_FOULS;
+
+        let input = ActionOutput {
+            situation: ActionSituation::FreeThrow,
+            possession: game.possession,
+            attackers: vec![2],
+            free_throws: 2,
+            ..Default::default()
+        };
+        let shooter_id = game.attacking_players_array()[2].id;
+
+        let result = substitution::should_execute(&input, &game, action_rng, description_rng)
+            .expect("Fouled out player should be substituted");
+        assert_eq!(result.situation, ActionSituation::FreeThrow);
+        assert_eq!(result.free_throws, 2);
+        assert!(result.attack_stats_update.is_none());
+        assert_eq!(
+            game.attacking_players_array()[result.attackers[0]].id,
+            shooter_id
+        );
+
+        let defense_update = result
+            .defense_stats_update
+            .expect("Defense should have a substitution");
+        assert_eq!(defense_update[&fouled_out_id].position, None);
+        assert!(defense_update
+            .iter()
+            .filter(|(&id, _)| id != fouled_out_id)
+            .all(|(_, stats)| stats.position.is_some()));
+
+        Ok(())
+    }
+}
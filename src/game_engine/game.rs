@@ -1,8 +1,9 @@
 use super::{
     action::{Action, ActionOutput, ActionSituation},
+    adjustment::{self, GameAdjustment, GameAdjustmentKind, MAX_TIMEOUTS_PER_GAME},
     constants::*,
     timer::{Period, Timer},
-    types::{GameStatsMap, Possession, TeamInGame},
+    types::{injury_description, EnginePlayer, GameStatsMap, Possession, TeamInGame},
 };
 use crate::{
     app_version,
@@ -14,16 +15,17 @@ use crate::{
         utils::is_default,
         DEFAULT_PLANET_ID,
     },
-    game_engine::{end_of_quarter, substitution, TournamentId},
+    game_engine::{end_of_quarter, replay::GameSetup, substitution, TournamentId},
     types::*,
 };
+use anyhow::anyhow;
 use itertools::Itertools;
 use rand::{seq::IndexedRandom, RngExt, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 pub struct GameSummary {
     pub id: GameId,
     pub home_team_id: TeamId,
@@ -38,6 +40,15 @@ pub struct GameSummary {
     pub away_team_knocked_out: bool,
     pub home_quarters_score: [u16; 4],
     pub away_quarters_score: [u16; 4],
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub home_overtime_score: u16,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub away_overtime_score: u16,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub overtimes: u8,
     pub location: PlanetId,
     #[serde(skip_serializing_if = "is_default")]
     #[serde(default)]
@@ -64,6 +75,7 @@ pub struct GameSummary {
 impl GameSummary {
     pub fn from_game(game: &Game) -> GameSummary {
         let (home_quarters_score, away_quarters_score) = game.get_score_by_quarter();
+        let (home_overtime_score, away_overtime_score) = game.get_overtime_score();
         Self {
             id: game.id,
             home_team_id: game.home_team_in_game.team_id,
@@ -74,6 +86,9 @@ impl GameSummary {
             away_team_knocked_out: game.is_team_knocked_out(Possession::Away),
             home_quarters_score,
             away_quarters_score,
+            home_overtime_score,
+            away_overtime_score,
+            overtimes: game.overtimes(),
             location: game.location,
             planet_name: game.planet_name.clone(),
             attendance: game.attendance,
@@ -88,8 +103,8 @@ impl GameSummary {
 
     pub fn get_score(&self) -> (u16, u16) {
         (
-            self.home_quarters_score.iter().sum(),
-            self.away_quarters_score.iter().sum(),
+            self.home_quarters_score.iter().sum::<u16>() + self.home_overtime_score,
+            self.away_quarters_score.iter().sum::<u16>() + self.away_overtime_score,
         )
     }
 }
@@ -139,6 +154,20 @@ pub struct Game {
     #[serde(skip_serializing_if = "is_default")]
     #[serde(default)]
     app_version: [usize; 3],
+    // What the teams do not keep track of, so that the game can be played again from the start.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub setup: Option<Box<GameSetup>>,
+    // Decisions taken by the teams during the game, waiting to be applied.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub pending_adjustments: Vec<GameAdjustment>,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub adjustments: Vec<GameAdjustment>,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    timeout_until: u16,
 }
 
 impl Game {
@@ -150,6 +179,29 @@ impl Game {
         !self.is_network()
     }
 
+    pub fn app_version(&self) -> [usize; 3] {
+        self.app_version
+    }
+
+    // Draws are an opt-in rule for friendly local games, every other game goes to overtime.
+    pub fn with_draw_allowed(mut self, allow_draw: bool) -> Self {
+        if let Some(setup) = self.setup.as_mut() {
+            setup.allow_draw = allow_draw;
+        }
+        self
+    }
+
+    pub fn allows_draw(&self) -> bool {
+        self.is_local()
+            && self.part_of_tournament.is_none()
+            && self.setup.as_ref().is_some_and(|setup| setup.allow_draw)
+    }
+
+    fn goes_to_overtime(&self) -> bool {
+        let (home_score, away_score) = self.get_score();
+        home_score == away_score && !self.allows_draw() && !self.timer.has_ended()
+    }
+
     pub fn test(home_team_in_game: TeamInGame, away_team_in_game: TeamInGame) -> Self {
         Game::new(
             GameId::new_v4(),
@@ -202,6 +254,12 @@ impl Game {
                 .sum::<u8>() as f32
                 / 100.0;
 
+        let setup = GameSetup::new(
+            &home_team_in_game,
+            &away_team_in_game,
+            planet_total_population,
+        );
+
         let mut game = Self {
             id,
             home_team_in_game,
@@ -221,6 +279,10 @@ impl Game {
             away_team_mvps: None,
             part_of_tournament,
             app_version: app_version(),
+            setup: Some(Box::new(setup)),
+            pending_adjustments: vec![],
+            adjustments: vec![],
+            timeout_until: 0,
         };
         let seed = game.get_rng_seed();
         let mut rng = ChaCha8Rng::from_seed(seed);
@@ -355,7 +417,9 @@ impl Game {
             ("TO", stats.turnovers, -1.5),
             (
                 "Acc",
-                stats.attempted_2pt - stats.made_2pt + stats.attempted_3pt - stats.made_3pt,
+                stats.attempted_2pt - stats.made_2pt + stats.attempted_3pt - stats.made_3pt
+                    + stats.attempted_ft
+                    - stats.made_ft,
                 -0.5,
             ),
         ];
@@ -427,12 +491,15 @@ impl Game {
             ActionSituation::Fastbreak => Action::Fastbreak,
             ActionSituation::MissedShot => Action::Rebound,
             ActionSituation::EndOfQuarter => Action::StartOfQuarter,
+            ActionSituation::FreeThrow => Action::FreeThrow,
             ActionSituation::AfterSubstitution | ActionSituation::BallInBackcourt => {
                 let brawl_probability = BRAWL_ACTION_PROBABILITY
-                    * (self.home_team_in_game.tactic.brawl_probability_modifier()
-                        + self.away_team_in_game.tactic.brawl_probability_modifier());
+                    * (self.home_team_in_game.brawl_probability_modifier()
+                        + self.away_team_in_game.brawl_probability_modifier());
                 if action_rng.random_bool(brawl_probability) {
                     Action::Brawl
+                } else if action_rng.random_bool(FOUL_ACTION_PROBABILITY) {
+                    Action::Foul
                 } else {
                     match self.possession {
                         Possession::Home => self.home_team_in_game.pick_action(action_rng)?,
@@ -443,10 +510,16 @@ impl Game {
             ActionSituation::BallInMidcourt
             | ActionSituation::AfterDefensiveRebound
             | ActionSituation::AfterLongOffensiveRebound
-            | ActionSituation::Turnover => match self.possession {
-                Possession::Home => self.home_team_in_game.pick_action(action_rng)?,
-                Possession::Away => self.away_team_in_game.pick_action(action_rng)?,
-            },
+            | ActionSituation::Turnover => {
+                if action_rng.random_bool(FOUL_ACTION_PROBABILITY) {
+                    Action::Foul
+                } else {
+                    match self.possession {
+                        Possession::Home => self.home_team_in_game.pick_action(action_rng)?,
+                        Possession::Away => self.away_team_in_game.pick_action(action_rng)?,
+                    }
+                }
+            }
         };
 
         Some(action)
@@ -496,6 +569,9 @@ impl Game {
                     player_stats.update(stats);
                     player.add_tiredness(stats.extra_tiredness);
                     player.add_morale(stats.extra_morale);
+                    if let Some(severity) = stats.injury {
+                        player.add_injury(severity);
+                    }
                 }
             }
         }
@@ -527,9 +603,54 @@ impl Game {
         }
     }
 
+    // Exhausted players risk an injury in every action they take part in.
+    fn roll_tiredness_injuries(&self, result: &mut ActionOutput, action_rng: &mut ChaCha8Rng) {
+        for (stats_update, players) in [
+            (
+                result.attack_stats_update.as_mut(),
+                self.all_attacking_players(),
+            ),
+            (
+                result.defense_stats_update.as_mut(),
+                self.all_defending_players(),
+            ),
+        ] {
+            let Some(updates) = stats_update else {
+                continue;
+            };
+
+            // Sort to keep the rng sequence deterministic.
+            for (id, update) in updates.iter_mut().sorted_by_key(|(id, _)| **id) {
+                let Some(player) = players.get(id) else {
+                    continue;
+                };
+                if update.extra_tiredness <= 0.0
+                    || update.injury.is_some()
+                    || player.is_injured()
+                    || player.tiredness < MIN_TIREDNESS_FOR_INJURY
+                {
+                    continue;
+                }
+
+                if let Some(severity) = player.roll_injury(TIREDNESS_INJURY_PROBABILITY, action_rng)
+                {
+                    update.injury = Some(severity);
+                    result
+                        .description
+                        .push_str(&injury_description(player, severity));
+                }
+            }
+        }
+    }
+
     fn apply_tiredness_update(&mut self) {
         // Apply low generic tiredness to all playing players and recovery for bench players.
+        let defending_team_id = self.defending_team().team_id;
         for team in [&mut self.home_team_in_game, &mut self.away_team_in_game] {
+            let mut tiredness_modifier = team.playing_tiredness_modifier();
+            if team.team_id == defending_team_id {
+                tiredness_modifier *= team.defensive_scheme.defending_tiredness_modifier();
+            }
             for (id, player) in team.players.iter_mut() {
                 let stats = team.stats.get_mut(id).expect("Player should have stats");
                 if stats.is_playing() && !self.timer.is_break() {
@@ -539,9 +660,7 @@ impl Game {
                             .position
                             .expect("Playing player should have a position")
                             as usize] += 1;
-                        player.add_tiredness(
-                            TirednessCost::LOW * team.tactic.playing_tiredness_modifier(),
-                        );
+                        player.add_tiredness(TirednessCost::LOW * tiredness_modifier);
                     }
                 } else if !player.is_knocked_out() {
                     // We don't use add_tiredness here because otherwise the stamina would have an effect.
@@ -659,6 +778,30 @@ impl Game {
         (home_quarters_score, away_quarters_score)
     }
 
+    pub fn get_overtime_score(&self) -> (u16, u16) {
+        let (home_quarters_score, away_quarters_score) = self.get_score_by_quarter();
+        self.action_results
+            .iter()
+            .rev()
+            .find(|action| action.start_at.period().is_overtime())
+            .map(|action| {
+                (
+                    action.home_score - home_quarters_score.iter().sum::<u16>(),
+                    action.away_score - away_quarters_score.iter().sum::<u16>(),
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    // Number of overtimes played so far.
+    pub fn overtimes(&self) -> u8 {
+        let period = self.timer.period();
+        [Period::OT1, Period::OT2, Period::OT3, Period::OT4]
+            .iter()
+            .filter(|overtime| period >= **overtime)
+            .count() as u8
+    }
+
     pub fn is_team_knocked_out(&self, side: Possession) -> bool {
         match side {
             Possession::Home => self
@@ -713,6 +856,125 @@ impl Game {
         }
     }
 
+    pub fn team_in_game(&self, team_id: TeamId) -> Option<&TeamInGame> {
+        if self.home_team_in_game.team_id == team_id {
+            Some(&self.home_team_in_game)
+        } else if self.away_team_in_game.team_id == team_id {
+            Some(&self.away_team_in_game)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn team_in_game_mut(&mut self, team_id: TeamId) -> Option<&mut TeamInGame> {
+        if self.home_team_in_game.team_id == team_id {
+            Some(&mut self.home_team_in_game)
+        } else if self.away_team_in_game.team_id == team_id {
+            Some(&mut self.away_team_in_game)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_in_timeout(&self) -> bool {
+        self.timer.value < self.timeout_until
+    }
+
+    pub(crate) fn start_timeout(&mut self, until: u16) {
+        self.timeout_until = until;
+        self.next_step = self.next_step.max(until);
+    }
+
+    pub(crate) fn has_reached_next_step(&self) -> bool {
+        self.timer.reached(self.next_step)
+    }
+
+    pub fn timeouts_used(&self, team_id: TeamId) -> usize {
+        self.adjustments
+            .iter()
+            .filter(|a| a.team_id == team_id && a.kind == GameAdjustmentKind::Timeout)
+            .count()
+    }
+
+    pub fn timeouts_left(&self, team_id: TeamId) -> usize {
+        let requested = self
+            .pending_adjustments
+            .iter()
+            .filter(|a| a.team_id == team_id && a.kind == GameAdjustmentKind::Timeout)
+            .count();
+        MAX_TIMEOUTS_PER_GAME.saturating_sub(self.timeouts_used(team_id) + requested)
+    }
+
+    fn sort_pending_adjustments(&mut self) {
+        let home_team_id = self.home_team_in_game.team_id;
+        self.pending_adjustments
+            .sort_by_key(|a| (a.timer.value, a.team_id != home_team_id));
+    }
+
+    pub fn add_adjustment(&mut self, adjustment: GameAdjustment) -> AppResult<()> {
+        if self.has_ended() {
+            return Err(anyhow!("Game has already ended"));
+        }
+
+        let team = self
+            .team_in_game(adjustment.team_id)
+            .ok_or_else(|| anyhow!("Team is not playing in this game"))?;
+        adjustment.validate(team)?;
+
+        if adjustment.kind == GameAdjustmentKind::Timeout
+            && self.timeouts_left(adjustment.team_id) == 0
+        {
+            return Err(anyhow!("{} has no timeouts left", team.name));
+        }
+
+        // If the game already went past the adjustment timer, we need to play it again
+        // from the start so that the result is the same as for peers who received it in time.
+        let is_late = adjustment.timer <= self.timer;
+        self.pending_adjustments.push(adjustment);
+        self.sort_pending_adjustments();
+
+        if is_late {
+            self.resimulate()?;
+        }
+
+        Ok(())
+    }
+
+    fn resimulate(&mut self) -> AppResult<()> {
+        let setup = self
+            .setup
+            .as_ref()
+            .ok_or_else(|| anyhow!("Game {} has no setup and cannot be resimulated", self.id))?;
+        let (home_team_in_game, away_team_in_game) = setup.initial_teams(self)?;
+
+        let mut game = Game::new(
+            self.id,
+            home_team_in_game,
+            away_team_in_game,
+            self.starting_at,
+            self.location,
+            setup.planet_total_population,
+            &self.planet_name,
+            self.part_of_tournament,
+        )
+        .with_draw_allowed(setup.allow_draw);
+        game.pending_adjustments = self
+            .adjustments
+            .iter()
+            .chain(self.pending_adjustments.iter())
+            .cloned()
+            .collect();
+        game.sort_pending_adjustments();
+
+        while game.timer < self.timer && !game.has_ended() {
+            let current_tick = self.starting_at + game.timer.plus(1).as_tick();
+            game.tick(current_tick);
+        }
+
+        *self = game;
+        Ok(())
+    }
+
     pub fn has_started(&self, timestamp: Tick) -> bool {
         self.starting_at <= timestamp
     }
@@ -733,7 +995,10 @@ impl Game {
         seed.reverse();
         let description_rng = &mut ChaCha8Rng::from_seed(seed);
 
-        if self.timer.has_ended() {
+        // The game ends at the beginning of the break after the regulation time or after an overtime,
+        // unless the score is tied, in which case the break leads to the next overtime.
+        let period = self.timer.period();
+        if period.can_end_game() && self.timer.value == period.start() && !self.goes_to_overtime() {
             self.ended_at = Some(current_tick);
             self.home_team_mvps = Some(self.team_mvps(Possession::Home));
             self.away_team_mvps = Some(self.team_mvps(Possession::Away));
@@ -762,8 +1027,8 @@ impl Game {
                 ..Default::default()
             });
 
-            if self.winner.is_none() {
-                // It's a draw. In this case we have a final total brawl to determine the winner.
+            if self.winner.is_none() && !self.allows_draw() {
+                // Still a draw after the last overtime. In this case we have a final total brawl to determine the winner.
                 // The team who has possession after the brawl is the winner.
                 let action_input = self.action_results[self.action_results.len() - 1].clone();
                 let result =
@@ -793,6 +1058,7 @@ impl Game {
         }
 
         self.apply_tiredness_update();
+        adjustment::apply_pending(self);
 
         if !self.timer.reached(self.next_step) {
             return;
@@ -828,6 +1094,7 @@ impl Game {
             }
         };
 
+        self.roll_tiredness_injuries(&mut result, action_rng);
         self.apply_game_stats_update(
             result.attack_stats_update.as_ref(),
             result.defense_stats_update.as_ref(),
@@ -921,7 +1188,10 @@ impl Game {
             (false, false) =>
             // Check if teams make substitutions. Only if ball is out
             {
-                if situation == ActionSituation::BallInBackcourt {
+                if matches!(
+                    situation,
+                    ActionSituation::BallInBackcourt | ActionSituation::FreeThrow
+                ) {
                     let action_input = self.action_results[self.action_results.len() - 1].clone();
                     if let Some(sub) = substitution::should_execute(
                         &action_input,
@@ -948,10 +1218,78 @@ mod tests {
     use crate::core::{Rated, TickInterval};
     use crate::game_engine::action::{ActionSituation, Advantage};
     use crate::game_engine::game::GameSummary;
+    use crate::game_engine::timer::Period;
     use crate::game_engine::types::{GameStatsMap, Possession, TeamInGame};
     use crate::types::AppResult;
     use crate::types::{SystemTimeTick, Tick};
 
+    // Play the regulation time and tie the score right before its end.
+    fn tied_game(allow_draw: bool) -> Game {
+        let mut game =
+            Game::test(TeamInGame::test(), TeamInGame::test()).with_draw_allowed(allow_draw);
+        let mut current_tick = game.starting_at;
+        while game.timer.value + 1 < Period::B4.start() {
+            game.tick(current_tick);
+            current_tick += TickInterval::SHORT;
+        }
+
+        let last = game
+            .action_results
+            .last_mut()
+            .expect("Game should have actions");
+        let score = last.home_score.max(last.away_score);
+        last.home_score = score;
+        last.away_score = score;
+        game
+    }
+
+    #[test]
+    fn test_game_overtime() -> AppResult<()> {
+        let mut game = tied_game(false);
+        let mut current_tick = game.starting_at + game.timer.as_tick();
+        game.tick(current_tick);
+        assert_eq!(game.timer.period(), Period::B4);
+        assert!(!game.has_ended());
+
+        while !game.has_ended() {
+            current_tick += TickInterval::SHORT;
+            game.tick(current_tick);
+        }
+
+        assert!(game.timer.period() >= Period::BOT1);
+        assert!(game.winner.is_some());
+        assert!(game.overtimes() >= 1);
+
+        let (home_score, away_score) = game.get_score();
+        let (home_quarters_score, away_quarters_score) = game.get_score_by_quarter();
+        let (home_overtime_score, away_overtime_score) = game.get_overtime_score();
+        assert_eq!(
+            home_quarters_score.iter().sum::<u16>() + home_overtime_score,
+            home_score
+        );
+        assert_eq!(
+            away_quarters_score.iter().sum::<u16>() + away_overtime_score,
+            away_score
+        );
+        let summary = GameSummary::from_game(&game);
+        assert_eq!(summary.get_score(), (home_score, away_score));
+        assert_eq!(summary.overtimes, game.overtimes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_draw_allowed() -> AppResult<()> {
+        let mut game = tied_game(true);
+        game.tick(game.starting_at + game.timer.as_tick());
+        assert_eq!(game.timer.period(), Period::B4);
+        assert!(game.has_ended());
+        assert!(game.winner.is_none());
+        assert_eq!(game.overtimes(), 0);
+
+        Ok(())
+    }
+
     #[test]
     fn test_game_consistency() -> AppResult<()> {
         let home_team_in_game = TeamInGame::test();
@@ -966,14 +1304,18 @@ mod tests {
 
         let mut home_score = 0;
         let mut away_score = 0;
+        // Points go to the team attacking when the action starts:
+        // free throws keep the possession, so we can't rely on the possession after the action.
+        let mut possession = game.action_results[0].possession;
         for action in game.action_results.iter() {
-            if action.possession == Possession::Away {
+            if possession == Possession::Home {
                 assert!(home_score + action.score_change == action.home_score);
                 assert!(away_score == action.away_score);
             } else {
                 assert!(home_score == action.home_score);
                 assert!(away_score + action.score_change == action.away_score);
             }
+            possession = action.possession;
             println!(
                 "+ {} -> {} - {} ",
                 action.score_change, home_score, away_score
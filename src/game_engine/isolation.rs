@@ -38,7 +38,10 @@ pub(crate) fn execute(
     };
 
     let iso = attacking_players_array[iso_idx];
-    let defender = defending_players_array[iso_idx];
+    let [defender_idx] = game
+        .defending_team()
+        .defender_indexes([iso_idx], action_rng);
+    let defender = defending_players_array[defender_idx];
 
     let timer_increase = 2 + action_rng.random_range(0..=3);
 
@@ -57,25 +60,19 @@ pub(crate) fn execute(
     let atk_result = iso.roll(action_rng)
         + iso.technical.ball_handling.game_value()
         + (0.75 * iso.athletics.quickness + 0.25 * iso.mental.aggression).game_value()
-        + game
-            .attacking_team()
-            .tactic
-            .attack_roll_bonus(&Action::Isolation);
+        + game.attacking_team().attack_roll_bonus(&Action::Isolation);
 
     let def_result = defender.roll(action_rng)
         + defender.defense.perimeter_defense.game_value()
         + (0.75 * defender.athletics.quickness + 0.25 * defender.defense.steal).game_value()
-        + game
-            .defending_team()
-            .tactic
-            .defense_roll_bonus(&Action::Isolation);
+        + game.defending_team().defense_roll_bonus(&Action::Isolation);
 
     let mut result = match atk_result  - def_result  {
             x if x >= ADV_ATTACK_LIMIT => ActionOutput {
                 possession: input.possession,
                 advantage: Advantage::Attack,
                 attackers: vec![iso_idx],
-                defenders: vec![iso_idx],
+                defenders: vec![defender_idx],
                 situation: shot::dunk_or_close_shot(iso, action_rng),
                 description: [
                     format!(
@@ -141,7 +138,7 @@ pub(crate) fn execute(
                 possession: input.possession,
                 advantage: Advantage::Neutral,
                 attackers: vec![iso_idx],
-                defenders: vec![iso_idx], //got the switch
+                defenders: vec![defender_idx], //got the switch
                 situation: ActionSituation::CloseShot,
                 description: [
                     format!(
@@ -191,7 +188,7 @@ pub(crate) fn execute(
                 away_score: input.away_score,
                 ..Default::default()
             },
-            x if x > ADV_DEFENSE_LIMIT => {
+            x if x > game.defending_team().adv_defense_limit() => {
                 // Check if driver can kick out to an open teammate
                 let num_ok_players = attacking_players_array
                     .iter().filter(|p| !p.is_knocked_out()).count();
@@ -237,7 +234,7 @@ pub(crate) fn execute(
                         possession: input.possession,
                         advantage: Advantage::Defense,
                         attackers: vec![iso_idx],
-                        defenders: vec![iso_idx],
+                        defenders: vec![defender_idx],
                         situation: ActionSituation::MediumShot,
                         description: [
                             format!(
@@ -300,7 +297,7 @@ pub(crate) fn execute(
                 defender_update.extra_morale += MoraleModifier::SMALL_BONUS;
 
                 // Equivalent to `- def_result - target_defender.defense.steal.game_value() <= STEAL_LIMIT`
-                let with_steal = def_result + defender.defense.steal.game_value() >= -STEAL_LIMIT;
+                let with_steal = def_result + defender.defense.steal.game_value() >= -game.defending_team().steal_limit();
 
 
             if with_steal {
@@ -309,16 +306,15 @@ pub(crate) fn execute(
                 iso_update.extra_morale += MoraleModifier::MEDIUM_MALUS;
             }
 
-            let situation = if with_steal && action_rng.random_bool(FASTBREAK_ACTION_PROBABILITY * game.defending_team().tactic.fastbreak_probability_modifier()){
+            let situation = if with_steal && action_rng.random_bool(FASTBREAK_ACTION_PROBABILITY * game.defending_team().fastbreak_probability_modifier()){
                 ActionSituation::Fastbreak
             } else {
                 ActionSituation::Turnover
             };
 
-            // After possession flips, the defender who stole is at the same index
-            // as the attacker they were guarding (players are mirrored by position).
+            // After possession flips, the defender who stole becomes the attacker.
             let attackers = if with_steal {
-                vec![iso_idx]
+                vec![defender_idx]
             } else {vec![]};
 
             let end_at = if with_steal {
@@ -1,8 +1,11 @@
 pub mod action;
+pub mod adjustment;
+pub mod box_score;
 mod brawl;
 mod constants;
 mod end_of_quarter;
 mod fastbreak;
+mod foul;
 pub mod game;
 mod isolation;
 mod jump_ball;
@@ -10,6 +13,7 @@ mod off_the_screen;
 mod pick_and_roll;
 mod post;
 mod rebound;
+pub mod replay;
 mod shot;
 mod start_of_quarter;
 mod substitution;
@@ -21,5 +25,6 @@ pub mod types;
 
 pub use constants::{MIN_TIREDNESS_FOR_ROLL_DECLINE, RECOVERING_TIREDNESS_PER_SHORT_TICK};
 pub use tournament::{
-    Tournament, TournamentId, TournamentState, TournamentSummary, TournamentType,
+    BracketSlot, Tournament, TournamentBracket, TournamentId, TournamentStanding,
+    TournamentState, TournamentSummary, TournamentType,
 };
@@ -70,11 +70,27 @@ pub(crate) fn execute(
         _ => None,
     };
 
+    let defending_team = game.defending_team();
+    let (playmaker_defender_idx, target_defender_idx) = if let Some(screener_idx) = screener_idx {
+        let [playmaker_defender_idx, target_defender_idx, screener_defender_idx] =
+            defending_team.defender_indexes([play_idx, target_idx, screener_idx], action_rng);
+        // The screener's defender takes the target coming off the screen.
+        if defending_team.defensive_scheme.switches_screens() {
+            (playmaker_defender_idx, screener_defender_idx)
+        } else {
+            (playmaker_defender_idx, target_defender_idx)
+        }
+    } else {
+        let [playmaker_defender_idx, target_defender_idx] =
+            defending_team.defender_indexes([play_idx, target_idx], action_rng);
+        (playmaker_defender_idx, target_defender_idx)
+    };
+
     let playmaker = attacking_players_array[play_idx];
-    let playmaker_defender = defending_players_array[play_idx];
+    let playmaker_defender = defending_players_array[playmaker_defender_idx];
 
     let target = attacking_players_array[target_idx];
-    let target_defender = defending_players_array[target_idx];
+    let target_defender = defending_players_array[target_defender_idx];
 
     let screener = screener_idx.map(|idx| attacking_players_array[idx]);
 
@@ -105,7 +121,6 @@ pub(crate) fn execute(
         + (0.5 * playmaker.mental.vision + 0.5 * target.mental.intuition).game_value()
         + game
             .attacking_team()
-            .tactic
             .attack_roll_bonus(&Action::OffTheScreen);
 
     let def_result = playmaker_defender.roll(action_rng)
@@ -116,7 +131,6 @@ pub(crate) fn execute(
             .game_value()
         + game
             .defending_team()
-            .tactic
             .defense_roll_bonus(&Action::OffTheScreen);
 
     let mut result = match atk_result  - def_result  {
@@ -124,7 +138,7 @@ pub(crate) fn execute(
                 possession: input.possession,
                 advantage: Advantage::Attack,
                 attackers: vec![target_idx],
-                defenders: vec![target_idx],
+                defenders: vec![target_defender_idx],
                 situation: ActionSituation::LongShot,
                 description: if let Some(s) = screener {[
                     format!(
@@ -180,7 +194,7 @@ pub(crate) fn execute(
                 possession: input.possession,
                 advantage: Advantage::Neutral,
                 attackers: vec![target_idx],
-                defenders: vec![target_idx],
+                defenders: vec![target_defender_idx],
                 situation: ActionSituation::LongShot,
                 description: if let Some(s) = screener {[
                     format!(
@@ -232,11 +246,11 @@ pub(crate) fn execute(
                     away_score: input.away_score,
                 ..Default::default()
             },
-            x if x > ADV_DEFENSE_LIMIT => ActionOutput {
+            x if x > defending_team.adv_defense_limit() => ActionOutput {
                 possession: input.possession,
                 advantage: Advantage::Defense,
                 attackers: vec![target_idx],
-                defenders: vec![target_idx],
+                defenders: vec![target_defender_idx],
                 situation: ActionSituation::MediumShot,
                 description: [
                     format!(
@@ -273,7 +287,7 @@ pub(crate) fn execute(
                 playmaker_defender_update.extra_morale += MoraleModifier::SMALL_BONUS;
 
                 // Equivalent to `- def_result - target_defender.defense.steal.game_value() <= STEAL_LIMIT`
-                let with_steal = def_result + target_defender.defense.steal.game_value() >= -STEAL_LIMIT;
+                let with_steal = def_result + target_defender.defense.steal.game_value() >= -defending_team.steal_limit();
 
                 if with_steal{
                     target_defender_update.steals = 1;
@@ -281,16 +295,15 @@ pub(crate) fn execute(
                     playmaker_update.extra_morale += MoraleModifier::MEDIUM_MALUS;
                 }
 
-                let situation = if with_steal && action_rng.random_bool(FASTBREAK_ACTION_PROBABILITY * game.defending_team().tactic.fastbreak_probability_modifier()){
+                let situation = if with_steal && action_rng.random_bool(FASTBREAK_ACTION_PROBABILITY * game.defending_team().fastbreak_probability_modifier()){
                     ActionSituation::Fastbreak
                 } else {
                     ActionSituation::Turnover
                 };
 
-                // After possession flips, the defender who stole is at the same index
-                // as the attacker they were guarding (players are mirrored by position).
+                // After possession flips, the defender who stole becomes the attacker.
                 let attackers = if with_steal {
-                    vec![target_idx]
+                    vec![target_defender_idx]
                 } else {vec![]};
 
                 let end_at = if with_steal {
@@ -94,7 +94,6 @@ fn playmaker_uses_the_screen(
     let attacking_players_array = game.attacking_players_array();
     let defending_players_array = game.defending_players_array();
     let playmaker = attacking_players_array[play_idx];
-    let playmaker_defender = defending_players_array[play_idx];
 
     // Select a screener
     let screener_idx = {
@@ -125,7 +124,16 @@ fn playmaker_uses_the_screen(
         ..Default::default()
     };
 
-    let screener_defender = defending_players_array[screener_idx];
+    let defending_team = game.defending_team();
+    let [mut playmaker_defender_idx, mut screener_defender_idx] =
+        defending_team.defender_indexes([play_idx, screener_idx], action_rng);
+    // Switching defenders trade assignments on the screen.
+    if defending_team.defensive_scheme.switches_screens() {
+        std::mem::swap(&mut playmaker_defender_idx, &mut screener_defender_idx);
+    }
+    let playmaker_defender = defending_players_array[playmaker_defender_idx];
+
+    let screener_defender = defending_players_array[screener_defender_idx];
     let screener_defender_update = GameStats {
         extra_tiredness: TirednessCost::LOW,
         ..Default::default()
@@ -136,7 +144,6 @@ fn playmaker_uses_the_screen(
         ..Default::default()
     };
 
-    // FIXME: playmaker defender and target defender can be same player
     let mut playmaker_defender_update = GameStats {
         extra_tiredness: TirednessCost::MEDIUM,
         ..Default::default()
@@ -148,7 +155,6 @@ fn playmaker_uses_the_screen(
         + (0.5 * screener.athletics.strength + 0.5 * playmaker.mental.intuition).game_value()
         + game
             .attacking_team()
-            .tactic
             .attack_roll_bonus(&Action::PickAndRoll);
 
     let def_result = playmaker_defender.roll(action_rng)
@@ -159,7 +165,6 @@ fn playmaker_uses_the_screen(
             .game_value()
         + game
             .defending_team()
-            .tactic
             .defense_roll_bonus(&Action::PickAndRoll);
 
     // Split: if playmaker has good vision and passing, it passes to another player off-the-screen
@@ -214,7 +219,7 @@ fn playmaker_uses_the_screen(
                     possession: input.possession,
                     advantage: Advantage::Attack,
                     attackers: vec![play_idx],
-                    defenders: vec![play_idx],
+                    defenders: vec![playmaker_defender_idx],
                     situation: ActionSituation::LongShot,
                     description: [
                         format!(
@@ -248,7 +253,7 @@ fn playmaker_uses_the_screen(
                     possession: input.possession,
                     advantage: Advantage::Neutral,
                     attackers: vec![play_idx],
-                    defenders: vec![play_idx],
+                    defenders: vec![playmaker_defender_idx],
                     situation: ActionSituation::LongShot,
                     description: [
                         format!(
@@ -278,13 +283,13 @@ fn playmaker_uses_the_screen(
                     away_score: input.away_score,
                     ..Default::default()
                 },
-                x if x > ADV_DEFENSE_LIMIT => {
+                x if x > defending_team.adv_defense_limit() => {
                     match action_rng.random_bool(0.5) {
                         false => ActionOutput {
                             possession: input.possession,
                             advantage: Advantage::Defense,
                             attackers: vec![play_idx],
-                            defenders: vec![screener_idx],
+                            defenders: vec![screener_defender_idx],
                             situation: ActionSituation::LongShot,
                             description:[
                                 format!(
@@ -310,7 +315,7 @@ fn playmaker_uses_the_screen(
                             possession: input.possession,
                             advantage: Advantage::Defense,
                             attackers: vec![play_idx],
-                            defenders: vec![play_idx],
+                            defenders: vec![playmaker_defender_idx],
                             situation: ActionSituation::LongShot,
                             description:[
                                 format!(
@@ -343,7 +348,7 @@ fn playmaker_uses_the_screen(
                     playmaker_update.extra_morale += MoraleModifier::SMALL_MALUS;
                     playmaker_defender_update.extra_morale += MoraleModifier::SMALL_BONUS;
                     // Equivalent to `- def_result - target_defender.defense.steal.game_value() <= STEAL_LIMIT`
-                    let with_steal = def_result + playmaker_defender.defense.steal.game_value() >= -STEAL_LIMIT;
+                    let with_steal = def_result + playmaker_defender.defense.steal.game_value() >= -defending_team.steal_limit();
 
                     if with_steal {
                         playmaker_defender_update.steals = 1;
@@ -351,16 +356,15 @@ fn playmaker_uses_the_screen(
                         playmaker_update.extra_morale += MoraleModifier::MEDIUM_MALUS;
                     }
 
-                    let situation = if with_steal && action_rng.random_bool(FASTBREAK_ACTION_PROBABILITY * game.defending_team().tactic.fastbreak_probability_modifier()){
+                    let situation = if with_steal && action_rng.random_bool(FASTBREAK_ACTION_PROBABILITY * game.defending_team().fastbreak_probability_modifier()){
                         ActionSituation::Fastbreak
                     } else {
                         ActionSituation::Turnover
                     };
 
-                    // After possession flips, the defender who stole is at the same index
-                    // as the attacker they were guarding (players are mirrored by position).
+                    // After possession flips, the defender who stole becomes the attacker.
                     let attackers = if with_steal {
-                        vec![play_idx]
+                        vec![playmaker_defender_idx]
                     } else {vec![]};
 
                     let end_at = if with_steal {
@@ -440,22 +444,29 @@ fn playmaker_passes_to_target(
 ) -> ActionOutput {
     let attacking_players_array = game.attacking_players_array();
     let defending_players_array = game.defending_players_array();
+    let defending_team = game.defending_team();
+    let [mut playmaker_defender_idx, mut target_defender_idx] =
+        defending_team.defender_indexes([play_idx, target_idx], action_rng);
+    // The target is the screener rolling to the basket: switching defenders trade assignments.
+    if defending_team.defensive_scheme.switches_screens() {
+        std::mem::swap(&mut playmaker_defender_idx, &mut target_defender_idx);
+    }
+
     let playmaker = attacking_players_array[play_idx];
-    let playmaker_defender = defending_players_array[play_idx];
+    let playmaker_defender = defending_players_array[playmaker_defender_idx];
 
     let mut playmaker_update = GameStats {
         extra_tiredness: TirednessCost::MEDIUM,
         ..Default::default()
     };
 
-    // FIXME: playmaker defender and target defender can be same player
     let mut playmaker_defender_update = GameStats {
         extra_tiredness: TirednessCost::MEDIUM,
         ..Default::default()
     };
 
     let target = attacking_players_array[target_idx];
-    let target_defender = defending_players_array[target_idx];
+    let target_defender = defending_players_array[target_defender_idx];
     let target_update = GameStats {
         extra_tiredness: TirednessCost::MEDIUM,
         ..Default::default()
@@ -473,7 +484,6 @@ fn playmaker_passes_to_target(
         + playmaker.technical.passing.game_value()
         + game
             .attacking_team()
-            .tactic
             .attack_roll_bonus(&Action::PickAndRoll);
 
     let def_result = playmaker_defender.roll(action_rng)
@@ -484,7 +494,6 @@ fn playmaker_passes_to_target(
             .game_value()
         + game
             .defending_team()
-            .tactic
             .defense_roll_bonus(&Action::PickAndRoll);
 
     let timer_increase = 4 + action_rng.random_range(0..=3);
@@ -494,7 +503,7 @@ fn playmaker_passes_to_target(
                 possession: input.possession,
                 advantage: Advantage::Attack,
                 attackers: vec![target_idx],
-                defenders: vec![play_idx],
+                defenders: vec![playmaker_defender_idx],
                 situation: shot::dunk_or_close_shot(target, action_rng),
                 description: [
                     format!(
@@ -529,7 +538,7 @@ fn playmaker_passes_to_target(
                 possession: input.possession,
                 advantage: Advantage::Neutral,
                 attackers: vec![target_idx],
-                defenders: vec![play_idx],
+                defenders: vec![playmaker_defender_idx],
                 situation: if action_rng.random_bool(((target.athletics.quickness - 0.5 * target_defender.defense.interior_defense).bound()/MAX_SKILL)as f64) {ActionSituation::CloseShot} else {ActionSituation::MediumShot},
                 description:[
                     format!(
@@ -560,7 +569,7 @@ fn playmaker_passes_to_target(
                     away_score: input.away_score,
                 ..Default::default()
             },
-            x if x > ADV_DEFENSE_LIMIT =>   {
+            x if x > defending_team.adv_defense_limit() =>   {
             // Split: if playmaker has good vision and passing, it passes to a third player
             let num_ok_players = game
                 .attacking_players_array()
@@ -611,7 +620,7 @@ fn playmaker_passes_to_target(
                     possession: input.possession,
                     advantage: Advantage::Defense,
                     attackers: vec![target_idx],
-                    defenders: vec![target_idx],
+                    defenders: vec![target_defender_idx],
                     situation: ActionSituation::MediumShot,
                     description:[
                         format!(
@@ -650,7 +659,7 @@ fn playmaker_passes_to_target(
                 playmaker_defender_update.extra_morale += MoraleModifier::SMALL_BONUS;
 
                 // Equivalent to `- def_result - target_defender.defense.steal.game_value() <= STEAL_LIMIT`
-                let with_steal = def_result + playmaker_defender.defense.steal.game_value() >= -STEAL_LIMIT;
+                let with_steal = def_result + playmaker_defender.defense.steal.game_value() >= -defending_team.steal_limit();
 
                 if with_steal {
                     playmaker_defender_update.steals = 1;
@@ -658,14 +667,14 @@ fn playmaker_passes_to_target(
                     playmaker_update.extra_morale += MoraleModifier::MEDIUM_MALUS;
                 }
 
-                let situation = if with_steal && action_rng.random_bool(FASTBREAK_ACTION_PROBABILITY * game.defending_team().tactic.fastbreak_probability_modifier()){
+                let situation = if with_steal && action_rng.random_bool(FASTBREAK_ACTION_PROBABILITY * game.defending_team().fastbreak_probability_modifier()){
                     ActionSituation::Fastbreak
                 } else {
                     ActionSituation::Turnover
                 };
 
                 let attackers = if with_steal {
-                    vec![play_idx]
+                    vec![playmaker_defender_idx]
                 } else {vec![]};
 
                 let end_at = if with_steal {
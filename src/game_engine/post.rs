@@ -42,7 +42,10 @@ pub(crate) fn execute(
     };
 
     let poster = attacking_players_array[post_idx];
-    let defender = defending_players_array[post_idx];
+    let [defender_idx] = game
+        .defending_team()
+        .defender_indexes([post_idx], action_rng);
+    let defender = defending_players_array[defender_idx];
 
     let timer_increase = 5 + action_rng.random_range(0..=5);
 
@@ -61,25 +64,19 @@ pub(crate) fn execute(
     let atk_result = poster.roll(action_rng)
         + poster.technical.post_moves.game_value()
         + poster.athletics.strength.game_value()
-        + game
-            .attacking_team()
-            .tactic
-            .attack_roll_bonus(&Action::Post);
+        + game.attacking_team().attack_roll_bonus(&Action::Post);
 
     let def_result = defender.roll(action_rng)
         + defender.defense.interior_defense.game_value()
         + (0.75 * defender.athletics.strength + 0.25 * defender.defense.steal).game_value()
-        + game
-            .defending_team()
-            .tactic
-            .defense_roll_bonus(&Action::Post);
+        + game.defending_team().defense_roll_bonus(&Action::Post);
 
     let mut result = match atk_result - def_result {
         x if x >= ADV_ATTACK_LIMIT => ActionOutput {
             possession: input.possession,
             advantage: Advantage::Attack,
             attackers: vec![post_idx],
-            defenders: vec![post_idx],
+            defenders: vec![defender_idx],
             situation: ActionSituation::CloseShot,
             description: [
                 format!(
@@ -121,7 +118,7 @@ pub(crate) fn execute(
             possession: input.possession,
             advantage: Advantage::Neutral,
             attackers: vec![post_idx],
-            defenders: vec![post_idx],
+            defenders: vec![defender_idx],
             situation: ActionSituation::CloseShot,
             description: [
                 format!(
@@ -159,7 +156,7 @@ pub(crate) fn execute(
             away_score: input.away_score,
             ..Default::default()
         },
-        x if x > ADV_DEFENSE_LIMIT => {
+        x if x > game.defending_team().adv_defense_limit() => {
             let num_ok_players = game
                 .attacking_players_array()
                 .iter()
@@ -215,7 +212,7 @@ pub(crate) fn execute(
                         possession: input.possession,
                         advantage: Advantage::Defense,
                         attackers: vec![post_idx],
-                        defenders: vec![post_idx],
+                        defenders: vec![defender_idx],
                         situation: ActionSituation::MediumShot,
                         description: [
                             format!(
@@ -255,7 +252,8 @@ pub(crate) fn execute(
             defender_update.extra_morale += MoraleModifier::SMALL_BONUS;
 
             // Equivalent to `- def_result - target_defender.defense.steal.game_value() <= STEAL_LIMIT`
-            let with_steal = def_result + defender.defense.steal.game_value() >= -STEAL_LIMIT;
+            let with_steal = def_result + defender.defense.steal.game_value()
+                >= -game.defending_team().steal_limit();
 
             if with_steal {
                 defender_update.steals = 1;
@@ -266,17 +264,18 @@ pub(crate) fn execute(
             let situation = if with_steal
                 && action_rng.random_bool(
                     FASTBREAK_ACTION_PROBABILITY
-                        * game
-                            .defending_team()
-                            .tactic
-                            .fastbreak_probability_modifier(),
+                        * game.defending_team().fastbreak_probability_modifier(),
                 ) {
                 ActionSituation::Fastbreak
             } else {
                 ActionSituation::Turnover
             };
 
-            let attackers = if with_steal { vec![post_idx] } else { vec![] };
+            let attackers = if with_steal {
+                vec![defender_idx]
+            } else {
+                vec![]
+            };
 
             let end_at = if with_steal {
                 input.end_at.plus(3 + action_rng.random_range(0..=3))
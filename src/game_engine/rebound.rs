@@ -36,10 +36,7 @@ pub(crate) fn execute(
         .map(|&p| {
             (0.5 * p.athletics.vertical + 0.5 * (0.25 * (p.info.height - 150.0))).game_value()
                 + p.technical.rebounds.game_value()
-                + game
-                    .attacking_team()
-                    .tactic
-                    .attack_roll_bonus(&Action::Rebound)
+                + game.attacking_team().attack_roll_bonus(&Action::Rebound)
         })
         .collect();
     let mut defense_rebounds: Vec<i16> = defending_players_array
@@ -47,10 +44,7 @@ pub(crate) fn execute(
         .map(|&p| {
             (0.5 * p.athletics.vertical + 0.5 * (0.25 * (p.info.height - 150.0))).game_value()
                 + p.technical.rebounds.game_value()
-                + game
-                    .defending_team()
-                    .tactic
-                    .defense_roll_bonus(&Action::Rebound)
+                + game.defending_team().defense_roll_bonus(&Action::Rebound)
         })
         .collect();
 
@@ -0,0 +1,369 @@
+use super::{
+    action::ActionOutput,
+    adjustment::GameAdjustment,
+    game::Game,
+    tactic::{CustomTactic, Tactic},
+    types::TeamInGame,
+    TournamentId,
+};
+use crate::{
+    args::ReplayArgs,
+    core::{utils::is_default, Injury, TickInterval},
+    store::{load_game, load_game_replay_file},
+    types::{AppResult, GameId, PlanetId, Tick},
+};
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+// The state of a team at the start of the game that can change during the game
+// and is not tracked by the initial fields of TeamInGame.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TeamSetup {
+    pub tactic: Tactic,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub custom_tactic: Option<CustomTactic>,
+    // The order is the same as TeamInGame::initial_positions.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub injuries: Vec<Option<Injury>>,
+}
+
+impl TeamSetup {
+    fn from_team_in_game(team: &TeamInGame) -> Self {
+        Self {
+            tactic: team.tactic,
+            custom_tactic: team.custom_tactic.clone(),
+            injuries: team
+                .initial_positions
+                .iter()
+                .map(|id| team.players.get(id).and_then(|player| player.injury))
+                .collect(),
+        }
+    }
+
+    fn restore(&self, team: &TeamInGame) -> AppResult<TeamInGame> {
+        let mut team = team.initial_state()?;
+        team.tactic = self.tactic;
+        team.custom_tactic = self.custom_tactic.clone();
+        for (player_id, injury) in team.initial_positions.iter().zip(self.injuries.iter()) {
+            if let Some(player) = team.players.get_mut(player_id) {
+                player.injury = *injury;
+            }
+        }
+
+        Ok(team)
+    }
+}
+
+// What is needed, together with the teams at the end of the game, to play the game again from the start.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GameSetup {
+    pub home_team: TeamSetup,
+    pub away_team: TeamSetup,
+    pub planet_total_population: u32,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub allow_draw: bool,
+}
+
+impl GameSetup {
+    pub fn new(
+        home_team_in_game: &TeamInGame,
+        away_team_in_game: &TeamInGame,
+        planet_total_population: u32,
+    ) -> Self {
+        Self {
+            home_team: TeamSetup::from_team_in_game(home_team_in_game),
+            away_team: TeamSetup::from_team_in_game(away_team_in_game),
+            planet_total_population,
+            allow_draw: false,
+        }
+    }
+
+    // Home and away teams as they entered the game.
+    pub fn initial_teams(&self, game: &Game) -> AppResult<(TeamInGame, TeamInGame)> {
+        Ok((
+            self.home_team.restore(&game.home_team_in_game)?,
+            self.away_team.restore(&game.away_team_in_game)?,
+        ))
+    }
+}
+
+// A replay contains everything needed to re-run a game with the engine,
+// together with the recorded action outputs to verify the new run against.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameReplay {
+    pub id: GameId,
+    pub home_team_in_game: TeamInGame,
+    pub away_team_in_game: TeamInGame,
+    pub planet_total_population: u32,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub allow_draw: bool,
+    pub starting_at: Tick,
+    pub location: PlanetId,
+    pub planet_name: String,
+    pub part_of_tournament: Option<TournamentId>,
+    pub action_results: Vec<ActionOutput>,
+    pub app_version: [usize; 3],
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub adjustments: Vec<GameAdjustment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayDivergence {
+    pub index: usize,
+    pub field: &'static str,
+    pub expected: Option<ActionOutput>,
+    pub found: Option<ActionOutput>,
+}
+
+impl Display for ReplayDivergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let describe = |output: &Option<ActionOutput>| match output {
+            Some(output) => format!("[{}] {}", output.start_at.format(), output.description),
+            None => "no action".to_string(),
+        };
+
+        write!(
+            f,
+            "Replay diverged at action {} ({}).\nExpected: {}\nFound:    {}",
+            self.index,
+            self.field,
+            describe(&self.expected),
+            describe(&self.found)
+        )
+    }
+}
+
+impl GameReplay {
+    pub fn from_game(game: &Game) -> AppResult<Self> {
+        let setup = game
+            .setup
+            .as_ref()
+            .ok_or_else(|| anyhow!("Game {} has no setup and cannot be replayed", game.id))?;
+        let (home_team_in_game, away_team_in_game) = setup.initial_teams(game)?;
+
+        Ok(Self {
+            id: game.id,
+            home_team_in_game,
+            away_team_in_game,
+            planet_total_population: setup.planet_total_population,
+            allow_draw: setup.allow_draw,
+            starting_at: game.starting_at,
+            location: game.location,
+            planet_name: game.planet_name.clone(),
+            part_of_tournament: game.part_of_tournament,
+            action_results: game.action_results.clone(),
+            app_version: game.app_version(),
+            adjustments: game.adjustments.clone(),
+        })
+    }
+
+    fn new_game(&self) -> Game {
+        let mut game = Game::new(
+            self.id,
+            self.home_team_in_game.clone(),
+            self.away_team_in_game.clone(),
+            self.starting_at,
+            self.location,
+            self.planet_total_population,
+            &self.planet_name,
+            self.part_of_tournament,
+        )
+        .with_draw_allowed(self.allow_draw);
+        game.pending_adjustments = self.adjustments.clone();
+        game
+    }
+
+    // Re-run the game from the start and compare every action output, as soon as it is produced,
+    // with the recorded one. Returns the first mismatch, if any.
+    pub fn first_divergence(&self) -> Option<ReplayDivergence> {
+        let mut game = self.new_game();
+        let mut current_tick = self.starting_at;
+        let mut checked = 0;
+
+        loop {
+            while checked < game.action_results.len() {
+                let found = &game.action_results[checked];
+                let expected = self.action_results.get(checked);
+
+                let field = match expected {
+                    Some(expected) => different_field(expected, found),
+                    None => Some("extra action"),
+                };
+
+                if let Some(field) = field {
+                    return Some(ReplayDivergence {
+                        index: checked,
+                        field,
+                        expected: expected.cloned(),
+                        found: Some(found.clone()),
+                    });
+                }
+                checked += 1;
+            }
+
+            if game.has_ended() {
+                break;
+            }
+
+            game.tick(current_tick);
+            current_tick += TickInterval::SHORT;
+        }
+
+        if checked < self.action_results.len() {
+            return Some(ReplayDivergence {
+                index: checked,
+                field: "missing action",
+                expected: Some(self.action_results[checked].clone()),
+                found: None,
+            });
+        }
+
+        None
+    }
+
+    // Returns the number of verified actions.
+    pub fn verify(&self) -> AppResult<usize> {
+        if let Some(divergence) = self.first_divergence() {
+            return Err(anyhow!("{divergence}"));
+        }
+
+        Ok(self.action_results.len())
+    }
+}
+
+fn different_field(expected: &ActionOutput, found: &ActionOutput) -> Option<&'static str> {
+    if expected.random_seed != found.random_seed {
+        Some("random_seed")
+    } else if expected.situation != found.situation {
+        Some("situation")
+    } else if expected.possession != found.possession {
+        Some("possession")
+    } else if expected.advantage != found.advantage {
+        Some("advantage")
+    } else if expected.start_at != found.start_at {
+        Some("start_at")
+    } else if expected.end_at != found.end_at {
+        Some("end_at")
+    } else if expected.attackers != found.attackers {
+        Some("attackers")
+    } else if expected.defenders != found.defenders {
+        Some("defenders")
+    } else if expected.assist_from != found.assist_from {
+        Some("assist_from")
+    } else if expected.foul_from != found.foul_from || expected.foul_on != found.foul_on {
+        Some("foul")
+    } else if expected.free_throws != found.free_throws {
+        Some("free_throws")
+    } else if expected.home_score != found.home_score
+        || expected.away_score != found.away_score
+        || expected.score_change != found.score_change
+    {
+        Some("score")
+    } else if expected.attack_stats_update != found.attack_stats_update {
+        Some("attack_stats_update")
+    } else if expected.defense_stats_update != found.defense_stats_update {
+        Some("defense_stats_update")
+    } else if expected.description != found.description {
+        Some("description")
+    } else {
+        None
+    }
+}
+
+pub fn run_replay(args: &ReplayArgs) -> AppResult<()> {
+    let replay = if let Some(path) = args.file.as_ref() {
+        load_game_replay_file(path)?
+    } else if let Some(game_id) = args.game_id.as_ref() {
+        GameReplay::from_game(&load_game(game_id)?)?
+    } else {
+        return Err(anyhow!("Either a game id or a replay file must be given"));
+    };
+
+    if replay.app_version != crate::app_version() {
+        println!(
+            "Warning: replay was recorded with version {:?}, running version {:?}",
+            replay.app_version,
+            crate::app_version()
+        );
+    }
+
+    let actions = replay.verify()?;
+    println!(
+        "Replay of game {} verified: {actions} actions match.",
+        replay.id
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GameReplay;
+    use crate::{
+        core::TickInterval,
+        game_engine::{game::Game, types::TeamInGame},
+        store::{deserialize, serialize},
+        types::AppResult,
+    };
+
+    fn played_game() -> Game {
+        let mut game = Game::test(TeamInGame::test(), TeamInGame::test());
+        let mut current_tick = game.starting_at;
+        while !game.has_ended() {
+            game.tick(current_tick);
+            current_tick += TickInterval::SHORT;
+        }
+        game
+    }
+
+    #[test]
+    fn test_replay_matches_original_game() -> AppResult<()> {
+        let game = played_game();
+
+        // Replays are built from the game as it is stored.
+        let stored_game: Game = deserialize(&serialize(&game)?)?;
+        let replay = GameReplay::from_game(&stored_game)?;
+        assert_eq!(replay.verify()?, game.action_results.len());
+
+        // Exported replays are plain json.
+        let replay: GameReplay = serde_json::from_str(&serde_json::to_string(&replay)?)?;
+        assert_eq!(replay.verify()?, game.action_results.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_replay_reports_first_divergence() -> AppResult<()> {
+        let game = played_game();
+        let mut replay = GameReplay::from_game(&game)?;
+
+        let tampered = replay.action_results.len() / 2;
+        replay.action_results[tampered].home_score += 1;
+        let divergence = replay
+            .first_divergence()
+            .expect("Tampered replay should diverge");
+        assert_eq!(divergence.index, tampered);
+        assert_eq!(divergence.field, "score");
+
+        let mut replay = GameReplay::from_game(&game)?;
+        replay.action_results.pop();
+        let divergence = replay
+            .first_divergence()
+            .expect("Truncated replay should diverge");
+        assert_eq!(divergence.index, game.action_results.len() - 1);
+        assert_eq!(divergence.field, "extra action");
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_without_setup_cannot_be_replayed() {
+        let mut game = played_game();
+        game.setup = None;
+        assert!(GameReplay::from_game(&game).is_err());
+    }
+}
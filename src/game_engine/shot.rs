@@ -1,6 +1,7 @@
 use super::{
     action::{ActionOutput, ActionSituation, Advantage},
     constants::*,
+    foul,
     game::Game,
     types::*,
 };
@@ -581,12 +582,26 @@ fn execute_shot(
     };
 
     let success = roll > 0;
-    let blocked_by =
-        if !success && input.advantage == Advantage::Defense && roll <= ADV_DEFENSE_LIMIT {
-            Some(defenders[0])
-        } else {
-            None
-        };
+
+    // A missed shot with a foul does not count as a field goal attempt.
+    let fouled_by = input.defenders.first().copied().filter(|&idx| {
+        foul::is_shooting_foul(defending_players_array[idx], shot_difficulty, action_rng)
+    });
+    let free_throws = match (success, shot_difficulty) {
+        (true, _) => 1,
+        (false, ShotDifficulty::Long) => 3,
+        (false, _) => 2,
+    };
+
+    let blocked_by = if !success
+        && fouled_by.is_none()
+        && input.advantage == Advantage::Defense
+        && roll <= ADV_DEFENSE_LIMIT
+    {
+        Some(defenders[0])
+    } else {
+        None
+    };
 
     let assist_by = if success {
         input.assist_from.map(|idx| attacking_players_array[idx])
@@ -594,7 +609,7 @@ fn execute_shot(
         None
     };
 
-    let description = description(
+    let mut description = description(
         description_rng,
         shooter,
         assist_by,
@@ -605,7 +620,40 @@ fn execute_shot(
         success,
     );
 
+    if let Some(idx) = fouled_by {
+        let defender = defending_players_array[idx];
+        let personal_fouls = game
+            .defending_team()
+            .stats
+            .get(&defender.id)
+            .map(|stats| stats.fouls)
+            .unwrap_or_default()
+            + 1;
+        description.push_str(&foul::shooting_foul_description(
+            description_rng,
+            shooter,
+            defender,
+            personal_fouls,
+            success,
+            free_throws,
+        ));
+    }
+
     let mut result = match success {
+        false if fouled_by.is_some() => ActionOutput {
+            possession: input.possession,
+            attackers: vec![shooter_idx],
+            situation: ActionSituation::FreeThrow,
+            free_throws,
+            foul_from: fouled_by,
+            foul_on: Some(shooter_idx),
+            description,
+            start_at: input.end_at,
+            end_at: input.end_at.plus(3 + action_rng.random_range(0..=2)),
+            home_score: input.home_score,
+            away_score: input.away_score,
+            ..Default::default()
+        },
         false => {
             // Attackers will get a malus in the rebound action.
             let advantage = match input.advantage {
@@ -651,6 +699,17 @@ fn execute_shot(
         }
     };
 
+    // And-one: the shooter keeps the ball for the free throw.
+    if success && fouled_by.is_some() {
+        result.possession = input.possession;
+        result.attackers = vec![shooter_idx];
+        result.situation = ActionSituation::FreeThrow;
+        result.free_throws = free_throws;
+        result.foul_from = fouled_by;
+        result.foul_on = Some(shooter_idx);
+        result.end_at = input.end_at.plus(3 + action_rng.random_range(0..=2));
+    }
+
     // Update stats
     let mut attack_stats_update = HashMap::new();
     let mut shooter_update = GameStats::default();
@@ -661,75 +720,77 @@ fn execute_shot(
         _ => TirednessCost::MEDIUM,
     };
 
-    match shot_difficulty {
-        ShotDifficulty::Dunk | ShotDifficulty::Close => {
-            shooter_update.attempted_2pt = 1;
-            shooter_update.last_action_shot = match game.possession {
-                Possession::Home => {
-                    let (x, y) = *HOME_CLOSE_SHOT_POSITIONS
-                        .choose(action_rng)
-                        .expect("There should be a shooting position");
-                    Some((x, y, result.score_change > 0))
-                }
-                Possession::Away => {
-                    let (x, y) = *AWAY_CLOSE_SHOT_POSITIONS
-                        .choose(action_rng)
-                        .expect("There should be a shooting position");
-                    Some((x, y, result.score_change > 0))
-                }
-            }
-        }
-        ShotDifficulty::Medium => {
-            shooter_update.attempted_2pt = 1;
-            shooter_update.last_action_shot = match game.possession {
-                Possession::Home => {
-                    let (x, y) = *HOME_MEDIUM_SHOT_POSITIONS
-                        .choose(action_rng)
-                        .expect("There should be a shooting position");
-                    Some((x, y, result.score_change > 0))
-                }
-                Possession::Away => {
-                    let (x, y) = *AWAY_MEDIUM_SHOT_POSITIONS
-                        .choose(action_rng)
-                        .expect("There should be a shooting position");
-                    Some((x, y, result.score_change > 0))
-                }
-            }
-        }
-        ShotDifficulty::Long => {
-            shooter_update.attempted_3pt = 1;
-            shooter_update.last_action_shot = match input.advantage {
-                Advantage::Defense => match game.possession {
+    if success || fouled_by.is_none() {
+        match shot_difficulty {
+            ShotDifficulty::Dunk | ShotDifficulty::Close => {
+                shooter_update.attempted_2pt = 1;
+                shooter_update.last_action_shot = match game.possession {
                     Possession::Home => {
-                        let (x, y) = *HOME_IMPOSSIBLE_SHOT_POSITIONS
+                        let (x, y) = *HOME_CLOSE_SHOT_POSITIONS
                             .choose(action_rng)
                             .expect("There should be a shooting position");
                         Some((x, y, result.score_change > 0))
                     }
                     Possession::Away => {
-                        let (x, y) = *AWAY_IMPOSSIBLE_SHOT_POSITIONS
+                        let (x, y) = *AWAY_CLOSE_SHOT_POSITIONS
                             .choose(action_rng)
                             .expect("There should be a shooting position");
                         Some((x, y, result.score_change > 0))
                     }
-                },
-                _ => match game.possession {
+                }
+            }
+            ShotDifficulty::Medium => {
+                shooter_update.attempted_2pt = 1;
+                shooter_update.last_action_shot = match game.possession {
                     Possession::Home => {
-                        let (x, y) = *HOME_LONG_SHOT_POSITIONS
+                        let (x, y) = *HOME_MEDIUM_SHOT_POSITIONS
                             .choose(action_rng)
                             .expect("There should be a shooting position");
                         Some((x, y, result.score_change > 0))
                     }
                     Possession::Away => {
-                        let (x, y) = *AWAY_LONG_SHOT_POSITIONS
+                        let (x, y) = *AWAY_MEDIUM_SHOT_POSITIONS
                             .choose(action_rng)
                             .expect("There should be a shooting position");
                         Some((x, y, result.score_change > 0))
                     }
-                },
+                }
             }
-        }
-    };
+            ShotDifficulty::Long => {
+                shooter_update.attempted_3pt = 1;
+                shooter_update.last_action_shot = match input.advantage {
+                    Advantage::Defense => match game.possession {
+                        Possession::Home => {
+                            let (x, y) = *HOME_IMPOSSIBLE_SHOT_POSITIONS
+                                .choose(action_rng)
+                                .expect("There should be a shooting position");
+                            Some((x, y, result.score_change > 0))
+                        }
+                        Possession::Away => {
+                            let (x, y) = *AWAY_IMPOSSIBLE_SHOT_POSITIONS
+                                .choose(action_rng)
+                                .expect("There should be a shooting position");
+                            Some((x, y, result.score_change > 0))
+                        }
+                    },
+                    _ => match game.possession {
+                        Possession::Home => {
+                            let (x, y) = *HOME_LONG_SHOT_POSITIONS
+                                .choose(action_rng)
+                                .expect("There should be a shooting position");
+                            Some((x, y, result.score_change > 0))
+                        }
+                        Possession::Away => {
+                            let (x, y) = *AWAY_LONG_SHOT_POSITIONS
+                                .choose(action_rng)
+                                .expect("There should be a shooting position");
+                            Some((x, y, result.score_change > 0))
+                        }
+                    },
+                }
+            }
+        };
+    }
 
     if success {
         shooter_update.points = result.score_change;
@@ -754,7 +815,7 @@ fn execute_shot(
             let passer_id = attacking_players_array[passer_index].id;
             attack_stats_update.insert(passer_id, passer_update);
         }
-    } else {
+    } else if fouled_by.is_none() {
         shooter_update.extra_morale += match input.advantage {
             Advantage::Defense => MoraleModifier::SMALL_MALUS,
             Advantage::Neutral => MoraleModifier::MEDIUM_MALUS,
@@ -766,10 +827,23 @@ fn execute_shot(
         }
     }
 
+    // Landing after a dunk is the most dangerous moment in the game.
+    if shot_difficulty == ShotDifficulty::Dunk {
+        if let Some(severity) = shooter.roll_injury(DUNK_INJURY_PROBABILITY, action_rng) {
+            shooter_update.injury = Some(severity);
+            result
+                .description
+                .push_str(&injury_description(shooter, severity));
+        }
+    }
+
     attack_stats_update.insert(shooter.id, shooter_update);
 
-    for defender in defenders.iter() {
+    for (&idx, defender) in input.defenders.iter().zip(defenders.iter()) {
         let mut defender_update = GameStats::default();
+        if fouled_by == Some(idx) {
+            defender_update.fouls = 1;
+        }
         if input.advantage == Advantage::Defense {
             if matches!(blocked_by, Some(player) if player.id == defender.id) {
                 defender_update.blocks = 1;
@@ -23,7 +23,14 @@ pub(crate) fn execute(
         Period::B2 => !game.won_jump_ball,
         // Q4: Assign possession to team that won the jump ball
         Period::B3 => game.won_jump_ball,
-        // OT: FIXME: OT are not handled atm
+        // OT: Assign possession with a jump ball
+        Period::B4 | Period::BOT1 | Period::BOT2 | Period::BOT3 => {
+            if action_rng.random_bool(0.5) {
+                Possession::Home
+            } else {
+                Possession::Away
+            }
+        }
         _ => unreachable!(),
     };
 
@@ -52,6 +59,20 @@ pub(crate) fn execute(
                 &game.away_team_in_game.name
             }
         ),
+        Period::B4 | Period::BOT1 | Period::BOT2 | Period::BOT3 => format!(
+            "It's the start of {}. {} won the jump ball.",
+            match input.end_at.period() {
+                Period::B4 => "the first overtime",
+                Period::BOT1 => "the second overtime",
+                Period::BOT2 => "the third overtime",
+                _ => "the last overtime",
+            },
+            if possession == Possession::Home {
+                &game.home_team_in_game.name
+            } else {
+                &game.away_team_in_game.name
+            }
+        ),
         _ => unreachable!("Invalid period {}", input.end_at.period()),
     };
 
@@ -23,6 +23,7 @@ use std::collections::HashMap;
 fn get_subs<'a>(
     players: &[&'a Player],
     team_stats: &GameStatsMap,
+    only_forced: bool,
     action_rng: &mut ChaCha8Rng,
 ) -> Vec<&'a Player> {
     let bench: Vec<&Player> = players
@@ -30,7 +31,7 @@ fn get_subs<'a>(
         .skip(MAX_GAME_POSITION as usize)
         .filter(|&p| {
             let stats = team_stats.get(&p.id).unwrap();
-            !stats.is_playing() && !p.is_knocked_out()
+            !stats.is_playing() && !p.is_knocked_out() && !stats.is_fouled_out() && !p.is_injured()
         })
         .copied()
         .collect();
@@ -39,6 +40,32 @@ fn get_subs<'a>(
         return vec![];
     }
 
+    // Fouled out and injured players must leave the game as soon as there is someone to replace them.
+    let forced_out = players.iter().take(MAX_GAME_POSITION as usize).find(|&p| {
+        let stats = team_stats.get(&p.id).unwrap();
+        stats.is_playing() && (stats.is_fouled_out() || p.is_injured())
+    });
+
+    if let Some(&out_candidate) = forced_out {
+        let out_position = team_stats
+            .get(&out_candidate.id)
+            .and_then(|stats| stats.position)
+            .expect("Out candidate should have a position");
+        let in_candidate = bench
+            .iter()
+            .max_by(|&a, &b| {
+                let v1 = a.in_game_rating_at_position(out_position) as u16;
+                let v2 = b.in_game_rating_at_position(out_position) as u16;
+                v1.cmp(&v2)
+            })
+            .expect("There should be a in candidate");
+        return vec![in_candidate, out_candidate];
+    }
+
+    if only_forced {
+        return vec![];
+    }
+
     let playing: Vec<&Player> = players
         .iter()
         .take(MAX_GAME_POSITION as usize)
@@ -111,10 +138,11 @@ fn get_subs<'a>(
 fn make_substitution(
     players: Vec<&Player>,
     stats: &GameStatsMap,
+    only_forced: bool,
     action_rng: &mut ChaCha8Rng,
     description_rng: &mut ChaCha8Rng,
 ) -> Option<(String, GameStatsMap)> {
-    let subs = get_subs(&players, stats, action_rng);
+    let subs = get_subs(&players, stats, only_forced, action_rng);
     if subs.is_empty() {
         return None;
     }
@@ -139,7 +167,23 @@ fn make_substitution(
     .cloned()
     .expect("There should be one option");
 
-    if tiredness == MAX_SKILL {
+    if stats.get(&player_out.id)?.is_fouled_out() {
+        description.push_str(
+            format!(
+                "{} fouled out and has to sit for the rest of the game. ",
+                player_out.info.short_name()
+            )
+            .as_str(),
+        );
+    } else if player_out.is_injured() {
+        description.push_str(
+            format!(
+                "{} is injured and can't go on. ",
+                player_out.info.short_name()
+            )
+            .as_str(),
+        );
+    } else if tiredness == MAX_SKILL {
         description.push_str(
             format!(
                 "{} {} completely done. ",
@@ -224,12 +268,19 @@ pub(crate) fn should_execute(
 ) -> Option<ActionOutput> {
     let home_players = &game.home_team_in_game.players;
     let away_players = &game.away_team_in_game.players;
+    // Before free throws only fouled out or injured players are replaced, and the free throws are still to be shot.
+    let before_free_throws = input.situation == ActionSituation::FreeThrow;
     let mut result = ActionOutput {
         advantage: input.advantage,
         possession: input.possession,
         attackers: input.attackers.clone(),
         defenders: input.defenders.clone(),
-        situation: ActionSituation::AfterSubstitution,
+        situation: if before_free_throws {
+            ActionSituation::FreeThrow
+        } else {
+            ActionSituation::AfterSubstitution
+        },
+        free_throws: input.free_throws,
         assist_from: input.assist_from,
         start_at: input.start_at,
         end_at: input.end_at,
@@ -243,6 +294,7 @@ pub(crate) fn should_execute(
     if let Some((description, stats_update)) = make_substitution(
         home_players.by_position(&game.home_team_in_game.stats),
         &game.home_team_in_game.stats,
+        before_free_throws,
         action_rng,
         description_rng,
     ) {
@@ -265,6 +317,7 @@ pub(crate) fn should_execute(
     if let Some((description, stats_update)) = make_substitution(
         away_players.by_position(&game.away_team_in_game.stats),
         &game.away_team_in_game.stats,
+        before_free_throws,
         action_rng,
         description_rng,
     ) {
@@ -293,6 +346,14 @@ pub(crate) fn should_execute(
         }
         away_sub = true;
     }
+    // Positions may have been reassigned, so the free throw shooter index has to follow the player.
+    if let (true, Some(update)) = (before_free_throws, result.attack_stats_update.as_ref()) {
+        let shooter = game.attacking_players_array()[input.attackers[0]];
+        if let Some(position) = update.get(&shooter.id).and_then(|stats| stats.position) {
+            result.attackers = vec![position as usize];
+        }
+    }
+
     if home_sub || away_sub {
         return Some(result);
     }
@@ -1,7 +1,13 @@
-use super::action::Action;
-use rand::seq::IteratorRandom;
+use super::{
+    action::Action,
+    constants::{ADV_DEFENSE_LIMIT, NUMBER_OF_ROLLS, STEAL_LIMIT},
+};
+use crate::{core::position::MAX_GAME_POSITION, types::AppResult};
+use anyhow::anyhow;
+use rand::{seq::IteratorRandom, RngExt};
 use rand_chacha::ChaCha8Rng;
 use rand_distr::{weighted::WeightedIndex, Distribution};
+use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::fmt::Display;
 use strum::IntoEnumIterator;
@@ -28,6 +34,30 @@ impl Display for Tactic {
     }
 }
 
+fn pick_weighted_action(
+    mut weights: [u8; 4],
+    rng: &mut ChaCha8Rng,
+    num_active_players: usize,
+) -> Option<Action> {
+    if num_active_players < 1 {
+        return None;
+    }
+
+    if num_active_players < 2 {
+        weights[1] = 0;
+        weights[2] = 0;
+    }
+
+    let action = match WeightedIndex::new(weights).ok()?.sample(rng) {
+        0 => Action::Isolation,
+        1 => Action::OffTheScreen,
+        2 => Action::PickAndRoll,
+        3 => Action::Post,
+        _ => unreachable!(),
+    };
+    Some(action)
+}
+
 impl Tactic {
     pub fn random(rng: &mut ChaCha8Rng) -> Self {
         Self::iter()
@@ -44,6 +74,15 @@ impl Tactic {
         }
     }
 
+    pub fn previous(&self) -> Self {
+        match self {
+            Self::Balanced => Self::Shooters,
+            Self::BigPirates => Self::Balanced,
+            Self::Arrembaggio => Self::BigPirates,
+            Self::Shooters => Self::Arrembaggio,
+        }
+    }
+
     pub fn description(&self) -> &str {
         match self {
             Self::Balanced => "A balanced tactic, trying to alternate several possible actions. Ideal for crews with low stamina.",
@@ -55,31 +94,18 @@ impl Tactic {
         }
     }
 
-    pub fn pick_action(&self, rng: &mut ChaCha8Rng, num_active_players: usize) -> Option<Action> {
-        if num_active_players < 1 {
-            return None;
-        }
-
-        let mut weights = match self {
+    // Weights for Isolation, OffTheScreen, PickAndRoll and Post.
+    pub fn action_weights(&self) -> [u8; 4] {
+        match self {
             Self::Balanced => [2, 2, 2, 2],
             Self::BigPirates => [1, 1, 2, 4],
             Self::Arrembaggio => [3, 1, 3, 1],
             Self::Shooters => [1, 4, 2, 1],
-        };
-
-        if num_active_players < 2 {
-            weights[1] = 0;
-            weights[2] = 0;
         }
+    }
 
-        let action = match WeightedIndex::new(weights).ok()?.sample(rng) {
-            0 => Action::Isolation,
-            1 => Action::OffTheScreen,
-            2 => Action::PickAndRoll,
-            3 => Action::Post,
-            _ => unreachable!(),
-        };
-        Some(action)
+    pub fn pick_action(&self, rng: &mut ChaCha8Rng, num_active_players: usize) -> Option<Action> {
+        pick_weighted_action(self.action_weights(), rng, num_active_players)
     }
 
     pub fn brawl_probability_modifier(&self) -> f64 {
@@ -201,3 +227,381 @@ impl Tactic {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, Default, Serialize_repr, Deserialize_repr, PartialEq, EnumIter)]
+#[repr(u8)]
+pub enum DefensiveScheme {
+    #[default]
+    ManToMan,
+    Zone,
+    FullCourtPress,
+    SwitchEverything,
+}
+
+impl Display for DefensiveScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ManToMan => write!(f, "Man-to-man"),
+            Self::Zone => write!(f, "Zone"),
+            Self::FullCourtPress => write!(f, "Full-court press"),
+            Self::SwitchEverything => write!(f, "Switch everything"),
+        }
+    }
+}
+
+impl DefensiveScheme {
+    pub fn random(rng: &mut ChaCha8Rng) -> Self {
+        Self::iter()
+            .choose_stable(rng)
+            .expect("There should be at least a defensive scheme")
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Self::ManToMan => Self::Zone,
+            Self::Zone => Self::FullCourtPress,
+            Self::FullCourtPress => Self::SwitchEverything,
+            Self::SwitchEverything => Self::ManToMan,
+        }
+    }
+
+    pub fn previous(&self) -> Self {
+        match self {
+            Self::ManToMan => Self::SwitchEverything,
+            Self::Zone => Self::ManToMan,
+            Self::FullCourtPress => Self::Zone,
+            Self::SwitchEverything => Self::FullCourtPress,
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            Self::ManToMan => "Every pirate guards the opponent at the same position. Solid, no surprises.",
+            Self::Zone => "Pirates guard an area rather than a player. Packs the paint and saves energy, but leaves shooters open and forces fewer turnovers.",
+            Self::FullCourtPress => "Harass the ball handler on the whole court. Forces many turnovers and steals, extremely tiring and vulnerable on fastbreaks.",
+            Self::SwitchEverything => "Defenders switch on every screen. Great against screens, but big pirates get punished by mismatches in the post.",
+        }
+    }
+
+    // Indexes of the defenders guarding the attackers at the given positions.
+    // Different attackers are always guarded by different defenders.
+    pub fn defender_indexes<const N: usize>(
+        &self,
+        attacker_idxs: [usize; N],
+        rng: &mut ChaCha8Rng,
+    ) -> [usize; N] {
+        match self {
+            // The defender is whoever guards the area the attackers end up in:
+            // neighbouring positions can swap their areas.
+            Self::Zone => {
+                let pairings: [[usize; MAX_GAME_POSITION as usize]; 3] =
+                    [[0, 1, 2, 3, 4], [1, 0, 3, 2, 4], [0, 2, 1, 4, 3]];
+                let pairing = pairings[rng.random_range(0..pairings.len())];
+                attacker_idxs.map(|idx| pairing[idx])
+            }
+            _ => attacker_idxs,
+        }
+    }
+
+    // When switching, the defenders of the ball handler and of the screener swap assignments.
+    pub fn switches_screens(&self) -> bool {
+        *self == Self::SwitchEverything
+    }
+
+    // Roll difference under which the attack turns the ball over.
+    pub fn adv_defense_limit(&self) -> i16 {
+        match self {
+            Self::ManToMan => ADV_DEFENSE_LIMIT,
+            Self::Zone => ADV_DEFENSE_LIMIT - NUMBER_OF_ROLLS as i16 / 3,
+            Self::FullCourtPress => ADV_DEFENSE_LIMIT + 2 * NUMBER_OF_ROLLS as i16 / 3,
+            Self::SwitchEverything => ADV_DEFENSE_LIMIT,
+        }
+    }
+
+    pub fn steal_limit(&self) -> i16 {
+        match self {
+            Self::ManToMan => STEAL_LIMIT,
+            Self::Zone => STEAL_LIMIT - (NUMBER_OF_ROLLS as i16 + 4) / 3,
+            Self::FullCourtPress => STEAL_LIMIT + 2 * (NUMBER_OF_ROLLS as i16 + 4) / 3,
+            Self::SwitchEverything => STEAL_LIMIT,
+        }
+    }
+
+    pub fn defending_tiredness_modifier(&self) -> f32 {
+        match self {
+            Self::ManToMan => 1.0,
+            Self::Zone => 0.8,
+            Self::FullCourtPress => 1.7,
+            Self::SwitchEverything => 1.05,
+        }
+    }
+
+    // Added to the tactic defense roll bonus.
+    pub fn defense_roll_bonus(&self, action: &Action) -> i16 {
+        match self {
+            Self::ManToMan => 0,
+            Self::Zone => match action {
+                Action::Isolation => 5,
+                Action::Post => 7,
+                Action::OffTheScreen => -3,
+                Action::Rebound => -2,
+                _ => 0,
+            },
+            Self::FullCourtPress => match action {
+                Action::PickAndRoll => -2,
+                Action::Post => -3,
+                Action::Fastbreak => -4,
+                _ => 0,
+            },
+            Self::SwitchEverything => match action {
+                Action::PickAndRoll => 4,
+                Action::OffTheScreen => 3,
+                Action::Isolation => -2,
+                Action::Post => -4,
+                _ => 0,
+            },
+        }
+    }
+}
+
+pub const MAX_CUSTOM_TACTICS: usize = 4;
+pub const MAX_CUSTOM_TACTIC_NAME_LENGTH: usize = 16;
+pub const MAX_ACTION_WEIGHT: u8 = 8;
+pub const MIN_FASTBREAK_AGGRESSIVENESS: u8 = 1;
+pub const MAX_FASTBREAK_AGGRESSIVENESS: u8 = 10;
+pub const MIN_TIREDNESS_BUDGET: u8 = 1;
+pub const MAX_TIREDNESS_BUDGET: u8 = 10;
+const NEUTRAL_FASTBREAK_AGGRESSIVENESS: u8 = 5;
+const NEUTRAL_TIREDNESS_BUDGET: u8 = 5;
+
+// A tactic defined by the crew. Roll bonuses and brawl probability come from the base tactic,
+// the tiredness budget trades extra tiredness for a bonus (or malus) to all rolls.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CustomTactic {
+    pub name: String,
+    pub base: Tactic,
+    // Weights for Isolation, OffTheScreen, PickAndRoll and Post.
+    pub action_weights: [u8; 4],
+    pub fastbreak_aggressiveness: u8,
+    pub tiredness_budget: u8,
+}
+
+impl CustomTactic {
+    pub fn new(name: impl Into<String>, base: Tactic) -> Self {
+        Self {
+            name: name.into(),
+            base,
+            action_weights: base.action_weights(),
+            fastbreak_aggressiveness: NEUTRAL_FASTBREAK_AGGRESSIVENESS,
+            tiredness_budget: NEUTRAL_TIREDNESS_BUDGET,
+        }
+    }
+
+    pub fn validate(&self) -> AppResult<()> {
+        let name_length = self.name.chars().count();
+        if name_length == 0 || name_length > MAX_CUSTOM_TACTIC_NAME_LENGTH {
+            return Err(anyhow!(
+                "Tactic name should be between 1 and {MAX_CUSTOM_TACTIC_NAME_LENGTH} characters"
+            ));
+        }
+
+        if self
+            .action_weights
+            .iter()
+            .any(|&weight| weight > MAX_ACTION_WEIGHT)
+        {
+            return Err(anyhow!(
+                "Action weights should be at most {MAX_ACTION_WEIGHT}"
+            ));
+        }
+
+        // Isolation and Post are the only actions available with a single active player.
+        if self.action_weights[0] + self.action_weights[3] == 0 {
+            return Err(anyhow!("Isolation and Post cannot both have weight 0"));
+        }
+
+        if !(MIN_FASTBREAK_AGGRESSIVENESS..=MAX_FASTBREAK_AGGRESSIVENESS)
+            .contains(&self.fastbreak_aggressiveness)
+        {
+            return Err(anyhow!(
+                "Fastbreak aggressiveness should be between {MIN_FASTBREAK_AGGRESSIVENESS} and {MAX_FASTBREAK_AGGRESSIVENESS}"
+            ));
+        }
+
+        if !(MIN_TIREDNESS_BUDGET..=MAX_TIREDNESS_BUDGET).contains(&self.tiredness_budget) {
+            return Err(anyhow!(
+                "Tiredness budget should be between {MIN_TIREDNESS_BUDGET} and {MAX_TIREDNESS_BUDGET}"
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn description(&self) -> String {
+        format!(
+            "Custom tactic based on {}. Fastbreak aggressiveness {}/{}, tiredness budget {}/{}.",
+            self.base,
+            self.fastbreak_aggressiveness,
+            MAX_FASTBREAK_AGGRESSIVENESS,
+            self.tiredness_budget,
+            MAX_TIREDNESS_BUDGET
+        )
+    }
+
+    pub fn pick_action(&self, rng: &mut ChaCha8Rng, num_active_players: usize) -> Option<Action> {
+        pick_weighted_action(self.action_weights, rng, num_active_players)
+    }
+
+    pub fn brawl_probability_modifier(&self) -> f64 {
+        self.base.brawl_probability_modifier()
+    }
+
+    pub fn playing_tiredness_modifier(&self) -> f32 {
+        self.base.playing_tiredness_modifier() * (0.5 + 0.1 * self.tiredness_budget as f32)
+    }
+
+    pub fn fastbreak_probability_modifier(&self) -> f64 {
+        self.fastbreak_aggressiveness as f64 / NEUTRAL_FASTBREAK_AGGRESSIVENESS as f64
+    }
+
+    // One roll point every two budget points: at the extremes of the budget the win rate
+    // between identical crews moves by about 8% (see tests/game_stats.rs).
+    fn effort_bonus(&self) -> i16 {
+        (self.tiredness_budget as i16 - NEUTRAL_TIREDNESS_BUDGET as i16) / 2
+    }
+
+    pub fn attack_roll_bonus(&self, action: &Action) -> i16 {
+        self.base.attack_roll_bonus(action) + self.effort_bonus()
+    }
+
+    pub fn defense_roll_bonus(&self, action: &Action) -> i16 {
+        self.base.defense_roll_bonus(action) + self.effort_bonus()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CustomTactic, DefensiveScheme, Tactic, MAX_ACTION_WEIGHT, MAX_TIREDNESS_BUDGET};
+    use crate::{
+        core::position::MAX_GAME_POSITION,
+        game_engine::{
+            action::Action,
+            constants::{ADV_DEFENSE_LIMIT, STEAL_LIMIT},
+        },
+        types::AppResult,
+    };
+    use itertools::Itertools;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_defensive_scheme_defender_indexes() {
+        let rng = &mut ChaCha8Rng::seed_from_u64(0);
+        let attackers = [0, 1, 2, 3, 4];
+        assert_eq!(
+            DefensiveScheme::ManToMan.defender_indexes(attackers, rng),
+            attackers
+        );
+
+        // Zone defenders may swap areas, but never guard two attackers at once.
+        let mut swapped = false;
+        for _ in 0..100 {
+            let defenders = DefensiveScheme::Zone.defender_indexes(attackers, rng);
+            assert!(defenders
+                .iter()
+                .all(|&idx| idx < MAX_GAME_POSITION as usize));
+            assert_eq!(defenders.iter().unique().count(), attackers.len());
+            swapped |= defenders != attackers;
+        }
+        assert!(swapped);
+
+        assert!(DefensiveScheme::SwitchEverything.switches_screens());
+        assert!(!DefensiveScheme::ManToMan.switches_screens());
+    }
+
+    #[test]
+    fn test_defensive_scheme_limits() {
+        assert_eq!(
+            DefensiveScheme::ManToMan.adv_defense_limit(),
+            ADV_DEFENSE_LIMIT
+        );
+        assert_eq!(DefensiveScheme::ManToMan.steal_limit(), STEAL_LIMIT);
+
+        // The press forces more turnovers and steals, the zone fewer.
+        let press = DefensiveScheme::FullCourtPress;
+        let zone = DefensiveScheme::Zone;
+        assert!(press.adv_defense_limit() > ADV_DEFENSE_LIMIT);
+        assert!(press.steal_limit() > STEAL_LIMIT);
+        assert!(zone.adv_defense_limit() < ADV_DEFENSE_LIMIT);
+        assert!(zone.steal_limit() < STEAL_LIMIT);
+        assert!(press.defending_tiredness_modifier() > zone.defending_tiredness_modifier());
+
+        for scheme in DefensiveScheme::iter() {
+            assert_eq!(scheme.next().previous(), scheme);
+        }
+    }
+
+    #[test]
+    fn test_custom_tactic_validation() -> AppResult<()> {
+        for tactic in Tactic::iter() {
+            CustomTactic::new("Custom", tactic).validate()?;
+        }
+
+        let valid = CustomTactic::new("Custom", Tactic::Balanced);
+
+        let mut tactic = valid.clone();
+        tactic.name = String::new();
+        assert!(tactic.validate().is_err());
+
+        let mut tactic = valid.clone();
+        tactic.action_weights[1] = MAX_ACTION_WEIGHT + 1;
+        assert!(tactic.validate().is_err());
+
+        let mut tactic = valid.clone();
+        tactic.action_weights = [0, 4, 4, 0];
+        assert!(tactic.validate().is_err());
+
+        let mut tactic = valid.clone();
+        tactic.fastbreak_aggressiveness = 0;
+        assert!(tactic.validate().is_err());
+
+        let mut tactic = valid;
+        tactic.tiredness_budget = MAX_TIREDNESS_BUDGET + 1;
+        assert!(tactic.validate().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_tactic_follows_action_weights() {
+        let mut tactic = CustomTactic::new("Posting", Tactic::BigPirates);
+        tactic.action_weights = [0, 0, 0, 3];
+        let rng = &mut ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..100 {
+            assert_eq!(tactic.pick_action(rng, 5), Some(Action::Post));
+        }
+
+        // With a single active player, only Isolation and Post can be picked.
+        tactic.action_weights = [1, 8, 8, 0];
+        for _ in 0..100 {
+            assert_eq!(tactic.pick_action(rng, 1), Some(Action::Isolation));
+        }
+    }
+
+    #[test]
+    fn test_neutral_custom_tactic_matches_base() {
+        for base in Tactic::iter() {
+            let tactic = CustomTactic::new("Custom", base);
+            assert_eq!(
+                tactic.playing_tiredness_modifier(),
+                base.playing_tiredness_modifier()
+            );
+            assert_eq!(tactic.fastbreak_probability_modifier(), 1.0);
+            assert_eq!(
+                tactic.attack_roll_bonus(&Action::Post),
+                base.attack_roll_bonus(&Action::Post)
+            );
+        }
+    }
+}
@@ -4,12 +4,17 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 use strum::Display;
 
 const MINUTES_PER_QUARTER: u16 = 10;
+const MINUTES_PER_OVERTIME: u16 = 5;
 const MINUTES_PER_BREAK: u16 = 2;
 // const HALFTIME_BREAK_DURATION: u16 = 10;
 // const QUARTERS: u16 = 4;
 const SECONDS_PER_MINUTE: u16 = 60;
+pub const MAX_OVERTIMES: u16 = 4;
+// Duration of the regulation time, from the start of the game to the end of Q4.
 pub const MAX_TIME_IN_SECONDS: u16 =
     SECONDS_PER_MINUTE * (MINUTES_PER_QUARTER * 4 + MINUTES_PER_BREAK * 3);
+pub const MAX_TIME_WITH_OVERTIMES_IN_SECONDS: u16 = MAX_TIME_IN_SECONDS
+    + SECONDS_PER_MINUTE * MAX_OVERTIMES * (MINUTES_PER_BREAK + MINUTES_PER_OVERTIME);
 
 #[derive(
     Debug, Display, Default, PartialEq, PartialOrd, Clone, Copy, Serialize_repr, Deserialize_repr,
@@ -26,6 +31,14 @@ pub enum Period {
     B3,
     Q4,
     B4,
+    OT1,
+    BOT1,
+    OT2,
+    BOT2,
+    OT3,
+    BOT3,
+    OT4,
+    BOT4,
 }
 
 impl Period {
@@ -39,7 +52,15 @@ impl Period {
             Self::Q3 => Self::B3,
             Self::B3 => Self::Q4,
             Self::Q4 => Self::B4,
-            Self::B4 => Self::B4,
+            Self::B4 => Self::OT1,
+            Self::OT1 => Self::BOT1,
+            Self::BOT1 => Self::OT2,
+            Self::OT2 => Self::BOT2,
+            Self::BOT2 => Self::OT3,
+            Self::OT3 => Self::BOT3,
+            Self::BOT3 => Self::OT4,
+            Self::OT4 => Self::BOT4,
+            Self::BOT4 => Self::BOT4,
         }
     }
 
@@ -54,31 +75,69 @@ impl Period {
             Self::B3 => Self::Q3,
             Self::Q4 => Self::B3,
             Self::B4 => Self::Q4,
+            Self::OT1 => Self::B4,
+            Self::BOT1 => Self::OT1,
+            Self::OT2 => Self::BOT1,
+            Self::BOT2 => Self::OT2,
+            Self::OT3 => Self::BOT2,
+            Self::BOT3 => Self::OT3,
+            Self::OT4 => Self::BOT3,
+            Self::BOT4 => Self::OT4,
         }
     }
-    pub fn start(&self) -> u16 {
+
+    pub fn is_overtime(&self) -> bool {
+        matches!(self, Self::OT1 | Self::OT2 | Self::OT3 | Self::OT4)
+    }
+
+    pub fn is_break(&self) -> bool {
+        matches!(
+            self,
+            Self::NotStarted
+                | Self::B1
+                | Self::B2
+                | Self::B3
+                | Self::B4
+                | Self::BOT1
+                | Self::BOT2
+                | Self::BOT3
+                | Self::BOT4
+        )
+    }
+
+    // The game can end at the beginning of the break after the regulation time or after an overtime.
+    // If the score is tied at that point, the game goes to the next overtime instead.
+    pub fn can_end_game(&self) -> bool {
+        matches!(
+            self,
+            Self::B4 | Self::BOT1 | Self::BOT2 | Self::BOT3 | Self::BOT4
+        )
+    }
+
+    fn duration(&self) -> u16 {
         match self {
             Self::NotStarted => 0,
-            Self::Q1 => 1,
-            Self::B1 | Self::B2 | Self::B3 | Self::B4 => {
-                self.previous().start() + SECONDS_PER_MINUTE * MINUTES_PER_QUARTER
-            }
-            Self::Q2 | Self::Q3 | Self::Q4 => {
-                self.previous().start() + SECONDS_PER_MINUTE * MINUTES_PER_BREAK
+            Self::Q1 | Self::Q2 | Self::Q3 | Self::Q4 => SECONDS_PER_MINUTE * MINUTES_PER_QUARTER,
+            Self::OT1 | Self::OT2 | Self::OT3 | Self::OT4 => {
+                SECONDS_PER_MINUTE * MINUTES_PER_OVERTIME
             }
+            // The last break has no duration, since the game cannot go on after it.
+            Self::BOT4 => 0,
+            _ => SECONDS_PER_MINUTE * MINUTES_PER_BREAK,
+        }
+    }
+
+    pub fn start(&self) -> u16 {
+        match self {
+            Self::NotStarted => 0,
+            _ => self.previous().end() + 1,
         }
     }
 
     pub fn end(&self) -> u16 {
         match self {
             Self::NotStarted => 0,
-            Self::Q1 | Self::Q2 | Self::Q3 | Self::Q4 => {
-                self.previous().end() + SECONDS_PER_MINUTE * MINUTES_PER_QUARTER
-            }
-            Self::B1 | Self::B2 | Self::B3 => {
-                self.previous().end() + SECONDS_PER_MINUTE * MINUTES_PER_BREAK
-            }
-            Self::B4 => MAX_TIME_IN_SECONDS,
+            _ => self.previous().end() + self.duration(),
         }
     }
 }
@@ -106,35 +165,23 @@ impl Timer {
     }
 
     pub fn period(&self) -> Period {
-        match self.value {
-            x if x < Period::Q1.start() => Period::Q1.previous(),
-            x if x < Period::B1.start() => Period::B1.previous(),
-            x if x < Period::Q2.start() => Period::Q2.previous(),
-            x if x < Period::B2.start() => Period::B2.previous(),
-            x if x < Period::Q3.start() => Period::Q3.previous(),
-            x if x < Period::B3.start() => Period::B3.previous(),
-            x if x < Period::Q4.start() => Period::Q4.previous(),
-            x if x < Period::B4.start() => Period::B4.previous(),
-            _ => Period::B4,
+        let mut period = Period::NotStarted;
+        while period != Period::BOT4 && self.value >= period.next().start() {
+            period = period.next();
         }
+        period
     }
 
     pub fn minutes(&self) -> u16 {
-        (self.period().end() - self.value) / SECONDS_PER_MINUTE
+        self.period().end().saturating_sub(self.value) / SECONDS_PER_MINUTE
     }
 
     pub fn seconds(&self) -> u16 {
-        if self.value > MAX_TIME_IN_SECONDS {
-            return 0;
-        }
-        (MAX_TIME_IN_SECONDS - self.value) % SECONDS_PER_MINUTE
+        self.period().end().saturating_sub(self.value) % SECONDS_PER_MINUTE
     }
 
     pub fn is_break(&self) -> bool {
-        matches!(
-            self.period(),
-            Period::NotStarted | Period::B1 | Period::B2 | Period::B3 | Period::B4
-        )
+        self.period().is_break()
     }
 
     pub fn plus(&self, seconds: u16) -> Self {
@@ -149,11 +196,12 @@ impl Timer {
 
     pub fn format(&self) -> String {
         if self.has_ended() {
-            return "Q4 00:00".to_string();
+            return format!("{:2} 00:00", self.period().previous());
         }
 
         if self.is_break() && self.value == self.period().end() {
-            format!("{:2} 10:00", self.period().next(),)
+            let next = self.period().next();
+            format!("{:2} {:02}:00", next, next.duration() / SECONDS_PER_MINUTE)
         } else {
             format!(
                 "{:2} {:02}:{:02}",
@@ -179,14 +227,19 @@ impl Timer {
         self.value > 0
     }
 
+    // Whether the game cannot go on anymore, even if it is tied. Games usually end before,
+    // see Period::can_end_game.
     pub fn has_ended(&self) -> bool {
-        self.period() == Period::B4
+        self.period() == Period::BOT4
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::game_engine::timer::{self, Period, Timer, MINUTES_PER_BREAK, MINUTES_PER_QUARTER};
+    use crate::game_engine::timer::{
+        self, Period, Timer, MAX_TIME_WITH_OVERTIMES_IN_SECONDS, MINUTES_PER_BREAK,
+        MINUTES_PER_OVERTIME, MINUTES_PER_QUARTER,
+    };
     use std::io::{stdout, Write};
 
     #[test]
@@ -281,8 +334,27 @@ mod tests {
         assert_eq!(timer.has_ended(), false);
 
         timer.tick();
-        assert_eq!(timer.format(), "Q4 00:00");
+        assert_eq!(timer.format(), "B4 01:59");
+        assert_eq!(timer.period(), super::Period::B4);
+        assert!(timer.period().can_end_game());
+        assert!(!timer.has_ended());
+
+        timer.tick_by(60 * 2 - 1);
+        assert_eq!(timer.format(), "OT1 05:00");
         assert_eq!(timer.period(), super::Period::B4);
+
+        timer.tick();
+        assert_eq!(timer.format(), "OT1 04:59");
+        assert_eq!(timer.period(), super::Period::OT1);
+
+        timer.tick_by((60 * 2 + 60 * 5) * 3 + 60 * 5 - 1);
+        assert_eq!(timer.format(), "OT4 00:00");
+        assert_eq!(timer.period(), super::Period::OT4);
+        assert!(!timer.has_ended());
+
+        timer.tick();
+        assert_eq!(timer.format(), "OT4 00:00");
+        assert_eq!(timer.period(), super::Period::BOT4);
         assert_eq!(timer.has_ended(), true);
     }
 
@@ -372,6 +444,18 @@ mod tests {
 
         timer.tick_by(1);
         assert_eq!(timer.period(), super::Period::B4);
+        assert!(!timer.has_ended());
+
+        timer.tick_by(60 * MINUTES_PER_BREAK);
+        assert_eq!(timer.period(), super::Period::OT1);
+        assert!(timer.period().is_overtime());
+
+        timer.tick_by(60 * MINUTES_PER_OVERTIME);
+        assert_eq!(timer.period(), super::Period::BOT1);
+        assert!(timer.period().can_end_game());
+
+        timer.tick_by(MAX_TIME_WITH_OVERTIMES_IN_SECONDS);
+        assert_eq!(timer.period(), super::Period::BOT4);
         assert_eq!(timer.has_ended(), true);
     }
 
@@ -393,6 +477,13 @@ mod tests {
         assert_eq!(Timer::from(Period::B3.end()).format(), "Q4 10:00");
         assert_eq!(Timer::from(Period::Q4.start()).format(), "Q4 09:59");
         assert_eq!(Timer::from(Period::Q4.end()).format(), "Q4 00:00");
-        assert_eq!(Timer::from(Period::B4.start()).format(), "Q4 00:00");
+        assert_eq!(Timer::from(Period::B4.start()).format(), "B4 01:59");
+        assert_eq!(Timer::from(Period::B4.end()).format(), "OT1 05:00");
+        assert_eq!(Timer::from(Period::OT1.start()).format(), "OT1 04:59");
+        assert_eq!(Timer::from(Period::OT1.end()).format(), "OT1 00:00");
+        assert_eq!(Timer::from(Period::BOT3.end()).format(), "OT4 05:00");
+        assert_eq!(Timer::from(Period::OT4.end()).format(), "OT4 00:00");
+        assert_eq!(Timer::from(Period::BOT4.start()).format(), "OT4 00:00");
+        assert_eq!(Period::OT4.end(), MAX_TIME_WITH_OVERTIMES_IN_SECONDS);
     }
 }
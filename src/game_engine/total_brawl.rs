@@ -1,4 +1,4 @@
-use super::{action::*, game::Game, types::*};
+use super::{action::*, constants::BRAWL_INJURY_PROBABILITY, game::Game, types::*};
 use crate::core::{constants::TirednessCost, player::Trait, Skill};
 use rand::{seq::IndexedRandom, RngExt};
 use rand_chacha::ChaCha8Rng;
@@ -204,6 +204,23 @@ pub(crate) fn execute(
         }
     };
 
+    // Nobody leaves a total brawl unscathed.
+    for (players, stats_update) in [
+        (attacking_players_array, &mut attack_stats_update),
+        (defending_players_array, &mut defense_stats_update),
+    ] {
+        for player in players.iter() {
+            if let Some(severity) = player.roll_injury(BRAWL_INJURY_PROBABILITY / 2.0, action_rng) {
+                if let Some(update) = stats_update.get_mut(&player.id) {
+                    update.injury = Some(severity);
+                }
+                output
+                    .description
+                    .push_str(&injury_description(player, severity));
+            }
+        }
+    }
+
     output.attack_stats_update = Some(attack_stats_update);
     output.defense_stats_update = Some(defense_stats_update);
 
@@ -6,6 +6,7 @@ use crate::{
         timer,
         types::TeamInGame,
     },
+    network::protocol::PROTOCOL_VERSION,
     types::{
         AppResult, GameId, GameMap, GameSummaryMap, KartoffelId, PlanetId, PlayerMap,
         SystemTimeTick, TeamId, Tick,
@@ -13,12 +14,13 @@ use crate::{
 };
 use anyhow::anyhow;
 use itertools::Itertools;
+use libp2p::PeerId;
 use rand::{seq::SliceRandom, RngExt, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
-use std::collections::HashMap;
-use strum::Display;
+use std::collections::{HashMap, HashSet};
+use strum::{Display, EnumIter};
 
 pub type TournamentId = uuid::Uuid;
 
@@ -45,6 +47,12 @@ pub struct TournamentSummary {
     #[serde(skip_serializing_if = "is_default")]
     #[serde(default)]
     tournament_rating: Skill,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub standings: Vec<TournamentStanding>,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub bracket_slots: Vec<BracketSlot>,
     app_version: [usize; 3],
 }
 
@@ -82,6 +90,8 @@ impl TournamentSummary {
                 })
                 .expect("Ended tournament should have a winner"),
             tournament_rating: tournament.rating(),
+            standings: tournament.standings.clone(),
+            bracket_slots: tournament.bracket_slots.clone(),
             app_version: tournament.app_version,
         }
     }
@@ -95,14 +105,28 @@ impl TournamentSummary {
     pub fn name(&self) -> String {
         format!("{} {}", self.planet_name, self.tournament_type)
     }
+
+    pub fn tournament_type(&self) -> TournamentType {
+        self.tournament_type
+    }
+
+    pub fn rounds(&self, bracket: TournamentBracket) -> Vec<(usize, Vec<GameId>)> {
+        games_by_round(&self.game_ids, &self.bracket_slots, bracket)
+    }
 }
 
-#[derive(Debug, Default, Display, Clone, Copy, Serialize_repr, Deserialize_repr, PartialEq)]
+#[derive(
+    Debug, Default, Display, Clone, Copy, Serialize_repr, Deserialize_repr, PartialEq, EnumIter,
+)]
 #[repr(u8)]
 pub enum TournamentType {
     #[default]
     Cup,
     Supercup,
+    League,
+    #[strum(to_string = "Double Elimination")]
+    DoubleElimination,
+    Swiss,
 }
 
 impl TournamentType {
@@ -110,6 +134,9 @@ impl TournamentType {
         match self {
             Self::Cup => 4,
             Self::Supercup => 8,
+            Self::League => 6,
+            Self::DoubleElimination => 8,
+            Self::Swiss => 8,
         }
     }
 
@@ -117,8 +144,230 @@ impl TournamentType {
         match self {
             Self::Cup => 5 * MINUTES,
             Self::Supercup => 45 * MINUTES,
+            Self::League => 60 * MINUTES,
+            Self::DoubleElimination => 45 * MINUTES,
+            Self::Swiss => 45 * MINUTES,
+        }
+    }
+
+    // Formats whose games are generated one round at a time, once the previous round is over.
+    pub fn is_round_based(&self) -> bool {
+        matches!(self, Self::League | Self::DoubleElimination | Self::Swiss)
+    }
+
+    pub fn has_standings(&self) -> bool {
+        matches!(self, Self::League | Self::Swiss)
+    }
+
+    // Upper bound on the number of rounds of round based formats.
+    fn max_number_of_rounds(&self, participants: usize) -> usize {
+        match self {
+            Self::Cup | Self::Supercup => 0,
+            // With an odd number of teams, every round one team has a bye.
+            Self::League if participants.is_multiple_of(2) => participants.saturating_sub(1),
+            Self::League => participants,
+            // Every team but the winner is eliminated after two losses,
+            // the winner can lose at most once.
+            Self::DoubleElimination => (2 * participants).saturating_sub(1),
+            // ceil(log2(n))
+            Self::Swiss => (participants.max(2) as u32 - 1).ilog2() as usize + 1,
+        }
+    }
+}
+
+#[derive(
+    Debug, Default, Display, Clone, Copy, Serialize_repr, Deserialize_repr, PartialEq, Eq, Hash,
+)]
+#[repr(u8)]
+pub enum TournamentBracket {
+    #[default]
+    Main,
+    Winners,
+    Losers,
+    #[strum(to_string = "Grand Final")]
+    GrandFinal,
+}
+
+// Round and bracket of a game of a round based tournament.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct BracketSlot {
+    pub round: usize,
+    pub bracket: TournamentBracket,
+}
+
+fn games_by_round(
+    game_ids: &[GameId],
+    bracket_slots: &[BracketSlot],
+    bracket: TournamentBracket,
+) -> Vec<(usize, Vec<GameId>)> {
+    let mut rounds: Vec<(usize, Vec<GameId>)> = vec![];
+    for (game_id, slot) in game_ids.iter().zip(bracket_slots.iter()) {
+        if slot.bracket != bracket {
+            continue;
+        }
+        match rounds.last_mut() {
+            Some((round, ids)) if *round == slot.round => ids.push(*game_id),
+            _ => rounds.push((slot.round, vec![*game_id])),
+        }
+    }
+    rounds
+}
+
+// Sorted pair, used to check if two teams have already played each other.
+fn matchup(a: TeamId, b: TeamId) -> (TeamId, TeamId) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+// Pair teams in the given order, avoiding rematches when possible.
+// With an odd number of teams, the last team that has not had a bye yet sits out.
+fn pair_teams(
+    order: &[TeamId],
+    played: &HashSet<(TeamId, TeamId)>,
+    byes: &[TeamId],
+) -> Vec<(TeamId, TeamId)> {
+    let mut teams = order.to_vec();
+    if !teams.len().is_multiple_of(2) {
+        let bye_idx = teams
+            .iter()
+            .rposition(|team_id| !byes.contains(team_id))
+            .unwrap_or(teams.len() - 1);
+        teams.remove(bye_idx);
+    }
+
+    let mut pairings = vec![];
+    while teams.len() >= 2 {
+        let home = teams.remove(0);
+        let away_idx = teams
+            .iter()
+            .position(|&team_id| !played.contains(&matchup(home, team_id)))
+            .unwrap_or_default();
+        pairings.push((home, teams.remove(away_idx)));
+    }
+    pairings
+}
+
+// Points awarded in league standings.
+const POINTS_PER_WIN: u16 = 2;
+const POINTS_PER_DRAW: u16 = 1;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TournamentStanding {
+    pub team_id: TeamId,
+    pub team_name: String,
+    pub wins: u16,
+    pub draws: u16,
+    pub losses: u16,
+    pub points_for: u32,
+    pub points_against: u32,
+    // Swiss byes count as a win.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub byes: u16,
+}
+
+impl TournamentStanding {
+    pub fn games_played(&self) -> u16 {
+        self.wins + self.draws + self.losses
+    }
+
+    pub fn points(&self) -> u16 {
+        POINTS_PER_WIN * (self.wins + self.byes) + POINTS_PER_DRAW * self.draws
+    }
+
+    pub fn point_differential(&self) -> i32 {
+        self.points_for as i32 - self.points_against as i32
+    }
+
+    fn add_result(&mut self, scored: u16, conceded: u16, winner: Option<TeamId>) {
+        self.points_for += scored as u32;
+        self.points_against += conceded as u32;
+        match winner {
+            Some(team_id) if team_id == self.team_id => self.wins += 1,
+            Some(_) => self.losses += 1,
+            None => self.draws += 1,
+        }
+    }
+}
+
+// Compute the standings from the ended games. Ties are broken, in order, by:
+// points in the games between the tied teams, point differential, points scored and team id.
+fn compute_standings(
+    participants: &HashMap<TeamId, TeamInGame>,
+    results: &[GameSummary],
+    byes: &[TeamId],
+) -> Vec<TournamentStanding> {
+    let mut standings: HashMap<TeamId, TournamentStanding> = participants
+        .values()
+        .map(|team| {
+            (
+                team.team_id,
+                TournamentStanding {
+                    team_id: team.team_id,
+                    team_name: team.name.clone(),
+                    ..Default::default()
+                },
+            )
+        })
+        .collect();
+
+    for result in results {
+        let (home_score, away_score) = result.get_score();
+        if let Some(standing) = standings.get_mut(&result.home_team_id) {
+            standing.add_result(home_score, away_score, result.winner);
+        }
+        if let Some(standing) = standings.get_mut(&result.away_team_id) {
+            standing.add_result(away_score, home_score, result.winner);
         }
     }
+
+    for team_id in byes {
+        if let Some(standing) = standings.get_mut(team_id) {
+            standing.byes += 1;
+        }
+    }
+
+    let points = |team_id: &TeamId| {
+        standings
+            .get(team_id)
+            .map(|s| s.points())
+            .unwrap_or_default()
+    };
+
+    // Head-to-head points only count games between teams with the same number of points.
+    let mut head_to_head: HashMap<TeamId, u16> = HashMap::new();
+    for result in results {
+        if points(&result.home_team_id) != points(&result.away_team_id) {
+            continue;
+        }
+        match result.winner {
+            Some(team_id) => *head_to_head.entry(team_id).or_default() += POINTS_PER_WIN,
+            None => {
+                *head_to_head.entry(result.home_team_id).or_default() += POINTS_PER_DRAW;
+                *head_to_head.entry(result.away_team_id).or_default() += POINTS_PER_DRAW;
+            }
+        }
+    }
+
+    standings
+        .into_values()
+        .sorted_by(|a, b| {
+            b.points()
+                .cmp(&a.points())
+                .then(
+                    head_to_head
+                        .get(&b.team_id)
+                        .unwrap_or(&0)
+                        .cmp(head_to_head.get(&a.team_id).unwrap_or(&0)),
+                )
+                .then(b.point_differential().cmp(&a.point_differential()))
+                .then(b.points_for.cmp(&a.points_for))
+                .then(a.team_id.cmp(&b.team_id))
+        })
+        .collect()
 }
 
 #[derive(Debug, Display, PartialEq)]
@@ -151,6 +400,10 @@ pub struct Tournament {
     tournament_type: TournamentType,
     kartoffel_id: KartoffelId,
     pub organizer_id: TeamId,
+    // Set when the organizer sends the tournament, so that peers can check who signed it.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub organizer_peer_id: Option<PeerId>,
     pub max_participants: usize,
     canceled: bool,
     pub registered_teams: HashMap<TeamId, TeamInGame>,
@@ -164,7 +417,18 @@ pub struct Tournament {
     game_time_interval: Tick,
     ended_at: Option<Tick>,
     pub winner: Option<TeamId>,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub standings: Vec<TournamentStanding>,
+    // Round and bracket of each game in games, for round based formats.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub bracket_slots: Vec<BracketSlot>,
     app_version: [usize; 3],
+    // Missing for peers older than protocol versioning.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub protocol_version: u32,
 }
 
 impl Tournament {
@@ -241,17 +505,19 @@ impl Tournament {
 
     pub fn max_ending_time(&self) -> Tick {
         let n = self.max_participants;
-        let game_duration = timer::MAX_TIME_IN_SECONDS as Tick * SECONDS;
+        let game_duration = timer::MAX_TIME_WITH_OVERTIMES_IN_SECONDS as Tick * SECONDS;
+        if self.tournament_type.is_round_based() {
+            return self.round_starting_at(self.tournament_type.max_number_of_rounds(n));
+        }
         let rounds = (n as u32 - 1).ilog2() as Tick + 1; // ceil(log2(n))
         let interval_rounds = (n as u32).ilog2() as Tick; // floor(log2(n))
         self.starting_at() + rounds * game_duration + interval_rounds * self.game_time_interval
     }
 
-    pub fn new(organizer: &Team, tournament_type: TournamentType) -> AppResult<Self> {
+    pub fn new(organizer: &Team, tournament_type: TournamentType, now: Tick) -> AppResult<Self> {
         organizer.can_organize_tournament()?;
 
-        let now = Tick::now();
-        let registrations_closing_at = Tick::now() + tournament_type.registration_duration();
+        let registrations_closing_at = now + tournament_type.registration_duration();
         if registrations_closing_at <= now {
             return Err(anyhow!("Tournament is closing registrations in the past!"));
         }
@@ -264,6 +530,7 @@ impl Tournament {
             max_participants,
             registrations_closing_at,
             app_version: app_version(),
+            protocol_version: PROTOCOL_VERSION,
             game_time_interval: 30 * MINUTES,
             tournament_type,
             ..Default::default()
@@ -278,6 +545,7 @@ impl Tournament {
             max_participants,
             registrations_closing_at: Tick::now() + SECONDS,
             game_time_interval: 30 * MINUTES,
+            protocol_version: PROTOCOL_VERSION,
             ..Default::default()
         };
 
@@ -304,6 +572,34 @@ impl Tournament {
         format!("{} {}", self.planet_name, self.tournament_type)
     }
 
+    pub fn tournament_type(&self) -> TournamentType {
+        self.tournament_type
+    }
+
+    // Number of rounds generated so far, for round based formats.
+    pub fn current_round(&self) -> usize {
+        self.bracket_slots
+            .last()
+            .map(|slot| slot.round + 1)
+            .unwrap_or_default()
+    }
+
+    // Total number of rounds, if known in advance.
+    pub fn number_of_rounds(&self) -> Option<usize> {
+        match self.tournament_type {
+            TournamentType::League | TournamentType::Swiss => Some(
+                self.tournament_type
+                    .max_number_of_rounds(self.participants.len()),
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn rounds(&self, bracket: TournamentBracket) -> Vec<(usize, Vec<GameId>)> {
+        let game_ids = self.games.iter().map(|game| game.id).collect_vec();
+        games_by_round(&game_ids, &self.bracket_slots, bracket)
+    }
+
     pub fn register_team(
         &mut self,
         team: &Team,
@@ -368,6 +664,11 @@ impl Tournament {
         assert!(self.winner.is_none());
         assert!(self.pending_team_for_next_game.is_none());
 
+        if self.tournament_type.is_round_based() {
+            let pairings = self.round_pairings(0, &[]).unwrap_or_default();
+            return self.push_round_games(0, pairings);
+        }
+
         let rng = &mut self.get_rng(self.starting_at());
 
         // Initialize initial games.
@@ -438,6 +739,10 @@ impl Tournament {
             )
         }
 
+        if self.tournament_type.is_round_based() {
+            return self.generate_next_round_games(current_tick, games, past_games);
+        }
+
         let mut tournament_games = vec![];
         let mut generated_past_games = 0;
         for game in self.games.iter() {
@@ -509,13 +814,15 @@ impl Tournament {
                     .get(&winner_team_id)
                     .expect("Team should be a participant");
 
+                // Games going to overtime last longer than the regulation time.
+                let game_duration =
+                    timer::MAX_TIME_IN_SECONDS.max(game.timer.period().previous().end()) as Tick
+                        * SECONDS;
                 let new_game = self.new_game(
                     rng,
                     home_team_in_game.clone(),
                     away_team_in_game.clone(),
-                    game.starting_at
-                        + timer::MAX_TIME_IN_SECONDS as Tick * SECONDS
-                        + self.game_time_interval,
+                    game.starting_at + game_duration + self.game_time_interval,
                 );
                 self.games.push(new_game.clone());
                 new_games.push(new_game);
@@ -529,6 +836,330 @@ impl Tournament {
         Ok(new_games)
     }
 
+    fn round_starting_at(&self, round: usize) -> Tick {
+        let game_duration = timer::MAX_TIME_WITH_OVERTIMES_IN_SECONDS as Tick * SECONDS;
+        self.starting_at() + round as Tick * (game_duration + 2 * self.game_time_interval)
+    }
+
+    // Participants in a random order which is the same for every peer.
+    fn seeded_order(&self) -> Vec<TeamId> {
+        let rng = &mut self.get_rng(self.starting_at());
+        let mut teams = self.participants.keys().copied().sorted().collect_vec();
+        teams.shuffle(rng);
+        teams
+    }
+
+    // Teams that did not play in each of the rounds generated so far.
+    fn byes(&self) -> Vec<TeamId> {
+        let mut byes = vec![];
+        for round in 0..self.current_round() {
+            let playing: HashSet<TeamId> = self
+                .games
+                .iter()
+                .zip(self.bracket_slots.iter())
+                .filter(|(_, slot)| slot.round == round)
+                .flat_map(|(game, _)| {
+                    [
+                        game.home_team_in_game.team_id,
+                        game.away_team_in_game.team_id,
+                    ]
+                })
+                .collect();
+            byes.extend(
+                self.participants
+                    .keys()
+                    .filter(|team_id| !playing.contains(team_id))
+                    .sorted(),
+            );
+        }
+        byes
+    }
+
+    fn played_matchups(&self) -> HashSet<(TeamId, TeamId)> {
+        self.games
+            .iter()
+            .map(|game| {
+                matchup(
+                    game.home_team_in_game.team_id,
+                    game.away_team_in_game.team_id,
+                )
+            })
+            .collect()
+    }
+
+    fn ended_game_summaries(
+        &self,
+        games: &GameMap,
+        past_games: &GameSummaryMap,
+    ) -> Vec<GameSummary> {
+        self.games
+            .iter()
+            .filter_map(|game| match games.get(&game.id) {
+                Some(game) if game.has_ended() => Some(GameSummary::from_game(game)),
+                Some(_) => None,
+                None => past_games.get(&game.id).cloned(),
+            })
+            .collect_vec()
+    }
+
+    // Round robin pairings using the circle method: the first team is fixed
+    // and the others rotate by one position every round.
+    fn league_round_pairings(&self, round: usize) -> Vec<(TeamId, TeamId)> {
+        let mut teams = self.seeded_order().into_iter().map(Some).collect_vec();
+        if !teams.len().is_multiple_of(2) {
+            teams.push(None);
+        }
+
+        if teams.len() < 2 {
+            return vec![];
+        }
+
+        let rotating = teams.len() - 1;
+        let mut order = vec![teams[0]];
+        for idx in 0..rotating {
+            order.push(teams[1 + (idx + rotating - round % rotating) % rotating]);
+        }
+
+        (0..order.len() / 2)
+            .filter_map(|idx| {
+                let (home, away) = (order[idx]?, order[order.len() - 1 - idx]?);
+                // Alternate home and away for the fixed team.
+                if idx == 0 && round % 2 == 1 {
+                    Some((away, home))
+                } else {
+                    Some((home, away))
+                }
+            })
+            .collect()
+    }
+
+    // Teams are ranked by points and point differential, then paired top to bottom
+    // with the closest ranked team they have not played yet.
+    fn swiss_round_pairings(&self, results: &[GameSummary]) -> Vec<(TeamId, TeamId)> {
+        let byes = self.byes();
+        let standings: HashMap<TeamId, TournamentStanding> =
+            compute_standings(&self.participants, results, &byes)
+                .into_iter()
+                .map(|standing| (standing.team_id, standing))
+                .collect();
+
+        let order = self
+            .seeded_order()
+            .into_iter()
+            .sorted_by(|a, b| {
+                let (a, b) = (&standings[a], &standings[b]);
+                b.points()
+                    .cmp(&a.points())
+                    .then(b.point_differential().cmp(&a.point_differential()))
+            })
+            .collect_vec();
+
+        pair_teams(&order, &self.played_matchups(), &byes)
+    }
+
+    // Teams with no losses play in the winners bracket, teams with one loss in the losers bracket.
+    // When a single team is left in each bracket, they meet in the grand final. If the team coming
+    // from the losers bracket wins, both teams have one loss and the grand final is played again.
+    fn double_elimination_round_pairings(
+        &self,
+        results: &[GameSummary],
+    ) -> AppResult<Vec<(TeamId, TeamId, TournamentBracket)>> {
+        let mut losses: HashMap<TeamId, usize> = HashMap::new();
+        for result in results {
+            let loser = match result.winner {
+                Some(team_id) if team_id == result.home_team_id => result.away_team_id,
+                Some(_) => result.home_team_id,
+                None => return Err(anyhow!("Tournament game should have a winner.")),
+            };
+            *losses.entry(loser).or_default() += 1;
+        }
+
+        let order = self.seeded_order();
+        let with_losses = |count: usize| {
+            order
+                .iter()
+                .filter(|team_id| losses.get(team_id).copied().unwrap_or_default() == count)
+                .copied()
+                .collect_vec()
+        };
+        let winners = with_losses(0);
+        let losers = with_losses(1);
+
+        let grand_final_played = self
+            .bracket_slots
+            .iter()
+            .any(|slot| slot.bracket == TournamentBracket::GrandFinal);
+
+        let pairings = match (winners.as_slice(), losers.as_slice()) {
+            ([winner], [loser]) => vec![(*winner, *loser, TournamentBracket::GrandFinal)],
+            ([], [home, away]) if grand_final_played => {
+                vec![(*home, *away, TournamentBracket::GrandFinal)]
+            }
+            _ => {
+                let played = self.played_matchups();
+                let byes = self.byes();
+                pair_teams(&winners, &played, &byes)
+                    .into_iter()
+                    .map(|(home, away)| (home, away, TournamentBracket::Winners))
+                    .chain(
+                        pair_teams(&losers, &played, &byes)
+                            .into_iter()
+                            .map(|(home, away)| (home, away, TournamentBracket::Losers)),
+                    )
+                    .collect()
+            }
+        };
+
+        Ok(pairings)
+    }
+
+    // Returns no pairings when the tournament is over.
+    fn round_pairings(
+        &self,
+        round: usize,
+        results: &[GameSummary],
+    ) -> AppResult<Vec<(TeamId, TeamId, TournamentBracket)>> {
+        if round
+            >= self
+                .tournament_type
+                .max_number_of_rounds(self.participants.len())
+        {
+            return Ok(vec![]);
+        }
+
+        let pairings = match self.tournament_type {
+            TournamentType::Cup | TournamentType::Supercup => vec![],
+            TournamentType::League => self
+                .league_round_pairings(round)
+                .into_iter()
+                .map(|(home, away)| (home, away, TournamentBracket::Main))
+                .collect(),
+            TournamentType::Swiss => self
+                .swiss_round_pairings(results)
+                .into_iter()
+                .map(|(home, away)| (home, away, TournamentBracket::Main))
+                .collect(),
+            TournamentType::DoubleElimination => self.double_elimination_round_pairings(results)?,
+        };
+
+        Ok(pairings)
+    }
+
+    fn push_round_games(
+        &mut self,
+        round: usize,
+        pairings: Vec<(TeamId, TeamId, TournamentBracket)>,
+    ) -> Vec<Game> {
+        let rng = &mut self.get_rng(self.games.len() as u64 + 1);
+        let round_starting_at = self.round_starting_at(round);
+
+        let mut new_games = vec![];
+        for (idx, (home_team_id, away_team_id, bracket)) in pairings.iter().enumerate() {
+            let home_team_in_game = self
+                .participants
+                .get(home_team_id)
+                .expect("Team should be a participant");
+            let away_team_in_game = self
+                .participants
+                .get(away_team_id)
+                .expect("Team should be a participant");
+            let game = self.new_game(
+                rng,
+                home_team_in_game.clone(),
+                away_team_in_game.clone(),
+                round_starting_at
+                    + self.game_time_interval * (idx + 1) as u64 / pairings.len() as u64,
+            );
+            new_games.push(game);
+            self.bracket_slots.push(BracketSlot {
+                round,
+                bracket: *bracket,
+            });
+        }
+
+        self.games.extend(new_games.iter().cloned());
+        new_games
+    }
+
+    // A new round is generated once all games of the previous one have ended.
+    // When no more games can be paired, the tournament ends.
+    fn generate_next_round_games(
+        &mut self,
+        current_tick: Tick,
+        games: &GameMap,
+        past_games: &GameSummaryMap,
+    ) -> AppResult<Vec<Game>> {
+        let all_games_ended = self.games.iter().all(|game| {
+            games
+                .get(&game.id)
+                .map(|game| game.has_ended())
+                .unwrap_or(true)
+        });
+        if !all_games_ended {
+            return Ok(vec![]);
+        }
+
+        let results = self.ended_game_summaries(games, past_games);
+        let round = self.current_round();
+        let pairings = self.round_pairings(round, &results)?;
+        if !pairings.is_empty() {
+            return Ok(self.push_round_games(round, pairings));
+        }
+
+        if self.tournament_type.has_standings() {
+            self.standings = self.standings(games, past_games);
+            self.winner = self.standings.first().map(|standing| standing.team_id);
+        } else {
+            // The winner is the only team with less than two losses.
+            let losers = results
+                .iter()
+                .filter_map(|result| match result.winner {
+                    Some(team_id) if team_id == result.home_team_id => Some(result.away_team_id),
+                    Some(_) => Some(result.home_team_id),
+                    None => None,
+                })
+                .counts();
+            self.winner = self
+                .seeded_order()
+                .into_iter()
+                .find(|team_id| losers.get(team_id).copied().unwrap_or_default() < 2);
+        }
+
+        if self.winner.is_none() {
+            return Err(anyhow!("Tournament should have a winner."));
+        }
+
+        self.ended_at = Some(current_tick);
+        log::info!(
+            "Tournament {} is over: all rounds have been played.",
+            self.id
+        );
+        Ok(vec![])
+    }
+
+    // Current standings, computed from the tournament games that have ended.
+    pub fn standings(
+        &self,
+        games: &GameMap,
+        past_games: &GameSummaryMap,
+    ) -> Vec<TournamentStanding> {
+        if self.has_ended() {
+            return self.standings.clone();
+        }
+
+        let byes = if self.tournament_type == TournamentType::Swiss {
+            self.byes()
+        } else {
+            vec![]
+        };
+
+        compute_standings(
+            &self.participants,
+            &self.ended_game_summaries(games, past_games),
+            &byes,
+        )
+    }
+
     pub fn active_games<'a>(&'a self, games: &'a GameMap) -> Vec<&'a Game> {
         self.games
             .iter()
@@ -569,14 +1200,17 @@ impl Rated for Tournament {
 #[cfg(test)]
 mod tests {
 
+    use super::{compute_standings, matchup, pair_teams, POINTS_PER_WIN};
     use crate::core::{Player, Team, TeamLocation, TickInterval, MAX_PLAYERS_PER_GAME, SECONDS};
     use crate::game_engine::game::GameSummary;
-    use crate::game_engine::{Tournament, TournamentState, TournamentType};
+    use crate::game_engine::TournamentBracket;
+    use crate::game_engine::{Tournament, TournamentState, TournamentSummary, TournamentType};
     use crate::types::{
         AppResult, GameMap, GameSummaryMap, PlanetId, PlayerMap, SystemTimeTick, TeamId, Tick,
     };
     use itertools::Itertools;
     use libp2p::PeerId;
+    use std::collections::HashSet;
 
     #[test]
     fn test_tournament_determinism() -> AppResult<()> {
@@ -695,12 +1329,12 @@ mod tests {
 
     #[test]
     fn test_tournament_error_registrations_closed() -> AppResult<()> {
-        let mut organizer = Team::random(None);
+        let mut organizer = Team::random(None, Tick::now());
         let planet_id = PlanetId::default();
         organizer.space_cove = Some(crate::core::SpaceCove::ready(planet_id));
         organizer.current_location = TeamLocation::OnPlanet { planet_id };
 
-        let mut tournament = Tournament::new(&organizer, TournamentType::Cup)?;
+        let mut tournament = Tournament::new(&organizer, TournamentType::Cup, Tick::now())?;
 
         let team = Team {
             id: TeamId::new_v4(),
@@ -724,13 +1358,13 @@ mod tests {
 
     #[test]
     fn test_tournament_error_wrong_location() -> AppResult<()> {
-        let mut organizer = Team::random(None);
+        let mut organizer = Team::random(None, Tick::now());
         let planet_id = PlanetId::default();
         organizer.space_cove = Some(crate::core::SpaceCove::ready(planet_id));
         organizer.current_location = TeamLocation::OnPlanet { planet_id };
 
         let timestamp = Tick::now();
-        let mut tournament = Tournament::new(&organizer, TournamentType::Supercup)?;
+        let mut tournament = Tournament::new(&organizer, TournamentType::Supercup, timestamp)?;
 
         let mut players = PlayerMap::new();
         for _ in 0..MAX_PLAYERS_PER_GAME {
@@ -870,11 +1504,16 @@ mod tests {
                 game.tick(current_tick);
             }
 
-            // Collect starting_at for games that just ended (before generate_next_games consumes them)
-            let ended_starting_ats: Vec<Tick> = games
+            // Collect starting_at and duration for games that just ended (before generate_next_games consumes them)
+            let ended_starting_ats: Vec<(Tick, Tick)> = games
                 .values()
                 .filter(|g| g.has_ended())
-                .map(|g| g.starting_at)
+                .map(|g| {
+                    // Games going to overtime last until the end of their last overtime.
+                    let duration =
+                        timer::MAX_TIME_IN_SECONDS.max(g.timer.period().previous().end());
+                    (g.starting_at, duration as Tick * SECONDS)
+                })
                 .collect();
 
             let new_games =
@@ -888,12 +1527,10 @@ mod tests {
 
             for game in &new_games {
                 // The new game should be scheduled at:
-                // ended_game.starting_at + MAX_TIME_IN_SECONDS (as Tick) + game_time_interval
+                // ended_game.starting_at + MAX_TIME_IN_SECONDS (as Tick) + overtimes + game_time_interval
                 let game_time_interval = 30 * crate::core::MINUTES;
-                let matches_any = ended_starting_ats.iter().any(|&ended_start| {
-                    let expected = ended_start
-                        + timer::MAX_TIME_IN_SECONDS as Tick * SECONDS
-                        + game_time_interval;
+                let matches_any = ended_starting_ats.iter().any(|&(ended_start, duration)| {
+                    let expected = ended_start + duration + game_time_interval;
                     game.starting_at == expected
                 });
                 assert!(
@@ -1098,4 +1735,261 @@ mod tests {
         // Canceled tournament should have no games generated
         assert!(tournament.games.is_empty());
     }
+
+    #[test]
+    fn test_league_round_robin() -> AppResult<()> {
+        for num_participants in [2, 5, 6] {
+            let mut tournament = Tournament::test(num_participants, 6);
+            tournament.tournament_type = TournamentType::League;
+            tournament.registrations_closing_at = 0;
+            let mut games = GameMap::new();
+            let mut past_game_summaries = GameSummaryMap::new();
+
+            for game in tournament.initialize() {
+                games.insert(game.id, game);
+            }
+
+            let mut current_tick: Tick = 0;
+            while !tournament.has_ended() {
+                for game in games.values_mut() {
+                    game.tick(current_tick);
+                }
+
+                let new_games =
+                    tournament.generate_next_games(current_tick, &games, &past_game_summaries)?;
+
+                for game in games.values().filter(|g| g.has_ended()) {
+                    past_game_summaries.insert(game.id, GameSummary::from_game(game));
+                }
+
+                games.retain(|_, g| !g.has_ended());
+
+                for game in new_games {
+                    assert!(game.starting_at >= current_tick);
+                    games.insert(game.id, game);
+                }
+
+                current_tick += TickInterval::SHORT;
+            }
+
+            assert!(current_tick <= tournament.max_ending_time());
+            assert_eq!(
+                tournament.games.len(),
+                num_participants * (num_participants - 1) / 2
+            );
+
+            // Every team plays every other team exactly once.
+            let pairings = tournament
+                .games
+                .iter()
+                .map(|game| {
+                    let mut pair = [
+                        game.home_team_in_game.team_id,
+                        game.away_team_in_game.team_id,
+                    ];
+                    pair.sort();
+                    pair
+                })
+                .unique()
+                .count();
+            assert_eq!(pairings, tournament.games.len());
+
+            let standings = &tournament.standings;
+            assert_eq!(standings.len(), num_participants);
+            assert_eq!(tournament.winner, Some(standings[0].team_id));
+            for standing in standings {
+                assert_eq!(standing.games_played() as usize, num_participants - 1);
+            }
+            for (a, b) in standings.iter().tuple_windows() {
+                assert!(a.points() >= b.points());
+            }
+
+            let summary = TournamentSummary::from_tournament(&tournament);
+            assert_eq!(summary.tournament_type(), TournamentType::League);
+            assert_eq!(summary.standings, tournament.standings);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_league_standings_tie_breakers() {
+        let tournament = Tournament::test(3, 6);
+        let team = |idx: u128| TeamId::from_u128(idx);
+        let result = |home: u128, away: u128, home_score: u16, away_score: u16| {
+            let mut summary = GameSummary::default();
+            summary.home_team_id = team(home);
+            summary.away_team_id = team(away);
+            summary.home_quarters_score = [home_score, 0, 0, 0];
+            summary.away_quarters_score = [away_score, 0, 0, 0];
+            summary.winner = Some(if home_score > away_score {
+                team(home)
+            } else {
+                team(away)
+            });
+            summary
+        };
+
+        // Every team has one win: team 1 has the best differential,
+        // but head-to-head does not break a three-way tie.
+        let results = vec![
+            result(0, 1, 50, 40),
+            result(1, 2, 80, 40),
+            result(2, 0, 60, 58),
+        ];
+        let standings = compute_standings(&tournament.participants, &results, &[]);
+        assert_eq!(
+            standings.iter().map(|s| s.team_id).collect_vec(),
+            vec![team(1), team(0), team(2)]
+        );
+
+        // Teams 0 and 1 are tied on points: team 1 wins the head-to-head
+        // despite the worse differential.
+        let results = vec![result(0, 1, 40, 42), result(0, 2, 90, 40)];
+        let standings = compute_standings(&tournament.participants, &results, &[]);
+        assert_eq!(
+            standings.iter().map(|s| s.team_id).collect_vec(),
+            vec![team(1), team(0), team(2)]
+        );
+        assert_eq!(standings[0].points(), standings[1].points());
+
+        // A swiss bye counts as a win.
+        let standings = compute_standings(&tournament.participants, &results, &[team(2)]);
+        let standing = standings
+            .iter()
+            .find(|s| s.team_id == team(2))
+            .expect("Team should have a standing");
+        assert_eq!(standing.games_played(), 1);
+        assert_eq!(standing.points(), POINTS_PER_WIN);
+    }
+
+    fn play_tournament(tournament: &mut Tournament) -> AppResult<GameSummaryMap> {
+        let mut games = GameMap::new();
+        let mut past_game_summaries = GameSummaryMap::new();
+
+        for game in tournament.initialize() {
+            games.insert(game.id, game);
+        }
+
+        let mut current_tick = tournament.starting_at();
+        while !tournament.has_ended() {
+            for game in games.values_mut() {
+                if game.has_started(current_tick) {
+                    game.tick(current_tick);
+                }
+            }
+
+            let new_games =
+                tournament.generate_next_games(current_tick, &games, &past_game_summaries)?;
+
+            for game in games.values().filter(|g| g.has_ended()) {
+                past_game_summaries.insert(game.id, GameSummary::from_game(game));
+            }
+
+            games.retain(|_, g| !g.has_ended());
+
+            for game in new_games {
+                assert!(game.starting_at >= current_tick);
+                games.insert(game.id, game);
+            }
+
+            current_tick += TickInterval::SHORT;
+        }
+
+        assert!(current_tick <= tournament.max_ending_time());
+        assert_eq!(tournament.bracket_slots.len(), tournament.games.len());
+        Ok(past_game_summaries)
+    }
+
+    #[test]
+    fn test_round_based_tournaments_determinism() -> AppResult<()> {
+        for tournament_type in [TournamentType::DoubleElimination, TournamentType::Swiss] {
+            let mut tournament = Tournament::test(7, 8);
+            tournament.tournament_type = tournament_type;
+            let mut replay_tournament = tournament.clone();
+
+            play_tournament(&mut tournament)?;
+            play_tournament(&mut replay_tournament)?;
+            assert!(tournament == replay_tournament);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_double_elimination() -> AppResult<()> {
+        for num_participants in [2, 3, 5, 8] {
+            let mut tournament = Tournament::test(num_participants, 8);
+            tournament.tournament_type = TournamentType::DoubleElimination;
+            let past_games = play_tournament(&mut tournament)?;
+
+            let losers = past_games
+                .values()
+                .map(|game| {
+                    if game.winner == Some(game.home_team_id) {
+                        game.away_team_id
+                    } else {
+                        game.home_team_id
+                    }
+                })
+                .counts();
+
+            // Every team is eliminated after two losses, except the winner.
+            let winner = tournament.winner.expect("Tournament should have a winner");
+            assert!(losers.get(&winner).copied().unwrap_or_default() < 2);
+            for team_id in tournament.participants.keys().filter(|&&id| id != winner) {
+                assert_eq!(losers.get(team_id), Some(&2));
+            }
+
+            let grand_finals = tournament
+                .bracket_slots
+                .iter()
+                .filter(|slot| slot.bracket == TournamentBracket::GrandFinal)
+                .count();
+            assert!((1..=2).contains(&grand_finals));
+            assert_eq!(
+                tournament.games.len(),
+                2 * (num_participants - 1) + grand_finals - 1
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_swiss() -> AppResult<()> {
+        for num_participants in [2, 5, 8] {
+            let mut tournament = Tournament::test(num_participants, 8);
+            tournament.tournament_type = TournamentType::Swiss;
+            play_tournament(&mut tournament)?;
+
+            let rounds = tournament
+                .number_of_rounds()
+                .expect("Swiss has fixed rounds");
+            assert_eq!(tournament.current_round(), rounds);
+            assert_eq!(tournament.games.len(), rounds * (num_participants / 2));
+
+            // Each round, every team plays once or has a bye, and no team has two byes.
+            let byes = tournament.byes();
+            assert_eq!(byes.len(), rounds * (num_participants % 2));
+            assert!(byes.iter().all_unique());
+
+            let standings = &tournament.standings;
+            assert_eq!(tournament.winner, Some(standings[0].team_id));
+            for standing in standings {
+                assert_eq!((standing.games_played() + standing.byes) as usize, rounds);
+            }
+
+            let summary = TournamentSummary::from_tournament(&tournament);
+            assert_eq!(summary.rounds(TournamentBracket::Main).len(), rounds);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_pair_teams_avoids_rematches() {
+        let teams = (0..5).map(TeamId::from_u128).collect_vec();
+        let played = HashSet::from([matchup(teams[0], teams[1])]);
+
+        // The last team without a bye sits out.
+        let pairings = pair_teams(&teams, &played, &[teams[4]]);
+        assert_eq!(pairings, vec![(teams[0], teams[2]), (teams[1], teams[4])]);
+    }
 }
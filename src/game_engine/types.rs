@@ -1,7 +1,12 @@
-use super::{action::Action, constants::MIN_TIREDNESS_FOR_ROLL_DECLINE, tactic::Tactic};
+use super::{
+    action::Action,
+    constants::{MAX_PERSONAL_FOULS, MIN_TIREDNESS_FOR_ROLL_DECLINE},
+    tactic::{CustomTactic, DefensiveScheme, Tactic},
+};
 use crate::{
     core::{
         constants::MAX_PLAYERS_PER_GAME,
+        injury::InjurySeverity,
         player::Player,
         position::{GamePosition, MAX_GAME_POSITION},
         skill::{MAX_SKILL, MIN_SKILL},
@@ -18,11 +23,11 @@ use anyhow::anyhow;
 use itertools::Itertools;
 
 use libp2p::PeerId;
-use std::sync::LazyLock;
-use rand::{RngExt};
+use rand::RngExt;
 use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
+use std::sync::LazyLock;
 use std::{collections::HashMap, ops::Not};
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
@@ -56,6 +61,12 @@ pub struct GameStats {
     pub made_3pt: u16,
     #[serde(skip_serializing_if = "is_default")]
     #[serde(default)]
+    pub attempted_ft: u16,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub made_ft: u16,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
     pub offensive_rebounds: u16,
     #[serde(skip_serializing_if = "is_default")]
     #[serde(default)]
@@ -74,6 +85,9 @@ pub struct GameStats {
     pub turnovers: u16,
     #[serde(skip_serializing_if = "is_default")]
     #[serde(default)]
+    pub fouls: u16,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
     pub plus_minus: i32,
     #[serde(skip_serializing_if = "is_default")]
     #[serde(default)]
@@ -81,6 +95,10 @@ pub struct GameStats {
     #[serde(skip_serializing_if = "is_default")]
     #[serde(default)]
     pub extra_tiredness: f32,
+    // Injury suffered during the action, applied to the player like extra morale and tiredness.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub injury: Option<InjurySeverity>,
     #[serde(skip_serializing_if = "is_default")]
     #[serde(default)]
     // Contains all the shots made by the player as a tuple (x, y, is_made)
@@ -107,12 +125,15 @@ impl GameStats {
         self.made_2pt += stats.made_2pt;
         self.attempted_3pt += stats.attempted_3pt;
         self.made_3pt += stats.made_3pt;
+        self.attempted_ft += stats.attempted_ft;
+        self.made_ft += stats.made_ft;
         self.offensive_rebounds += stats.offensive_rebounds;
         self.defensive_rebounds += stats.defensive_rebounds;
         self.assists += stats.assists;
         self.steals += stats.steals;
         self.blocks += stats.blocks;
         self.turnovers += stats.turnovers;
+        self.fouls += stats.fouls;
         if let Some(shot) = stats.last_action_shot {
             self.shots.push(shot);
             assert!(!self.shots.is_empty());
@@ -127,6 +148,11 @@ impl GameStats {
     pub fn is_playing(&self) -> bool {
         self.position.is_some()
     }
+
+    // Only meaningful for the stats of a single game.
+    pub fn is_fouled_out(&self) -> bool {
+        self.fouls >= MAX_PERSONAL_FOULS
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -145,6 +171,12 @@ pub struct TeamInGame {
     pub players: PlayerMap,
     pub stats: GameStatsMap,
     pub tactic: Tactic,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub custom_tactic: Option<CustomTactic>,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub defensive_scheme: DefensiveScheme,
     pub training_focus: Option<TrainingFocus>,
     pub momentum: u8,
     #[serde(skip_serializing_if = "is_default")]
@@ -154,9 +186,21 @@ pub struct TeamInGame {
 
 impl TeamInGame {
     pub fn new(team: &Team, players: PlayerMap) -> Self {
+        // Sort to keep the initial positions deterministic.
+        let lineup = players.keys().copied().sorted().collect_vec();
+        Self::with_lineup(team, lineup, players)
+    }
+
+    // Players take the initial positions in the lineup order.
+    fn with_lineup(team: &Team, lineup: Vec<PlayerId>, mut players: PlayerMap) -> Self {
+        // Career logs are not needed during the game and would bloat stored games.
+        for player in players.values_mut() {
+            player.career.clear();
+        }
+
         let mut stats = HashMap::new();
 
-        for (idx, &player_id) in players.keys().enumerate() {
+        for (idx, &player_id) in lineup.iter().enumerate() {
             let mut player_stats = GameStats::default();
             if (idx as GamePosition) < MAX_GAME_POSITION {
                 player_stats.position = Some(idx as GamePosition);
@@ -164,28 +208,35 @@ impl TeamInGame {
             stats.insert(player_id, player_stats.clone());
         }
 
-        let initial_tiredness = players
-            .keys()
+        let initial_tiredness = lineup
+            .iter()
             .map(|id| players.get(id).unwrap().tiredness)
             .collect();
-        let initial_morale = players
-            .keys()
+        let initial_morale = lineup
+            .iter()
             .map(|id| players.get(id).unwrap().morale)
             .collect();
 
         let network_game_rating = team.network_game_rating.clone();
+        let custom_tactic = team.active_custom_tactic().cloned();
+        let tactic = custom_tactic
+            .as_ref()
+            .map(|custom| custom.base)
+            .unwrap_or(team.game_tactic);
         Self {
             team_id: team.id,
             peer_id: team.peer_id,
             reputation: team.reputation,
             name: team.name.clone(),
-            initial_positions: players.keys().copied().collect_vec(),
+            initial_positions: lineup,
             initial_tiredness,
             initial_morale,
             version: team.version,
             players,
             stats,
-            tactic: team.game_tactic,
+            tactic,
+            custom_tactic,
+            defensive_scheme: team.defensive_scheme,
             training_focus: team.training_focus,
             network_game_rating,
             ..Default::default()
@@ -207,6 +258,30 @@ impl TeamInGame {
         Self::new(&team, players)
     }
 
+    // The team as it entered the game: players get back their initial tiredness and morale
+    // and stats are reset to the initial positions.
+    pub fn initial_state(&self) -> AppResult<Self> {
+        let mut team = self.clone();
+        let mut stats = HashMap::new();
+        for (idx, player_id) in team.initial_positions.iter().enumerate() {
+            let mut player_stats = GameStats::default();
+            if (idx as GamePosition) < MAX_GAME_POSITION {
+                player_stats.position = Some(idx as GamePosition);
+            }
+            stats.insert(*player_id, player_stats);
+
+            let player = team
+                .players
+                .get_mut(player_id)
+                .ok_or_else(|| anyhow!("Cannot get player {player_id} for {}", team.name))?;
+            player.tiredness = team.initial_tiredness[idx];
+            player.morale = team.initial_morale[idx];
+        }
+        team.stats = stats;
+
+        Ok(team)
+    }
+
     // We expose this function rather than from_team because we need to get the players anyway.
     pub fn from_team_id(team_id: &TeamId, teams: &TeamMap, players: &PlayerMap) -> AppResult<Self> {
         let team = if let Some(team) = teams.get(team_id) {
@@ -214,17 +289,35 @@ impl TeamInGame {
         } else {
             return Err(anyhow!("Could not find team {team_id}"));
         };
-        let mut team_players = PlayerMap::new();
-        for &player_id in team.player_ids.iter().take(MAX_PLAYERS_PER_GAME) {
-            let player = if let Some(player) = players.get(&player_id) {
+        let mut team_players = vec![];
+        for player_id in team.player_ids.iter() {
+            let player = if let Some(player) = players.get(player_id) {
                 player
             } else {
                 return Err(anyhow!("Could not find player {player_id}"));
             };
-            team_players.insert(player_id, player.clone());
+            team_players.push(player);
         }
 
-        Ok(TeamInGame::new(team, team_players))
+        // Injured pirates are unavailable: without a full healthy lineup the team cannot play.
+        team_players.retain(|p| !p.is_injured());
+        if team_players.len() < MAX_GAME_POSITION as usize {
+            return Err(anyhow!("{} has not enough healthy players", team.name));
+        }
+
+        let team_players = team_players
+            .into_iter()
+            .take(MAX_PLAYERS_PER_GAME)
+            .collect_vec();
+
+        // Positions follow the order of the crew.
+        let lineup = team_players.iter().map(|player| player.id).collect_vec();
+        let team_players = team_players
+            .into_iter()
+            .map(|player| (player.id, player.clone()))
+            .collect();
+
+        Ok(TeamInGame::with_lineup(team, lineup, team_players))
     }
 
     pub fn pick_action(&self, rng: &mut ChaCha8Rng) -> Option<Action> {
@@ -234,8 +327,77 @@ impl TeamInGame {
             .filter(|p| !p.is_knocked_out())
             .count();
 
+        if let Some(custom_tactic) = self.custom_tactic.as_ref() {
+            return custom_tactic.pick_action(rng, num_active_players);
+        }
+
         self.tactic.pick_action(rng, num_active_players)
     }
+
+    pub fn tactic_name(&self) -> String {
+        if let Some(custom_tactic) = self.custom_tactic.as_ref() {
+            return custom_tactic.name.clone();
+        }
+
+        self.tactic.to_string()
+    }
+
+    pub fn brawl_probability_modifier(&self) -> f64 {
+        if let Some(custom_tactic) = self.custom_tactic.as_ref() {
+            return custom_tactic.brawl_probability_modifier();
+        }
+
+        self.tactic.brawl_probability_modifier()
+    }
+
+    pub fn playing_tiredness_modifier(&self) -> f32 {
+        if let Some(custom_tactic) = self.custom_tactic.as_ref() {
+            return custom_tactic.playing_tiredness_modifier();
+        }
+
+        self.tactic.playing_tiredness_modifier()
+    }
+
+    pub fn fastbreak_probability_modifier(&self) -> f64 {
+        if let Some(custom_tactic) = self.custom_tactic.as_ref() {
+            return custom_tactic.fastbreak_probability_modifier();
+        }
+
+        self.tactic.fastbreak_probability_modifier()
+    }
+
+    pub fn attack_roll_bonus(&self, action: &Action) -> i16 {
+        if let Some(custom_tactic) = self.custom_tactic.as_ref() {
+            return custom_tactic.attack_roll_bonus(action);
+        }
+
+        self.tactic.attack_roll_bonus(action)
+    }
+
+    pub fn defense_roll_bonus(&self, action: &Action) -> i16 {
+        let scheme_bonus = self.defensive_scheme.defense_roll_bonus(action);
+        if let Some(custom_tactic) = self.custom_tactic.as_ref() {
+            return custom_tactic.defense_roll_bonus(action) + scheme_bonus;
+        }
+
+        self.tactic.defense_roll_bonus(action) + scheme_bonus
+    }
+
+    pub fn defender_indexes<const N: usize>(
+        &self,
+        attacker_idxs: [usize; N],
+        rng: &mut ChaCha8Rng,
+    ) -> [usize; N] {
+        self.defensive_scheme.defender_indexes(attacker_idxs, rng)
+    }
+
+    pub fn adv_defense_limit(&self) -> i16 {
+        self.defensive_scheme.adv_defense_limit()
+    }
+
+    pub fn steal_limit(&self) -> i16 {
+        self.defensive_scheme.steal_limit()
+    }
 }
 
 impl Rated for TeamInGame {
@@ -305,6 +467,40 @@ fn get_shot_positions(mask: PitchImage) -> Vec<(u8, u8)> {
     positions
 }
 
+#[cfg(test)]
+#[test]
+fn test_injured_players_sit_out() -> AppResult<()> {
+    let mut team = Team {
+        id: TeamId::new_v4(),
+        ..Default::default()
+    };
+    let mut players = PlayerMap::new();
+    for _ in 0..MAX_GAME_POSITION + 1 {
+        let player = Player::default().randomize(None);
+        team.player_ids.push(player.id);
+        players.insert(player.id, player);
+    }
+    let teams = TeamMap::from([(team.id, team.clone())]);
+
+    let injured_id = team.player_ids[0];
+    players
+        .get_mut(&injured_id)
+        .unwrap()
+        .add_injury(InjurySeverity::Minor);
+    let team_in_game = TeamInGame::from_team_id(&team.id, &teams, &players)?;
+    assert!(!team_in_game.players.contains_key(&injured_id));
+    assert_eq!(team_in_game.players.len(), MAX_GAME_POSITION as usize);
+
+    // Without enough healthy pirates, the team cannot play.
+    players
+        .get_mut(&team.player_ids[1])
+        .unwrap()
+        .add_injury(InjurySeverity::Minor);
+    assert!(TeamInGame::from_team_id(&team.id, &teams, &players).is_err());
+
+    Ok(())
+}
+
 #[cfg(test)]
 #[test]
 // test GameStats serialization and deserialization
@@ -317,12 +513,15 @@ fn test_gamestats_serde() {
     stats.made_2pt = 8;
     stats.attempted_3pt = 9;
     stats.made_3pt = 10;
+    stats.attempted_ft = 4;
+    stats.made_ft = 3;
     stats.offensive_rebounds = 0;
     stats.defensive_rebounds = 12;
     stats.assists = 13;
     stats.steals = 14;
     stats.blocks = 15;
     stats.turnovers = 0;
+    stats.fouls = 2;
     stats.plus_minus = 17;
     stats.extra_morale = 18.0;
     stats.extra_tiredness = 19.0;
@@ -335,10 +534,28 @@ fn test_gamestats_serde() {
     assert_eq!(stats, deserialized);
 }
 
+pub(crate) fn injury_description(player: &Player, severity: InjurySeverity) -> String {
+    match severity {
+        InjurySeverity::Minor => format!(
+            " {} got hurt and will need some rest.",
+            player.info.short_name()
+        ),
+        InjurySeverity::Moderate => format!(
+            " {} is injured, that looks painful.",
+            player.info.short_name()
+        ),
+        InjurySeverity::Severe => format!(
+            " {} is badly injured and won't be back for a while!",
+            player.info.short_name()
+        ),
+    }
+}
+
 pub trait EnginePlayer {
     fn min_roll(&self) -> i16;
     fn max_roll(&self) -> i16;
     fn roll(&self, rng: &mut ChaCha8Rng) -> i16;
+    fn roll_injury(&self, probability: f64, rng: &mut ChaCha8Rng) -> Option<InjurySeverity>;
     fn in_game_rating_at_position(&self, position: GamePosition) -> f32;
 }
 
@@ -368,6 +585,16 @@ impl EnginePlayer for Player {
             .min(self.max_roll())
     }
 
+    fn roll_injury(&self, probability: f64, rng: &mut ChaCha8Rng) -> Option<InjurySeverity> {
+        // Tired pirates get injured more easily, up to twice as often.
+        let probability = probability * (1.0 + self.tiredness / MAX_SKILL) as f64;
+        if rng.random_bool(probability.clamp(0.0, 1.0)) {
+            Some(InjurySeverity::random(rng))
+        } else {
+            None
+        }
+    }
+
     fn in_game_rating_at_position(&self, position: GamePosition) -> f32 {
         if self.is_knocked_out() {
             return 0.0;
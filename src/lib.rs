@@ -9,9 +9,11 @@ pub mod image;
 pub mod network;
 #[cfg(feature = "relayer")]
 pub mod relayer;
+pub mod simulation;
 pub mod space_adventure;
 #[cfg(feature = "ssh")]
 pub mod ssh;
+pub mod storage;
 pub mod store;
 pub mod tick_event_handler;
 pub mod tui;
@@ -4,14 +4,18 @@ use log4rs::append::file::FileAppender;
 use log4rs::config::{Appender, Config, Root};
 use log4rs::encode::pattern::PatternEncoder;
 use rebels::app::App;
-use rebels::args::AppArgs;
 #[cfg(any(feature = "relayer", feature = "ssh"))]
 use rebels::args::AppMode;
+use rebels::args::{AppArgs, AppCommand};
+use rebels::game_engine::box_score::run_export;
+use rebels::game_engine::replay::run_replay;
 #[cfg(feature = "relayer")]
 use rebels::relayer::Relayer;
+use rebels::simulation::run_simulation;
 #[cfg(feature = "ssh")]
 use rebels::ssh::AppServer;
-use rebels::store::store_path;
+use rebels::storage::{open_storage, set_storage};
+use rebels::store::{run_history, run_snapshots, store_path};
 use rebels::tui::Tui;
 use rebels::types::AppResult;
 
@@ -29,6 +33,22 @@ async fn main() -> AppResult<()> {
     log4rs::init_config(config)?;
 
     let args = AppArgs::parse();
+    set_storage(open_storage(args.storage)?);
+
+    match args.command.as_ref() {
+        Some(AppCommand::Simulate(simulate_args)) => return run_simulation(simulate_args),
+        Some(AppCommand::Replay(replay_args)) => return run_replay(replay_args),
+        Some(AppCommand::Export(export_args)) => return run_export(export_args),
+        Some(AppCommand::Snapshots(snapshots_args)) => {
+            return run_snapshots(
+                snapshots_args,
+                args.store_prefix(),
+                args.save_passphrase().as_deref(),
+            )
+        }
+        Some(AppCommand::History(history_args)) => return run_history(history_args),
+        None => {}
+    }
 
     #[cfg(any(feature = "relayer", feature = "ssh"))]
     let mode = args.app_mode();
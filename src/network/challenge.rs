@@ -1,4 +1,6 @@
+use super::protocol::PROTOCOL_VERSION;
 use super::types::NetworkRequestState;
+use crate::core::utils::is_default;
 use crate::game_engine::types::TeamInGame;
 use crate::types::Tick;
 use libp2p::PeerId;
@@ -13,6 +15,10 @@ pub struct Challenge {
     pub away_team_in_game: TeamInGame,
     pub starting_at: Option<Tick>,
     pub app_version: [usize; 3],
+    // Missing for peers older than protocol versioning.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub protocol_version: u32,
 }
 
 impl Challenge {
@@ -33,6 +39,7 @@ impl Challenge {
             away_team_in_game,
             starting_at: None,
             app_version,
+            protocol_version: PROTOCOL_VERSION,
         }
     }
 
@@ -1,5 +1,16 @@
+use crate::core::constants::SECONDS;
+use crate::types::Tick;
+use std::time::Duration;
+
 pub(crate) const TOPIC: &str = "rebels-b2b";
 pub const DEFAULT_NETWORK_PORT: u16 = 37202;
 pub(crate) const DEFAULT_SEED_PORT: u16 = 37201;
 pub(crate) const DEFAULT_SEED_URL: &str = "frittura.org";
 pub(crate) const DEFAULT_SEED_IPV6: &str = "2a01:239:22f:7e00::1";
+pub(crate) const DIRECT_PROTOCOL: &str = "/rebels/direct/1.0.0";
+pub(crate) const DIRECT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+pub(crate) const MAX_DIRECT_REQUEST_ATTEMPTS: u8 = 3;
+// Doubled at every new attempt.
+pub(crate) const DIRECT_REQUEST_RETRY_DELAY: Duration = Duration::from_secs(5);
+pub(crate) const CHAT_RATE_LIMIT_MESSAGES: usize = 5;
+pub(crate) const CHAT_RATE_LIMIT_INTERVAL: Tick = 10 * SECONDS;
@@ -0,0 +1,235 @@
+use super::types::{ChatChannel, ChatHistoryEntry};
+use crate::store::{deserialize, serialize};
+use crate::types::{AppResult, Tick};
+use anyhow::anyhow;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use libp2p::identity::{Keypair, PublicKey};
+use libp2p::PeerId;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+
+// Identity multihash code: ed25519 peer ids embed the public key itself.
+const IDENTITY_MULTIHASH_CODE: u64 = 0x00;
+const KEY_DERIVATION_CONTEXT: &[u8] = b"rebels direct message v1";
+const NONCE_LENGTH: usize = 12;
+pub const MAX_DIRECT_MESSAGE_LENGTH: usize = 4096;
+
+// A message between two crews, readable only by them. The key is derived with
+// x25519 from the ed25519 identity keys of the two peers, so no key exchange is
+// needed and relayers can store and forward messages without reading them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EncryptedDirectMessage {
+    pub timestamp: Tick,
+    pub from_peer_id: PeerId,
+    pub to_peer_id: PeerId,
+    // Random nonce followed by the ChaCha20-Poly1305 ciphertext.
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedDirectMessage {
+    pub fn new(
+        keypair: &Keypair,
+        to_peer_id: PeerId,
+        timestamp: Tick,
+        author: &str,
+        message: &str,
+    ) -> AppResult<Self> {
+        if message.len() > MAX_DIRECT_MESSAGE_LENGTH {
+            return Err(anyhow!("Direct message is too long"));
+        }
+
+        let from_peer_id = keypair.public().to_peer_id();
+        let cipher = ChaCha20Poly1305::new(&shared_key(keypair, &to_peer_id)?);
+        let nonce = rand::random::<[u8; NONCE_LENGTH]>();
+        let plaintext = serialize(&(author, message))?;
+        let aad = associated_data(timestamp, &from_peer_id, &to_peer_id);
+
+        let mut ciphertext = nonce.to_vec();
+        ciphertext.extend(
+            cipher
+                .encrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: &plaintext,
+                        aad: &aad,
+                    },
+                )
+                .map_err(|_| anyhow!("Could not encrypt direct message"))?,
+        );
+
+        Ok(Self {
+            timestamp,
+            from_peer_id,
+            to_peer_id,
+            ciphertext,
+        })
+    }
+
+    // The message can be decrypted by both the sender and the recipient.
+    pub fn decrypt(&self, keypair: &Keypair) -> AppResult<ChatHistoryEntry> {
+        let own_peer_id = keypair.public().to_peer_id();
+        let other_peer_id = if own_peer_id == self.from_peer_id {
+            self.to_peer_id
+        } else if own_peer_id == self.to_peer_id {
+            self.from_peer_id
+        } else {
+            return Err(anyhow!("Direct message is not addressed to us"));
+        };
+
+        if self.ciphertext.len() < NONCE_LENGTH {
+            return Err(anyhow!("Direct message is too short"));
+        }
+        let (nonce, ciphertext) = self.ciphertext.split_at(NONCE_LENGTH);
+
+        let cipher = ChaCha20Poly1305::new(&shared_key(keypair, &other_peer_id)?);
+        let aad = associated_data(self.timestamp, &self.from_peer_id, &self.to_peer_id);
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| anyhow!("Could not decrypt direct message"))?;
+
+        let (author, message) = deserialize::<(String, String)>(&plaintext)?;
+        Ok(ChatHistoryEntry {
+            timestamp: self.timestamp,
+            from_peer_id: self.from_peer_id,
+            author,
+            message,
+            channel: ChatChannel::default(),
+        })
+    }
+}
+
+// Sender, recipient and timestamp are authenticated, so they cannot be altered in transit.
+fn associated_data(timestamp: Tick, from_peer_id: &PeerId, to_peer_id: &PeerId) -> Vec<u8> {
+    let mut aad = timestamp.to_be_bytes().to_vec();
+    aad.extend(from_peer_id.to_bytes());
+    aad.extend(to_peer_id.to_bytes());
+    aad
+}
+
+fn montgomery_public_key(peer_id: &PeerId) -> AppResult<MontgomeryPoint> {
+    let multihash = peer_id.as_ref();
+    if multihash.code() != IDENTITY_MULTIHASH_CODE {
+        return Err(anyhow!("Peer {peer_id} does not embed its public key"));
+    }
+
+    let public_key = PublicKey::try_decode_protobuf(multihash.digest())?.try_into_ed25519()?;
+    let point = CompressedEdwardsY(public_key.to_bytes())
+        .decompress()
+        .ok_or_else(|| anyhow!("Invalid public key for peer {peer_id}"))?;
+    Ok(point.to_montgomery())
+}
+
+fn shared_key(keypair: &Keypair, peer_id: &PeerId) -> AppResult<Key> {
+    // The x25519 scalar of an ed25519 key is the clamped first half of the hashed seed.
+    let secret = keypair.clone().try_into_ed25519()?.secret();
+    let mut scalar = [0; 32];
+    scalar.copy_from_slice(&Sha512::digest(secret.as_ref())[..32]);
+    let shared_secret = montgomery_public_key(peer_id)?.mul_clamped(scalar);
+
+    let own_peer_id = keypair.public().to_peer_id();
+    let (first, second) = if own_peer_id < *peer_id {
+        (own_peer_id, *peer_id)
+    } else {
+        (*peer_id, own_peer_id)
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_DERIVATION_CONTEXT);
+    hasher.update(shared_secret.as_bytes());
+    hasher.update(first.to_bytes());
+    hasher.update(second.to_bytes());
+    Ok(*Key::from_slice(&hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EncryptedDirectMessage;
+    use crate::network::network_store_data::NetworkStoreData;
+    use crate::network::types::{NetworkData, SignedNetworkData, SignerContext};
+    use crate::types::{
+        AppResult, GameMap, PlayerMap, SystemTimeTick, TeamId, TeamMap, Tick, TournamentMap,
+    };
+    use libp2p::identity::Keypair;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_direct_message_encryption() -> AppResult<()> {
+        let sender = Keypair::generate_ed25519();
+        let recipient = Keypair::generate_ed25519();
+        let eavesdropper = Keypair::generate_ed25519();
+
+        let encrypted = EncryptedDirectMessage::new(
+            &sender,
+            recipient.public().to_peer_id(),
+            Tick::now(),
+            "Sender crew",
+            "Meet at the asteroid",
+        )?;
+
+        let entry = encrypted.decrypt(&recipient)?;
+        assert_eq!(entry.from_peer_id, sender.public().to_peer_id());
+        assert_eq!(entry.author, "Sender crew");
+        assert_eq!(entry.message, "Meet at the asteroid");
+        assert_eq!(encrypted.decrypt(&sender)?, entry);
+
+        assert!(encrypted.decrypt(&eavesdropper).is_err());
+
+        // Relayers keep the message once and forward it signed with their own key.
+        let relayer = Keypair::generate_ed25519();
+        let mut relayer_store = NetworkStoreData::default();
+        relayer_store.push_pending_direct_message(encrypted.clone());
+        relayer_store.push_pending_direct_message(encrypted.clone());
+        let pending = relayer_store.take_pending_direct_messages(&recipient.public().to_peer_id());
+        assert_eq!(pending, vec![encrypted.clone()]);
+
+        let relayed = SignedNetworkData::sign(
+            &NetworkData::DirectMessage {
+                timestamp: encrypted.timestamp,
+                message: encrypted.clone(),
+            },
+            &relayer,
+        )?;
+        let relay_peers = HashSet::from([relayer.public().to_peer_id()]);
+        let context = SignerContext {
+            relay_peers: &relay_peers,
+            own_peer_id: None,
+            own_team_id: TeamId::default(),
+            teams: &TeamMap::new(),
+            players: &PlayerMap::new(),
+            tournaments: &TournamentMap::new(),
+            games: &GameMap::new(),
+        };
+        let (signer, _) = relayed.verify(&context)?;
+        assert_eq!(signer, relayer.public().to_peer_id());
+
+        // Other peers cannot forward messages they did not write.
+        let forwarded = SignedNetworkData::sign(
+            &NetworkData::DirectMessage {
+                timestamp: encrypted.timestamp,
+                message: encrypted.clone(),
+            },
+            &eavesdropper,
+        )?;
+        assert!(forwarded.verify(&context).is_err());
+
+        // Forwarding the message as if it came from another peer breaks authentication.
+        let mut forged = encrypted.clone();
+        forged.to_peer_id = eavesdropper.public().to_peer_id();
+        assert!(forged.decrypt(&eavesdropper).is_err());
+
+        let mut tampered = encrypted;
+        tampered.timestamp += 1;
+        assert!(tampered.decrypt(&recipient).is_err());
+
+        Ok(())
+    }
+}
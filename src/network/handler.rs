@@ -1,30 +1,43 @@
 use super::challenge::Challenge;
 use super::constants::*;
+use super::direct_message::EncryptedDirectMessage;
 use super::network_callback::NetworkCallback;
+use super::protocol::{
+    check_game_compatibility, identify_protocol_version, parse_identify_protocol_version,
+};
 use super::trade::Trade;
 use super::types::SeedInfo;
-use super::types::{NetworkData, NetworkGame, NetworkRequestState, NetworkTeam};
+use super::types::{
+    ChatChannel, ChatHistoryEntry, DirectAck, NetworkData, NetworkGame, NetworkRequestState,
+    NetworkTeam, SignedNetworkData, SignerContext,
+};
 use crate::app::AppEvent;
 use crate::core::world::World;
 use crate::core::Team;
+use crate::game_engine::adjustment::GameAdjustment;
 use crate::game_engine::types::TeamInGame;
 use crate::game_engine::{Tournament, TournamentId};
 use crate::network::network_store_data::NetworkStoreData;
 use crate::network::types::TournamentRequestState;
-use crate::store::serialize;
 use crate::types::{AppResult, GameId, HashMapWithResult, PlayerMap};
 use crate::types::{PlayerId, TeamId};
 use crate::types::{SystemTimeTick, Tick};
 use anyhow::anyhow;
 use futures::StreamExt;
 use itertools::Itertools;
+use libp2p::core::transport::ListenerId;
+use libp2p::core::ConnectedPoint;
 use libp2p::gossipsub::{self, IdentTopic};
 use libp2p::identity::Keypair;
 use libp2p::multiaddr::Protocol;
+use libp2p::request_response::{self, OutboundRequestId, ProtocolSupport, ResponseChannel};
+use libp2p::swarm::behaviour::toggle::Toggle;
 use libp2p::swarm::{DialError, NetworkBehaviour, SwarmEvent};
-use libp2p::{identify, identity, kad, noise, tcp, yamux, PeerId, StreamProtocol, TransportError};
+use libp2p::{autonat, dcutr, identify, identity, kad, mdns, noise, relay, tcp, yamux};
 use libp2p::{Multiaddr, Swarm};
+use libp2p::{PeerId, StreamProtocol, TransportError};
 use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
 use std::time::Duration;
@@ -34,10 +47,14 @@ use tokio_util::sync::CancellationToken;
 
 /// Strip /p2p/ suffix and filter out non-routable address.
 pub(crate) fn sanitize_addr(addr: &Multiaddr) -> Option<Multiaddr> {
-    let clean: Multiaddr = addr
-        .iter()
-        .filter(|p| !matches!(p, Protocol::P2p(_)))
-        .collect();
+    // Relayed addresses can only be dialed with the relay peer id, so they are kept whole.
+    let clean: Multiaddr = if is_relayed(addr) {
+        addr.clone()
+    } else {
+        addr.iter()
+            .filter(|p| !matches!(p, Protocol::P2p(_)))
+            .collect()
+    };
     if is_routable(&clean) {
         Some(clean)
     } else {
@@ -45,15 +62,46 @@ pub(crate) fn sanitize_addr(addr: &Multiaddr) -> Option<Multiaddr> {
     }
 }
 
+/// Like sanitize_addr, but relayed addresses also get the /p2p/ suffix of the peer
+/// listening on the circuit, which is needed to dial them.
+pub(crate) fn sanitize_peer_addr(peer_id: &PeerId, addr: &Multiaddr) -> Option<Multiaddr> {
+    let clean = sanitize_addr(addr)?;
+    if is_relayed(&clean) && !matches!(clean.iter().last(), Some(Protocol::P2p(_))) {
+        Some(clean.with(Protocol::P2p(*peer_id)))
+    } else {
+        Some(clean)
+    }
+}
+
 /// Strip /p2p/ suffixes and filter out non-routable addresses from identify listen_addrs.
-pub(crate) fn sanitize_listen_addrs(addrs: &[Multiaddr]) -> Vec<Multiaddr> {
-    addrs.iter().filter_map(sanitize_addr).collect()
+pub(crate) fn sanitize_listen_addrs(peer_id: &PeerId, addrs: &[Multiaddr]) -> Vec<Multiaddr> {
+    addrs
+        .iter()
+        .filter_map(|addr| sanitize_peer_addr(peer_id, addr))
+        .collect()
+}
+
+fn is_relayed(addr: &Multiaddr) -> bool {
+    addr.iter().any(|p| matches!(p, Protocol::P2pCircuit))
+}
+
+/// Address to listen on to be reachable through a circuit of the given relay.
+pub(crate) fn relay_circuit_addr(relay_addr: &Multiaddr, relay_peer_id: PeerId) -> Multiaddr {
+    relay_addr
+        .iter()
+        .filter(|p| !matches!(p, Protocol::P2p(_)))
+        .collect::<Multiaddr>()
+        .with(Protocol::P2p(relay_peer_id))
+        .with(Protocol::P2pCircuit)
 }
 
 /// Extract the IP from an observed address and combine it with our actual listen port.
 /// The observed_addr from identify contains the correct public IP but the wrong
 /// (ephemeral) port from the outbound connection.
-fn build_external_addr_from_observed(observed_addr: &Multiaddr, listen_port: u16) -> Option<Multiaddr> {
+fn build_external_addr_from_observed(
+    observed_addr: &Multiaddr,
+    listen_port: u16,
+) -> Option<Multiaddr> {
     let mut ip_proto = None;
     for proto in observed_addr.iter() {
         match proto {
@@ -102,6 +150,39 @@ pub struct Behaviour {
     pub gossipsub: gossipsub::Behaviour,
     pub identify: identify::Behaviour,
     pub kademlia: kad::Behaviour<kad::store::MemoryStore>,
+    pub direct: request_response::json::Behaviour<NetworkData, DirectAck>,
+    pub relay_client: relay::client::Behaviour,
+    // Only relayer nodes act as circuit relays for peers behind NAT.
+    pub relay: Toggle<relay::Behaviour>,
+    pub dcutr: dcutr::Behaviour,
+    pub autonat: autonat::Behaviour,
+    // Optional discovery of peers on the local network, so games work without internet.
+    pub mdns: Toggle<mdns::tokio::Behaviour>,
+}
+
+fn listen_on_relay(
+    swarm: &mut Swarm<Behaviour>,
+    relay_candidates: &[(PeerId, Multiaddr)],
+) -> Option<ListenerId> {
+    let (relay_peer_id, relay_addr) = relay_candidates.first()?;
+    match swarm.listen_on(relay_circuit_addr(relay_addr, *relay_peer_id)) {
+        Ok(listener_id) => {
+            log::info!("Listening through relay {relay_peer_id}");
+            Some(listener_id)
+        }
+        Err(e) => {
+            log::error!("Could not listen through relay {relay_peer_id}: {e}");
+            None
+        }
+    }
+}
+
+fn format_nat_status(status: &autonat::NatStatus) -> String {
+    match status {
+        autonat::NatStatus::Public(address) => format!("public at {address}"),
+        autonat::NatStatus::Private => "private".to_string(),
+        autonat::NatStatus::Unknown => "unknown".to_string(),
+    }
 }
 
 #[derive(Debug, Default)]
@@ -113,10 +194,23 @@ enum SwarmStatus {
     },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 enum SwarmCommand {
-    Dial { address: Multiaddr },
-    Send { topic: IdentTopic, data: Vec<u8> },
+    Dial {
+        address: Multiaddr,
+    },
+    Send {
+        topic: IdentTopic,
+        data: Vec<u8>,
+    },
+    SendDirect {
+        peer_id: PeerId,
+        data: Box<NetworkData>,
+        attempt: u8,
+    },
+    Respond {
+        channel: ResponseChannel<DirectAck>,
+    },
 }
 
 #[derive(Debug)]
@@ -126,6 +220,15 @@ pub struct NetworkHandler {
     own_peer_id: PeerId,
     pub seed_addresses: Vec<Multiaddr>,
     swarm_status: SwarmStatus,
+    relay_server: bool,
+    mdns: bool,
+    // Protocol versions advertised by peers through identify.
+    peer_protocol_versions: HashMap<PeerId, u32>,
+    // Peers advertising the relay protocol, whose ban lists are applied if trusted.
+    relay_peers: HashSet<PeerId>,
+    trust_relayer_bans: bool,
+    // Arrival time of the recent chat messages of each peer.
+    chat_message_timestamps: HashMap<PeerId, Vec<Tick>>,
 }
 
 impl NetworkHandler {
@@ -134,6 +237,8 @@ impl NetworkHandler {
         tcp_port: u16,
         use_ipv4: bool,
         use_ipv6: bool,
+        relay_server: bool,
+        mdns: bool,
     ) -> AppResult<Swarm<Behaviour>> {
         // To content-address message, we can take the hash of message and use it as an ID.
         let message_id_fn = |message: &gossipsub::Message| {
@@ -161,7 +266,7 @@ impl NetworkHandler {
         let peer_id = keypair.public().to_peer_id();
 
         let identify = identify::Behaviour::new(
-            identify::Config::new("/rebels/1.0.0".to_string(), keypair.public())
+            identify::Config::new(identify_protocol_version(), keypair.public())
                 .with_push_listen_addr_updates(true),
         );
 
@@ -170,11 +275,24 @@ impl NetworkHandler {
         let store = kad::store::MemoryStore::new(peer_id);
         let kademlia = kad::Behaviour::with_config(peer_id, store, kad_config);
 
-        let behaviour = Behaviour {
-            gossipsub,
-            identify,
-            kademlia,
-        };
+        let direct = request_response::json::Behaviour::new(
+            [(StreamProtocol::new(DIRECT_PROTOCOL), ProtocolSupport::Full)],
+            request_response::Config::default().with_request_timeout(DIRECT_REQUEST_TIMEOUT),
+        );
+
+        let relay = Toggle::from(
+            relay_server.then(|| relay::Behaviour::new(peer_id, relay::Config::default())),
+        );
+        let dcutr = dcutr::Behaviour::new(peer_id);
+        let autonat = autonat::Behaviour::new(peer_id, autonat::Config::default());
+        let mdns = Toggle::from(if mdns {
+            Some(mdns::tokio::Behaviour::new(
+                mdns::Config::default(),
+                peer_id,
+            )?)
+        } else {
+            None
+        });
 
         let mut swarm = libp2p::SwarmBuilder::with_existing_identity(keypair)
             .with_tokio()
@@ -184,7 +302,18 @@ impl NetworkHandler {
                 yamux::Config::default,
             )?
             .with_dns()?
-            .with_behaviour(|_| behaviour)?
+            .with_relay_client(noise::Config::new, yamux::Config::default)?
+            .with_behaviour(|_, relay_client| Behaviour {
+                gossipsub,
+                identify,
+                kademlia,
+                direct,
+                relay_client,
+                relay,
+                dcutr,
+                autonat,
+                mdns,
+            })?
             .with_swarm_config(|cfg| {
                 cfg.with_idle_connection_timeout(Duration::from_secs(u64::MAX))
             })
@@ -225,6 +354,12 @@ impl NetworkHandler {
             own_peer_id,
             seed_addresses: vec![],
             swarm_status: SwarmStatus::Uninitialized,
+            relay_server: false,
+            mdns: false,
+            peer_protocol_versions: HashMap::new(),
+            relay_peers: HashSet::new(),
+            trust_relayer_bans: false,
+            chat_message_timestamps: HashMap::new(),
         }
     }
 
@@ -260,6 +395,12 @@ impl NetworkHandler {
             own_peer_id,
             seed_addresses,
             swarm_status: SwarmStatus::Uninitialized,
+            relay_server: false,
+            mdns: false,
+            peer_protocol_versions: HashMap::new(),
+            relay_peers: HashSet::new(),
+            trust_relayer_bans: false,
+            chat_message_timestamps: HashMap::new(),
         })
     }
 
@@ -270,6 +411,24 @@ impl NetworkHandler {
         self
     }
 
+    pub fn with_relay_server(mut self) -> Self {
+        self.relay_server = true;
+
+        self
+    }
+
+    pub fn with_mdns(mut self) -> Self {
+        self.mdns = true;
+
+        self
+    }
+
+    pub fn with_relayer_bans(mut self) -> Self {
+        self.trust_relayer_bans = true;
+
+        self
+    }
+
     pub fn set_keypair(&mut self, keypair: Keypair) {
         self.local_keypair = keypair;
         self.own_peer_id = self.local_keypair.public().to_peer_id();
@@ -284,6 +443,69 @@ impl NetworkHandler {
         &self.own_peer_id
     }
 
+    pub fn set_peer_protocol_version(&mut self, peer_id: PeerId, protocol_version: u32) {
+        self.peer_protocol_versions
+            .insert(peer_id, protocol_version);
+    }
+
+    pub fn peer_protocol_version(&self, peer_id: &PeerId) -> Option<u32> {
+        self.peer_protocol_versions.get(peer_id).copied()
+    }
+
+    // Relayers are only trusted when reached through the configured seed addresses:
+    // the protocols advertised by a peer cannot be trusted.
+    pub fn add_relay_peer_if_seed(&mut self, peer_id: PeerId, endpoint: &ConnectedPoint) -> bool {
+        let ConnectedPoint::Dialer { address, .. } = endpoint else {
+            return false;
+        };
+
+        let address = address
+            .iter()
+            .filter(|protocol| !matches!(protocol, Protocol::P2p(_)))
+            .collect::<Multiaddr>();
+        if !self.seed_addresses.contains(&address) {
+            return false;
+        }
+
+        self.relay_peers.insert(peer_id)
+    }
+
+    pub fn signer_context<'a>(&'a self, world: &'a World) -> SignerContext<'a> {
+        SignerContext {
+            relay_peers: &self.relay_peers,
+            own_peer_id: Some(*self.own_peer_id()),
+            own_team_id: world.own_team_id,
+            teams: &world.teams,
+            players: &world.players,
+            tournaments: &world.tournaments,
+            games: &world.games,
+        }
+    }
+
+    pub fn is_trusted_relayer(&self, peer_id: &PeerId) -> bool {
+        self.trust_relayer_bans && self.relay_peers.contains(peer_id)
+    }
+
+    pub fn check_chat_rate_limit(&mut self, peer_id: PeerId) -> AppResult<()> {
+        let now = Tick::now();
+        let timestamps = self.chat_message_timestamps.entry(peer_id).or_default();
+        timestamps.retain(|timestamp| now.saturating_sub(*timestamp) < CHAT_RATE_LIMIT_INTERVAL);
+        if timestamps.len() >= CHAT_RATE_LIMIT_MESSAGES {
+            return Err(anyhow!("Peer {peer_id} is sending chat messages too fast"));
+        }
+        timestamps.push(now);
+        Ok(())
+    }
+
+    // Peers that have not been identified yet pass, the protocol version carried
+    // by challenges, trades and tournaments is checked on its own.
+    pub fn check_game_compatibility(&self, peer_id: &PeerId) -> AppResult<()> {
+        if let Some(protocol_version) = self.peer_protocol_version(peer_id) {
+            check_game_compatibility(protocol_version)?;
+        }
+        Ok(())
+    }
+
     pub fn start_polling_events(
         &mut self,
         event_sender: mpsc::Sender<AppEvent>,
@@ -294,20 +516,38 @@ impl NetworkHandler {
     ) -> JoinHandle<()> {
         let local_keypair = self.local_keypair.clone();
         let own_peer_id = *self.own_peer_id();
+        let relay_server = self.relay_server;
+        let mdns = self.mdns;
 
         let (sender, mut receiver) = mpsc::channel(64);
+        // Used to schedule retries of direct requests, without keeping the channel open.
+        let retry_sender = sender.downgrade();
 
         self.swarm_status = SwarmStatus::Ready { sender };
         let handle = tokio::spawn(async move {
-            let mut swarm =
-                if let Ok(swarm) = Self::new_swarm(local_keypair, tcp_port, use_ipv4, use_ipv6) {
-                    swarm
-                } else {
-                    return;
-                };
+            let mut swarm = if let Ok(swarm) = Self::new_swarm(
+                local_keypair.clone(),
+                tcp_port,
+                use_ipv4,
+                use_ipv6,
+                relay_server,
+                mdns,
+            ) {
+                swarm
+            } else {
+                return;
+            };
 
             assert_eq!(own_peer_id, *swarm.local_peer_id());
             let mut kad_bootstrapped = false;
+            // Direct requests waiting for an ack, with the number of attempts so far.
+            let mut pending_requests: HashMap<OutboundRequestId, (NetworkData, u8)> =
+                HashMap::new();
+            // Relays we dialed ourselves, used to be reachable when AutoNAT reports we are private.
+            let mut dialed_addresses: HashMap<PeerId, Multiaddr> = HashMap::new();
+            let mut relay_candidates: Vec<(PeerId, Multiaddr)> = vec![];
+            let mut relay_listener: Option<ListenerId> = None;
+            let mut is_private = false;
 
             loop {
                 tokio::select! {
@@ -334,7 +574,7 @@ impl NetworkHandler {
                             SwarmEvent::Behaviour(BehaviourEvent::Identify(
                                 identify::Event::Received { peer_id, info, .. }
                             )) => {
-                                let clean_addrs = sanitize_listen_addrs(&info.listen_addrs);
+                                let clean_addrs = sanitize_listen_addrs(peer_id, &info.listen_addrs);
                                 log::debug!(
                                     "Identify from {peer_id}: adding {} routable addrs to Kademlia (from {} total)",
                                     clean_addrs.len(),
@@ -353,13 +593,68 @@ impl NetworkHandler {
                                     log::debug!("Adding external address from observed: {ext_addr}");
                                     swarm.add_external_address(ext_addr);
                                 }
+
+                                if info.protocols.contains(&relay::HOP_PROTOCOL_NAME)
+                                    && !relay_candidates.iter().any(|(relay_peer_id, _)| relay_peer_id == peer_id)
+                                {
+                                    if let Some(address) = dialed_addresses.get(peer_id) {
+                                        relay_candidates.push((*peer_id, address.clone()));
+                                        if is_private && relay_listener.is_none() {
+                                            relay_listener = listen_on_relay(&mut swarm, &relay_candidates);
+                                        }
+                                    }
+                                }
+                            }
+                            SwarmEvent::Behaviour(BehaviourEvent::Autonat(
+                                autonat::Event::StatusChanged { new, .. }
+                            )) => {
+                                is_private = matches!(new, autonat::NatStatus::Private);
+                                if is_private && relay_listener.is_none() {
+                                    relay_listener = listen_on_relay(&mut swarm, &relay_candidates);
+                                } else if !is_private {
+                                    if let Some(listener_id) = relay_listener.take() {
+                                        swarm.remove_listener(listener_id);
+                                    }
+                                }
+                            }
+                            SwarmEvent::Behaviour(BehaviourEvent::Mdns(mdns::Event::Discovered(peers))) => {
+                                // Local addresses are not routable, so they are added here
+                                // instead of going through identify.
+                                for (peer_id, address) in peers {
+                                    swarm.behaviour_mut().kademlia.add_address(peer_id, address.clone());
+                                    if !swarm.is_connected(peer_id) {
+                                        if let Err(e) = swarm.dial(address.clone()) {
+                                            log::debug!("Could not dial local peer {peer_id} at {address}: {e}");
+                                        }
+                                    }
+                                }
+                            }
+                            SwarmEvent::Behaviour(BehaviourEvent::Mdns(mdns::Event::Expired(peers))) => {
+                                for (peer_id, address) in peers {
+                                    swarm.behaviour_mut().kademlia.remove_address(peer_id, address);
+                                }
+                            }
+                            SwarmEvent::ListenerClosed { listener_id, .. } => {
+                                // Try the next relay on the following status change or identify.
+                                if relay_listener == Some(*listener_id) {
+                                    relay_listener = None;
+                                    if !relay_candidates.is_empty() {
+                                        relay_candidates.rotate_left(1);
+                                    }
+                                }
                             }
                             SwarmEvent::NewExternalAddrOfPeer { peer_id, address } => {
-                                if let Some(clean) = sanitize_addr(address) {
+                                if let Some(clean) = sanitize_peer_addr(peer_id, address) {
                                     swarm.behaviour_mut().kademlia.add_address(peer_id, clean);
                                 }
                             }
-                            SwarmEvent::ConnectionEstablished { .. } => {
+                            SwarmEvent::ConnectionEstablished { peer_id, endpoint, .. } => {
+                                if let ConnectedPoint::Dialer { address, .. } = endpoint {
+                                    if !is_relayed(address) {
+                                        dialed_addresses.insert(*peer_id, address.clone());
+                                    }
+                                }
+
                                 if !kad_bootstrapped
                                     && swarm.behaviour_mut().kademlia.bootstrap().is_ok()
                                 {
@@ -367,10 +662,74 @@ impl NetworkHandler {
                                     log::info!("Kademlia bootstrap initiated");
                                 }
                             }
+                            SwarmEvent::Behaviour(BehaviourEvent::Direct(
+                                request_response::Event::Message {
+                                    message: request_response::Message::Response { request_id, .. },
+                                    ..
+                                }
+                            )) => {
+                                pending_requests.remove(request_id);
+                            }
+                            SwarmEvent::Behaviour(BehaviourEvent::Direct(
+                                request_response::Event::OutboundFailure { peer, request_id, error, .. }
+                            )) => {
+                                // Failures are handled here: the app only hears about requests that are given up.
+                                let Some((data, attempts)) = pending_requests.remove(request_id) else {
+                                    continue;
+                                };
+
+                                if attempts < MAX_DIRECT_REQUEST_ATTEMPTS {
+                                    let delay = DIRECT_REQUEST_RETRY_DELAY * 2u32.pow(attempts as u32 - 1);
+                                    log::debug!("Retrying direct request to {peer} in {delay:?}: {error}");
+                                    let retry_sender = retry_sender.clone();
+                                    let peer_id = *peer;
+                                    tokio::spawn(async move {
+                                        tokio::time::sleep(delay).await;
+                                        if let Some(sender) = retry_sender.upgrade() {
+                                            let retry = SwarmCommand::SendDirect {
+                                                peer_id,
+                                                data: Box::new(data),
+                                                attempt: attempts + 1,
+                                            };
+                                            if sender.send(retry).await.is_err() {
+                                                log::debug!("Swarm closed before retrying direct request to {peer_id}");
+                                            }
+                                        }
+                                    });
+                                    continue;
+                                }
+
+                                // Challenges and trades are private and are dropped if the peer cannot be reached.
+                                // Direct messages go over gossip, where relayers keep them for offline peers.
+                                if matches!(data, NetworkData::DirectMessage { .. }) {
+                                    match SignedNetworkData::sign(&data, &local_keypair).and_then(|signed| signed.to_bytes()) {
+                                        Ok(data) => {
+                                            if let Err(e) = swarm
+                                                .behaviour_mut()
+                                                .gossipsub
+                                                .publish(IdentTopic::new(TOPIC), data) {
+                                                    log::error!("Swarm send error: {e}");
+                                                }
+                                        }
+                                        Err(e) => log::error!("Could not sign network data: {e}"),
+                                    }
+                                }
+
+                                let failure = AppEvent::DirectRequestFailed {
+                                    peer_id: *peer,
+                                    network_data: Box::new(data),
+                                    error: error.to_string(),
+                                };
+                                if event_sender.send(failure).await.is_err() {
+                                    log::warn!("App receiver dropped; stopping network loop");
+                                    break;
+                                }
+                                continue;
+                            }
                             _ => {}
                         }
 
-                        if event_sender.send(AppEvent::NetworkEvent(event)).await.is_err() {
+                        if event_sender.send(AppEvent::NetworkEvent(Box::new(event))).await.is_err() {
                             log::warn!("App receiver dropped; stopping network loop");
                             break;
                         }
@@ -392,6 +751,15 @@ impl NetworkHandler {
                                     log::error!("Swarm dial error for {address}: {e}");
                                 }
                             }
+                            SwarmCommand::SendDirect { peer_id, data, attempt } => {
+                                let request_id = swarm.behaviour_mut().direct.send_request(&peer_id, (*data).clone());
+                                pending_requests.insert(request_id, (*data, attempt));
+                            }
+                            SwarmCommand::Respond { channel } => {
+                                if swarm.behaviour_mut().direct.send_response(channel, DirectAck).is_err() {
+                                    log::debug!("Could not acknowledge direct request, connection closed");
+                                }
+                            }
                         }
                     }
                 }
@@ -401,11 +769,24 @@ impl NetworkHandler {
         handle
     }
 
+    pub fn sign(&self, data: &NetworkData) -> AppResult<Vec<u8>> {
+        SignedNetworkData::sign(data, &self.local_keypair)?.to_bytes()
+    }
+
     fn _send(&self, data: &NetworkData) -> AppResult<()> {
         match &self.swarm_status {
             SwarmStatus::Uninitialized => {}
             SwarmStatus::Ready { sender } => {
-                let data = serialize(data)?;
+                if let Some(peer_id) = data.direct_peer_id(self.own_peer_id) {
+                    sender.try_send(SwarmCommand::SendDirect {
+                        peer_id,
+                        data: Box::new(data.clone()),
+                        attempt: 1,
+                    })?;
+                    return Ok(());
+                }
+
+                let data = self.sign(data)?;
                 sender.try_send(SwarmCommand::Send {
                     topic: IdentTopic::new(TOPIC),
                     data,
@@ -447,15 +828,51 @@ impl NetworkHandler {
         from_peer_id: PeerId,
         author: String,
         message: String,
+        channel: ChatChannel,
     ) -> AppResult<()> {
         self._send(&NetworkData::Message {
             timestamp,
             from_peer_id,
             author,
             message,
+            channel,
         })
     }
 
+    // Direct messages that cannot be delivered are published over gossip,
+    // where relayers keep them until the recipient comes online.
+    // Challenges and trades that cannot be delivered are dropped instead.
+    pub fn send_direct_message(
+        &self,
+        timestamp: Tick,
+        to_peer_id: PeerId,
+        author: &str,
+        message: &str,
+    ) -> AppResult<()> {
+        let message = EncryptedDirectMessage::new(
+            &self.local_keypair,
+            to_peer_id,
+            timestamp,
+            author,
+            message,
+        )?;
+        self.forward_direct_message(message)
+    }
+
+    pub fn forward_direct_message(&self, message: EncryptedDirectMessage) -> AppResult<()> {
+        self._send(&NetworkData::DirectMessage {
+            timestamp: message.timestamp,
+            message,
+        })
+    }
+
+    pub fn decrypt_direct_message(
+        &self,
+        message: &EncryptedDirectMessage,
+    ) -> AppResult<ChatHistoryEntry> {
+        message.decrypt(&self.local_keypair)
+    }
+
     pub fn send_sync_request(&mut self) -> AppResult<()> {
         self._send(&NetworkData::SyncRequest)
     }
@@ -473,6 +890,14 @@ impl NetworkHandler {
         })
     }
 
+    #[cfg(feature = "relayer")]
+    pub fn send_ban_list(&self, banned_peer_ids: Vec<PeerId>) -> AppResult<()> {
+        self._send(&NetworkData::BanList {
+            timestamp: Tick::now(),
+            banned_peer_ids,
+        })
+    }
+
     pub fn send_seed_info(&mut self, network_store_data: NetworkStoreData) -> AppResult<()> {
         let seed_info = SeedInfo::new(network_store_data)?;
 
@@ -542,6 +967,18 @@ impl NetworkHandler {
         })
     }
 
+    pub fn send_game_adjustment(
+        &self,
+        game_id: GameId,
+        adjustment: GameAdjustment,
+    ) -> AppResult<()> {
+        self._send(&NetworkData::GameAdjustment {
+            timestamp: Tick::now(),
+            game_id,
+            adjustment,
+        })
+    }
+
     fn send_team(&self, world: &World, team_id: TeamId) -> AppResult<()> {
         let team = NetworkTeam::from_team_id(world, &team_id, *self.own_peer_id())?;
         self._send(&NetworkData::Team {
@@ -550,7 +987,11 @@ impl NetworkHandler {
         })
     }
 
-    pub fn send_tournament(&self, tournament: Tournament) -> AppResult<()> {
+    pub fn send_tournament(&self, mut tournament: Tournament) -> AppResult<()> {
+        // Tournaments resent by participants keep the organizer peer_id.
+        tournament
+            .organizer_peer_id
+            .get_or_insert(*self.own_peer_id());
         self._send(&NetworkData::Tournament {
             timestamp: Tick::now(),
             tournament,
@@ -594,6 +1035,7 @@ impl NetworkHandler {
         team_id: TeamId,
         app_version: [usize; 3],
     ) -> AppResult<Challenge> {
+        self.check_game_compatibility(&peer_id)?;
         self.send_own_team(world)?;
         let mut home_team_in_game =
             TeamInGame::from_team_id(&world.own_team_id, &world.teams, &world.players)?;
@@ -620,6 +1062,7 @@ impl NetworkHandler {
         proposer_player_id: PlayerId,
         target_player_id: PlayerId,
     ) -> AppResult<Trade> {
+        self.check_game_compatibility(&target_peer_id)?;
         self.send_own_team(world)?;
 
         let proposer_player = world.players.get_or_err(&proposer_player_id)?.clone();
@@ -771,19 +1214,102 @@ impl NetworkHandler {
                 info,
                 ..
             })) => {
-                let listen_addrs = sanitize_listen_addrs(&info.listen_addrs);
+                let listen_addrs = sanitize_listen_addrs(&peer_id, &info.listen_addrs);
                 Some(NetworkCallback::PeerIdentified {
                     peer_id,
                     listen_addrs,
+                    protocol_version: parse_identify_protocol_version(&info.protocol_version),
                 })
             }
             SwarmEvent::Behaviour(BehaviourEvent::Identify(_)) => None,
             SwarmEvent::Behaviour(BehaviourEvent::Kademlia(_)) => None,
+            SwarmEvent::Behaviour(BehaviourEvent::Direct(request_response::Event::Message {
+                peer,
+                message:
+                    request_response::Message::Request {
+                        request, channel, ..
+                    },
+                ..
+            })) => {
+                // Delivery is acknowledged right away, the reply is sent as a new request.
+                if let SwarmStatus::Ready { sender } = &self.swarm_status {
+                    if let Err(e) = sender.try_send(SwarmCommand::Respond { channel }) {
+                        log::error!("Could not acknowledge direct request: {e}");
+                    }
+                }
+                Some(NetworkCallback::HandleDirectMessage {
+                    peer_id: peer,
+                    network_data: Box::new(request),
+                })
+            }
+            SwarmEvent::Behaviour(BehaviourEvent::Direct(_)) => None,
+            SwarmEvent::Behaviour(BehaviourEvent::Autonat(autonat::Event::StatusChanged {
+                old,
+                new,
+            })) => Some(NetworkCallback::PushSwarmPanelLog {
+                timestamp: Tick::now(),
+                peer_id: None,
+                text: format!(
+                    "Reachability changed from {} to {}",
+                    format_nat_status(&old),
+                    format_nat_status(&new)
+                ),
+                level: log::Level::Info,
+            }),
+            SwarmEvent::Behaviour(BehaviourEvent::RelayClient(
+                relay::client::Event::ReservationReqAccepted {
+                    relay_peer_id,
+                    renewal: false,
+                    ..
+                },
+            )) => Some(NetworkCallback::PushSwarmPanelLog {
+                timestamp: Tick::now(),
+                peer_id: Some(relay_peer_id),
+                text: format!("Reachable through relay {relay_peer_id}"),
+                level: log::Level::Info,
+            }),
+            SwarmEvent::Behaviour(BehaviourEvent::Dcutr(dcutr::Event {
+                remote_peer_id,
+                result,
+            })) => {
+                let (text, level) = match result {
+                    Ok(_) => (
+                        format!("Direct connection to {remote_peer_id} via hole punching"),
+                        log::Level::Debug,
+                    ),
+                    Err(e) => (
+                        format!("Hole punching to {remote_peer_id} failed: {e}"),
+                        log::Level::Warn,
+                    ),
+                };
+                Some(NetworkCallback::PushSwarmPanelLog {
+                    timestamp: Tick::now(),
+                    peer_id: Some(remote_peer_id),
+                    text,
+                    level,
+                })
+            }
+            SwarmEvent::Behaviour(BehaviourEvent::Mdns(mdns::Event::Discovered(peers))) => {
+                let local_peers = peers.iter().map(|(peer_id, _)| peer_id).unique().count();
+                Some(NetworkCallback::PushSwarmPanelLog {
+                    timestamp: Tick::now(),
+                    peer_id: None,
+                    text: format!("Discovered {local_peers} peers on the local network"),
+                    level: log::Level::Info,
+                })
+            }
+            SwarmEvent::Behaviour(BehaviourEvent::Mdns(_))
+            | SwarmEvent::Behaviour(BehaviourEvent::Autonat(_))
+            | SwarmEvent::Behaviour(BehaviourEvent::RelayClient(_))
+            | SwarmEvent::Behaviour(BehaviourEvent::Relay(_)) => None,
             SwarmEvent::NewExternalAddrOfPeer { peer_id, address } => {
-                sanitize_addr(&address).map(|clean| NetworkCallback::PeerIdentified {
+                sanitize_peer_addr(&peer_id, &address).map(|clean| {
+                    NetworkCallback::PeerIdentified {
                         peer_id,
                         listen_addrs: vec![clean],
-                    })
+                        protocol_version: None,
+                    }
+                })
             }
             SwarmEvent::ExpiredListenAddr {
                 listener_id: _,
@@ -798,6 +1324,9 @@ impl NetworkHandler {
                 peer_id, endpoint, ..
             } => {
                 self.connected_peers_count += 1;
+                if self.add_relay_peer_if_seed(peer_id, &endpoint) {
+                    log::info!("Trusting seed {peer_id} as relayer");
+                }
                 Some(NetworkCallback::HandleConnectionEstablished { peer_id, endpoint })
             }
             SwarmEvent::ConnectionClosed { peer_id, .. } => {
@@ -834,26 +1363,107 @@ impl NetworkHandler {
 
 #[cfg(test)]
 mod tests {
-    use super::TOPIC;
+    use super::{
+        relay_circuit_addr, sanitize_peer_addr, BehaviourEvent, NetworkHandler,
+        CHAT_RATE_LIMIT_MESSAGES, TOPIC,
+    };
     use crate::{
         app::App,
-        core::{constants::NETWORK_GAME_START_DELAY, types::TeamLocation, world::World},
+        core::{
+            constants::NETWORK_GAME_START_DELAY, skill::MAX_SKILL, team::Team, types::TeamLocation,
+            world::World,
+        },
+        game_engine::{timer::Timer, types::TeamInGame, Tournament, TournamentId},
         network::{
+            challenge::Challenge,
             network_callback::NetworkCallback,
-            types::{NetworkData, NetworkRequestState, NetworkTeam},
+            protocol::PROTOCOL_VERSION,
+            types::{
+                ChatChannel, NetworkData, NetworkGame, NetworkRequestState, NetworkTeam,
+                SignedNetworkData, SignerContext, TournamentRequestState,
+            },
         },
         store::{deserialize, serialize},
-        types::{AppResult, HashMapWithResult, SystemTimeTick, Tick},
+        types::{
+            AppResult, GameId, GameMap, HashMapWithResult, PlayerMap, SystemTimeTick, TeamId,
+            TeamMap, Tick, TournamentMap,
+        },
         ui::UiCallback,
     };
     use anyhow::anyhow;
+    use futures::StreamExt;
     use libp2p::{
+        core::{transport::PortUse, ConnectedPoint, Endpoint},
         gossipsub::{IdentTopic, Message},
-        PeerId,
+        identity::Keypair,
+        mdns,
+        multiaddr::Protocol,
+        relay,
+        swarm::SwarmEvent,
+        Multiaddr, PeerId,
     };
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
 
+    const SEED_ADDRESS: &str = "/ip4/10.0.0.1/tcp/37000";
+
+    fn dialed(address: &str) -> ConnectedPoint {
+        ConnectedPoint::Dialer {
+            address: address.parse().expect("Invalid test address"),
+            role_override: Endpoint::Dialer,
+            port_use: PortUse::Reuse,
+        }
+    }
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    #[test]
+    fn test_undelivered_challenge_is_dropped() -> AppResult<()> {
+        let mut app1 = App::test_with_network_handler()?;
+        let app2 = App::test_with_network_handler()?;
+        let target_peer_id = *app2.network_handler.own_peer_id();
+
+        let planet_id = app1.world.get_own_team()?.home_planet_id;
+        let mut own_team2 = app2.world.get_own_team()?.clone();
+        own_team2.peer_id = Some(target_peer_id);
+        own_team2.current_location = TeamLocation::OnPlanet { planet_id };
+        for player_id in own_team2.player_ids.iter() {
+            let player = app2.world.players.get(player_id).unwrap();
+            app1.world.players.insert(*player_id, player.clone());
+        }
+        app1.world.teams.insert(own_team2.id, own_team2);
+
+        UiCallback::ChallengeTeam {
+            team_id: app2.world.own_team_id,
+        }
+        .call(&mut app1)?;
+        let challenge = app1
+            .world
+            .get_own_team()?
+            .sent_challenges
+            .get(&app2.world.own_team_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Challenge should have been sent"))?;
+
+        // The challenge is not published over gossip, the user is told instead.
+        let cb = NetworkCallback::HandleFailedDirectRequest {
+            peer_id: target_peer_id,
+            network_data: Box::new(NetworkData::Challenge {
+                timestamp: Tick::now(),
+                challenge,
+            }),
+            error: "Dial failure".to_string(),
+        };
+        assert!(cb.call(&mut app1)?.is_some());
+        assert!(!app1
+            .world
+            .get_own_team()?
+            .sent_challenges
+            .contains_key(&app2.world.own_team_id));
+
+        Ok(())
+    }
+
     #[test]
     fn test_network_challenge_success() -> AppResult<()> {
         let topic = IdentTopic::new(TOPIC);
@@ -907,12 +1517,28 @@ mod tests {
             .unwrap()
             .clone();
 
+        // Challenges from older protocols are refused, even if the peer was not identified.
+        let mut outdated_challenge = syn_challenge.clone();
+        outdated_challenge.protocol_version = 1;
+        let outdated_cb = NetworkCallback::HandleMessage {
+            message: Message {
+                source: None,
+                data: app1.network_handler.sign(&NetworkData::Challenge {
+                    timestamp: Tick::now(),
+                    challenge: outdated_challenge,
+                })?,
+                sequence_number: None,
+                topic: topic.clone().into(),
+            },
+        };
+        assert!(outdated_cb.call(&mut app2).is_err());
+
         // Mock up send_challenge
         let network_data = NetworkData::Challenge {
             timestamp: Tick::now(),
             challenge: syn_challenge,
         };
-        let data = serialize::<NetworkData>(&network_data)?;
+        let data = app1.network_handler.sign(&network_data)?;
 
         let message = Message {
             source: None,
@@ -921,6 +1547,14 @@ mod tests {
             topic: topic.clone().into(),
         };
         let cb = NetworkCallback::HandleMessage { message };
+
+        // Peers on an incompatible protocol cannot challenge us.
+        app2.network_handler
+            .set_peer_protocol_version(proposer_peer_id, 1);
+        assert!(cb.call(&mut app2).is_err());
+        app2.network_handler
+            .set_peer_protocol_version(proposer_peer_id, PROTOCOL_VERSION);
+
         assert!(cb.call(&mut app2).is_ok());
 
         let own_team2 = app2.world.get_own_team()?.clone();
@@ -952,21 +1586,22 @@ mod tests {
             timestamp: Tick::now(),
             challenge: syn_ack_challenge,
         };
-        let data = serialize::<NetworkData>(&network_data)?;
-
-        let message = Message {
-            source: None,
-            data,
-            sequence_number: None,
-            topic: topic.clone().into(),
-        };
-
         // Check that challenge has been removed after accepting
         let own_team2 = app2.world.get_own_team()?.clone();
         let received_challenge = own_team2.received_challenges.get(&app1.world.own_team_id);
         assert!(received_challenge.is_none());
 
-        let cb = NetworkCallback::HandleMessage { message };
+        // Replies are sent directly, so they must come from one of the challenge peers.
+        let forged_cb = NetworkCallback::HandleDirectMessage {
+            peer_id: PeerId::random(),
+            network_data: Box::new(network_data.clone()),
+        };
+        assert!(forged_cb.call(&mut app1).is_err());
+
+        let cb = NetworkCallback::HandleDirectMessage {
+            peer_id: target_peer_id,
+            network_data: Box::new(network_data),
+        };
         let own_team1 = app1.world.get_own_team()?.clone();
         assert!(own_team1.current_game.is_none());
         assert!(cb.call(&mut app1).is_ok());
@@ -981,7 +1616,7 @@ mod tests {
             timestamp: Tick::now(),
             challenge: ack_challenge,
         };
-        let data = serialize::<NetworkData>(&network_data)?;
+        let data = app1.network_handler.sign(&network_data)?;
 
         let message = Message {
             source: None,
@@ -1047,4 +1682,492 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_relayed_addresses_are_kept() -> AppResult<()> {
+        let relay_peer_id = PeerId::random();
+        let peer_id = PeerId::random();
+        let relay_addr: Multiaddr = "/ip4/1.2.3.4/tcp/37201".parse()?;
+
+        let circuit_addr = relay_circuit_addr(&relay_addr, relay_peer_id);
+        let clean = sanitize_peer_addr(&peer_id, &circuit_addr).unwrap();
+        assert_eq!(clean, circuit_addr.with(Protocol::P2p(peer_id)));
+
+        let direct_addr = relay_addr.clone().with(Protocol::P2p(peer_id));
+        assert_eq!(sanitize_peer_addr(&peer_id, &direct_addr), Some(relay_addr));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_relay_reservation() -> AppResult<()> {
+        let mut relay_swarm =
+            NetworkHandler::new_swarm(Keypair::generate_ed25519(), 0, true, false, true, false)?;
+        let relay_peer_id = *relay_swarm.local_peer_id();
+        let relay_addr = loop {
+            if let SwarmEvent::NewListenAddr { address, .. } = relay_swarm.select_next_some().await
+            {
+                if address
+                    .iter()
+                    .any(|p| matches!(p, Protocol::Ip4(ip) if ip.is_loopback()))
+                {
+                    break address;
+                }
+            }
+        };
+        // Reservations advertise the relay external addresses.
+        relay_swarm.add_external_address(relay_addr.clone());
+
+        let mut client_swarm =
+            NetworkHandler::new_swarm(Keypair::generate_ed25519(), 0, true, false, false, false)?;
+        client_swarm.listen_on(relay_circuit_addr(&relay_addr, relay_peer_id))?;
+
+        let accepted_by = tokio::time::timeout(Duration::from_secs(30), async {
+            loop {
+                tokio::select! {
+                    _ = relay_swarm.select_next_some() => {}
+                    event = client_swarm.select_next_some() => {
+                        if let SwarmEvent::Behaviour(BehaviourEvent::RelayClient(
+                            relay::client::Event::ReservationReqAccepted { relay_peer_id, .. },
+                        )) = event
+                        {
+                            break relay_peer_id;
+                        }
+                    }
+                }
+            }
+        })
+        .await?;
+        assert_eq!(accepted_by, relay_peer_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires multicast on the local network"]
+    async fn test_mdns_discovery() -> AppResult<()> {
+        let mut swarm1 =
+            NetworkHandler::new_swarm(Keypair::generate_ed25519(), 0, true, false, false, true)?;
+        let mut swarm2 =
+            NetworkHandler::new_swarm(Keypair::generate_ed25519(), 0, true, false, false, true)?;
+        let peer_id2 = *swarm2.local_peer_id();
+
+        let discovered = tokio::time::timeout(Duration::from_secs(30), async {
+            loop {
+                tokio::select! {
+                    _ = swarm2.select_next_some() => {}
+                    event = swarm1.select_next_some() => {
+                        if let SwarmEvent::Behaviour(BehaviourEvent::Mdns(
+                            mdns::Event::Discovered(peers),
+                        )) = event
+                        {
+                            if peers.iter().any(|(peer_id, _)| *peer_id == peer_id2) {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        })
+        .await;
+        assert!(discovered.is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_signed_team_cannot_be_forged() -> AppResult<()> {
+        let mut world = World::new(None);
+        let rng = &mut ChaCha8Rng::seed_from_u64(0);
+        let home_planet = *world.planets.keys().next().unwrap();
+        let team_id =
+            world.generate_random_team(rng, home_planet, "Testen".into(), "Tosten".into(), None)?;
+
+        let keypair = Keypair::generate_ed25519();
+        let peer_id = keypair.public().to_peer_id();
+        let network_data = NetworkData::Team {
+            timestamp: Tick::now(),
+            team: NetworkTeam::from_team_id(&world, &team_id, peer_id)?,
+        };
+
+        let relay_peers = HashSet::new();
+        let context = SignerContext {
+            relay_peers: &relay_peers,
+            own_peer_id: None,
+            own_team_id: TeamId::default(),
+            teams: &world.teams,
+            players: &PlayerMap::new(),
+            tournaments: &world.tournaments,
+            games: &world.games,
+        };
+        let bytes = SignedNetworkData::sign(&network_data, &keypair)?.to_bytes()?;
+        let (signer, _) = SignedNetworkData::from_bytes(&bytes)?.verify(&context)?;
+        assert_eq!(signer, peer_id);
+
+        // Tampering with the payload breaks the signature.
+        let mut tampered = bytes.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert!(SignedNetworkData::from_bytes(&tampered)?
+            .verify(&context)
+            .is_err());
+
+        // A valid signature from another peer cannot impersonate the team owner.
+        let forger = Keypair::generate_ed25519();
+        let forged = SignedNetworkData::sign(&network_data, &forger)?;
+        assert!(forged.verify(&context).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_data_without_owner_is_rejected() -> AppResult<()> {
+        let world = World::new(None);
+        let relayer = Keypair::generate_ed25519().public().to_peer_id();
+        let peer_id = Keypair::generate_ed25519().public().to_peer_id();
+        let relay_peers = HashSet::from([relayer]);
+        let context = SignerContext {
+            relay_peers: &relay_peers,
+            own_peer_id: None,
+            own_team_id: TeamId::default(),
+            teams: &world.teams,
+            players: &PlayerMap::new(),
+            tournaments: &world.tournaments,
+            games: &world.games,
+        };
+
+        let ban_list = NetworkData::BanList {
+            timestamp: Tick::now(),
+            banned_peer_ids: vec![],
+        };
+        assert!(ban_list.check_signer(relayer, &context).is_ok());
+        assert!(ban_list.check_signer(peer_id, &context).is_err());
+
+        // Responses about unknown tournaments cannot be attributed to the organizer.
+        let request = NetworkData::TournamentRegistrationRequest {
+            timestamp: Tick::now(),
+            tournament_id: TournamentId::new_v4(),
+            team_id: TeamId::new_v4(),
+            team_data: None,
+            request_state: TournamentRequestState::RegistrationOk,
+        };
+        assert!(request.check_signer(peer_id, &context).is_err());
+
+        // Tournaments must be signed by their organizer.
+        let mut tournament = Tournament::test(0, 4);
+        tournament.organizer_peer_id = Some(peer_id);
+        let network_data = NetworkData::Tournament {
+            timestamp: Tick::now(),
+            tournament: tournament.clone(),
+        };
+        // The organizer team is not known yet.
+        assert!(network_data.check_signer(peer_id, &context).is_err());
+
+        let organizer = Team {
+            id: tournament.organizer_id,
+            peer_id: Some(peer_id),
+            ..Default::default()
+        };
+        let teams = TeamMap::from([(organizer.id, organizer)]);
+        let context = SignerContext {
+            relay_peers: &relay_peers,
+            own_peer_id: None,
+            own_team_id: TeamId::default(),
+            teams: &teams,
+            players: &PlayerMap::new(),
+            tournaments: &world.tournaments,
+            games: &world.games,
+        };
+        assert!(network_data.check_signer(peer_id, &context).is_ok());
+        assert!(network_data.check_signer(relayer, &context).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tournament_cannot_be_forged_by_participant() -> AppResult<()> {
+        let organizer_peer_id = Keypair::generate_ed25519().public().to_peer_id();
+        let participant_peer_id = Keypair::generate_ed25519().public().to_peer_id();
+
+        let mut tournament = Tournament::test(2, 4);
+        tournament.organizer_peer_id = Some(organizer_peer_id);
+        for team in tournament.participants.values_mut() {
+            team.peer_id = Some(participant_peer_id);
+        }
+        tournament.initialize();
+
+        let organizer = Team {
+            id: tournament.organizer_id,
+            peer_id: Some(organizer_peer_id),
+            ..Default::default()
+        };
+        let teams = TeamMap::from([(organizer.id, organizer)]);
+        let relay_peers = HashSet::new();
+        let games = GameMap::new();
+        let network_data = NetworkData::Tournament {
+            timestamp: Tick::now(),
+            tournament: tournament.clone(),
+        };
+
+        // A participant cannot introduce a tournament in the name of the organizer.
+        let tournaments = TournamentMap::new();
+        let context = SignerContext {
+            relay_peers: &relay_peers,
+            own_peer_id: None,
+            own_team_id: TeamId::default(),
+            teams: &teams,
+            players: &PlayerMap::new(),
+            tournaments: &tournaments,
+            games: &games,
+        };
+        assert!(network_data
+            .check_signer(participant_peer_id, &context)
+            .is_err());
+
+        // But it can resend a tournament received from the organizer.
+        let tournaments = TournamentMap::from([(tournament.id, tournament)]);
+        let context = SignerContext {
+            relay_peers: &relay_peers,
+            own_peer_id: None,
+            own_team_id: TeamId::default(),
+            teams: &teams,
+            players: &PlayerMap::new(),
+            tournaments: &tournaments,
+            games: &games,
+        };
+        assert!(network_data
+            .check_signer(participant_peer_id, &context)
+            .is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_cannot_forge_opponent() -> AppResult<()> {
+        let mut world = World::new(None);
+        let rng = &mut ChaCha8Rng::seed_from_u64(0);
+        let home_planet = *world.planets.keys().next().unwrap();
+        let home_team_id =
+            world.generate_random_team(rng, home_planet, "Home".into(), "Home".into(), None)?;
+        let away_team_id =
+            world.generate_random_team(rng, home_planet, "Away".into(), "Away".into(), None)?;
+
+        let proposer_peer_id = Keypair::generate_ed25519().public().to_peer_id();
+        let target_peer_id = Keypair::generate_ed25519().public().to_peer_id();
+        world.teams.get_mut_or_err(&home_team_id)?.peer_id = Some(proposer_peer_id);
+        world.teams.get_mut_or_err(&away_team_id)?.peer_id = Some(target_peer_id);
+
+        let mut challenge = Challenge::new(
+            proposer_peer_id,
+            target_peer_id,
+            TeamInGame::from_team_id(&home_team_id, &world.teams, &world.players)?,
+            TeamInGame::from_team_id(&away_team_id, &world.teams, &world.players)?,
+            [0; 3],
+        );
+        challenge.state = NetworkRequestState::Ack;
+        let game = NetworkGame {
+            id: GameId::new_v4(),
+            home_team_in_game: challenge.home_team_in_game.clone(),
+            away_team_in_game: challenge.away_team_in_game.clone(),
+            location: home_planet,
+            attendance: 0,
+            starting_at: Tick::now(),
+            timer: Timer::default(),
+            part_of_tournament: None,
+            adjustments: vec![],
+        };
+
+        let relay_peers = HashSet::new();
+        let context = SignerContext {
+            relay_peers: &relay_peers,
+            own_peer_id: None,
+            own_team_id: TeamId::default(),
+            teams: &world.teams,
+            players: &world.players,
+            tournaments: &world.tournaments,
+            games: &world.games,
+        };
+
+        let ack = NetworkData::Challenge {
+            timestamp: Tick::now(),
+            challenge: challenge.clone(),
+        };
+        assert!(ack.check_signer(proposer_peer_id, &context).is_ok());
+        // The acknowledgement is only sent by the proposer.
+        assert!(ack.check_signer(target_peer_id, &context).is_err());
+
+        let network_game = NetworkData::Game {
+            timestamp: Tick::now(),
+            game: game.clone(),
+        };
+        assert!(network_game
+            .check_signer(proposer_peer_id, &context)
+            .is_ok());
+        assert!(network_game.check_signer(target_peer_id, &context).is_ok());
+
+        // Neither participant can change the team of the other one.
+        let mut forged_team = challenge.away_team_in_game.clone();
+        for player in forged_team.players.values_mut() {
+            player.athletics.quickness = MAX_SKILL;
+        }
+        challenge.away_team_in_game = forged_team.clone();
+        let ack = NetworkData::Challenge {
+            timestamp: Tick::now(),
+            challenge,
+        };
+        assert!(ack.check_signer(proposer_peer_id, &context).is_err());
+
+        let network_game = NetworkData::Game {
+            timestamp: Tick::now(),
+            game: NetworkGame {
+                away_team_in_game: forged_team,
+                ..game
+            },
+        };
+        assert!(network_game
+            .check_signer(proposer_peer_id, &context)
+            .is_err());
+        assert!(network_game.check_signer(target_peer_id, &context).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_only_seeds_are_trusted_as_relayers() {
+        let mut handler = NetworkHandler::test_default();
+        handler.seed_addresses = vec![SEED_ADDRESS.parse().expect("Invalid test address")];
+        let seed_peer_id = Keypair::generate_ed25519().public().to_peer_id();
+        let peer_id = Keypair::generate_ed25519().public().to_peer_id();
+
+        // Peers advertising the relay protocol are not trusted just for that.
+        assert!(!handler.add_relay_peer_if_seed(peer_id, &dialed("/ip4/10.0.0.2/tcp/37000")));
+        assert!(!handler.add_relay_peer_if_seed(
+            peer_id,
+            &ConnectedPoint::Listener {
+                local_addr: SEED_ADDRESS.parse().expect("Invalid test address"),
+                send_back_addr: "/ip4/10.0.0.2/tcp/37000"
+                    .parse()
+                    .expect("Invalid test address"),
+            }
+        ));
+        assert!(!handler
+            .signer_context(&World::new(None))
+            .relay_peers
+            .contains(&peer_id));
+
+        assert!(handler.add_relay_peer_if_seed(
+            seed_peer_id,
+            &dialed(&format!("{SEED_ADDRESS}/p2p/{seed_peer_id}"))
+        ));
+        assert!(handler
+            .signer_context(&World::new(None))
+            .relay_peers
+            .contains(&seed_peer_id));
+    }
+
+    #[test]
+    fn test_chat_moderation() -> AppResult<()> {
+        let mut app = App::test_with_network_handler()?;
+        app.network_handler.seed_addresses = vec![SEED_ADDRESS.parse()?];
+        let topic = IdentTopic::new(TOPIC);
+        let spammer = Keypair::generate_ed25519();
+        let spammer_peer_id = spammer.public().to_peer_id();
+        let relayer = Keypair::generate_ed25519();
+        let relayer_peer_id = relayer.public().to_peer_id();
+
+        let signed_message = |network_data: NetworkData, keypair: &Keypair| {
+            Ok::<_, anyhow::Error>(NetworkCallback::HandleMessage {
+                message: Message {
+                    source: None,
+                    data: SignedNetworkData::sign(&network_data, keypair)?.to_bytes()?,
+                    sequence_number: None,
+                    topic: topic.clone().into(),
+                },
+            })
+        };
+        let chat_message = |index: usize| {
+            signed_message(
+                NetworkData::Message {
+                    timestamp: Tick::now() + index as Tick,
+                    from_peer_id: spammer_peer_id,
+                    author: "Spammer".to_string(),
+                    message: format!("Cheap scraps {index}"),
+                    channel: ChatChannel::Global,
+                },
+                &spammer,
+            )
+        };
+
+        // Incoming chat messages are rate limited per peer.
+        for index in 0..CHAT_RATE_LIMIT_MESSAGES {
+            assert!(chat_message(index)?.call(&mut app).is_ok());
+        }
+        assert!(chat_message(CHAT_RATE_LIMIT_MESSAGES)?
+            .call(&mut app)
+            .is_err());
+        assert_eq!(
+            app.world.network_store_data.chat_history.len(),
+            CHAT_RATE_LIMIT_MESSAGES
+        );
+
+        // Blocking a peer removes its history and drops its messages.
+        UiCallback::ToggleBlockPeer {
+            peer_id: spammer_peer_id,
+        }
+        .call(&mut app)?;
+        assert!(app.world.network_store_data.chat_history.is_empty());
+        assert!(chat_message(CHAT_RATE_LIMIT_MESSAGES + 1)?
+            .call(&mut app)
+            .is_ok());
+        assert!(app.world.network_store_data.chat_history.is_empty());
+
+        UiCallback::ToggleBlockPeer {
+            peer_id: spammer_peer_id,
+        }
+        .call(&mut app)?;
+        assert!(!app
+            .world
+            .network_store_data
+            .is_peer_blocked(&spammer_peer_id));
+
+        // Ban lists are only accepted from relayers, and applied when relayer bans are trusted.
+        let ban_list = signed_message(
+            NetworkData::BanList {
+                timestamp: Tick::now(),
+                banned_peer_ids: vec![spammer_peer_id],
+            },
+            &relayer,
+        )?;
+        assert!(ban_list.call(&mut app).is_err());
+        assert!(!app
+            .world
+            .network_store_data
+            .is_peer_blocked(&spammer_peer_id));
+
+        app.network_handler
+            .add_relay_peer_if_seed(relayer_peer_id, &dialed(SEED_ADDRESS));
+        ban_list.call(&mut app)?;
+        assert!(!app
+            .world
+            .network_store_data
+            .is_peer_blocked(&spammer_peer_id));
+
+        app.network_handler = NetworkHandler::test_default().with_relayer_bans();
+        app.network_handler.seed_addresses = vec![SEED_ADDRESS.parse()?];
+        assert!(ban_list.call(&mut app).is_err());
+        assert!(!app
+            .world
+            .network_store_data
+            .is_peer_blocked(&spammer_peer_id));
+
+        app.network_handler
+            .add_relay_peer_if_seed(relayer_peer_id, &dialed(SEED_ADDRESS));
+        ban_list.call(&mut app)?;
+        assert!(app
+            .world
+            .network_store_data
+            .is_peer_blocked(&spammer_peer_id));
+
+        Ok(())
+    }
 }
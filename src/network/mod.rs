@@ -1,7 +1,10 @@
 pub mod challenge;
 pub mod constants;
+pub mod direct_message;
 pub mod handler;
 pub mod network_callback;
 pub mod network_store_data;
+pub mod plausibility;
+pub mod protocol;
 pub mod trade;
 pub mod types;
@@ -1,16 +1,20 @@
 use super::challenge::Challenge;
+use super::direct_message::EncryptedDirectMessage;
 use super::handler::{sanitize_addr, NetworkHandler};
+use super::plausibility::check_network_data;
+use super::protocol::{compatibility, Compatibility, PROTOCOL_VERSION};
 use super::trade::Trade;
-use super::types::{NetworkData, NetworkGame, NetworkRequestState, NetworkTeam, SeedInfo};
-use crate::app_version;
+use super::types::{
+    NetworkData, NetworkGame, NetworkRequestState, NetworkTeam, SeedInfo, SignedNetworkData,
+};
 use crate::core::constants::NETWORK_GAME_START_DELAY;
 use crate::core::{Team, TournamentRegistrationState, World, MAX_AVG_TIREDNESS_PER_AUTO_GAME};
+use crate::game_engine::adjustment::GameAdjustment;
 use crate::game_engine::game::GameSummary;
 use crate::game_engine::types::TeamInGame;
 use crate::game_engine::{Tournament, TournamentId, TournamentState};
-use crate::network::types::{ChatHistoryEntry, TournamentRequestState};
-use crate::store::deserialize;
-use crate::types::{AppResult, HashMapWithResult, PlayerMap, SystemTimeTick, TeamId, Tick};
+use crate::network::types::{ChatChannel, ChatHistoryEntry, TournamentRequestState};
+use crate::types::{AppResult, GameId, HashMapWithResult, PlayerMap, SystemTimeTick, TeamId, Tick};
 use crate::ui::{PopupMessage, UiScreen};
 use crate::{app::App, types::AppCallback};
 use anyhow::anyhow;
@@ -20,6 +24,7 @@ use libp2p::{gossipsub::Message, Multiaddr, PeerId};
 use rand::seq::SliceRandom;
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
+use std::collections::HashSet;
 
 #[derive(Debug, Clone)]
 pub enum NetworkCallback {
@@ -51,11 +56,22 @@ pub enum NetworkCallback {
     HandleMessage {
         message: Message,
     },
+    HandleDirectMessage {
+        peer_id: PeerId,
+        network_data: Box<NetworkData>,
+    },
+    HandleFailedDirectRequest {
+        peer_id: PeerId,
+        network_data: Box<NetworkData>,
+        error: String,
+    },
     PeerIdentified {
         peer_id: PeerId,
         listen_addrs: Vec<Multiaddr>,
+        protocol_version: Option<u32>,
     },
 }
+
 impl NetworkCallback {
     fn bind_address(address: Multiaddr) -> AppCallback {
         Box::new(move |app: &mut App| {
@@ -104,8 +120,7 @@ impl NetworkCallback {
 
     fn handle_sync_request() -> AppCallback {
         Box::new(move |app: &mut App| {
-            app.network_handler
-                .send_seed_info(app.world.network_store_data.to_broadcast_snapshot())?;
+            // Seed info is only accepted from relayers, peers resend their own data.
             app.world.dirty_network = true;
             Ok(None)
         })
@@ -186,21 +201,148 @@ impl NetworkCallback {
         timestamp: Tick,
         author: String,
         message: String,
+        channel: ChatChannel,
     ) -> AppCallback {
         Box::new(move |app: &mut App| {
+            if app.world.network_store_data.is_peer_blocked(&peer_id) {
+                return Ok(None);
+            }
+            app.network_handler.check_chat_rate_limit(peer_id)?;
+
             app.ui
-                .push_chat_event(timestamp, peer_id, author.clone(), message.clone());
+                .push_chat_event(timestamp, peer_id, author.clone(), message.clone(), channel);
             let entry = ChatHistoryEntry {
                 timestamp,
                 from_peer_id: peer_id,
                 author: author.clone(),
                 message: message.clone(),
+                channel,
             };
             app.world.network_store_data.chat_history.insert(entry);
             Ok(None)
         })
     }
 
+    fn handle_direct_message_topic(message: EncryptedDirectMessage) -> AppCallback {
+        Box::new(move |app: &mut App| {
+            // Direct messages between other crews are only kept by relayers.
+            if message.to_peer_id != *app.network_handler.own_peer_id()
+                || app
+                    .world
+                    .network_store_data
+                    .is_peer_blocked(&message.from_peer_id)
+            {
+                return Ok(None);
+            }
+
+            let entry = app.network_handler.decrypt_direct_message(&message)?;
+            // Relayers may deliver a message again, it is only shown once.
+            if app
+                .world
+                .network_store_data
+                .add_direct_message(message.from_peer_id, entry)
+            {
+                app.ui.push_direct_message(message.from_peer_id);
+            }
+            Ok(None)
+        })
+    }
+
+    // Challenges and trades that could not reach the peer are dropped.
+    fn handle_failed_direct_request(
+        peer_id: PeerId,
+        network_data: NetworkData,
+        error: String,
+    ) -> AppCallback {
+        Box::new(move |app: &mut App| {
+            // Direct messages have already been published over gossip.
+            if matches!(network_data, NetworkData::DirectMessage { .. }) {
+                app.ui.push_log_event(
+                    Tick::now(),
+                    Some(peer_id),
+                    format!("Direct message sent over gossip, the crew is not reachable: {error}"),
+                    log::Level::Info,
+                );
+                return Ok(None);
+            }
+
+            let own_team = app.world.get_own_team_mut()?;
+            let description = match &network_data {
+                NetworkData::Challenge { challenge, .. } => {
+                    own_team.remove_challenge(
+                        challenge.home_team_in_game.team_id,
+                        challenge.away_team_in_game.team_id,
+                    );
+                    let other_team = if challenge.home_team_in_game.team_id == own_team.id {
+                        &challenge.away_team_in_game
+                    } else {
+                        &challenge.home_team_in_game
+                    };
+                    format!("Challenge with {}", other_team.name)
+                }
+                NetworkData::Trade { trade, .. } => {
+                    own_team.remove_trade(trade.proposer_player.id, trade.target_player.id);
+                    format!(
+                        "Trade of {} for {}",
+                        trade.proposer_player.info.short_name(),
+                        trade.target_player.info.short_name()
+                    )
+                }
+                _ => return Err(anyhow!("Unexpected direct request to {peer_id}")),
+            };
+            app.world.dirty = true;
+            app.world.dirty_ui = true;
+
+            app.ui.push_log_event(
+                Tick::now(),
+                Some(peer_id),
+                format!("{description} could not be delivered: {error}"),
+                log::Level::Warn,
+            );
+            Ok(Some(format!(
+                "{description} could not be delivered, the other crew is not reachable."
+            )))
+        })
+    }
+
+    fn handle_ban_list_topic(peer_id: Option<PeerId>, banned_peer_ids: Vec<PeerId>) -> AppCallback {
+        Box::new(move |app: &mut App| {
+            // Ban lists are opt-in, and only accepted from relayers.
+            let Some(relayer_peer_id) =
+                peer_id.filter(|peer_id| app.network_handler.is_trusted_relayer(peer_id))
+            else {
+                return Ok(None);
+            };
+
+            let own_peer_id = *app.network_handler.own_peer_id();
+            let ban_list: HashSet<PeerId> = banned_peer_ids
+                .iter()
+                .filter(|&&peer_id| peer_id != own_peer_id)
+                .copied()
+                .collect();
+
+            let data = &mut app.world.network_store_data;
+            if data.relayer_ban_lists.get(&relayer_peer_id) == Some(&ban_list) {
+                return Ok(None);
+            }
+
+            data.chat_history
+                .retain(|entry| !ban_list.contains(&entry.from_peer_id));
+            for peer_id in ban_list.iter() {
+                app.ui.remove_chat_events_from(peer_id);
+            }
+            app.ui.push_log_event(
+                Tick::now(),
+                Some(relayer_peer_id),
+                format!("Relayer ban list updated: {} peers banned", ban_list.len()),
+                log::Level::Info,
+            );
+            data.relayer_ban_lists.insert(relayer_peer_id, ban_list);
+
+            Ok(None)
+        })
+    }
+
     fn handle_relayer_message_to_team_topic(
         timestamp: Tick,
         message: String,
@@ -307,7 +449,7 @@ impl NetworkCallback {
             // ---- Organizer: handle incoming requests from other teams ----
             if tournament.organizer_id == app.world.own_team_id {
                 assert!(team_id != app.world.own_team_id);
-                let timestamp = Tick::now();
+                let timestamp = app.world.clock.now();
                 match tournament.state(timestamp) {
                     TournamentState::Registration => {
                         if request_state != TournamentRequestState::RegistrationRequest {
@@ -344,7 +486,7 @@ impl NetworkCallback {
                             return Ok(None);
                         };
 
-                        match tournament.register_team(team, players.clone(), Tick::now()) {
+                        match tournament.register_team(team, players.clone(), timestamp) {
                             Ok(()) => {
                                 send_and_log(
                                     &mut app.network_handler,
@@ -452,7 +594,7 @@ impl NetworkCallback {
                     .get_mut(&app.world.own_team_id)
                     .expect("Own team should exist");
 
-                let timestamp = Tick::now();
+                let timestamp = app.world.clock.now();
 
                 if let TournamentRequestState::Cancellation { reason } = &request_state {
                     own_team.tournament_registration_state = TournamentRegistrationState::None;
@@ -714,6 +856,25 @@ impl NetworkCallback {
         })
     }
 
+    fn handle_game_adjustment_topic(
+        peer_id: Option<PeerId>,
+        timestamp: Tick,
+        game_id: GameId,
+        adjustment: GameAdjustment,
+    ) -> AppCallback {
+        Box::new(move |app: &mut App| {
+            app.ui.push_log_event(
+                timestamp,
+                peer_id,
+                format!("Deserialized adjustment for game {game_id}"),
+                log::Level::Debug,
+            );
+            app.world
+                .add_network_game_adjustment(peer_id, game_id, adjustment.clone())?;
+            Ok(None)
+        })
+    }
+
     fn handle_seed_topic(
         peer_id: Option<PeerId>,
         timestamp: Tick,
@@ -1013,17 +1174,6 @@ impl NetworkCallback {
                         return Err(anyhow!("Team is not challenge receiver"));
                     }
 
-                    let [own_major_version, own_minor_version, own_patch_version] = app_version();
-                    let [challenge_major_version, challenge_minor_version, challenge_patch_version] =
-                        challenge.app_version;
-                    if challenge_major_version != own_major_version
-                        || challenge_minor_version != own_minor_version
-                    {
-                        return Err(anyhow!(
-                            "App versions do not match: Proposer version {challenge_major_version}.{challenge_minor_version}.{challenge_patch_version} - Target version {own_major_version}.{own_minor_version}.{own_patch_version}"
-                        ));
-                    }
-
                     let own_team = app.world.get_own_team()?;
                     let average_tiredness = own_team.average_tiredness(&app.world);
 
@@ -1067,17 +1217,6 @@ impl NetworkCallback {
                         return Err(anyhow!("Invalid challenge: team is not challenge sender"));
                     }
 
-                    let [own_major_version, own_minor_version, own_patch_version] = app_version();
-                    let [challenge_major_version, challenge_minor_version, challenge_patch_version] =
-                        challenge.app_version;
-                    if challenge_major_version != own_major_version
-                        || challenge_minor_version != own_minor_version
-                    {
-                        return Err(anyhow!(
-                            "App versions do not match: Proposer version {challenge_major_version}.{challenge_minor_version}.{challenge_patch_version} - Target version {own_major_version}.{own_minor_version}.{own_patch_version}"
-                        ));
-                    }
-
                     let mut handle_syn_ack = || -> AppResult<()> {
                         let mut home_team_in_game = TeamInGame::from_team_id(
                             &app.world.own_team_id,
@@ -1246,6 +1385,103 @@ impl NetworkCallback {
         })
     }
 
+    fn handle_network_data(
+        app: &mut App,
+        peer_id: PeerId,
+        network_data: NetworkData,
+    ) -> AppResult<Option<String>> {
+        if network_data.requires_game_compatibility() {
+            if let Err(err) = app
+                .network_handler
+                .check_game_compatibility(&peer_id)
+                .and_then(|_| network_data.check_protocol_version())
+            {
+                // Let the proposer know why the request was refused.
+                match network_data {
+                    NetworkData::Challenge { mut challenge, .. }
+                        if challenge.state == NetworkRequestState::Syn =>
+                    {
+                        challenge.state = NetworkRequestState::Failed {
+                            error_message: err.to_string(),
+                        };
+                        app.network_handler.send_challenge(challenge)?;
+                    }
+                    NetworkData::Trade { mut trade, .. }
+                        if trade.state == NetworkRequestState::Syn =>
+                    {
+                        trade.state = NetworkRequestState::Failed {
+                            error_message: err.to_string(),
+                        };
+                        app.network_handler.send_trade(trade)?;
+                    }
+                    _ => {}
+                }
+                return Err(err);
+            }
+        }
+
+        let peer_id = Some(peer_id);
+
+        match network_data {
+            NetworkData::Team { timestamp, team } => {
+                Self::handle_team_topic(peer_id, timestamp, team)(app)
+            }
+            NetworkData::Message {
+                timestamp,
+                from_peer_id,
+                author,
+                message,
+                channel,
+            } => Self::handle_message_topic(from_peer_id, timestamp, author, message, channel)(app),
+            NetworkData::Challenge {
+                timestamp,
+                challenge,
+            } => Self::handle_challenge_topic(peer_id, timestamp, challenge)(app),
+            NetworkData::Trade { timestamp, trade } => {
+                Self::handle_trade_topic(peer_id, timestamp, trade)(app)
+            }
+            NetworkData::Game { timestamp, game } => {
+                Self::handle_game_topic(peer_id, timestamp, game)(app)
+            }
+            NetworkData::SeedInfo {
+                timestamp,
+                seed_info,
+            } => Self::handle_seed_topic(peer_id, timestamp, seed_info)(app),
+            NetworkData::RelayerMessageToTeam {
+                timestamp,
+                message,
+                team_id,
+            } => Self::handle_relayer_message_to_team_topic(timestamp, message, team_id)(app),
+            NetworkData::SyncRequest => Self::handle_sync_request()(app),
+            NetworkData::TournamentRegistrationRequest {
+                tournament_id,
+                team_id,
+                team_data,
+                request_state,
+                ..
+            } => Self::handle_tournament_registration_request_topic(
+                tournament_id,
+                team_id,
+                team_data,
+                request_state,
+            )(app),
+            NetworkData::Tournament { tournament, .. } => {
+                Self::handle_tournament_topic(tournament)(app)
+            }
+            NetworkData::GameAdjustment {
+                timestamp,
+                game_id,
+                adjustment,
+            } => Self::handle_game_adjustment_topic(peer_id, timestamp, game_id, adjustment)(app),
+            NetworkData::DirectMessage { message, .. } => {
+                Self::handle_direct_message_topic(message)(app)
+            }
+            NetworkData::BanList {
+                banned_peer_ids, ..
+            } => Self::handle_ban_list_topic(peer_id, banned_peer_ids)(app),
+        }
+    }
+
     pub fn call(&self, app: &mut App) -> AppResult<Option<String>> {
         match self {
             Self::PushSwarmPanelLog {
@@ -1279,62 +1515,67 @@ impl NetworkCallback {
                 Ok(None)
             }
             Self::HandleMessage { message } => {
-                let peer_id = message.source;
-
-                let network_data = deserialize::<NetworkData>(&message.data)?;
-                match network_data {
-                    NetworkData::Team { timestamp, team } => {
-                        Self::handle_team_topic(peer_id, timestamp, team)(app)
-                    }
-                    NetworkData::Message {
-                        timestamp,
-                        from_peer_id,
-                        author,
-                        message,
-                    } => Self::handle_message_topic(from_peer_id, timestamp, author, message)(app),
-                    NetworkData::Challenge {
-                        timestamp,
-                        challenge,
-                    } => Self::handle_challenge_topic(peer_id, timestamp, challenge)(app),
-                    NetworkData::Trade { timestamp, trade } => {
-                        Self::handle_trade_topic(peer_id, timestamp, trade)(app)
-                    }
-                    NetworkData::Game { timestamp, game } => {
-                        Self::handle_game_topic(peer_id, timestamp, game)(app)
-                    }
-                    NetworkData::SeedInfo {
-                        timestamp,
-                        seed_info,
-                    } => Self::handle_seed_topic(peer_id, timestamp, seed_info)(app),
-                    NetworkData::RelayerMessageToTeam {
-                        timestamp,
-                        message,
-                        team_id,
-                    } => {
-                        Self::handle_relayer_message_to_team_topic(timestamp, message, team_id)(app)
-                    }
-                    NetworkData::SyncRequest => Self::handle_sync_request()(app),
-                    NetworkData::TournamentRegistrationRequest {
-                        tournament_id,
-                        team_id,
-                        team_data,
-                        request_state,
-                        ..
-                    } => Self::handle_tournament_registration_request_topic(
-                        tournament_id,
-                        team_id,
-                        team_data,
-                        request_state,
-                    )(app),
-                    NetworkData::Tournament { tournament, .. } => {
-                        Self::handle_tournament_topic(tournament)(app)
-                    }
+                let (signer, network_data) = SignedNetworkData::from_bytes(&message.data)?
+                    .verify(&app.network_handler.signer_context(&app.world))?;
+                if message.source.is_some_and(|source| source != signer) {
+                    return Err(anyhow!(
+                        "Network data signed by {signer} was published by another peer"
+                    ));
                 }
+                check_network_data(&network_data)?;
+                Self::handle_network_data(app, signer, network_data)
             }
+            Self::HandleDirectMessage {
+                peer_id,
+                network_data,
+            } => {
+                if !matches!(
+                    network_data.as_ref(),
+                    NetworkData::Challenge { .. }
+                        | NetworkData::Trade { .. }
+                        | NetworkData::DirectMessage { .. }
+                ) {
+                    return Err(anyhow!("Unexpected direct network data from {peer_id}"));
+                }
+                // Direct connections are authenticated, so the peer_id is the sender.
+                network_data
+                    .check_signer(*peer_id, &app.network_handler.signer_context(&app.world))?;
+                check_network_data(network_data)?;
+                Self::handle_network_data(app, *peer_id, network_data.as_ref().clone())
+            }
+            Self::HandleFailedDirectRequest {
+                peer_id,
+                network_data,
+                error,
+            } => Self::handle_failed_direct_request(
+                *peer_id,
+                network_data.as_ref().clone(),
+                error.clone(),
+            )(app),
             Self::PeerIdentified {
                 peer_id,
                 listen_addrs,
+                protocol_version,
             } => {
+                if let Some(protocol_version) = protocol_version {
+                    app.network_handler
+                        .set_peer_protocol_version(*peer_id, *protocol_version);
+                    let compatibility = compatibility(PROTOCOL_VERSION, *protocol_version);
+                    app.ui.push_log_event(
+                        Tick::now(),
+                        Some(*peer_id),
+                        format!(
+                            "Peer uses network protocol v{protocol_version}, {}",
+                            compatibility.as_str()
+                        ),
+                        if compatibility == Compatibility::Full {
+                            log::Level::Debug
+                        } else {
+                            log::Level::Warn
+                        },
+                    );
+                }
+
                 if *peer_id != *app.network_handler.own_peer_id() {
                     if let Some(addr) = listen_addrs.iter().next() {
                         app.world
@@ -1,5 +1,6 @@
 use crate::{
-    core::DAYS,
+    core::{utils::is_default, DAYS},
+    network::direct_message::EncryptedDirectMessage,
     network::types::{ChatHistoryEntry, NetworkTeam, PlayerRanking, TeamRanking},
     types::*,
 };
@@ -14,6 +15,8 @@ const TOP_PLAYER_RANKING_LENGTH: usize = 20;
 const TOP_TEAM_RANKING_LENGTH: usize = 10;
 const RANDOM_PEER_ADDRESSES_LENGTH: usize = 10;
 const MAX_CHAT_HISTORY: usize = 200;
+const MAX_DIRECT_MESSAGES_PER_CONVERSATION: usize = 500;
+const MAX_PENDING_DIRECT_MESSAGES_PER_PEER: usize = 50;
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 pub struct NetworkStoreData {
@@ -23,6 +26,22 @@ pub struct NetworkStoreData {
     pub peer_addresses: HashMap<PeerId, Multiaddr>,
     pub peer_last_connection: HashMap<PeerId, Tick>,
     pub chat_history: HashSet<ChatHistoryEntry>,
+    // Decrypted direct messages, by the other peer of the conversation.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub direct_messages: HashMap<PeerId, HashSet<ChatHistoryEntry>>,
+    // Encrypted direct messages kept by relayers until the recipient comes online.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub pending_direct_messages: HashMap<PeerId, Vec<EncryptedDirectMessage>>,
+    // Peers whose chat and direct messages are dropped.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub blocked_peers: HashSet<PeerId>,
+    // Ban lists received from trusted relayers, by relayer.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub relayer_ban_lists: HashMap<PeerId, HashSet<PeerId>>,
 }
 
 impl NetworkStoreData {
@@ -50,7 +69,9 @@ impl NetworkStoreData {
         }
 
         for entry in other.chat_history.iter() {
-            self.chat_history.insert(entry.clone());
+            if !self.is_peer_blocked(&entry.from_peer_id) {
+                self.chat_history.insert(entry.clone());
+            }
         }
 
         // Peers are not updated here, as they are only added if the connection is succesfull
@@ -82,6 +103,25 @@ impl NetworkStoreData {
             .peer_last_connection
             .retain(|_, timestamp| now.saturating_sub(*timestamp) <= PEERS_RETENTION_DURATION);
 
+        for conversation in self_store.direct_messages.values_mut() {
+            if conversation.len() > MAX_DIRECT_MESSAGES_PER_CONVERSATION {
+                *conversation = conversation
+                    .drain()
+                    .sorted_by_key(|e| e.timestamp)
+                    .rev()
+                    .take(MAX_DIRECT_MESSAGES_PER_CONVERSATION)
+                    .collect();
+            }
+        }
+
+        for messages in self_store.pending_direct_messages.values_mut() {
+            messages
+                .retain(|message| now.saturating_sub(message.timestamp) <= CHAT_RETENTION_DURATION);
+        }
+        self_store
+            .pending_direct_messages
+            .retain(|_, messages| !messages.is_empty());
+
         self_store
     }
 
@@ -152,19 +192,85 @@ impl NetworkStoreData {
     }
 
     pub fn get_top_team_ranking(&self) -> Vec<(TeamId, TeamRanking)> {
+        let now = Tick::now();
         self.team_ranking
             .iter()
             .sorted_by(|(_, a), (_, b)| {
                 b.team
-                    .reputation
-                    .partial_cmp(&a.team.reputation)
-                    .expect("Reputation should exist")
+                    .network_game_rating
+                    .conservative_rating(now)
+                    .total_cmp(&a.team.network_game_rating.conservative_rating(now))
+                    .then(b.team.reputation.total_cmp(&a.team.reputation))
             })
             .take(TOP_TEAM_RANKING_LENGTH)
             .map(|(id, ranking)| (*id, ranking.clone()))
             .collect()
     }
 
+    pub fn is_peer_blocked(&self, peer_id: &PeerId) -> bool {
+        self.blocked_peers.contains(peer_id)
+            || self
+                .relayer_ban_lists
+                .values()
+                .any(|ban_list| ban_list.contains(peer_id))
+    }
+
+    // Returns true if the peer is now blocked, false if it was unblocked.
+    pub fn toggle_blocked_peer(&mut self, peer_id: PeerId) -> bool {
+        if self.blocked_peers.remove(&peer_id) {
+            return false;
+        }
+
+        self.blocked_peers.insert(peer_id);
+        self.chat_history
+            .retain(|entry| entry.from_peer_id != peer_id);
+        true
+    }
+
+    // Returns true if the message was not already in the conversation.
+    pub fn add_direct_message(&mut self, peer_id: PeerId, entry: ChatHistoryEntry) -> bool {
+        self.direct_messages
+            .entry(peer_id)
+            .or_default()
+            .insert(entry)
+    }
+
+    pub fn get_conversation(&self, peer_id: &PeerId) -> Vec<ChatHistoryEntry> {
+        self.direct_messages
+            .get(peer_id)
+            .map(|conversation| {
+                conversation
+                    .iter()
+                    .sorted_by_key(|e| (e.timestamp, e.from_peer_id))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn push_pending_direct_message(&mut self, message: EncryptedDirectMessage) {
+        let messages = self
+            .pending_direct_messages
+            .entry(message.to_peer_id)
+            .or_default();
+        if messages.contains(&message) {
+            return;
+        }
+        messages.push(message);
+        if messages.len() > MAX_PENDING_DIRECT_MESSAGES_PER_PEER {
+            messages.remove(0);
+        }
+    }
+
+    pub fn take_pending_direct_messages(
+        &mut self,
+        peer_id: &PeerId,
+    ) -> Vec<EncryptedDirectMessage> {
+        self.pending_direct_messages
+            .remove(peer_id)
+            .unwrap_or_default()
+    }
+
     pub fn get_recent_chat_history(&self) -> Vec<ChatHistoryEntry> {
         self.chat_history
             .iter()
@@ -0,0 +1,194 @@
+use super::direct_message::MAX_DIRECT_MESSAGE_LENGTH;
+use super::types::NetworkData;
+use crate::{
+    core::{
+        player::Player,
+        skill::{MAX_SKILL, MIN_SKILL},
+        team::Team,
+    },
+    game_engine::types::TeamInGame,
+    types::{AppResult, PlayerMap},
+};
+use anyhow::anyhow;
+
+// Checks on data received over the network, so that tampered teams and players
+// are rejected before they are stored in the world.
+
+const MAX_BAN_LIST_LENGTH: usize = 10_000;
+
+fn check_skill(name: &str, value: f32) -> AppResult<()> {
+    if !value.is_finite() || !(MIN_SKILL..=MAX_SKILL).contains(&value) {
+        return Err(anyhow!("Implausible {name} value {value}"));
+    }
+    Ok(())
+}
+
+pub fn check_player(player: &Player) -> AppResult<()> {
+    for skill in player.current_skill_array() {
+        check_skill("skill", skill)?;
+    }
+    for skill in player.skills_training.iter() {
+        check_skill("skill training", *skill)?;
+    }
+    check_skill("reputation", player.reputation)?;
+    check_skill("potential", player.potential)?;
+    check_skill("tiredness", player.tiredness)?;
+    check_skill("morale", player.morale)?;
+
+    Ok(())
+}
+
+fn check_team_in_game(team: &TeamInGame) -> AppResult<()> {
+    for player in team.players.values() {
+        check_player(player)?;
+    }
+    Ok(())
+}
+
+pub fn check_team(team: &Team, players: &PlayerMap) -> AppResult<()> {
+    let peer_id = team
+        .peer_id
+        .ok_or_else(|| anyhow!("Team {} has no peer_id", team.id))?;
+
+    if team.player_ids.len() != players.len()
+        || team.player_ids.iter().any(|id| !players.contains_key(id))
+    {
+        return Err(anyhow!("Team {} players do not match its roster", team.id));
+    }
+    if team.player_ids.len() > team.spaceship.crew_capacity() as usize {
+        return Err(anyhow!("Team {} exceeds its crew capacity", team.id));
+    }
+
+    for player in players.values() {
+        if player.team != Some(team.id) {
+            return Err(anyhow!(
+                "Player {} is not part of team {}",
+                player.id,
+                team.id
+            ));
+        }
+        if player.peer_id != Some(peer_id) {
+            return Err(anyhow!("Player {} has the wrong peer_id", player.id));
+        }
+        check_player(player)?;
+    }
+
+    check_skill("reputation", team.reputation)?;
+
+    if team.fuel() > team.fuel_capacity() {
+        return Err(anyhow!("Team {} carries more fuel than its tank", team.id));
+    }
+    if team.used_storage_capacity() > team.storage_capacity() {
+        return Err(anyhow!("Team {} carries more than its storage", team.id));
+    }
+
+    Ok(())
+}
+
+pub fn check_network_data(data: &NetworkData) -> AppResult<()> {
+    match data {
+        NetworkData::Team { team, .. } => check_team(&team.team, &team.players),
+        NetworkData::Challenge { challenge, .. } => {
+            check_team_in_game(&challenge.home_team_in_game)?;
+            check_team_in_game(&challenge.away_team_in_game)
+        }
+        NetworkData::Trade { trade, .. } => {
+            check_player(&trade.proposer_player)?;
+            check_player(&trade.target_player)
+        }
+        NetworkData::Game { game, .. } => {
+            check_team_in_game(&game.home_team_in_game)?;
+            check_team_in_game(&game.away_team_in_game)
+        }
+        NetworkData::TournamentRegistrationRequest {
+            team_data: Some((team, players)),
+            ..
+        } => check_team(team, players),
+        NetworkData::Tournament { tournament, .. } => {
+            for team in tournament
+                .registered_teams
+                .values()
+                .chain(tournament.participants.values())
+            {
+                check_team_in_game(team)?;
+            }
+            Ok(())
+        }
+        NetworkData::DirectMessage { message, .. } => {
+            // Encryption adds the nonce, the tag and the serialization overhead.
+            if message.ciphertext.len() > 2 * MAX_DIRECT_MESSAGE_LENGTH {
+                return Err(anyhow!("Implausible direct message length"));
+            }
+            Ok(())
+        }
+        NetworkData::BanList {
+            banned_peer_ids, ..
+        } => {
+            if banned_peer_ids.len() > MAX_BAN_LIST_LENGTH {
+                return Err(anyhow!("Implausible ban list length"));
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+// Versions only grow, so an older version of known data is either stale or forged.
+pub fn check_version(name: &str, current: Option<u64>, received: u64) -> AppResult<()> {
+    match current {
+        Some(current) if received < current => Err(anyhow!(
+            "Received {name} version {received} older than current version {current}"
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_team, check_version};
+    use crate::{
+        core::{resources::Resource, world::World},
+        network::types::NetworkTeam,
+        types::AppResult,
+    };
+    use libp2p::PeerId;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_tampered_team_is_rejected() -> AppResult<()> {
+        let mut world = World::new(None);
+        let rng = &mut ChaCha8Rng::seed_from_u64(0);
+        let home_planet = *world.planets.keys().next().unwrap();
+        let team_id =
+            world.generate_random_team(rng, home_planet, "Test".into(), "Ship".into(), None)?;
+        let network_team = NetworkTeam::from_team_id(&world, &team_id, PeerId::random())?;
+        check_team(&network_team.team, &network_team.players)?;
+
+        let mut tampered = network_team.clone();
+        let player_id = tampered.team.player_ids[0];
+        tampered
+            .players
+            .get_mut(&player_id)
+            .unwrap()
+            .athletics
+            .quickness = 25.0;
+        assert!(check_team(&tampered.team, &tampered.players).is_err());
+
+        let mut tampered = network_team.clone();
+        tampered.players.get_mut(&player_id).unwrap().peer_id = Some(PeerId::random());
+        assert!(check_team(&tampered.team, &tampered.players).is_err());
+
+        let mut tampered = network_team.clone();
+        let capacity = tampered.team.spaceship.fuel_capacity();
+        tampered.team.resources.insert(Resource::FUEL, capacity + 1);
+        assert!(tampered.team.fuel() > capacity);
+        assert!(check_team(&tampered.team, &tampered.players).is_err());
+
+        assert!(check_version("team", Some(3), 2).is_err());
+        assert!(check_version("team", Some(3), 3).is_ok());
+        assert!(check_version("team", None, 0).is_ok());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::types::AppResult;
+use anyhow::anyhow;
+
+// Version of the network protocol, exchanged through identify. It must be bumped
+// once per release whenever peers would disagree on the data they exchange or on
+// how games are simulated.
+pub const PROTOCOL_VERSION: u32 = 2;
+const IDENTIFY_PROTOCOL_PREFIX: &str = "/rebels/";
+
+// For each protocol version: the oldest version it can exchange public data with
+// (teams, rankings, chat), and the oldest version it can play games, tournaments
+// and trades with.
+const COMPATIBILITY_MATRIX: [(u32, u32, u32); 2] = [
+    // Unsigned gossip payloads.
+    (1, 1, 1),
+    // This release: signed payloads, direct challenges and trades, encrypted direct
+    // messages, overtime, fouls, injuries and defensive schemes.
+    (2, 2, 2),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compatibility {
+    Full,
+    DataOnly,
+    Incompatible,
+}
+
+impl Compatibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Full => "compatible",
+            Self::DataOnly => "compatible for public data only",
+            Self::Incompatible => "incompatible",
+        }
+    }
+}
+
+pub fn identify_protocol_version() -> String {
+    format!("{IDENTIFY_PROTOCOL_PREFIX}{PROTOCOL_VERSION}")
+}
+
+// Older releases advertised "/rebels/1.0.0".
+pub fn parse_identify_protocol_version(protocol_version: &str) -> Option<u32> {
+    protocol_version
+        .strip_prefix(IDENTIFY_PROTOCOL_PREFIX)?
+        .split('.')
+        .next()?
+        .parse()
+        .ok()
+}
+
+// The matrix row of the newer version decides. A version newer than any we know
+// is assumed to share public data only, since games could be simulated differently.
+pub fn compatibility(version: u32, other_version: u32) -> Compatibility {
+    let older = version.min(other_version);
+    let newer = version.max(other_version);
+
+    let Some((_, min_data_version, min_game_version)) = COMPATIBILITY_MATRIX
+        .iter()
+        .find(|(matrix_version, _, _)| *matrix_version == newer)
+    else {
+        return if older == newer {
+            Compatibility::Full
+        } else {
+            Compatibility::DataOnly
+        };
+    };
+
+    if older >= *min_game_version {
+        Compatibility::Full
+    } else if older >= *min_data_version {
+        Compatibility::DataOnly
+    } else {
+        Compatibility::Incompatible
+    }
+}
+
+pub fn check_game_compatibility(other_version: u32) -> AppResult<()> {
+    let compatibility = compatibility(PROTOCOL_VERSION, other_version);
+    if compatibility != Compatibility::Full {
+        return Err(anyhow!(
+            "Peer network protocol v{other_version} is {} with ours (v{PROTOCOL_VERSION}): update to the same release to play together",
+            compatibility.as_str()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_game_compatibility, compatibility, identify_protocol_version,
+        parse_identify_protocol_version, Compatibility, COMPATIBILITY_MATRIX, PROTOCOL_VERSION,
+    };
+
+    #[test]
+    fn test_compatibility_matrix() {
+        assert_eq!(
+            COMPATIBILITY_MATRIX.last().map(|(version, _, _)| *version),
+            Some(PROTOCOL_VERSION)
+        );
+
+        assert_eq!(
+            parse_identify_protocol_version(&identify_protocol_version()),
+            Some(PROTOCOL_VERSION)
+        );
+        assert_eq!(parse_identify_protocol_version("/rebels/1.0.0"), Some(1));
+        assert_eq!(parse_identify_protocol_version("/ipfs/0.1.0"), None);
+
+        assert_eq!(compatibility(2, 2), Compatibility::Full);
+        assert_eq!(compatibility(2, 1), Compatibility::Incompatible);
+        assert_eq!(compatibility(1, 2), Compatibility::Incompatible);
+        assert_eq!(
+            compatibility(PROTOCOL_VERSION, PROTOCOL_VERSION + 1),
+            Compatibility::DataOnly
+        );
+
+        assert!(check_game_compatibility(PROTOCOL_VERSION).is_ok());
+        assert!(check_game_compatibility(1).is_err());
+    }
+}
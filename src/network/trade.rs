@@ -1,5 +1,6 @@
+use super::protocol::PROTOCOL_VERSION;
 use super::types::NetworkRequestState;
-use crate::core::{player::Player, skill::Rated};
+use crate::core::{player::Player, skill::Rated, utils::is_default};
 use libp2p::PeerId;
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +12,10 @@ pub struct Trade {
     pub proposer_player: Player,
     pub target_player: Player,
     pub extra_satoshis: i64,
+    // Missing for peers older than protocol versioning.
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub protocol_version: u32,
 }
 
 impl Trade {
@@ -28,6 +33,7 @@ impl Trade {
             proposer_player,
             target_player,
             extra_satoshis,
+            protocol_version: PROTOCOL_VERSION,
         }
     }
 
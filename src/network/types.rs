@@ -1,13 +1,16 @@
 use super::challenge::Challenge;
+use super::direct_message::EncryptedDirectMessage;
+use super::protocol::check_game_compatibility;
 use super::trade::Trade;
 use crate::core::planet::Planet;
-use crate::core::position::{GamePosition, MAX_GAME_POSITION};
 use crate::core::skill::Skill;
+use crate::core::utils::is_default;
+use crate::game_engine::adjustment::GameAdjustment;
 use crate::game_engine::timer::Timer;
-use crate::game_engine::types::GameStats;
 use crate::game_engine::{Tournament, TournamentId};
 use crate::network::network_store_data::NetworkStoreData;
-use crate::types::{HashMapWithResult, PlanetId, PlayerMap, Tick};
+use crate::store::{deserialize, serialize};
+use crate::types::{GameMap, HashMapWithResult, PlanetId, PlayerMap, TeamMap, Tick, TournamentMap};
 use crate::{
     core::{player::Player, team::Team, world::World},
     game_engine::types::TeamInGame,
@@ -15,9 +18,10 @@ use crate::{
 };
 use anyhow::anyhow;
 use itertools::Itertools;
+use libp2p::identity::{Keypair, PublicKey};
 use libp2p::PeerId;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use strum_macros::Display;
 
@@ -54,6 +58,9 @@ pub enum NetworkData {
         from_peer_id: PeerId,
         author: String,
         message: String,
+        #[serde(skip_serializing_if = "is_default")]
+        #[serde(default)]
+        channel: ChatChannel,
     },
     Game {
         timestamp: Tick,
@@ -80,8 +87,305 @@ pub enum NetworkData {
         timestamp: Tick,
         tournament: Tournament,
     },
+    GameAdjustment {
+        timestamp: Tick,
+        game_id: GameId,
+        adjustment: GameAdjustment,
+    },
+    DirectMessage {
+        timestamp: Tick,
+        message: EncryptedDirectMessage,
+    },
+    BanList {
+        timestamp: Tick,
+        banned_peer_ids: Vec<PeerId>,
+    },
+}
+
+// What the receiver knows about the peers allowed to sign data
+// that does not carry the peer_id of its owner.
+pub struct SignerContext<'a> {
+    pub relay_peers: &'a HashSet<PeerId>,
+    pub own_peer_id: Option<PeerId>,
+    pub own_team_id: TeamId,
+    pub teams: &'a TeamMap,
+    pub players: &'a PlayerMap,
+    pub tournaments: &'a TournamentMap,
+    pub games: &'a GameMap,
+}
+
+impl SignerContext<'_> {
+    // A team in game built by another peer than its owner must match the last team
+    // signed by the owner, so that the signer cannot forge its opponent.
+    fn is_last_signed_team(&self, team_in_game: &TeamInGame) -> bool {
+        let Some(team) = self.teams.get(&team_in_game.team_id) else {
+            return false;
+        };
+
+        // The own team is local, but other peers know it by our peer id.
+        let owner_peer_id = if team.id == self.own_team_id {
+            self.own_peer_id
+        } else {
+            team.peer_id
+        };
+        if owner_peer_id.is_none() || team_in_game.peer_id != owner_peer_id {
+            return false;
+        }
+
+        team_in_game.name == team.name
+            && team_in_game.players.values().all(|player| {
+                team.player_ids.contains(&player.id)
+                    && self.players.get(&player.id).is_some_and(|known| {
+                        known.current_skill_array() == player.current_skill_array()
+                    })
+            })
+    }
+}
+
+impl TournamentRequestState {
+    // The other states are sent by the registering team.
+    fn is_sent_by_organizer(&self) -> bool {
+        matches!(
+            self,
+            Self::RegistrationDeclined { .. }
+                | Self::RegistrationOk
+                | Self::ConfirmationRequest
+                | Self::ParticipationDeclined { .. }
+                | Self::ParticipationOk
+                | Self::Cancellation { .. }
+        )
+    }
+}
+
+impl NetworkData {
+    // Data can only be sent by the peer owning it: the team, the tournament organizer
+    // or a relayer. Data that cannot be attributed to its signer is rejected.
+    pub fn check_signer(&self, signer: PeerId, context: &SignerContext) -> AppResult<()> {
+        let is_valid = match self {
+            Self::Team { team, .. } => team.team.peer_id == Some(signer),
+            // Each step is sent by the peer that built it. The acknowledgement is
+            // sent by the proposer, but also carries the team of the target.
+            Self::Challenge { challenge, .. } => match challenge.state {
+                NetworkRequestState::Syn => {
+                    challenge.proposer_peer_id == signer
+                        && challenge.home_team_in_game.peer_id == Some(signer)
+                }
+                NetworkRequestState::SynAck => {
+                    challenge.target_peer_id == signer
+                        && challenge.away_team_in_game.peer_id == Some(signer)
+                }
+                NetworkRequestState::Ack => {
+                    challenge.proposer_peer_id == signer
+                        && challenge.home_team_in_game.peer_id == Some(signer)
+                        && context.is_last_signed_team(&challenge.away_team_in_game)
+                }
+                NetworkRequestState::Failed { .. } => {
+                    challenge.proposer_peer_id == signer || challenge.target_peer_id == signer
+                }
+            },
+            Self::Trade { trade, .. } => {
+                trade.proposer_peer_id == signer || trade.target_peer_id == signer
+            }
+            Self::Message { from_peer_id, .. } => *from_peer_id == signer,
+            Self::Game { game, .. } => {
+                (game.home_team_in_game.peer_id == Some(signer)
+                    && context.is_last_signed_team(&game.away_team_in_game))
+                    || (game.away_team_in_game.peer_id == Some(signer)
+                        && context.is_last_signed_team(&game.home_team_in_game))
+            }
+            Self::SeedInfo { .. } | Self::RelayerMessageToTeam { .. } | Self::BanList { .. } => {
+                context.relay_peers.contains(&signer)
+            }
+            // The request carries no data, anyone can ask relayers to sync.
+            Self::SyncRequest => true,
+            Self::TournamentRegistrationRequest {
+                tournament_id,
+                team_id,
+                team_data,
+                request_state,
+                ..
+            } => match team_data {
+                Some((team, _)) => team.id == *team_id && team.peer_id == Some(signer),
+                None => context
+                    .tournaments
+                    .get(tournament_id)
+                    .is_some_and(|tournament| {
+                        if request_state.is_sent_by_organizer() {
+                            tournament.organizer_peer_id == Some(signer)
+                        } else {
+                            tournament
+                                .registered_teams
+                                .get(team_id)
+                                .is_some_and(|team| team.peer_id == Some(signer))
+                        }
+                    }),
+            },
+            Self::Tournament { tournament, .. } => match context.tournaments.get(&tournament.id) {
+                // Known tournaments keep the organizer they were first received with.
+                // Participants resend initialized tournaments, so that games can be followed.
+                Some(known) => {
+                    tournament.organizer_id == known.organizer_id
+                        && tournament.organizer_peer_id == known.organizer_peer_id
+                        && (known.organizer_peer_id == Some(signer)
+                            || (tournament.is_initialized()
+                                && known
+                                    .participants
+                                    .values()
+                                    .any(|team| team.peer_id == Some(signer))))
+                }
+                // New tournaments are only accepted from the owner of the organizer team.
+                None => {
+                    tournament.organizer_peer_id == Some(signer)
+                        && context
+                            .teams
+                            .get(&tournament.organizer_id)
+                            .is_some_and(|team| team.peer_id == Some(signer))
+                }
+            },
+            Self::GameAdjustment {
+                game_id,
+                adjustment,
+                ..
+            } => context
+                .games
+                .get(game_id)
+                .and_then(|game| game.team_in_game(adjustment.team_id))
+                .is_some_and(|team| team.peer_id == Some(signer)),
+            // Direct messages are authenticated by their encryption,
+            // so relayers can forward them for offline peers.
+            Self::DirectMessage { message, .. } => {
+                message.from_peer_id == signer || context.relay_peers.contains(&signer)
+            }
+        };
+
+        if !is_valid {
+            return Err(anyhow!("Network data is not signed by its owner {signer}"));
+        }
+        Ok(())
+    }
+
+    // Challenges, trades and tournaments carry the protocol version of their sender,
+    // so that they can be checked even when the sender has not been identified.
+    pub fn check_protocol_version(&self) -> AppResult<()> {
+        let protocol_version = match self {
+            Self::Challenge { challenge, .. } => challenge.protocol_version,
+            Self::Trade { trade, .. } => trade.protocol_version,
+            Self::Tournament { tournament, .. } => tournament.protocol_version,
+            _ => return Ok(()),
+        };
+        check_game_compatibility(protocol_version)
+    }
+
+    // Data that only makes sense between peers simulating games the same way.
+    pub fn requires_game_compatibility(&self) -> bool {
+        matches!(
+            self,
+            Self::Challenge { .. }
+                | Self::Trade { .. }
+                | Self::Game { .. }
+                | Self::GameAdjustment { .. }
+                | Self::Tournament { .. }
+                | Self::TournamentRegistrationRequest { .. }
+        )
+    }
+
+    // Private negotiations are sent directly to the other peer instead of being broadcast.
+    pub fn direct_peer_id(&self, own_peer_id: PeerId) -> Option<PeerId> {
+        let (proposer_peer_id, target_peer_id) = match self {
+            Self::Challenge { challenge, .. } => {
+                (challenge.proposer_peer_id, challenge.target_peer_id)
+            }
+            Self::Trade { trade, .. } => (trade.proposer_peer_id, trade.target_peer_id),
+            Self::DirectMessage { message, .. } => return Some(message.to_peer_id),
+            _ => return None,
+        };
+
+        if proposer_peer_id == own_peer_id {
+            Some(target_peer_id)
+        } else {
+            Some(proposer_peer_id)
+        }
+    }
+}
+
+// Serialized NetworkData signed with the sender keypair, so that data relayed
+// by other peers cannot be forged in the name of another peer.
+// Encoded as [public key length][public key][signature length][signature][payload].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedNetworkData {
+    public_key: Vec<u8>,
+    signature: Vec<u8>,
+    payload: Vec<u8>,
+}
+
+impl SignedNetworkData {
+    pub fn sign(data: &NetworkData, keypair: &Keypair) -> AppResult<Self> {
+        let payload = serialize(data)?;
+        Ok(Self {
+            public_key: keypair.public().encode_protobuf(),
+            signature: keypair.sign(&payload)?,
+            payload,
+        })
+    }
+
+    // Returns the data together with the peer_id of the signer.
+    pub fn verify(&self, context: &SignerContext) -> AppResult<(PeerId, NetworkData)> {
+        let (signer, data) = self.verify_signature()?;
+        data.check_signer(signer, context)?;
+        Ok((signer, data))
+    }
+
+    // Only checks the signature, without checking that the signer owns the data.
+    pub fn verify_signature(&self) -> AppResult<(PeerId, NetworkData)> {
+        let public_key = PublicKey::try_decode_protobuf(&self.public_key)?;
+        if !public_key.verify(&self.payload, &self.signature) {
+            return Err(anyhow!("Invalid network data signature"));
+        }
+
+        let signer = public_key.to_peer_id();
+        let data = deserialize::<NetworkData>(&self.payload)?;
+        Ok((signer, data))
+    }
+
+    pub fn to_bytes(&self) -> AppResult<Vec<u8>> {
+        let mut bytes = vec![];
+        for field in [&self.public_key, &self.signature] {
+            let len = u16::try_from(field.len())?;
+            bytes.extend_from_slice(&len.to_be_bytes());
+            bytes.extend_from_slice(field);
+        }
+        bytes.extend_from_slice(&self.payload);
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> AppResult<Self> {
+        let mut rest = bytes;
+        let mut fields = vec![];
+        for _ in 0..2 {
+            if rest.len() < 2 {
+                return Err(anyhow!("Truncated signed network data"));
+            }
+            let len = u16::from_be_bytes([rest[0], rest[1]]) as usize;
+            if rest.len() < 2 + len {
+                return Err(anyhow!("Truncated signed network data"));
+            }
+            fields.push(rest[2..2 + len].to_vec());
+            rest = &rest[2 + len..];
+        }
+
+        Ok(Self {
+            signature: fields.pop().expect("Signature should be there"),
+            public_key: fields.pop().expect("Public key should be there"),
+            payload: rest.to_vec(),
+        })
+    }
 }
 
+// Response to a direct request, only acknowledging the delivery.
+// Replies to challenges and trades travel as new direct requests.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct DirectAck;
+
 #[derive(Debug, Clone, Display, Default, Serialize, Deserialize, PartialEq, Hash)]
 pub enum NetworkRequestState {
     #[default]
@@ -126,17 +430,19 @@ impl NetworkTeam {
             })
             .collect_vec();
 
-            // Set the peer_id on players and convert the Vec<&Player> into a PlayerMap
+        // Set the peer_id on players and convert the Vec<&Player> into a PlayerMap
         let mut team_players = PlayerMap::new();
         for player in players.iter_mut() {
             let mut team_player = player.clone();
             team_player.peer_id = Some(peer_id);
+            // Career logs are kept locally to keep network messages small.
+            team_player.career.clear();
             team_players.insert(player.id, team_player);
         }
 
         // Set the peer_id for team we are sending out
         // This means that the team can be challenged online and it will not be stored.
-        team.peer_id = Some(peer_id);        
+        team.peer_id = Some(peer_id);
 
         Ok(Self::new(team, team_players, asteroids))
     }
@@ -152,57 +458,31 @@ pub struct NetworkGame {
     pub starting_at: Tick,
     pub timer: Timer,
     pub part_of_tournament: Option<TournamentId>,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub adjustments: Vec<GameAdjustment>,
 }
 
 impl NetworkGame {
     pub fn from_game_id(world: &World, game_id: &GameId) -> AppResult<Self> {
         let game = world.games.get_or_err(game_id)?.clone();
 
-        let mut home_team_in_game = game.home_team_in_game.clone();
-        // Reset stats
-        let mut stats = HashMap::new();
-        for (idx, player_id) in home_team_in_game.initial_positions.iter().enumerate() {
-            // Set position in stats to initial one
-            let mut player_stats = GameStats::default();
-            if (idx as GamePosition) < MAX_GAME_POSITION {
-                player_stats.position = Some(idx as GamePosition);
-            }
-            stats.insert(*player_id, player_stats.clone());
-
-            let player = home_team_in_game
-                .players
-                .get_mut(player_id)
-                .ok_or_else(|| anyhow!("Cannot get player for home team in game".to_string()))?;
-            // Reset tiredness to initial one
-            let tiredness = home_team_in_game.initial_tiredness[idx];
-            player.tiredness = tiredness;
-            // Reset morale to initial one
-            let morale = home_team_in_game.initial_morale[idx];
-            player.morale = morale;
-        }
-        home_team_in_game.stats = stats;
-
-        let mut away_team_in_game = game.away_team_in_game.clone();
-        let mut stats = HashMap::new();
-        for (idx, player_id) in away_team_in_game.initial_positions.iter().enumerate() {
-            let mut player_stats = GameStats::default();
-            if (idx as GamePosition) < MAX_GAME_POSITION {
-                player_stats.position = Some(idx as GamePosition);
-            }
-            stats.insert(*player_id, player_stats.clone());
+        // Send the teams as they entered the game, since tactics can also change during the game.
+        let (home_team_in_game, away_team_in_game) = if let Some(setup) = game.setup.as_ref() {
+            setup.initial_teams(&game)?
+        } else {
+            (
+                game.home_team_in_game.initial_state()?,
+                game.away_team_in_game.initial_state()?,
+            )
+        };
 
-            let player = away_team_in_game
-                .players
-                .get_mut(player_id)
-                .ok_or_else(|| anyhow!("Cannot get player for away team in game".to_string()))?;
-            // Reset tiredness to initial one
-            let tiredness = away_team_in_game.initial_tiredness[idx];
-            player.tiredness = tiredness;
-            // Reset morale to initial one
-            let morale = away_team_in_game.initial_morale[idx];
-            player.morale = morale;
-        }
-        away_team_in_game.stats = stats;
+        let adjustments = game
+            .adjustments
+            .iter()
+            .chain(game.pending_adjustments.iter())
+            .cloned()
+            .collect();
 
         Ok(Self {
             id: game.id,
@@ -213,6 +493,7 @@ impl NetworkGame {
             starting_at: game.starting_at,
             timer: game.timer,
             part_of_tournament: game.part_of_tournament,
+            adjustments,
         })
     }
 }
@@ -255,12 +536,45 @@ impl PlayerRanking {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum ChatChannel {
+    #[default]
+    Global,
+    Planet {
+        planet_id: PlanetId,
+    },
+    Tournament {
+        tournament_id: TournamentId,
+    },
+}
+
+impl ChatChannel {
+    pub fn name(&self, world: &World) -> String {
+        match self {
+            Self::Global => "Global".to_string(),
+            Self::Planet { planet_id } => world
+                .planets
+                .get(planet_id)
+                .map(|planet| planet.name.clone())
+                .unwrap_or_else(|| "Planet".to_string()),
+            Self::Tournament { tournament_id } => world
+                .tournaments
+                .get(tournament_id)
+                .map(|tournament| tournament.name())
+                .unwrap_or_else(|| "Tournament".to_string()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct ChatHistoryEntry {
     pub timestamp: Tick,
     pub from_peer_id: PeerId,
     pub author: String,
     pub message: String,
+    #[serde(skip_serializing_if = "is_default")]
+    #[serde(default)]
+    pub channel: ChatChannel,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
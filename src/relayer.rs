@@ -2,12 +2,13 @@ use crate::app::AppEvent;
 use crate::network::constants::{DEFAULT_SEED_PORT, TOPIC};
 use crate::network::handler::{self, BehaviourEvent, NetworkHandler};
 use crate::network::network_store_data::NetworkStoreData;
-use crate::network::types::{ChatHistoryEntry, NetworkData};
+use crate::network::plausibility::check_network_data;
+use crate::network::types::{ChatHistoryEntry, NetworkData, SignedNetworkData, SignerContext};
 use crate::store::*;
-use crate::types::{AppResult, TeamId};
+use crate::types::{AppResult, GameMap, PlayerMap, TeamId, TeamMap, Tick, TournamentMap};
 use libp2p::gossipsub::IdentTopic;
-use libp2p::{gossipsub, identify, swarm::SwarmEvent};
-use std::collections::HashMap;
+use libp2p::{gossipsub, identify, swarm::SwarmEvent, PeerId};
+use std::collections::{HashMap, HashSet};
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
@@ -16,6 +17,8 @@ pub struct Relayer {
     relayer_messages: Vec<String>,
     last_message_sent_to_team: HashMap<TeamId, usize>,
     network_store_data: NetworkStoreData,
+    banned_peer_ids: Vec<PeerId>,
+    ban_list_modified_at: Option<Tick>,
 }
 
 impl Default for Relayer {
@@ -26,18 +29,39 @@ impl Default for Relayer {
 
 impl Relayer {
     pub fn new() -> Self {
+        // Drops expired peers and direct messages kept for offline peers.
         let network_store_data = if let Ok(data) = load_relayer_network_store_data() {
-            data
+            data.to_store()
         } else {
             NetworkStoreData::default()
         };
 
         Self {
             network_handler: NetworkHandler::new(None)
-                .expect("Failed to initialize network handler"),
+                .expect("Failed to initialize network handler")
+                .with_relay_server(),
             relayer_messages: Vec::new(),
             last_message_sent_to_team: HashMap::new(),
             network_store_data,
+            banned_peer_ids: load_relayer_ban_list().unwrap_or_default(),
+            ban_list_modified_at: relayer_ban_list_modified_at(),
+        }
+    }
+
+    // Pick up changes to the ban list without restarting, reading it only when the file changes.
+    fn refresh_ban_list(&mut self) {
+        let modified_at = relayer_ban_list_modified_at();
+        if modified_at == self.ban_list_modified_at {
+            return;
+        }
+
+        match load_relayer_ban_list() {
+            Ok(banned_peer_ids) => {
+                println!("Loaded {} banned peers", banned_peer_ids.len());
+                self.banned_peer_ids = banned_peer_ids;
+                self.ban_list_modified_at = modified_at;
+            }
+            Err(e) => println!("Error loading ban list: {e}"),
         }
     }
 
@@ -55,7 +79,7 @@ impl Relayer {
         );
         loop {
             if let Some(AppEvent::NetworkEvent(swarm_event)) = event_receiver.recv().await {
-                let result = self.handle_network_events(swarm_event);
+                let result = self.handle_network_events(*swarm_event);
                 if result.is_err() {
                     println!("Error handling network event: {result:?}");
                 }
@@ -77,6 +101,26 @@ impl Relayer {
                     println!("Sending info to {peer_id}");
                     self.network_handler
                         .send_seed_info(self.network_store_data.to_broadcast_snapshot())?;
+
+                    self.refresh_ban_list();
+                    if !self.banned_peer_ids.is_empty() {
+                        self.network_handler
+                            .send_ban_list(self.banned_peer_ids.clone())?;
+                    }
+
+                    let pending_messages = self
+                        .network_store_data
+                        .take_pending_direct_messages(&peer_id);
+                    if !pending_messages.is_empty() {
+                        println!(
+                            "Delivering {} direct messages to {peer_id}",
+                            pending_messages.len()
+                        );
+                        for message in pending_messages {
+                            self.network_handler.forward_direct_message(message)?;
+                        }
+                        save_relayer_network_store_data(&self.network_store_data, false)?;
+                    }
                 }
             }
 
@@ -85,7 +129,37 @@ impl Relayer {
                 ..
             })) => {
                 assert!(message.topic == IdentTopic::new(TOPIC).hash());
-                let network_data = deserialize::<NetworkData>(&message.data)?;
+                let (signer, network_data) =
+                    SignedNetworkData::from_bytes(&message.data)?.verify_signature()?;
+                // Relayers only keep data signed by the peers owning it. The other data
+                // is ignored, as the relayer lacks the context to attribute it.
+                if !matches!(
+                    network_data,
+                    NetworkData::Team { .. }
+                        | NetworkData::Message { .. }
+                        | NetworkData::DirectMessage { .. }
+                        | NetworkData::SyncRequest
+                ) {
+                    return Ok(());
+                }
+                let context = SignerContext {
+                    relay_peers: &HashSet::new(),
+                    own_peer_id: None,
+                    own_team_id: TeamId::default(),
+                    teams: &TeamMap::new(),
+                    players: &PlayerMap::new(),
+                    tournaments: &TournamentMap::new(),
+                    games: &GameMap::new(),
+                };
+                network_data.check_signer(signer, &context)?;
+                if message.source.is_some_and(|source| source != signer) {
+                    return Ok(());
+                }
+                check_network_data(&network_data)?;
+                if self.banned_peer_ids.contains(&signer) {
+                    return Ok(());
+                }
+
                 if let NetworkData::Team {
                     timestamp,
                     team: network_team,
@@ -143,16 +217,25 @@ impl Relayer {
                     from_peer_id,
                     author,
                     message,
+                    channel,
                 } = network_data
                 {
+                    self.network_handler.check_chat_rate_limit(from_peer_id)?;
                     let entry = ChatHistoryEntry {
                         timestamp,
                         from_peer_id,
                         author,
                         message,
+                        channel,
                     };
                     println!("Chat message stored: {entry:#?}");
                     self.network_store_data.chat_history.insert(entry);
+                } else if let NetworkData::DirectMessage { message, .. } = network_data {
+                    // Direct messages reach gossip when the recipient could not be reached,
+                    // so they are kept until the recipient subscribes again.
+                    println!("Direct message stored for {}", message.to_peer_id);
+                    self.network_store_data.push_pending_direct_message(message);
+                    save_relayer_network_store_data(&self.network_store_data, false)?;
                 } else if let NetworkData::SyncRequest = network_data {
                     self.network_handler
                         .send_seed_info(self.network_store_data.to_broadcast_snapshot())?;
@@ -165,14 +248,18 @@ impl Relayer {
                 ..
             })) => {
                 // Store the first routable listen address for this peer
-                if let Some(addr) = info.listen_addrs.iter().find_map(handler::sanitize_addr) {
+                if let Some(addr) = info
+                    .listen_addrs
+                    .iter()
+                    .find_map(|addr| handler::sanitize_peer_addr(&peer_id, addr))
+                {
                     self.network_store_data.update_peer_addresses(peer_id, addr);
                     save_relayer_network_store_data(&self.network_store_data, false)?;
                 }
             }
 
             SwarmEvent::NewExternalAddrOfPeer { peer_id, address } => {
-                if let Some(clean) = handler::sanitize_addr(&address) {
+                if let Some(clean) = handler::sanitize_peer_addr(&peer_id, &address) {
                     self.network_store_data
                         .update_peer_addresses(peer_id, clean);
                     save_relayer_network_store_data(&self.network_store_data, false)?;
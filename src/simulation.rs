@@ -0,0 +1,431 @@
+use crate::{
+    app::App,
+    args::{AppArgs, ReportFormat, SimulateArgs},
+    core::{csv_field, Clock, GameResult, Player, Resource, World, DAYS},
+    game_engine::{types::GameStats, TournamentId},
+    types::{AppResult, PlayerId, StorableResourceMap, TeamId, Tick},
+};
+use anyhow::anyhow;
+use itertools::Itertools;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::Serialize;
+use std::fmt::Write;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter};
+
+const MAX_LEADERS_PER_STAT: usize = 5;
+// The simulation starts at a fixed time, so that the same seed always gives the same report.
+// It is on a day boundary so that all tick intervals are aligned.
+const SIMULATION_EPOCH: Tick = 19_676 * DAYS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumIter)]
+enum LeaderStat {
+    Points,
+    Rebounds,
+    Assists,
+    Steals,
+    Blocks,
+    PlusMinus,
+}
+
+impl LeaderStat {
+    fn value(&self, stats: &GameStats) -> i32 {
+        match self {
+            Self::Points => stats.points as i32,
+            Self::Rebounds => (stats.offensive_rebounds + stats.defensive_rebounds) as i32,
+            Self::Assists => stats.assists as i32,
+            Self::Steals => stats.steals as i32,
+            Self::Blocks => stats.blocks as i32,
+            Self::PlusMinus => stats.plus_minus,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TeamReport {
+    pub id: TeamId,
+    pub name: String,
+    pub rating: f32,
+    pub wins: usize,
+    pub losses: usize,
+    pub draws: usize,
+    pub team_rating: f32,
+    pub reputation: f32,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StatLeader {
+    pub stat: String,
+    pub rank: usize,
+    pub player_id: PlayerId,
+    pub name: String,
+    pub team: Option<String>,
+    pub games: u16,
+    pub total: i32,
+    pub per_game: f32,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TournamentReport {
+    pub id: TournamentId,
+    pub name: String,
+    pub participants: usize,
+    pub winner: Option<TeamId>,
+    pub winner_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ResourceReport {
+    pub resource: String,
+    pub held: u64,
+    pub gathered: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SimulationReport {
+    pub app_version: [usize; 3],
+    pub seed: u64,
+    pub days: u64,
+    pub games_played: usize,
+    pub teams: Vec<TeamReport>,
+    pub leaders: Vec<StatLeader>,
+    pub tournaments: Vec<TournamentReport>,
+    pub economy: Vec<ResourceReport>,
+}
+
+impl SimulationReport {
+    pub fn from_world(world: &World, seed: u64, days: u64) -> AppResult<Self> {
+        let mut teams = world
+            .teams
+            .values()
+            .filter(|team| team.peer_id.is_none())
+            .map(|team| {
+                let record = &team.local_game_rating.record;
+                Ok(TeamReport {
+                    id: team.id,
+                    name: team.name.clone(),
+                    rating: team.local_game_rating.rating,
+                    wins: record.get(&GameResult::Win).copied().unwrap_or_default(),
+                    losses: record.get(&GameResult::Loss).copied().unwrap_or_default(),
+                    draws: record.get(&GameResult::Draw).copied().unwrap_or_default(),
+                    team_rating: world.team_rating(&team.id)?,
+                    reputation: team.reputation,
+                })
+            })
+            .collect::<AppResult<Vec<TeamReport>>>()?;
+        teams.sort_by(|a, b| b.rating.total_cmp(&a.rating).then(a.name.cmp(&b.name)));
+
+        let players = world
+            .players
+            .values()
+            .filter(|player| player.peer_id.is_none())
+            .filter(|player| player.historical_stats.games.iter().sum::<u16>() > 0)
+            .collect_vec();
+
+        let mut leaders = vec![];
+        for stat in LeaderStat::iter() {
+            let ranking = players
+                .iter()
+                .sorted_by(|a, b| {
+                    stat.value(&b.historical_stats)
+                        .cmp(&stat.value(&a.historical_stats))
+                        .then(a.id.cmp(&b.id))
+                })
+                .take(MAX_LEADERS_PER_STAT);
+
+            for (idx, player) in ranking.enumerate() {
+                leaders.push(StatLeader::new(world, player, stat, idx + 1));
+            }
+        }
+
+        let mut tournaments = world
+            .past_tournaments
+            .values()
+            .map(|summary| TournamentReport {
+                id: summary.id,
+                name: summary.name(),
+                participants: summary.participant_ids.len(),
+                winner: summary.winner,
+                winner_name: summary.winner_name.clone(),
+            })
+            .collect_vec();
+        tournaments.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let economy = Resource::iter()
+            .map(|resource| {
+                let (held, gathered) = world
+                    .teams
+                    .values()
+                    .filter(|team| team.peer_id.is_none())
+                    .fold((0, 0), |(held, gathered), team| {
+                        (
+                            held + team.resources.value(&resource) as u64,
+                            gathered + team.resources_gathered.value(&resource) as u64,
+                        )
+                    });
+                ResourceReport {
+                    resource: resource.to_string(),
+                    held,
+                    gathered,
+                }
+            })
+            .collect_vec();
+
+        Ok(Self {
+            app_version: crate::app_version(),
+            seed,
+            days,
+            games_played: world.past_games.len(),
+            teams,
+            leaders,
+            tournaments,
+            economy,
+        })
+    }
+
+    pub fn to_json(&self) -> AppResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    // The CSV report is in long format (one value per row), so that the different sections
+    // can share the same columns and be easily filtered when comparing patches.
+    pub fn to_csv(&self) -> String {
+        let mut csv = "section,id,name,metric,value\n".to_string();
+        let mut push_row = |section: &str, id: &str, name: &str, metric: &str, value: String| {
+            let _ = writeln!(
+                csv,
+                "{section},{id},{},{metric},{}",
+                csv_field(name),
+                csv_field(&value)
+            );
+        };
+
+        push_row("simulation", "", "", "seed", self.seed.to_string());
+        push_row("simulation", "", "", "days", self.days.to_string());
+        push_row(
+            "simulation",
+            "",
+            "",
+            "games_played",
+            self.games_played.to_string(),
+        );
+
+        for team in self.teams.iter() {
+            let id = team.id.to_string();
+            push_row("team", &id, &team.name, "rating", team.rating.to_string());
+            push_row("team", &id, &team.name, "wins", team.wins.to_string());
+            push_row("team", &id, &team.name, "losses", team.losses.to_string());
+            push_row("team", &id, &team.name, "draws", team.draws.to_string());
+            push_row(
+                "team",
+                &id,
+                &team.name,
+                "team_rating",
+                team.team_rating.to_string(),
+            );
+            push_row(
+                "team",
+                &id,
+                &team.name,
+                "reputation",
+                team.reputation.to_string(),
+            );
+        }
+
+        for leader in self.leaders.iter() {
+            let id = leader.player_id.to_string();
+            let metric = leader.stat.to_lowercase();
+            push_row(
+                "leader",
+                &id,
+                &leader.name,
+                &metric,
+                leader.total.to_string(),
+            );
+            push_row(
+                "leader",
+                &id,
+                &leader.name,
+                &format!("{metric}_per_game"),
+                leader.per_game.to_string(),
+            );
+        }
+
+        for tournament in self.tournaments.iter() {
+            let id = tournament.id.to_string();
+            push_row(
+                "tournament",
+                &id,
+                &tournament.name,
+                "participants",
+                tournament.participants.to_string(),
+            );
+            push_row(
+                "tournament",
+                &id,
+                &tournament.name,
+                "winner",
+                tournament.winner_name.clone(),
+            );
+        }
+
+        for resource in self.economy.iter() {
+            push_row(
+                "economy",
+                "",
+                &resource.resource,
+                "held",
+                resource.held.to_string(),
+            );
+            push_row(
+                "economy",
+                "",
+                &resource.resource,
+                "gathered",
+                resource.gathered.to_string(),
+            );
+        }
+
+        csv
+    }
+
+    pub fn formatted(&self, format: ReportFormat) -> AppResult<String> {
+        match format {
+            ReportFormat::Json => self.to_json(),
+            ReportFormat::Csv => Ok(self.to_csv()),
+        }
+    }
+}
+
+impl StatLeader {
+    fn new(world: &World, player: &Player, stat: LeaderStat, rank: usize) -> Self {
+        let stats = &player.historical_stats;
+        let games = stats.games.iter().sum::<u16>();
+        let total = stat.value(stats);
+        Self {
+            stat: stat.to_string(),
+            rank,
+            player_id: player.id,
+            name: player.info.full_name(),
+            team: player
+                .team
+                .and_then(|team_id| world.teams.get(&team_id))
+                .map(|team| team.name.clone()),
+            games,
+            total,
+            per_game: total as f32 / games.max(1) as f32,
+        }
+    }
+}
+
+// Generate a local world from the given seed and simulate it for the given number of days,
+// without UI, network, audio or persistence. The world clock is manual, so that the simulation
+// runs as fast as possible.
+fn simulate_app(seed: u64, days: u64) -> AppResult<App> {
+    let mut app = App::new(AppArgs::simulation(seed))?;
+    app.world.clock = Clock::manual(SIMULATION_EPOCH);
+    app.world.headless = true;
+    app.new_world();
+
+    let home_planet_id = *app
+        .world
+        .planets
+        .keys()
+        .sorted()
+        .next()
+        .ok_or(anyhow!("There should be at least one planet"))?;
+    app.world.own_team_id = app.world.generate_random_team(
+        &mut ChaCha8Rng::seed_from_u64(seed),
+        home_planet_id,
+        "Simulation".into(),
+        "Simulation".into(),
+        None,
+    )?;
+
+    log::info!("Simulating {days} days with seed {seed}");
+    app.fast_forward(days * DAYS)?;
+    log::info!("Simulation done");
+
+    Ok(app)
+}
+
+pub fn simulate_world(seed: u64, days: u64) -> AppResult<SimulationReport> {
+    let app = simulate_app(seed, days)?;
+    SimulationReport::from_world(&app.world, seed, days)
+}
+
+pub fn run_simulation(args: &SimulateArgs) -> AppResult<()> {
+    let report = simulate_world(args.seed, args.days)?;
+    let formatted = report.formatted(args.format)?;
+
+    if let Some(path) = args.output.as_ref() {
+        std::fs::write(path, formatted)?;
+    } else {
+        println!("{formatted}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::simulate_world;
+    use crate::{core::utils::TEAM_DATA, types::AppResult};
+    use uuid::Version;
+
+    #[test]
+    fn test_simulation_report() -> AppResult<()> {
+        let report = simulate_world(0, 1)?;
+        assert!(report.games_played > 0);
+        assert!(!report.teams.is_empty());
+        assert!(!report.leaders.is_empty());
+        assert!(report.economy.iter().any(|resource| resource.held > 0));
+        assert!(report
+            .teams
+            .iter()
+            .all(|team| team.id.get_version() == Some(Version::Random)));
+        assert!(report
+            .leaders
+            .iter()
+            .all(|leader| leader.player_id.get_version() == Some(Version::Random)));
+
+        let json = report.to_json()?;
+        assert!(json.contains("\"leaders\""));
+
+        let csv = report.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("section,id,name,metric,value"));
+        assert!(lines.all(|line| line.starts_with("simulation,")
+            || line.starts_with("team,")
+            || line.starts_with("leader,")
+            || line.starts_with("tournament,")
+            || line.starts_with("economy,")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulation_is_reproducible() -> AppResult<()> {
+        let report = simulate_world(0, 1)?;
+        assert_eq!(report, simulate_world(0, 1)?);
+        assert_ne!(report, simulate_world(1, 1)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulation_report_invariants() -> AppResult<()> {
+        let report = simulate_world(0, 1)?;
+        assert_eq!((report.seed, report.days), (0, 1));
+        // The generated local teams plus the own team.
+        assert_eq!(report.teams.len(), TEAM_DATA.len() + 1);
+
+        // Every game is played between two local teams, so it is in the record of both.
+        let (wins, losses, draws) = report.teams.iter().fold((0, 0, 0), |acc, team| {
+            (acc.0 + team.wins, acc.1 + team.losses, acc.2 + team.draws)
+        });
+        assert_eq!(wins, losses);
+        assert_eq!(wins + losses + draws, 2 * report.games_played);
+        Ok(())
+    }
+}
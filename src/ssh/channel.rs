@@ -82,6 +82,7 @@ pub struct AppChannel {
     state: AppChannelState,
     network_port: Option<u16>,
     username: String,
+    save_passphrase: Option<String>,
 }
 
 #[derive(Debug)]
@@ -99,6 +100,7 @@ impl AppChannel {
         _server_shutdown: CancellationToken,
         network_port: Option<u16>,
         username: String,
+        save_passphrase: Option<String>,
     ) -> Self {
         let state = AppChannelState::AwaitingPty { _server_shutdown };
 
@@ -108,6 +110,7 @@ impl AppChannel {
             state,
             network_port,
             username,
+            save_passphrase,
         }
     }
 
@@ -150,6 +153,7 @@ impl AppChannel {
             store_prefix,
             network_port,
             Some(CHANNEL_DISCONNECTION_TIME_IN_SECONDS),
+            self.save_passphrase.clone(),
         ))?;
 
         let tui = Tui::new_ssh(writer)?;
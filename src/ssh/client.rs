@@ -20,6 +20,8 @@ pub struct AppClient {
     shutdown: CancellationToken,
     channels: HashMap<ChannelId, AppChannel>,
     session_auth: SessionAuth,
+    // Worlds of users authenticated by password are encrypted with it.
+    save_passphrase: Option<String>,
 }
 
 impl AppClient {
@@ -29,6 +31,7 @@ impl AppClient {
             shutdown,
             channels: HashMap::new(),
             session_auth: SessionAuth::default(),
+            save_passphrase: None,
         }
     }
 
@@ -53,6 +56,7 @@ impl server::Handler for AppClient {
         // We defer checking username and password to channel_open_session so that it is possible
         // to send informative error messages to the user using session.write.
         self.session_auth = SessionAuth::new(username, password.to_string());
+        self.save_passphrase = Some(password.to_string());
 
         Ok(Auth::Accept)
     }
@@ -72,6 +76,7 @@ impl server::Handler for AppClient {
         // We defer checking username and password to channel_open_session so that it is possible
         // to send informative error messages to the user using session.write.
         self.session_auth = SessionAuth::new(username, public_key.to_string());
+        self.save_passphrase = None;
 
         Ok(Auth::Accept)
     }
@@ -140,6 +145,7 @@ impl server::Handler for AppClient {
             self.shutdown.clone(),
             self.network_port,
             self.session_auth.username.clone(),
+            self.save_passphrase.clone(),
         );
 
         let created = self.channels.insert(channel.id(), app_channel).is_none();
@@ -0,0 +1,78 @@
+use super::{Storage, StorageMetadata};
+use crate::types::{AppResult, SystemTimeTick, Tick};
+use std::path::PathBuf;
+
+// Each key is a file relative to the root folder, by default the game config directory.
+#[derive(Debug, Clone)]
+pub struct FileSystemStorage {
+    root: PathBuf,
+}
+
+impl FileSystemStorage {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+impl Storage for FileSystemStorage {
+    fn read(&self, key: &str) -> AppResult<Vec<u8>> {
+        Ok(std::fs::read(self.path(key))?)
+    }
+
+    fn write(&self, key: &str, bytes: &[u8]) -> AppResult<()> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> AppResult<()> {
+        std::fs::remove_file(self.path(key))?;
+        Ok(())
+    }
+
+    fn exists(&self, key: &str) -> bool {
+        self.path(key).exists()
+    }
+
+    fn metadata(&self, key: &str) -> AppResult<StorageMetadata> {
+        let metadata = std::fs::metadata(self.path(key))?;
+        Ok(StorageMetadata {
+            size: metadata.len(),
+            created_at: metadata.created().ok().map(Tick::from_system_time),
+            modified_at: metadata.modified().ok().map(Tick::from_system_time),
+        })
+    }
+
+    fn list(&self, prefix: &str) -> AppResult<Vec<String>> {
+        let (folder, name_prefix) = prefix.rsplit_once('/').unwrap_or(("", prefix));
+        let directory = self.path(folder);
+        if !directory.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut keys = vec![];
+        for entry in std::fs::read_dir(directory)? {
+            let Some(name) = entry?.file_name().to_str().map(|name| name.to_string()) else {
+                continue;
+            };
+            if !name.starts_with(name_prefix) {
+                continue;
+            }
+
+            if folder.is_empty() {
+                keys.push(name);
+            } else {
+                keys.push(format!("{folder}/{name}"));
+            }
+        }
+
+        Ok(keys)
+    }
+}
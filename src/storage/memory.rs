@@ -0,0 +1,84 @@
+use super::{Storage, StorageMetadata};
+use crate::types::{AppResult, SystemTimeTick, Tick};
+use anyhow::anyhow;
+use std::{collections::HashMap, sync::Mutex};
+
+#[derive(Debug, Clone)]
+struct MemoryEntry {
+    bytes: Vec<u8>,
+    created_at: Tick,
+    modified_at: Tick,
+}
+
+// Nothing is persisted: used by tests and by throwaway worlds.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    entries: Mutex<HashMap<String, MemoryEntry>>,
+}
+
+impl MemoryStorage {
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<String, MemoryEntry>> {
+        self.entries
+            .lock()
+            .expect("Memory storage lock should not be poisoned")
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn read(&self, key: &str) -> AppResult<Vec<u8>> {
+        self.entries()
+            .get(key)
+            .map(|entry| entry.bytes.clone())
+            .ok_or_else(|| anyhow!("No entry found for {key}"))
+    }
+
+    fn write(&self, key: &str, bytes: &[u8]) -> AppResult<()> {
+        let now = Tick::now();
+        self.entries()
+            .entry(key.to_string())
+            .and_modify(|entry| {
+                entry.bytes = bytes.to_vec();
+                entry.modified_at = now;
+            })
+            .or_insert_with(|| MemoryEntry {
+                bytes: bytes.to_vec(),
+                created_at: now,
+                modified_at: now,
+            });
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> AppResult<()> {
+        self.entries()
+            .remove(key)
+            .map(|_| ())
+            .ok_or_else(|| anyhow!("No entry found for {key}"))
+    }
+
+    fn exists(&self, key: &str) -> bool {
+        self.entries().contains_key(key)
+    }
+
+    fn metadata(&self, key: &str) -> AppResult<StorageMetadata> {
+        self.entries()
+            .get(key)
+            .map(|entry| StorageMetadata {
+                size: entry.bytes.len() as u64,
+                created_at: Some(entry.created_at),
+                modified_at: Some(entry.modified_at),
+            })
+            .ok_or_else(|| anyhow!("No entry found for {key}"))
+    }
+
+    fn list(&self, prefix: &str) -> AppResult<Vec<String>> {
+        Ok(self
+            .entries()
+            .keys()
+            .filter(|key| {
+                key.strip_prefix(prefix)
+                    .is_some_and(|name| !name.contains('/'))
+            })
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,258 @@
+mod filesystem;
+mod memory;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub use filesystem::FileSystemStorage;
+pub use memory::MemoryStorage;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStorage;
+
+use crate::{
+    args::StorageKind,
+    game_engine::{game::GameSummary, TournamentId, TournamentSummary},
+    store::{config_dirs, deserialize, serialize},
+    types::{AppResult, GameId, TeamId, Tick},
+};
+use std::sync::{Arc, LazyLock, RwLock};
+
+static GAME_SUMMARIES_PREFIX: &str = "games/summary_";
+static TOURNAMENT_SUMMARIES_PREFIX: &str = "tournaments/summary_";
+#[cfg(feature = "sqlite")]
+static SQLITE_DATABASE_FILENAME: &str = "rebels.sqlite";
+
+static STORAGE: LazyLock<RwLock<Arc<dyn Storage>>> = LazyLock::new(|| {
+    RwLock::new(Arc::new(FileSystemStorage::new(
+        config_dirs().expect("Failed to get config directory"),
+    )))
+});
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StorageMetadata {
+    pub size: u64,
+    pub created_at: Option<Tick>,
+    pub modified_at: Option<Tick>,
+}
+
+// Filter for the past games and tournaments. Results are sorted from the most recent.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HistoryQuery {
+    pub team_id: Option<TeamId>,
+    pub since: Option<Tick>,
+    pub until: Option<Tick>,
+    pub limit: Option<usize>,
+}
+
+impl HistoryQuery {
+    pub fn with_team(mut self, team_id: TeamId) -> Self {
+        self.team_id = Some(team_id);
+        self
+    }
+
+    pub fn with_since(mut self, since: Tick) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    pub fn with_until(mut self, until: Tick) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn matches(&self, team_ids: &[TeamId], timestamp: Tick) -> bool {
+        self.team_id
+            .is_none_or(|team_id| team_ids.contains(&team_id))
+            && self.since.is_none_or(|since| timestamp >= since)
+            && self.until.is_none_or(|until| timestamp <= until)
+    }
+
+    fn apply<T>(&self, mut items: Vec<T>, timestamp: impl Fn(&T) -> Tick) -> Vec<T> {
+        items.sort_by_key(|item| std::cmp::Reverse(timestamp(item)));
+        if let Some(limit) = self.limit {
+            items.truncate(limit);
+        }
+        items
+    }
+
+    pub fn filter_games(&self, games: impl IntoIterator<Item = GameSummary>) -> Vec<GameSummary> {
+        let games = games
+            .into_iter()
+            .filter(|game| self.matches(&[game.home_team_id, game.away_team_id], game.starting_at))
+            .collect();
+        self.apply(games, |game| game.starting_at)
+    }
+
+    pub fn filter_tournaments(
+        &self,
+        tournaments: impl IntoIterator<Item = TournamentSummary>,
+    ) -> Vec<TournamentSummary> {
+        let tournaments = tournaments
+            .into_iter()
+            .filter(|tournament| {
+                self.matches(
+                    &tournament.participant_ids,
+                    tournament.ended_at.unwrap_or_default(),
+                )
+            })
+            .collect();
+        self.apply(tournaments, |tournament| {
+            tournament.ended_at.unwrap_or_default()
+        })
+    }
+}
+
+// Storage for save files, keyed by their relative path (e.g. 'games/game_<id>.json.gz').
+// Past games and tournaments are also indexed by their summaries, so that they can be
+// queried without loading every file. Key-value backends keep one summary entry per game
+// or tournament, while backends with a query engine can override the index methods.
+pub trait Storage: Send + Sync {
+    fn read(&self, key: &str) -> AppResult<Vec<u8>>;
+    fn write(&self, key: &str, bytes: &[u8]) -> AppResult<()>;
+    fn remove(&self, key: &str) -> AppResult<()>;
+    fn exists(&self, key: &str) -> bool;
+    fn metadata(&self, key: &str) -> AppResult<StorageMetadata>;
+    // Keys in the same folder as the prefix that start with it.
+    fn list(&self, prefix: &str) -> AppResult<Vec<String>>;
+
+    fn index_game(&self, summary: &GameSummary) -> AppResult<()> {
+        self.write(&game_summary_key(&summary.id), &serialize(summary)?)
+    }
+
+    fn query_games(&self, query: &HistoryQuery) -> AppResult<Vec<GameSummary>> {
+        let games = self
+            .list(GAME_SUMMARIES_PREFIX)?
+            .iter()
+            .map(|key| deserialize(&self.read(key)?))
+            .collect::<AppResult<Vec<GameSummary>>>()?;
+        Ok(query.filter_games(games))
+    }
+
+    fn index_tournament(&self, summary: &TournamentSummary) -> AppResult<()> {
+        self.write(&tournament_summary_key(&summary.id), &serialize(summary)?)
+    }
+
+    fn query_tournaments(&self, query: &HistoryQuery) -> AppResult<Vec<TournamentSummary>> {
+        let tournaments = self
+            .list(TOURNAMENT_SUMMARIES_PREFIX)?
+            .iter()
+            .map(|key| deserialize(&self.read(key)?))
+            .collect::<AppResult<Vec<TournamentSummary>>>()?;
+        Ok(query.filter_tournaments(tournaments))
+    }
+}
+
+pub(crate) fn game_summary_key(game_id: &GameId) -> String {
+    format!("{GAME_SUMMARIES_PREFIX}{game_id}.json.gz")
+}
+
+fn tournament_summary_key(tournament_id: &TournamentId) -> String {
+    format!("{TOURNAMENT_SUMMARIES_PREFIX}{tournament_id}.json.gz")
+}
+
+pub fn open_storage(kind: StorageKind) -> AppResult<Arc<dyn Storage>> {
+    Ok(match kind {
+        StorageKind::Filesystem => Arc::new(FileSystemStorage::new(config_dirs()?)),
+        #[cfg(feature = "sqlite")]
+        StorageKind::Sqlite => Arc::new(SqliteStorage::open(
+            &config_dirs()?.join(SQLITE_DATABASE_FILENAME),
+        )?),
+        StorageKind::Memory => Arc::new(MemoryStorage::default()),
+    })
+}
+
+// The storage is shared by the whole process, so that all the worlds hosted by
+// the SSH server use the same backend.
+pub fn set_storage(storage: Arc<dyn Storage>) {
+    *STORAGE
+        .write()
+        .expect("Storage lock should not be poisoned") = storage;
+}
+
+pub fn storage() -> Arc<dyn Storage> {
+    STORAGE
+        .read()
+        .expect("Storage lock should not be poisoned")
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{game_summary_key, FileSystemStorage, HistoryQuery, MemoryStorage, Storage};
+    use crate::{
+        game_engine::game::{Game, GameSummary},
+        game_engine::types::TeamInGame,
+        types::AppResult,
+    };
+
+    fn test_storage(storage: &dyn Storage) -> AppResult<()> {
+        storage.write("games/game_a.json.gz", b"a")?;
+        storage.write("games/game_b.json.gz", b"bb")?;
+        storage.write("tournaments/tournament_a.json.gz", b"c")?;
+        assert_eq!(storage.read("games/game_b.json.gz")?, b"bb");
+        assert_eq!(storage.metadata("games/game_b.json.gz")?.size, 2);
+        assert!(storage.read("games/game_c.json.gz").is_err());
+
+        let mut keys = storage.list("games/game_")?;
+        keys.sort();
+        assert_eq!(keys, vec!["games/game_a.json.gz", "games/game_b.json.gz"]);
+
+        storage.remove("games/game_a.json.gz")?;
+        assert!(!storage.exists("games/game_a.json.gz"));
+
+        let games = (0..3)
+            .map(|i| {
+                let mut game = Game::test(TeamInGame::test(), TeamInGame::test());
+                game.starting_at += i;
+                GameSummary::from_game(&game)
+            })
+            .collect::<Vec<_>>();
+        for game in games.iter() {
+            storage.index_game(game)?;
+        }
+
+        let query = HistoryQuery::default().with_limit(2);
+        let found = storage.query_games(&query)?;
+        assert_eq!(found, vec![games[2].clone(), games[1].clone()]);
+
+        let query = HistoryQuery::default()
+            .with_team(games[0].home_team_id)
+            .with_until(games[1].starting_at);
+        assert_eq!(storage.query_games(&query)?, vec![games[0].clone()]);
+        assert!(storage.query_tournaments(&query)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_memory_storage() -> AppResult<()> {
+        test_storage(&MemoryStorage::default())
+    }
+
+    #[test]
+    fn test_corrupted_summary_is_an_error() -> AppResult<()> {
+        let storage = MemoryStorage::default();
+        let game = Game::test(TeamInGame::test(), TeamInGame::test());
+        storage.index_game(&GameSummary::from_game(&game))?;
+        storage.write(&game_summary_key(&game.id), b"corrupted")?;
+        assert!(storage.query_games(&HistoryQuery::default()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_filesystem_storage() -> AppResult<()> {
+        let root = std::env::temp_dir().join(format!("rebels_storage_{}", uuid::Uuid::new_v4()));
+        test_storage(&FileSystemStorage::new(root.clone()))?;
+        std::fs::remove_dir_all(root)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_storage() -> AppResult<()> {
+        test_storage(&super::SqliteStorage::in_memory()?)
+    }
+}
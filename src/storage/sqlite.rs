@@ -0,0 +1,234 @@
+use super::{HistoryQuery, Storage, StorageMetadata};
+use crate::{
+    game_engine::{game::GameSummary, TournamentSummary},
+    store::{deserialize, serialize},
+    types::{AppResult, SystemTimeTick, Tick},
+};
+use anyhow::anyhow;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{
+    path::Path,
+    sync::{Mutex, MutexGuard},
+};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS entries (
+        key TEXT PRIMARY KEY,
+        bytes BLOB NOT NULL,
+        created_at INTEGER NOT NULL,
+        modified_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS games (
+        id TEXT PRIMARY KEY,
+        home_team_id TEXT NOT NULL,
+        away_team_id TEXT NOT NULL,
+        starting_at INTEGER NOT NULL,
+        summary BLOB NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS games_starting_at ON games (starting_at);
+    CREATE TABLE IF NOT EXISTS tournaments (
+        id TEXT PRIMARY KEY,
+        ended_at INTEGER NOT NULL,
+        summary BLOB NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS tournament_participants (
+        tournament_id TEXT NOT NULL,
+        team_id TEXT NOT NULL,
+        PRIMARY KEY (tournament_id, team_id)
+    );
+";
+
+// Ticks are stored as signed integers, which is what SQLite supports.
+fn to_sql_tick(tick: Tick) -> i64 {
+    tick as i64
+}
+
+// A negative limit means no limit in SQLite.
+fn to_sql_limit(limit: Option<usize>) -> i64 {
+    limit.map(|limit| limit as i64).unwrap_or(-1)
+}
+
+// Every entry and the summaries of past games and tournaments live in a single
+// database file, so that past games can be queried with SQL.
+#[derive(Debug)]
+pub struct SqliteStorage {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteStorage {
+    pub fn open(path: &Path) -> AppResult<Self> {
+        Self::new(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> AppResult<Self> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    fn new(connection: Connection) -> AppResult<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        self.connection
+            .lock()
+            .expect("SQLite connection lock should not be poisoned")
+    }
+}
+
+impl Storage for SqliteStorage {
+    fn read(&self, key: &str) -> AppResult<Vec<u8>> {
+        self.connection()
+            .query_row("SELECT bytes FROM entries WHERE key = ?1", [key], |row| {
+                row.get(0)
+            })
+            .optional()?
+            .ok_or_else(|| anyhow!("No entry found for {key}"))
+    }
+
+    fn write(&self, key: &str, bytes: &[u8]) -> AppResult<()> {
+        let now = to_sql_tick(Tick::now());
+        self.connection().execute(
+            "INSERT INTO entries (key, bytes, created_at, modified_at) VALUES (?1, ?2, ?3, ?3)
+             ON CONFLICT (key) DO UPDATE SET bytes = excluded.bytes, modified_at = excluded.modified_at",
+            params![key, bytes, now],
+        )?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> AppResult<()> {
+        let removed = self
+            .connection()
+            .execute("DELETE FROM entries WHERE key = ?1", [key])?;
+        if removed == 0 {
+            return Err(anyhow!("No entry found for {key}"));
+        }
+        Ok(())
+    }
+
+    fn exists(&self, key: &str) -> bool {
+        self.connection()
+            .query_row("SELECT 1 FROM entries WHERE key = ?1", [key], |_| Ok(()))
+            .optional()
+            .is_ok_and(|row| row.is_some())
+    }
+
+    fn metadata(&self, key: &str) -> AppResult<StorageMetadata> {
+        self.connection()
+            .query_row(
+                "SELECT length(bytes), created_at, modified_at FROM entries WHERE key = ?1",
+                [key],
+                |row| {
+                    Ok(StorageMetadata {
+                        size: row.get::<_, i64>(0)? as u64,
+                        created_at: Some(row.get::<_, i64>(1)? as Tick),
+                        modified_at: Some(row.get::<_, i64>(2)? as Tick),
+                    })
+                },
+            )
+            .optional()?
+            .ok_or_else(|| anyhow!("No entry found for {key}"))
+    }
+
+    fn list(&self, prefix: &str) -> AppResult<Vec<String>> {
+        let connection = self.connection();
+        // Keys contain '_', which LIKE would treat as a wildcard.
+        let mut statement = connection.prepare(
+            "SELECT key FROM entries
+             WHERE substr(key, 1, length(?1)) = ?1 AND instr(substr(key, length(?1) + 1), '/') = 0",
+        )?;
+        let keys = statement
+            .query_map([prefix], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(keys)
+    }
+
+    fn index_game(&self, summary: &GameSummary) -> AppResult<()> {
+        self.connection().execute(
+            "INSERT OR REPLACE INTO games (id, home_team_id, away_team_id, starting_at, summary)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                summary.id.to_string(),
+                summary.home_team_id.to_string(),
+                summary.away_team_id.to_string(),
+                to_sql_tick(summary.starting_at),
+                serialize(summary)?
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn query_games(&self, query: &HistoryQuery) -> AppResult<Vec<GameSummary>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(
+            "SELECT summary FROM games
+             WHERE (?1 IS NULL OR home_team_id = ?1 OR away_team_id = ?1)
+               AND (?2 IS NULL OR starting_at >= ?2)
+               AND (?3 IS NULL OR starting_at <= ?3)
+             ORDER BY starting_at DESC
+             LIMIT ?4",
+        )?;
+        let summaries = statement
+            .query_map(
+                params![
+                    query.team_id.map(|team_id| team_id.to_string()),
+                    query.since.map(to_sql_tick),
+                    query.until.map(to_sql_tick),
+                    to_sql_limit(query.limit)
+                ],
+                |row| row.get::<_, Vec<u8>>(0),
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        summaries.iter().map(|bytes| deserialize(bytes)).collect()
+    }
+
+    fn index_tournament(&self, summary: &TournamentSummary) -> AppResult<()> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction()?;
+        transaction.execute(
+            "INSERT OR REPLACE INTO tournaments (id, ended_at, summary) VALUES (?1, ?2, ?3)",
+            params![
+                summary.id.to_string(),
+                to_sql_tick(summary.ended_at.unwrap_or_default()),
+                serialize(summary)?
+            ],
+        )?;
+        for team_id in summary.participant_ids.iter() {
+            transaction.execute(
+                "INSERT OR IGNORE INTO tournament_participants (tournament_id, team_id) VALUES (?1, ?2)",
+                params![summary.id.to_string(), team_id.to_string()],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn query_tournaments(&self, query: &HistoryQuery) -> AppResult<Vec<TournamentSummary>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(
+            "SELECT summary FROM tournaments
+             WHERE (?1 IS NULL OR id IN
+                    (SELECT tournament_id FROM tournament_participants WHERE team_id = ?1))
+               AND (?2 IS NULL OR ended_at >= ?2)
+               AND (?3 IS NULL OR ended_at <= ?3)
+             ORDER BY ended_at DESC
+             LIMIT ?4",
+        )?;
+        let summaries = statement
+            .query_map(
+                params![
+                    query.team_id.map(|team_id| team_id.to_string()),
+                    query.since.map(to_sql_tick),
+                    query.until.map(to_sql_tick),
+                    to_sql_limit(query.limit)
+                ],
+                |row| row.get::<_, Vec<u8>>(0),
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        summaries.iter().map(|bytes| deserialize(bytes)).collect()
+    }
+}
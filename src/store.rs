@@ -1,11 +1,21 @@
 #[cfg(feature = "relayer")]
 use crate::network::network_store_data::NetworkStoreData;
 use crate::{
-    core::world::World,
-    game_engine::{game::Game, Tournament, TournamentId},
+    args::{HistoryArgs, ReportFormat, SnapshotsArgs},
+    core::{csv_field, world::World, GameRating},
+    game_engine::{
+        game::{Game, GameSummary},
+        replay::GameReplay,
+        Tournament, TournamentId, TournamentSummary,
+    },
+    storage::{storage, HistoryQuery, StorageMetadata},
     types::*,
 };
 use anyhow::anyhow;
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use chrono::{DateTime, Local};
 use directories;
 use flate2::{
     read::{GzDecoder, ZlibDecoder},
@@ -14,55 +24,72 @@ use flate2::{
 };
 use include_dir::{include_dir, Dir};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::{
     io::{Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 pub static ASSETS_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/assets/");
 static PERSISTED_WORLD_FILENAME: &str = "world";
 static PERSISTED_GAMES_PREFIX: &str = "games/game_";
 static PERSISTED_TOURNAMENTS_PREFIX: &str = "tournaments/tournament_";
+static PERSISTED_SNAPSHOTS_DIRECTORY: &str = "snapshots";
+pub const DEFAULT_SNAPSHOT_RETENTION: usize = 10;
 static LEGACY_PERSISTED_GAMES_PREFIX: &str = "game_";
 #[cfg(feature = "relayer")]
 static PERSISTED_RELAYER_NETWORK_STORE_DATA_FILENAME: &str = "relayer/network_store_data";
 const COMPRESSION_LEVEL: u32 = 5;
 
+// Version of the world save schema. Bump it whenever a change to the world cannot
+// be handled by serde defaults, and append the corresponding migration below.
+pub const SAVE_SCHEMA_VERSION: u32 = 1;
+// The migration at index i takes a world saved with schema version i to version i + 1.
+const SAVE_MIGRATIONS: [fn(&mut Value) -> AppResult<()>; SAVE_SCHEMA_VERSION as usize] =
+    [migrate_elo_ratings];
+
+// Encrypted saves start with this header, followed by the salt, the nonce and the ciphertext.
+const ENCRYPTED_SAVE_MAGIC: &[u8] = b"REBELSENC1";
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+#[derive(Serialize)]
+struct SaveFile<'a> {
+    schema_version: u32,
+    world: &'a World,
+}
+
 fn prefixed_world_filename(store_prefix: &str) -> String {
     format!("{store_prefix}_{PERSISTED_WORLD_FILENAME}")
 }
 
 fn save_to_json<T: Serialize>(filename: &str, data: &T) -> AppResult<()> {
-    let path = store_path(&format!("{filename}.json.gz"))?;
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-
-    std::fs::write(path, serialize(data)?)?;
-
-    Ok(())
+    storage().write(&format!("{filename}.json.gz"), &serialize(data)?)
 }
 
 fn load_from_json<T: for<'a> Deserialize<'a> + Serialize>(filename: &str) -> AppResult<T> {
+    let storage = storage();
+
     // New gzip format
-    if let Ok(bytes) = std::fs::read(store_path(&format!("{filename}.json.gz"))?) {
+    if let Ok(bytes) = storage.read(&format!("{filename}.json.gz")) {
         return deserialize(&bytes);
     }
 
     // This fallback serves to migrate old zlib compression to the new gz format
-    let legacy_zlib = store_path(&format!("{filename}.json.compressed"))?;
-    if let Ok(bytes) = std::fs::read(&legacy_zlib) {
+    let legacy_zlib = format!("{filename}.json.compressed");
+    if let Ok(bytes) = storage.read(&legacy_zlib) {
         let data: T = deserialize(&bytes)?;
         save_to_json(filename, &data)?; // writes .json.gz
-        if let Err(e) = std::fs::remove_file(&legacy_zlib) {
+        if let Err(e) = storage.remove(&legacy_zlib) {
             log::warn!("Failed to delete legacy file {legacy_zlib:?}: {e}");
         }
         return Ok(data);
     }
 
     // This fallback serves to migrate old files to the new gz format
-    let file = std::fs::File::open(store_path(&format!("{filename}.json"))?)?;
-    Ok(serde_json::from_reader(file)?)
+    let bytes = storage.read(&format!("{filename}.json"))?;
+    Ok(serde_json::from_slice(&bytes)?)
 }
 
 fn compress(bytes: &[u8], level: u32) -> AppResult<Vec<u8>> {
@@ -99,7 +126,7 @@ pub fn deserialize<T: for<'a> Deserialize<'a>>(bytes: &[u8]) -> AppResult<T> {
     Ok(data)
 }
 
-fn config_dirs() -> AppResult<PathBuf> {
+pub(crate) fn config_dirs() -> AppResult<PathBuf> {
     // Linux:   /home/alice/.config/rebels
     // Windows: C:\Users\Alice\AppData\Roaming\frittura\rebels
     // macOS:   /Users/Alice/Library/Application Support/org.frittura.rebels
@@ -120,47 +147,396 @@ pub fn store_path(filename: &str) -> AppResult<PathBuf> {
 
 pub fn save_world_uncompressed(world: &World, store_prefix: &str) -> AppResult<()> {
     let filename = prefixed_world_filename(store_prefix);
-    std::fs::write(
-        store_path(&format!("{filename}.json"))?,
-        &serde_json::to_string_pretty(&world)?,
-    )?;
+    storage().write(
+        &format!("{filename}.json"),
+        serde_json::to_string_pretty(&world)?.as_bytes(),
+    )
+}
+
+// Saves written before the schema was versioned stored Elo ratings, which have been
+// replaced by Glicko-2 ratings. The deviation is derived from the games already played.
+fn migrate_elo_ratings(world: &mut Value) -> AppResult<()> {
+    let Some(teams) = world.get_mut("teams").and_then(Value::as_object_mut) else {
+        return Ok(());
+    };
+
+    for team in teams.values_mut() {
+        for key in ["local_game_rating", "network_game_rating"] {
+            if let Some(rating) = team.get_mut(key).and_then(Value::as_object_mut) {
+                rating.remove("has_been_above_2400");
+                let num_games = rating
+                    .get("record")
+                    .and_then(Value::as_object)
+                    .map(|record| record.values().filter_map(Value::as_u64).sum::<u64>())
+                    .unwrap_or_default();
+                rating.insert(
+                    "deviation".into(),
+                    GameRating::migrated_deviation(num_games as usize).into(),
+                );
+            }
+        }
+    }
 
     Ok(())
 }
 
+fn migrate_world(mut value: Value) -> AppResult<World> {
+    // Saves written before the schema was versioned contain the bare world.
+    let (schema_version, mut world) = match value.get("schema_version").and_then(Value::as_u64) {
+        Some(version) => (
+            version as u32,
+            value.get_mut("world").map(Value::take).unwrap_or_default(),
+        ),
+        None => (0, value),
+    };
+
+    if schema_version > SAVE_SCHEMA_VERSION {
+        return Err(anyhow!(
+            "Save file schema v{schema_version} is newer than ours (v{SAVE_SCHEMA_VERSION}): update the game to load it"
+        ));
+    }
+
+    for (version, migration) in SAVE_MIGRATIONS
+        .iter()
+        .enumerate()
+        .skip(schema_version as usize)
+    {
+        migration(&mut world)
+            .map_err(|e| anyhow!("Failed to migrate save file from schema v{version}: {e}"))?;
+        log::info!("Migrated save file from schema v{version}");
+    }
+
+    Ok(serde_json::from_value(world)?)
+}
+
+fn save_key(passphrase: &str, salt: &[u8]) -> AppResult<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut_slice())
+        .map_err(|e| anyhow!("Failed to derive save file key: {e}"))?;
+    Ok(key)
+}
+
+fn encrypt(bytes: &[u8], passphrase: &str) -> AppResult<Vec<u8>> {
+    let salt = rand::random::<[u8; SALT_LENGTH]>();
+    let nonce = rand::random::<[u8; NONCE_LENGTH]>();
+    let cipher = ChaCha20Poly1305::new(&save_key(passphrase, &salt)?);
+
+    let mut encrypted = ENCRYPTED_SAVE_MAGIC.to_vec();
+    encrypted.extend(salt);
+    encrypted.extend(nonce);
+    encrypted.extend(
+        cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: bytes,
+                    aad: ENCRYPTED_SAVE_MAGIC,
+                },
+            )
+            .map_err(|_| anyhow!("Could not encrypt save file"))?,
+    );
+    Ok(encrypted)
+}
+
+fn decrypt(bytes: &[u8], passphrase: &str) -> AppResult<Vec<u8>> {
+    let header_length = ENCRYPTED_SAVE_MAGIC.len() + SALT_LENGTH + NONCE_LENGTH;
+    if bytes.len() < header_length {
+        return Err(anyhow!("Encrypted save file is too short"));
+    }
+
+    let (salt, rest) = bytes[ENCRYPTED_SAVE_MAGIC.len()..].split_at(SALT_LENGTH);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LENGTH);
+    let cipher = ChaCha20Poly1305::new(&save_key(passphrase, salt)?);
+    cipher
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: ENCRYPTED_SAVE_MAGIC,
+            },
+        )
+        .map_err(|_| anyhow!("Could not decrypt save file: wrong passphrase or corrupted file"))
+}
+
+fn checksum(json: &[u8]) -> String {
+    Sha256::digest(json)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+// Returns the save file bytes and the checksum of the uncompressed json.
+fn encode_world(world: &World, passphrase: Option<&str>) -> AppResult<(Vec<u8>, String)> {
+    let json = serde_json::to_vec(&SaveFile {
+        schema_version: SAVE_SCHEMA_VERSION,
+        world,
+    })?;
+    let checksum = checksum(&json);
+    let bytes = compress(&json, COMPRESSION_LEVEL)?;
+
+    match passphrase {
+        Some(passphrase) => Ok((encrypt(&bytes, passphrase)?, checksum)),
+        None => Ok((bytes, checksum)),
+    }
+}
+
+// Unencrypted save files are returned as they are.
+fn decrypt_save(bytes: &[u8], passphrase: Option<&str>) -> AppResult<Vec<u8>> {
+    if bytes.starts_with(ENCRYPTED_SAVE_MAGIC) {
+        let passphrase = passphrase
+            .ok_or_else(|| anyhow!("Save file is encrypted and no passphrase was given"))?;
+        decrypt(bytes, passphrase)
+    } else {
+        Ok(bytes.to_vec())
+    }
+}
+
+fn decompress_save(bytes: Vec<u8>) -> AppResult<Vec<u8>> {
+    // Legacy saves can be plain json.
+    if bytes.trim_ascii_start().starts_with(b"{") {
+        Ok(bytes)
+    } else {
+        decompress(&bytes)
+    }
+}
+
+fn decode_save_json(bytes: &[u8], passphrase: Option<&str>) -> AppResult<Vec<u8>> {
+    decompress_save(decrypt_save(bytes, passphrase)?)
+}
+
+fn decode_decrypted_world(bytes: Vec<u8>) -> AppResult<World> {
+    let json = decompress_save(bytes)?;
+    migrate_world(serde_json::from_slice(&json)?)
+}
+
+#[cfg(test)]
+fn decode_world(bytes: &[u8], passphrase: Option<&str>) -> AppResult<World> {
+    decode_decrypted_world(decrypt_save(bytes, passphrase)?)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldSnapshot {
+    pub timestamp: Tick,
+    pub key: String,
+}
+
+impl WorldSnapshot {
+    fn new(store_prefix: &str, timestamp: Tick) -> Self {
+        let filename = prefixed_world_filename(store_prefix);
+        Self {
+            timestamp,
+            key: format!("{PERSISTED_SNAPSHOTS_DIRECTORY}/{filename}_{timestamp}.json.gz"),
+        }
+    }
+
+    fn checksum_key(&self) -> String {
+        self.key.replace(".json.gz", ".sha256")
+    }
+}
+
+fn save_world_snapshot(
+    store_prefix: &str,
+    bytes: &[u8],
+    checksum: &str,
+    retention: usize,
+) -> AppResult<()> {
+    let storage = storage();
+    let snapshot = WorldSnapshot::new(store_prefix, Tick::now());
+    storage.write(&snapshot.key, bytes)?;
+    storage.write(&snapshot.checksum_key(), checksum.as_bytes())?;
+
+    for snapshot in list_world_snapshots(store_prefix)?.iter().skip(retention) {
+        storage.remove(&snapshot.key)?;
+        if let Err(e) = storage.remove(&snapshot.checksum_key()) {
+            log::warn!("Failed to delete snapshot checksum: {e}");
+        }
+    }
+
+    Ok(())
+}
+
+// Snapshots are sorted from the most recent.
+pub fn list_world_snapshots(store_prefix: &str) -> AppResult<Vec<WorldSnapshot>> {
+    let prefix = format!(
+        "{PERSISTED_SNAPSHOTS_DIRECTORY}/{}_",
+        prefixed_world_filename(store_prefix)
+    );
+
+    let mut snapshots = vec![];
+    for key in storage().list(&prefix)? {
+        let Some(timestamp) = key
+            .strip_prefix(&prefix)
+            .and_then(|name| name.strip_suffix(".json.gz"))
+            .and_then(|timestamp| timestamp.parse().ok())
+        else {
+            continue;
+        };
+        snapshots.push(WorldSnapshot { timestamp, key });
+    }
+
+    snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.timestamp));
+    Ok(snapshots)
+}
+
+pub fn load_world_snapshot(snapshot: &WorldSnapshot, passphrase: Option<&str>) -> AppResult<World> {
+    let storage = storage();
+    let json = decode_save_json(&storage.read(&snapshot.key)?, passphrase)?;
+    let expected_checksum = String::from_utf8(storage.read(&snapshot.checksum_key())?)?;
+    if checksum(&json) != expected_checksum.trim() {
+        return Err(anyhow!("Snapshot checksum does not match"));
+    }
+
+    migrate_world(serde_json::from_slice(&json)?)
+}
+
+// The snapshot replaces the current save file, keeping its encryption.
+pub fn restore_world_snapshot(
+    store_prefix: &str,
+    timestamp: Tick,
+    passphrase: Option<&str>,
+) -> AppResult<()> {
+    let snapshot = list_world_snapshots(store_prefix)?
+        .into_iter()
+        .find(|snapshot| snapshot.timestamp == timestamp)
+        .ok_or_else(|| anyhow!("No snapshot found with timestamp {timestamp}"))?;
+    load_world_snapshot(&snapshot, passphrase)?;
+
+    let storage = storage();
+    let filename = prefixed_world_filename(store_prefix);
+    storage.write(
+        &format!("{filename}.json.gz"),
+        &storage.read(&snapshot.key)?,
+    )
+}
+
+pub fn run_snapshots(
+    args: &SnapshotsArgs,
+    store_prefix: &str,
+    passphrase: Option<&str>,
+) -> AppResult<()> {
+    if let Some(timestamp) = args.restore {
+        restore_world_snapshot(store_prefix, timestamp, passphrase)?;
+        println!("Restored snapshot {timestamp}.");
+        return Ok(());
+    }
+
+    let snapshots = list_world_snapshots(store_prefix)?;
+    if snapshots.is_empty() {
+        println!("No snapshots found for '{store_prefix}'.");
+    }
+
+    for snapshot in snapshots.iter() {
+        let date: DateTime<Local> = snapshot.timestamp.as_system_time().into();
+        let status = match load_world_snapshot(snapshot, passphrase) {
+            Ok(_) => "valid".to_string(),
+            Err(e) => format!("invalid: {e}"),
+        };
+        println!(
+            "{}  {}  {status}",
+            snapshot.timestamp,
+            date.format("%Y-%m-%d %H:%M:%S")
+        );
+    }
+
+    Ok(())
+}
+
+// Unencrypted saves are still loaded when a passphrase is given, and are encrypted on the next save.
+// A snapshot is also written when a retention is given, keeping only the most recent ones.
 pub fn save_world(
     world: &World,
     store_prefix: &str,
-    with_backup: bool,
+    passphrase: Option<&str>,
+    snapshot_retention: Option<usize>,
     with_uncompressed: bool,
 ) -> AppResult<()> {
     let data = world.to_store()?;
     let filename = prefixed_world_filename(store_prefix);
-    save_to_json(&filename, &data)?;
-    if with_backup {
-        let backup_filename = format!("{filename}.back");
-        save_to_json(&backup_filename, &data)?;
+    let (bytes, checksum) = encode_world(&data, passphrase)?;
+    storage().write(&format!("{filename}.json.gz"), &bytes)?;
+    if let Some(retention) = snapshot_retention.filter(|retention| *retention > 0) {
+        save_world_snapshot(store_prefix, &bytes, &checksum, retention)?;
     }
 
     if with_uncompressed {
-        std::fs::write(
-            store_path(&format!("{filename}.json"))?,
-            &serde_json::to_string_pretty(&data)?,
-        )?;
+        if passphrase.is_some() {
+            log::warn!("Not saving uncompressed world: the save file is encrypted");
+        } else {
+            storage().write(
+                &format!("{filename}.json"),
+                serde_json::to_string_pretty(&data)?.as_bytes(),
+            )?;
+        }
     }
 
     Ok(())
 }
 
-pub fn load_world(store_prefix: &str) -> AppResult<World> {
-    load_from_json::<World>(&prefixed_world_filename(store_prefix))
+// Also returns the timestamp of the snapshot the world was loaded from, if the save file was corrupted.
+pub fn load_world(
+    store_prefix: &str,
+    passphrase: Option<&str>,
+) -> AppResult<(World, Option<Tick>)> {
+    let filename = prefixed_world_filename(store_prefix);
+
+    // Legacy zlib and plain json saves are replaced by the next save.
+    for name in [
+        format!("{filename}.json.gz"),
+        format!("{filename}.json.compressed"),
+        format!("{filename}.json"),
+    ] {
+        if let Ok(bytes) = storage().read(&name) {
+            // A wrong or missing passphrase must not load an older, unencrypted snapshot.
+            let bytes = decrypt_save(&bytes, passphrase)?;
+            return decode_decrypted_world(bytes)
+                .map(|world| (world, None))
+                .or_else(|e| {
+                    log::error!("Failed to load world: {e}");
+                    load_last_valid_world_snapshot(store_prefix, passphrase)
+                        .map(|(world, timestamp)| (world, Some(timestamp)))
+                        .map_err(|_| e)
+                });
+        }
+    }
+
+    Err(anyhow!("No world file found for prefix '{store_prefix}'"))
+}
+
+fn load_last_valid_world_snapshot(
+    store_prefix: &str,
+    passphrase: Option<&str>,
+) -> AppResult<(World, Tick)> {
+    for snapshot in list_world_snapshots(store_prefix)? {
+        match load_world_snapshot(&snapshot, passphrase) {
+            Ok(world) => {
+                log::warn!("Loaded world from snapshot {}", snapshot.timestamp);
+                return Ok((world, snapshot.timestamp));
+            }
+            Err(e) => log::warn!("Skipping snapshot {}: {e}", snapshot.timestamp),
+        }
+    }
+
+    Err(anyhow!(
+        "No valid snapshot found for prefix '{store_prefix}'"
+    ))
 }
 
 pub fn save_game(game: &Game) -> AppResult<()> {
     save_to_json(&format!("{}{}", PERSISTED_GAMES_PREFIX, game.id), game)?;
+    storage().index_game(&GameSummary::from_game(game))?;
     Ok(())
 }
 
+// Load a replay from any location, either as plain or compressed json.
+pub fn load_game_replay_file(path: &Path) -> AppResult<GameReplay> {
+    let bytes = std::fs::read(path)?;
+    if let Ok(replay) = serde_json::from_slice(&bytes) {
+        return Ok(replay);
+    }
+    deserialize(&bytes)
+}
+
 pub fn load_game(game_id: &GameId) -> AppResult<Game> {
     // FIXME: remove this code, currently needed for migrating to new folder
     if let Ok(game) = load_from_json::<Game>(&format!("{PERSISTED_GAMES_PREFIX}{game_id}")) {
@@ -179,6 +555,9 @@ pub fn save_tournament(tournament: &Tournament) -> AppResult<()> {
         &format!("{}{}", PERSISTED_TOURNAMENTS_PREFIX, tournament.id),
         tournament,
     )?;
+    if tournament.has_ended() {
+        storage().index_tournament(&TournamentSummary::from_tournament(tournament))?;
+    }
     Ok(())
 }
 
@@ -186,6 +565,77 @@ pub fn load_tournament(tournament_id: &TournamentId) -> AppResult<Tournament> {
     load_from_json::<Tournament>(&format!("{PERSISTED_TOURNAMENTS_PREFIX}{tournament_id}"))
 }
 
+pub fn query_past_games(query: &HistoryQuery) -> AppResult<Vec<GameSummary>> {
+    storage().query_games(query)
+}
+
+pub fn query_past_tournaments(query: &HistoryQuery) -> AppResult<Vec<TournamentSummary>> {
+    storage().query_tournaments(query)
+}
+
+fn history(args: &HistoryArgs) -> AppResult<String> {
+    let query = HistoryQuery {
+        team_id: args.team_id,
+        since: args.since,
+        until: args.until,
+        limit: args.limit,
+    };
+
+    if args.tournaments {
+        let tournaments = query_past_tournaments(&query)?;
+        return match args.format {
+            ReportFormat::Json => Ok(serde_json::to_string_pretty(&tournaments)?),
+            ReportFormat::Csv => {
+                let mut csv = "id,name,ended_at,participants,winner\n".to_string();
+                for tournament in tournaments.iter() {
+                    csv.push_str(&format!(
+                        "{},{},{},{},{}\n",
+                        tournament.id,
+                        csv_field(&tournament.name()),
+                        tournament.ended_at.unwrap_or_default(),
+                        tournament.participant_ids.len(),
+                        csv_field(&tournament.winner_name)
+                    ));
+                }
+                Ok(csv)
+            }
+        };
+    }
+
+    let games = query_past_games(&query)?;
+    match args.format {
+        ReportFormat::Json => Ok(serde_json::to_string_pretty(&games)?),
+        ReportFormat::Csv => {
+            let mut csv =
+                "id,starting_at,home_team,away_team,home_score,away_score,overtimes\n".to_string();
+            for game in games.iter() {
+                let (home_score, away_score) = game.get_score();
+                csv.push_str(&format!(
+                    "{},{},{},{},{home_score},{away_score},{}\n",
+                    game.id,
+                    game.starting_at,
+                    csv_field(&game.home_team_name),
+                    csv_field(&game.away_team_name),
+                    game.overtimes
+                ));
+            }
+            Ok(csv)
+        }
+    }
+}
+
+pub fn run_history(args: &HistoryArgs) -> AppResult<()> {
+    let formatted = history(args)?;
+
+    if let Some(path) = args.output.as_ref() {
+        std::fs::write(path, formatted)?;
+    } else {
+        println!("{formatted}");
+    }
+
+    Ok(())
+}
+
 #[cfg(feature = "relayer")]
 pub fn load_relayer_messages() -> AppResult<Vec<String>> {
     // Load every message in the 'relayer/messages' directory.
@@ -209,6 +659,50 @@ pub fn load_relayer_messages() -> AppResult<Vec<String>> {
     Ok(messages)
 }
 
+#[cfg(feature = "relayer")]
+fn relayer_ban_list_path() -> AppResult<PathBuf> {
+    Ok(config_dirs()?.join("relayer/ban_list"))
+}
+
+#[cfg(feature = "relayer")]
+pub fn relayer_ban_list_modified_at() -> Option<Tick> {
+    let metadata = std::fs::metadata(relayer_ban_list_path().ok()?).ok()?;
+    metadata.modified().ok().map(Tick::from_system_time)
+}
+
+// The ban list is opt-in: one peer id per line in 'relayer/ban_list',
+// lines starting with '#' are comments. Invalid lines are skipped,
+// so that a typo does not lift the other bans.
+#[cfg(feature = "relayer")]
+fn parse_relayer_ban_list(ban_list: &str) -> Vec<libp2p::PeerId> {
+    let mut banned_peer_ids = vec![];
+    for (idx, line) in ban_list.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.parse() {
+            Ok(peer_id) => banned_peer_ids.push(peer_id),
+            Err(e) => log::warn!(
+                "Skipping invalid peer id on line {} of the ban list: {e}",
+                idx + 1
+            ),
+        }
+    }
+
+    banned_peer_ids
+}
+
+#[cfg(feature = "relayer")]
+pub fn load_relayer_ban_list() -> AppResult<Vec<libp2p::PeerId>> {
+    let path = relayer_ban_list_path()?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    Ok(parse_relayer_ban_list(&std::fs::read_to_string(&path)?))
+}
+
 #[cfg(feature = "relayer")]
 pub fn save_relayer_network_store_data(
     network_store_data: &NetworkStoreData,
@@ -231,23 +725,39 @@ pub fn load_relayer_network_store_data() -> AppResult<NetworkStoreData> {
 }
 
 pub fn get_world_size(store_prefix: &str) -> AppResult<u64> {
-    let size = world_file_data(store_prefix)?.len();
+    let size = world_file_data(store_prefix)?.size;
     Ok(size)
 }
 
-pub fn reset_store() -> AppResult<()> {
-    let dirs = directories::ProjectDirs::from("org", "frittura", "rebels")
-        .ok_or_else(|| anyhow!("Failed to get directories"))?;
-    let config_dirs = dirs.config_dir();
-    if config_dirs.exists() {
-        std::fs::remove_dir_all(config_dirs)?;
+// Only the world of the given prefix and its snapshots are removed, so that resetting
+// does not touch the worlds of the other SSH users. Past games and tournaments are kept.
+pub fn reset_store(store_prefix: &str) -> AppResult<()> {
+    let filename = prefixed_world_filename(store_prefix);
+    let storage = storage();
+
+    for key in [
+        format!("{filename}.json.gz"),
+        format!("{filename}.json.compressed"),
+        format!("{filename}.json"),
+    ] {
+        if storage.exists(&key) {
+            storage.remove(&key)?;
+        }
+    }
+
+    for snapshot in list_world_snapshots(store_prefix)? {
+        storage.remove(&snapshot.key)?;
+        if storage.exists(&snapshot.checksum_key()) {
+            storage.remove(&snapshot.checksum_key())?;
+        }
     }
-    std::fs::create_dir_all(config_dirs)?;
+
     Ok(())
 }
 
 pub fn save_game_exists(store_prefix: &str) -> bool {
     let filename = prefixed_world_filename(store_prefix);
+    let storage = storage();
 
     [
         format!("{filename}.json.gz"),
@@ -255,21 +765,20 @@ pub fn save_game_exists(store_prefix: &str) -> bool {
         format!("{filename}.json"),
     ]
     .iter()
-    .any(|f| store_path(f).map(|p| p.exists()).unwrap_or(false))
+    .any(|f| storage.exists(f))
 }
 
 pub fn save_data<C: AsRef<[u8]>>(filename: &str, data: &C) -> AppResult<()> {
-    std::fs::write(store_path(filename)?, data)?;
-    Ok(())
+    storage().write(filename, data.as_ref())
 }
 
 pub fn load_data(filename: &str) -> AppResult<Vec<u8>> {
-    let bytes = std::fs::read(store_path(filename)?)?;
-    Ok(bytes)
+    storage().read(filename)
 }
 
-pub fn world_file_data(store_prefix: &str) -> AppResult<std::fs::Metadata> {
+pub fn world_file_data(store_prefix: &str) -> AppResult<StorageMetadata> {
     let filename = prefixed_world_filename(store_prefix);
+    let storage = storage();
 
     let candidates = [
         format!("{filename}.json.gz"),
@@ -278,10 +787,8 @@ pub fn world_file_data(store_prefix: &str) -> AppResult<std::fs::Metadata> {
     ];
 
     for name in candidates {
-        if let Ok(path) = store_path(&name) {
-            if let Ok(metadata) = std::fs::metadata(&path) {
-                return Ok(metadata);
-            }
+        if let Ok(metadata) = storage.metadata(&name) {
+            return Ok(metadata);
         }
     }
 
@@ -291,8 +798,14 @@ pub fn world_file_data(store_prefix: &str) -> AppResult<std::fs::Metadata> {
 #[cfg(test)]
 mod tests {
     use crate::{
-        core::{player::Player, team::Team, world::World, MIN_PLAYERS_PER_GAME},
-        types::{AppResult, PlayerMap},
+        args::{HistoryArgs, ReportFormat},
+        core::{player::Player, team::Team, world::World, GameRating, MIN_PLAYERS_PER_GAME},
+        game_engine::{
+            game::{Game, GameSummary},
+            types::TeamInGame,
+        },
+        storage::{game_summary_key, storage},
+        types::{AppResult, PlayerMap, SystemTimeTick, Tick},
     };
     use directories;
     use itertools::Itertools;
@@ -324,26 +837,227 @@ mod tests {
         let mut world = World::new(None);
         world.initialize(true)?;
         world.own_team_id = world.teams.keys().collect_vec()[0].clone();
-        super::save_world(&world, store_prefix, false, false)?;
-        let _ = super::load_world(store_prefix)?;
+        super::save_world(&world, store_prefix, None, None, false)?;
+        let (_, snapshot) = super::load_world(store_prefix, None)?;
+        assert!(snapshot.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_world_snapshots() -> AppResult<()> {
+        use super::{list_world_snapshots, load_world, restore_world_snapshot, save_world};
+
+        let store_prefix = "test_snapshots";
+        let mut world = World::new(None);
+        world.initialize(true)?;
+        world.own_team_id = *world.teams.keys().collect_vec()[0];
+
+        for _ in 0..3 {
+            save_world(&world, store_prefix, None, Some(2), false)?;
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        let snapshots = list_world_snapshots(store_prefix)?;
+        assert_eq!(snapshots.len(), 2);
+        assert!(snapshots[0].timestamp > snapshots[1].timestamp);
+
+        // A corrupted save falls back to the last snapshot with a valid checksum.
+        let storage = storage();
+        let filename = super::prefixed_world_filename(store_prefix);
+        storage.write(&format!("{filename}.json.gz"), b"corrupted")?;
+        storage.write(&snapshots[0].checksum_key(), b"0")?;
+        let (loaded, snapshot) = load_world(store_prefix, None)?;
+        assert_eq!(loaded.own_team_id, world.own_team_id);
+        assert_eq!(snapshot, Some(snapshots[1].timestamp));
+
+        assert!(restore_world_snapshot(store_prefix, snapshots[0].timestamp, None).is_err());
+        restore_world_snapshot(store_prefix, snapshots[1].timestamp, None)?;
+        assert_eq!(
+            super::decode_world(&storage.read(&format!("{filename}.json.gz"))?, None)?.own_team_id,
+            world.own_team_id
+        );
+
+        for snapshot in snapshots {
+            storage.remove(&snapshot.checksum_key())?;
+            storage.remove(&snapshot.key)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_reset_store_keeps_other_worlds() -> AppResult<()> {
+        use super::{list_world_snapshots, reset_store, save_game_exists, save_world};
+
+        let mut world = World::new(None);
+        world.initialize(true)?;
+        world.own_team_id = *world.teams.keys().collect_vec()[0];
+
+        save_world(&world, "test_reset", None, Some(1), false)?;
+        save_world(&world, "test_reset_other", None, Some(1), false)?;
+
+        reset_store("test_reset")?;
+        assert!(!save_game_exists("test_reset"));
+        assert!(list_world_snapshots("test_reset")?.is_empty());
+        assert!(save_game_exists("test_reset_other"));
+        assert_eq!(list_world_snapshots("test_reset_other")?.len(), 1);
+
+        reset_store("test_reset_other")?;
+        assert!(!save_game_exists("test_reset_other"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrong_passphrase_does_not_load_snapshot() -> AppResult<()> {
+        use super::{list_world_snapshots, load_world, save_world};
+
+        let store_prefix = "test_snapshots_passphrase";
+        let mut world = World::new(None);
+        world.initialize(true)?;
+        world.own_team_id = *world.teams.keys().collect_vec()[0];
+
+        // The snapshot was taken before the save was encrypted.
+        save_world(&world, store_prefix, None, Some(1), false)?;
+        save_world(&world, store_prefix, Some("passphrase"), None, false)?;
+
+        assert!(load_world(store_prefix, None).is_err());
+        assert!(load_world(store_prefix, Some("wrong")).is_err());
+        let (loaded, snapshot) = load_world(store_prefix, Some("passphrase"))?;
+        assert_eq!(loaded.own_team_id, world.own_team_id);
+        assert!(snapshot.is_none());
+
+        let storage = storage();
+        for snapshot in list_world_snapshots(store_prefix)? {
+            storage.remove(&snapshot.checksum_key())?;
+            storage.remove(&snapshot.key)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_legacy_save_fixture() -> AppResult<()> {
+        use super::{decode_world, decompress, encode_world, migrate_world, SAVE_SCHEMA_VERSION};
+
+        // Saved by the 1.6.1 release, before the schema was versioned, with Elo ratings.
+        let bytes = include_bytes!("../tests/save_fixtures/world_v1_6_1.json.gz");
+        let legacy: serde_json::Value = serde_json::from_slice(&decompress(bytes)?)?;
+        let world = decode_world(bytes, None)?;
+        assert_eq!(world.past_games.len(), 45);
+
+        let mut deviations = vec![];
+        for team in world.teams.values() {
+            let rating = &team.local_game_rating;
+            let legacy_rating = &legacy["teams"][team.id.to_string()]["local_game_rating"];
+            if legacy_rating.is_null() {
+                assert_eq!(rating, &GameRating::default());
+                continue;
+            }
+
+            assert_eq!(
+                Some(rating.rating),
+                legacy_rating["rating"].as_f64().map(|rating| rating as f32)
+            );
+            assert!(rating.num_games() > 0);
+            assert_eq!(
+                rating.deviation,
+                GameRating::migrated_deviation(rating.num_games())
+            );
+            deviations.push((rating.num_games(), rating.deviation));
+        }
+
+        // Teams that played more games before the migration have more reliable ratings.
+        deviations.sort_by_key(|(num_games, _)| *num_games);
+        assert!(deviations[0].0 < deviations[deviations.len() - 1].0);
+        assert!(deviations
+            .windows(2)
+            .all(|pair| pair[0].0 == pair[1].0 || pair[0].1 > pair[1].1));
+
+        let reloaded = decode_world(&encode_world(&world, None)?.0, None)?;
+        assert_eq!(reloaded.own_team_id, world.own_team_id);
+        assert_eq!(reloaded.teams, world.teams);
+
+        let newer = serde_json::json!({
+            "schema_version": SAVE_SCHEMA_VERSION + 1,
+            "world": {},
+        });
+        assert!(migrate_world(newer).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_history() -> AppResult<()> {
+        use super::{history, save_game, PERSISTED_GAMES_PREFIX};
+
+        let game = Game::test(TeamInGame::test(), TeamInGame::test());
+        save_game(&game)?;
+
+        let mut args = HistoryArgs {
+            tournaments: false,
+            team_id: Some(game.home_team_in_game.team_id),
+            since: None,
+            until: None,
+            limit: None,
+            format: ReportFormat::Json,
+            output: None,
+        };
+        let games: Vec<GameSummary> = serde_json::from_str(&history(&args)?)?;
+        assert_eq!(games, vec![GameSummary::from_game(&game)]);
+
+        args.format = ReportFormat::Csv;
+        let csv = history(&args)?;
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.contains(&game.id.to_string()));
+
+        args.tournaments = true;
+        assert_eq!(history(&args)?.lines().count(), 1);
+
+        let storage = storage();
+        storage.remove(&format!("{PERSISTED_GAMES_PREFIX}{}.json.gz", game.id))?;
+        storage.remove(&game_summary_key(&game.id))?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_save() -> AppResult<()> {
+        use super::{decode_world, encode_world, ENCRYPTED_SAVE_MAGIC};
+
+        let mut world = World::new(Some(0));
+        world.initialize(true)?;
+        world.own_team_id = *world.teams.keys().collect_vec()[0];
+
+        let (encrypted, _) = encode_world(&world, Some("passphrase"))?;
+        assert!(encrypted.starts_with(ENCRYPTED_SAVE_MAGIC));
+
+        let decrypted = decode_world(&encrypted, Some("passphrase"))?;
+        assert_eq!(decrypted.teams, world.teams);
+        assert!(decode_world(&encrypted, None).is_err());
+        assert!(decode_world(&encrypted, Some("wrong")).is_err());
+
+        // Unencrypted saves can be loaded with a passphrase, to be encrypted on the next save.
+        let (plain, _) = encode_world(&world, None)?;
+        assert_eq!(
+            decode_world(&plain, Some("passphrase"))?.own_team_id,
+            world.own_team_id
+        );
         Ok(())
     }
 
     #[test]
     fn test_serialize_network_data() -> AppResult<()> {
         use super::{deserialize, serialize};
-        use crate::network::types::{NetworkData, NetworkTeam};
+        use crate::network::types::{ChatChannel, NetworkData, NetworkTeam};
         let value = NetworkData::Message {
             timestamp: 0,
             from_peer_id: PeerId::random(),
             author: "Test".to_string(),
             message: "Hello".to_string(),
+            channel: ChatChannel::Planet {
+                planet_id: uuid::Uuid::new_v4(),
+            },
         };
         let serialized_data = serialize(&value)?;
         let deserialized_data = deserialize(&serialized_data)?;
         assert!(value == deserialized_data);
 
-        let mut team = Team::random(None);
+        let mut team = Team::random(None, Tick::now());
 
         let mut players = PlayerMap::new();
         for _ in 0..MIN_PLAYERS_PER_GAME {
@@ -364,6 +1078,14 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "relayer")]
+    #[test]
+    fn test_invalid_ban_list_lines_are_skipped() {
+        let peer_id = PeerId::random();
+        let ban_list = format!("# Spammers\n{peer_id}\nnot a peer id\n\n");
+        assert_eq!(super::parse_relayer_ban_list(&ban_list), vec![peer_id]);
+    }
+
     #[cfg(feature = "relayer")]
     #[test]
     #[ignore]
@@ -23,13 +23,15 @@ use crate::{
     core::*,
     game_engine::{
         action::{ActionOutput, ActionSituation, Advantage},
+        adjustment::{suggested_substitution, GameAdjustmentKind},
         game::Game,
+        tactic::{CustomTactic, Tactic},
         timer::{Period, Timer},
         types::{GameStatsMap, Possession},
     },
     image::game::PitchImage,
     image::player::{PLAYER_IMAGE_HEIGHT, PLAYER_IMAGE_WIDTH},
-    types::{AppResult, GameId, SystemTimeTick, Tick},
+    types::{AppResult, GameId, SystemTimeTick},
     ui::constants::*,
 };
 use core::fmt::Debug;
@@ -45,6 +47,7 @@ use ratatui::{
     widgets::{Cell, Paragraph, Row, Table, Wrap},
 };
 use std::collections::HashMap;
+use strum::IntoEnumIterator;
 
 #[derive(Debug, Default)]
 pub struct GamePanel {
@@ -646,7 +649,7 @@ impl GamePanel {
 
             if let Some(side) = should_display_shot_gif_for {
                 let shot_tick = game.starting_at + last_action.start_at.as_tick();
-                let now = Tick::now();
+                let now = world.clock.now();
                 let shot_frame = now.saturating_sub(shot_tick) as usize / 140;
                 if shot_frame < RIGHT_SHOT_GIF.len() {
                     // After scoring the possesion is flipped, so the opposite team scored.
@@ -667,15 +670,142 @@ impl GamePanel {
             self.build_commentary(frame, game, split[0]);
         }
 
+        // Coaching buttons are shown only for the live game of the own team.
+        let box_area = if game.team_in_game(world.own_team_id).is_some() && !game.has_ended() {
+            let box_split =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).split(split[1]);
+            Self::build_coach_buttons(game, world, frame, box_split[1])?;
+            box_split[0]
+        } else {
+            split[1]
+        };
+
         if self.player_status_view {
-            Self::build_status_box(game, frame, split[1]);
+            Self::build_status_box(game, frame, box_area);
         } else {
-            Self::build_stats_box(game, frame, split[1]);
+            Self::build_stats_box(game, frame, box_area);
         }
 
         Ok(())
     }
 
+    fn build_coach_buttons(
+        game: &Game,
+        world: &World,
+        frame: &mut UiFrame,
+        area: Rect,
+    ) -> AppResult<()> {
+        let own_team = world.get_own_team()?;
+        let team_in_game = if let Some(team) = game.team_in_game(own_team.id) {
+            team
+        } else {
+            return Ok(());
+        };
+
+        let split = Layout::horizontal([
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+        ])
+        .split(area);
+
+        let timeouts_left = game.timeouts_left(own_team.id);
+        let mut timeout_button = Button::new(
+            format!("Timeout ({timeouts_left})"),
+            UiCallback::AdjustGame {
+                game_id: game.id,
+                kind: GameAdjustmentKind::Timeout,
+            },
+        )
+        .set_hover_text("Call a timeout at the next dead ball to let the players catch some breath.")
+        .set_hotkey(ui_key::game::TIMEOUT);
+        if timeouts_left == 0 {
+            timeout_button.disable(Some("No timeouts left"));
+        } else if game.is_in_timeout() {
+            timeout_button.disable(Some("Timeout in progress"));
+        }
+        frame.render_interactive_widget(timeout_button, split[0]);
+
+        // The tactic the team will play with once the pending changes are applied.
+        let planned = game
+            .pending_adjustments
+            .iter()
+            .filter(|a| a.team_id == own_team.id)
+            .filter_map(|a| match &a.kind {
+                GameAdjustmentKind::ChangeTactic {
+                    tactic,
+                    custom_tactic,
+                } => Some((*tactic, custom_tactic.clone())),
+                _ => None,
+            })
+            .last()
+            .unwrap_or((team_in_game.tactic, team_in_game.custom_tactic.clone()));
+
+        let options = Tactic::iter()
+            .map(|tactic| (tactic, None))
+            .chain(
+                own_team
+                    .custom_tactics
+                    .iter()
+                    .map(|custom| (custom.base, Some(custom.clone()))),
+            )
+            .collect_vec();
+        let next = options
+            .iter()
+            .position(|option| *option == planned)
+            .map(|index| options[(index + 1) % options.len()].clone())
+            .unwrap_or_else(|| options[0].clone());
+
+        let tactic_name = |(tactic, custom_tactic): &(Tactic, Option<CustomTactic>)| {
+            custom_tactic
+                .as_ref()
+                .map(|custom| custom.name.clone())
+                .unwrap_or_else(|| tactic.to_string())
+        };
+
+        let tactic_button = Button::new(
+            format!("Tactic: {}", tactic_name(&planned)),
+            UiCallback::AdjustGame {
+                game_id: game.id,
+                kind: GameAdjustmentKind::ChangeTactic {
+                    tactic: next.0,
+                    custom_tactic: next.1.clone(),
+                },
+            },
+        )
+        .set_hover_text(format!(
+            "Switch to {} at the next break or timeout.",
+            tactic_name(&next)
+        ))
+        .set_hotkey(ui_key::game::CHANGE_TACTIC);
+        frame.render_interactive_widget(tactic_button, split[1]);
+
+        let substitution_button =
+            if let Some((player_out, player_in)) = suggested_substitution(team_in_game) {
+                let out_name = team_in_game.players.get_or_err(&player_out)?.info.short_name();
+                let in_name = team_in_game.players.get_or_err(&player_in)?.info.short_name();
+                Button::new(
+                    format!("Rest {out_name}"),
+                    UiCallback::AdjustGame {
+                        game_id: game.id,
+                        kind: GameAdjustmentKind::Substitution {
+                            player_out,
+                            player_in,
+                        },
+                    },
+                )
+                .set_hover_text(format!(
+                    "Substitute {out_name} with {in_name} at the next dead ball."
+                ))
+            } else {
+                Button::new("Rest", UiCallback::None).disabled(Some("No substitution available"))
+            }
+            .set_hotkey(ui_key::game::SUBSTITUTION);
+        frame.render_interactive_widget(substitution_button, split[2]);
+
+        Ok(())
+    }
+
     fn format_commentary(
         &'_ self,
         action_result: &ActionOutput,
@@ -747,12 +877,15 @@ impl GamePanel {
         let mut made_2pt_total = 0;
         let mut attempted_3pt_total = 0;
         let mut made_3pt_total = 0;
+        let mut attempted_ft_total = 0;
+        let mut made_ft_total = 0;
         let mut assists_total = 0;
         let mut turnovers_total = 0;
         let mut defensive_rebounds_total = 0;
         let mut offensive_rebounds_total = 0;
         let mut steals_total = 0;
         let mut blocks_total = 0;
+        let mut fouls_total = 0;
         let mut brawls_total = 0;
         let mut plus_minus_total = 0;
 
@@ -763,12 +896,15 @@ impl GamePanel {
             made_2pt_total += player_data.made_2pt;
             attempted_3pt_total += player_data.attempted_3pt;
             made_3pt_total += player_data.made_3pt;
+            attempted_ft_total += player_data.attempted_ft;
+            made_ft_total += player_data.made_ft;
             assists_total += player_data.assists;
             turnovers_total += player_data.turnovers;
             defensive_rebounds_total += player_data.defensive_rebounds;
             offensive_rebounds_total += player_data.offensive_rebounds;
             steals_total += player_data.steals;
             blocks_total += player_data.blocks;
+            fouls_total += player_data.fouls;
             brawls_total += player_data.brawls.iter().sum::<u16>();
             plus_minus_total += player_data.plus_minus as i16;
 
@@ -799,6 +935,10 @@ impl GamePanel {
                     "{:>2}/{:<2}",
                     players_data[&player.id].made_3pt, players_data[&player.id].attempted_3pt
                 )),
+                Cell::from(format!(
+                    "{:>2}/{:<2}",
+                    players_data[&player.id].made_ft, players_data[&player.id].attempted_ft
+                )),
                 Cell::from(format!(
                     "{:>3}/{:<2}",
                     players_data[&player.id].assists, players_data[&player.id].turnovers
@@ -810,6 +950,7 @@ impl GamePanel {
                 )),
                 Cell::from(format!("{:^3}", players_data[&player.id].steals)),
                 Cell::from(format!("{:^3}", players_data[&player.id].blocks)),
+                Cell::from(format!("{:^3}", players_data[&player.id].fouls)),
                 Cell::from(format!(
                     "{:^3}",
                     players_data[&player.id].brawls.iter().sum::<u16>()
@@ -831,12 +972,14 @@ impl GamePanel {
             Cell::from(format!("{points_total:^3}")),
             Cell::from(format!("{made_2pt_total:>2}/{attempted_2pt_total:<2}")),
             Cell::from(format!("{made_3pt_total:>2}/{attempted_3pt_total:<2}")),
+            Cell::from(format!("{made_ft_total:>2}/{attempted_ft_total:<2}")),
             Cell::from(format!("{assists_total:>3}/{turnovers_total:<2}")),
             Cell::from(format!(
                 "{defensive_rebounds_total:>3}/{offensive_rebounds_total:<3}"
             )),
             Cell::from(format!("{steals_total:^3}")),
             Cell::from(format!("{blocks_total:^3}")),
+            Cell::from(format!("{fouls_total:^3}")),
             Cell::from(format!("{brawls_total:^3}")),
             Cell::from(format!("{:>+3}", plus_minus_total / 5)),
         ];
@@ -852,10 +995,12 @@ impl GamePanel {
                 Constraint::Length(4),
                 Constraint::Length(6),
                 Constraint::Length(5),
+                Constraint::Length(5),
                 Constraint::Length(6),
                 Constraint::Length(7),
                 Constraint::Length(3),
                 Constraint::Length(3),
+                Constraint::Length(3),
                 Constraint::Length(2),
                 Constraint::Length(3),
             ],
@@ -938,8 +1083,11 @@ impl GamePanel {
                     .saturating_sub(world.last_tick_short_interval)
                     .formatted()
             )));
-        } else if timer.has_ended() {
-            timer_lines.push(Line::from(Timer::from(timer.period().end()).format()));
+        } else if game.has_ended() && timer.period() == game.timer.period() {
+            // The game ended at the start of this break.
+            timer_lines.push(Line::from(
+                Timer::from(timer.period().previous().end()).format(),
+            ));
         } else if timer.is_break() {
             timer_lines.push(Line::from(Timer::from(timer.period().end()).format()));
             timer_lines.push(Line::from(format!(
@@ -1014,7 +1162,7 @@ impl GamePanel {
         frame.render_widget(home_table, home_box_split[0]);
         frame.render_widget(
             Span::styled(
-                format!("   Tactic: {}", game.home_team_in_game.tactic),
+                format!("   Tactic: {}", game.home_team_in_game.tactic_name()),
                 UiStyle::HIGHLIGHT,
             ),
             home_box_split[1],
@@ -1023,7 +1171,7 @@ impl GamePanel {
         frame.render_widget(away_table, away_box_split[0]);
         frame.render_widget(
             Span::styled(
-                format!("   Tactic: {}", game.away_team_in_game.tactic),
+                format!("   Tactic: {}", game.away_team_in_game.tactic_name()),
                 UiStyle::HIGHLIGHT,
             ),
             away_box_split[1],
@@ -1038,10 +1186,12 @@ impl GamePanel {
             "Pts",
             " 2pt ",
             " 3pt ",
+            " ft  ",
             "Ast/TO",
             "DRb/ORb",
             "Stl",
             "Blk",
+            "PF",
             "Brw",
             "+/-",
         ];
@@ -1053,10 +1203,12 @@ impl GamePanel {
             "Pts",
             " 2pt ",
             " 3pt ",
+            " ft  ",
             "Ast/TO",
             "DRb/ORb",
             "Stl",
             "Blk",
+            "PF",
             "Brw",
             "+/-",
         ];
@@ -1081,10 +1233,12 @@ impl GamePanel {
             Constraint::Length(3),                          //points
             Constraint::Length(6),                          //2pt
             Constraint::Length(5),                          //3pt
+            Constraint::Length(5),                          //free throws
             Constraint::Length(6),                          //assists/turnovers
             Constraint::Length(7),                          //defensive rebounds/offensive rebounds
             Constraint::Length(3),                          //steals
             Constraint::Length(3),                          //blocks
+            Constraint::Length(3),                          //fouls
             Constraint::Length(3),                          //brawls
             Constraint::Length(3),                          //plus minus
             Constraint::Fill(1),
@@ -11,6 +11,10 @@ use super::{
     utils::format_satoshi,
     widgets::*,
 };
+use crate::game_engine::tactic::{
+    CustomTactic, MAX_ACTION_WEIGHT, MAX_CUSTOM_TACTICS, MAX_FASTBREAK_AGGRESSIVENESS,
+    MAX_TIREDNESS_BUDGET, MIN_FASTBREAK_AGGRESSIVENESS, MIN_TIREDNESS_BUDGET,
+};
 use crate::game_engine::timer::Period;
 use crate::types::{HashMapWithResult, Tick};
 use crate::ui::popup_message::PopupMessage;
@@ -41,6 +45,7 @@ pub enum MyTeamView {
     #[default]
     Info,
     Team,
+    Tactics,
     Games,
     Market,
     Shipyard,
@@ -51,7 +56,8 @@ impl MyTeamView {
     const fn next(&self) -> Self {
         match self {
             Self::Info => Self::Team,
-            Self::Team => Self::Games,
+            Self::Team => Self::Tactics,
+            Self::Tactics => Self::Games,
             Self::Games => Self::Market,
             Self::Market => Self::Shipyard,
             Self::Shipyard => Self::Asteroids,
@@ -75,6 +81,8 @@ pub struct MyTeamPanel {
     planet_index: Option<usize>,
     spaceship_upgrade_index: usize,
     asteroid_index: Option<usize>,
+    tactic_index: Option<usize>,
+    max_tactic_index: usize,
     view: MyTeamView,
     player_widget_view: PlayerWidgetView,
     active_list: PanelList,
@@ -116,6 +124,16 @@ impl MyTeamPanel {
         .set_hotkey(ui_key::CYCLE_VIEW)
         .set_hover_text("View team information.");
 
+        let mut view_tactics_button = Button::new(
+            "Tactics",
+            UiCallback::SetMyTeamPanelView {
+                view: MyTeamView::Tactics,
+            },
+        )
+        .bold()
+        .set_hotkey(ui_key::CYCLE_VIEW)
+        .set_hover_text("View and edit custom tactics.");
+
         let mut view_games_button = Button::new(
             "Games",
             UiCallback::SetMyTeamPanelView {
@@ -167,6 +185,7 @@ impl MyTeamPanel {
         match self.view {
             MyTeamView::Info => view_info_button.select(),
             MyTeamView::Team => view_team_button.select(),
+            MyTeamView::Tactics => view_tactics_button.select(),
             MyTeamView::Games => view_games_button.select(),
             MyTeamView::Market => view_market_button.select(),
             MyTeamView::Shipyard => view_shipyard_button.select(),
@@ -180,16 +199,18 @@ impl MyTeamPanel {
             Constraint::Length(3),
             Constraint::Length(3),
             Constraint::Length(3),
+            Constraint::Length(3),
             Constraint::Min(0),
         ])
         .split(area);
 
         frame.render_interactive_widget(view_info_button, split[0]);
         frame.render_interactive_widget(view_team_button, split[1]);
-        frame.render_interactive_widget(view_games_button, split[2]);
-        frame.render_interactive_widget(view_market_button, split[3]);
-        frame.render_interactive_widget(view_shipyard_button, split[4]);
-        frame.render_interactive_widget(view_asteroids_button, split[5]);
+        frame.render_interactive_widget(view_tactics_button, split[2]);
+        frame.render_interactive_widget(view_games_button, split[3]);
+        frame.render_interactive_widget(view_market_button, split[4]);
+        frame.render_interactive_widget(view_shipyard_button, split[5]);
+        frame.render_interactive_widget(view_asteroids_button, split[6]);
 
         Ok(())
     }
@@ -261,14 +282,17 @@ impl MyTeamPanel {
             let line = vec![
                 Span::styled(format!("{:<8} ", resource.to_string()), resource.style()),
                 Span::styled(
-                    format!("{:>4}", planet.resource_buy_price(resource, merchant_bonus)),
+                    format!(
+                        "{:>4}",
+                        planet.resource_buy_price(resource, merchant_bonus, world.clock.now())
+                    ),
                     UiStyle::OK,
                 ),
                 Span::raw("/"),
                 Span::styled(
                     format!(
                         "{:<4}",
-                        planet.resource_sell_price(resource, merchant_bonus)
+                        planet.resource_sell_price(resource, merchant_bonus, world.clock.now())
                     ),
                     UiStyle::ERROR,
                 ),
@@ -396,8 +420,10 @@ impl MyTeamPanel {
         {
             let resource_split = layout.split(button_split[button_split_idx + 1]);
             let merchant_bonus = TeamBonus::TradePrice.current_team_bonus(world, &own_team.id)?;
-            let buy_unit_cost = planet.resource_buy_price(*resource, merchant_bonus);
-            let sell_unit_cost = planet.resource_sell_price(*resource, merchant_bonus);
+            let buy_unit_cost =
+                planet.resource_buy_price(*resource, merchant_bonus, world.clock.now());
+            let sell_unit_cost =
+                planet.resource_sell_price(*resource, merchant_bonus, world.clock.now());
             frame.render_widget(
                 Paragraph::new(Line::from(vec![
                     Span::styled(format!("{:<6} ", resource.to_string()), resource.style()),
@@ -500,11 +526,20 @@ impl MyTeamPanel {
             )),
             Line::from(vec![
                 Span::raw(format!(
-                    "Local Elo {:.0}",
-                    own_team.local_game_rating.rating
+                    "Local rating {:.0}±{:.0}",
+                    own_team.local_game_rating.rating,
+                    own_team
+                        .local_game_rating
+                        .deviation_at(world.last_tick_short_interval)
                 )),
                 Span::styled(
-                    format!("  Network Elo {:.0}", own_team.network_game_rating.rating),
+                    format!(
+                        "  Network rating {:.0}±{:.0}",
+                        own_team.network_game_rating.rating,
+                        own_team
+                            .network_game_rating
+                            .deviation_at(world.last_tick_short_interval)
+                    ),
                     UiStyle::NETWORK,
                 ),
             ]),
@@ -649,6 +684,7 @@ impl MyTeamPanel {
             Constraint::Length(3),
             Constraint::Length(3),
             Constraint::Length(3),
+            Constraint::Length(3),
         ])
         .split(split[0].inner(Margin {
             horizontal: 1,
@@ -659,8 +695,13 @@ impl MyTeamPanel {
             Layout::horizontal([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
                 .split(btm_split[0]);
 
+        let tactic_name = if let Some(custom_tactic) = own_team.active_custom_tactic() {
+            custom_tactic.name.clone()
+        } else {
+            own_team.game_tactic.to_string()
+        };
         let mut tactic_button = Button::new(
-            format!("tactic: {}", own_team.game_tactic),
+            format!("tactic: {tactic_name}"),
             UiCallback::SetTeamTactic {
                 tactic: own_team.game_tactic.next(),
             },
@@ -673,7 +714,7 @@ impl MyTeamPanel {
         .set_hotkey(ui_key::team::SET_TACTIC);
 
         let can_change_tactic = own_team.can_change_tactic();
-        if let Err(err) = can_change_tactic {
+        if let Err(err) = can_change_tactic.as_ref() {
             tactic_button.disable(Some(err.to_string()));
         }
 
@@ -700,6 +741,23 @@ impl MyTeamPanel {
         }
         frame.render_interactive_widget(training_button, top_button_split[1]);
 
+        let mut defensive_scheme_button = Button::new(
+            format!("Defense: {}", own_team.defensive_scheme),
+            UiCallback::SetTeamDefensiveScheme {
+                scheme: own_team.defensive_scheme.next(),
+            },
+        )
+        .set_hover_text(format!(
+            "{}: {}",
+            own_team.defensive_scheme,
+            own_team.defensive_scheme.description()
+        ))
+        .set_hotkey(ui_key::team::SET_DEFENSIVE_SCHEME);
+        if let Err(err) = can_change_tactic {
+            defensive_scheme_button.disable(Some(err.to_string()));
+        }
+        frame.render_interactive_widget(defensive_scheme_button, btm_split[1]);
+
         let local_challenge_button = Button::new(
             format!(
                 "Auto-accept local challenges: {}",
@@ -713,7 +771,7 @@ impl MyTeamPanel {
         )
         .set_hover_text("Accept challenges from local teams automatically.".to_string())
         .set_hotkey(ui_key::team::TOGGLE_ACCEPT_LOCAL_CHALLENGES);
-        frame.render_interactive_widget(local_challenge_button, btm_split[1]);
+        frame.render_interactive_widget(local_challenge_button, btm_split[2]);
 
         let network_challenge_button = Button::new(
             format!(
@@ -728,7 +786,7 @@ impl MyTeamPanel {
         )
         .set_hover_text("Accept challenges from network teams automatically.".to_string())
         .set_hotkey(ui_key::team::TOGGLE_ACCEPT_NETWORK_CHALLENGES);
-        frame.render_interactive_widget(network_challenge_button, btm_split[2]);
+        frame.render_interactive_widget(network_challenge_button, btm_split[3]);
 
         match own_team.current_location {
             TeamLocation::OnPlanet { .. } => {
@@ -767,6 +825,267 @@ impl MyTeamPanel {
         Ok(())
     }
 
+    fn render_tactics(&self, frame: &mut UiFrame, world: &World, area: Rect) -> AppResult<()> {
+        let own_team = world.get_own_team()?;
+        let split = Layout::horizontal([Constraint::Length(48), Constraint::Min(48)]).split(area);
+
+        frame.render_widget(
+            default_block().title(format!(
+                "Custom tactics ({}/{MAX_CUSTOM_TACTICS})",
+                own_team.custom_tactics.len()
+            )),
+            split[0],
+        );
+
+        let v_split = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(3),
+            Constraint::Length(3),
+        ])
+        .split(split[0].inner(Margin {
+            horizontal: 1,
+            vertical: 1,
+        }));
+
+        let options = own_team
+            .custom_tactics
+            .iter()
+            .enumerate()
+            .map(|(index, tactic)| {
+                if own_team.active_custom_tactic == Some(index) {
+                    (format!("{} (in use)", tactic.name), UiStyle::OWN_TEAM)
+                } else {
+                    (tactic.name.clone(), UiStyle::DEFAULT)
+                }
+            })
+            .collect_vec();
+
+        frame.render_stateful_interactive_widget(
+            selectable_list(options),
+            v_split[0],
+            &mut ClickableListState::default().with_selected(self.tactic_index),
+        );
+
+        let top_button_split =
+            Layout::horizontal([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+                .split(v_split[1]);
+        let btm_button_split =
+            Layout::horizontal([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+                .split(v_split[2]);
+
+        let mut new_button = Button::new("New", UiCallback::CreateCustomTactic)
+            .set_hover_text(format!(
+                "Create a new custom tactic based on {}.",
+                own_team.game_tactic
+            ))
+            .set_hotkey(ui_key::team::NEW_CUSTOM_TACTIC);
+        if own_team.custom_tactics.len() >= MAX_CUSTOM_TACTICS {
+            new_button.disable(Some(format!(
+                "Cannot have more than {MAX_CUSTOM_TACTICS} custom tactics"
+            )));
+        }
+        frame.render_interactive_widget(new_button, top_button_split[0]);
+
+        let (index, tactic) = if let Some(index) = self.tactic_index {
+            let index = index % own_team.custom_tactics.len();
+            (index, &own_team.custom_tactics[index])
+        } else {
+            frame.render_widget(
+                Paragraph::new(
+                    "Create a custom tactic to fine-tune how your crew plays: action weights, fastbreak aggressiveness and how much effort (and tiredness) the crew puts in.",
+                )
+                .wrap(Wrap { trim: true })
+                .block(default_block()),
+                split[1],
+            );
+            return Ok(());
+        };
+
+        let is_active = own_team.active_custom_tactic == Some(index);
+        let can_change_tactic = own_team.can_change_tactic();
+
+        let mut delete_button =
+            Button::new("Delete", UiCallback::DeleteCustomTactic { index })
+                .set_hover_text(format!("Delete {}.", tactic.name))
+                .set_hotkey(ui_key::team::DELETE_CUSTOM_TACTIC)
+                .block(default_block().border_style(UiStyle::WARNING));
+        if is_active {
+            if let Err(err) = can_change_tactic.as_ref() {
+                delete_button.disable(Some(err.to_string()));
+            }
+        }
+        frame.render_interactive_widget(delete_button, top_button_split[1]);
+
+        let mut use_button = if is_active {
+            Button::new(
+                format!("Stop using {}", tactic.name),
+                UiCallback::SetTeamCustomTactic { index: None },
+            )
+            .set_hover_text(format!("Go back to {}.", own_team.game_tactic))
+        } else {
+            Button::new(
+                format!("Use {}", tactic.name),
+                UiCallback::SetTeamCustomTactic { index: Some(index) },
+            )
+            .set_hover_text(format!("Use {} in the next games.", tactic.name))
+        }
+        .set_hotkey(ui_key::team::USE_CUSTOM_TACTIC);
+        if let Err(err) = can_change_tactic.as_ref() {
+            use_button.disable(Some(err.to_string()));
+        }
+        frame.render_interactive_widget(use_button, btm_button_split[0]);
+
+        let rename_button = Button::new(
+            "Rename",
+            UiCallback::PushUiPopup {
+                popup_message: PopupMessage::TacticNameDialog {
+                    index,
+                    timestamp: Tick::now(),
+                },
+            },
+        )
+        .set_hover_text(format!("Rename {}.", tactic.name))
+        .set_hotkey(ui_key::team::RENAME_CUSTOM_TACTIC);
+        frame.render_interactive_widget(rename_button, btm_button_split[1]);
+
+        let disabled_text = if is_active {
+            can_change_tactic.err().map(|err| err.to_string())
+        } else {
+            None
+        };
+        self.render_custom_tactic_editor(frame, index, tactic, disabled_text, split[1]);
+
+        Ok(())
+    }
+
+    fn render_custom_tactic_editor(
+        &self,
+        frame: &mut UiFrame,
+        index: usize,
+        tactic: &CustomTactic,
+        disabled_text: Option<String>,
+        area: Rect,
+    ) {
+        frame.render_widget(default_block().title(tactic.name.as_str()), area);
+
+        let split = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(area.inner(Margin {
+            horizontal: 2,
+            vertical: 1,
+        }));
+
+        let mut rows = vec![];
+        for (action_index, label) in ["Isolation", "Off the screen", "Pick and roll", "Post"]
+            .iter()
+            .enumerate()
+        {
+            let weight = tactic.action_weights[action_index];
+            let mut decreased = tactic.clone();
+            decreased.action_weights[action_index] = weight.saturating_sub(1);
+            let mut increased = tactic.clone();
+            increased.action_weights[action_index] = (weight + 1).min(MAX_ACTION_WEIGHT);
+            rows.push((
+                label.to_string(),
+                format!("{weight:>2}/{MAX_ACTION_WEIGHT}"),
+                decreased,
+                increased,
+            ));
+        }
+
+        let mut decreased = tactic.clone();
+        decreased.fastbreak_aggressiveness = tactic
+            .fastbreak_aggressiveness
+            .saturating_sub(1)
+            .max(MIN_FASTBREAK_AGGRESSIVENESS);
+        let mut increased = tactic.clone();
+        increased.fastbreak_aggressiveness =
+            (tactic.fastbreak_aggressiveness + 1).min(MAX_FASTBREAK_AGGRESSIVENESS);
+        rows.push((
+            "Fastbreak".to_string(),
+            format!(
+                "{:>2}/{MAX_FASTBREAK_AGGRESSIVENESS}",
+                tactic.fastbreak_aggressiveness
+            ),
+            decreased,
+            increased,
+        ));
+
+        let mut decreased = tactic.clone();
+        decreased.tiredness_budget = tactic
+            .tiredness_budget
+            .saturating_sub(1)
+            .max(MIN_TIREDNESS_BUDGET);
+        let mut increased = tactic.clone();
+        increased.tiredness_budget = (tactic.tiredness_budget + 1).min(MAX_TIREDNESS_BUDGET);
+        rows.push((
+            "Tiredness budget".to_string(),
+            format!("{:>2}/{MAX_TIREDNESS_BUDGET}", tactic.tiredness_budget),
+            decreased,
+            increased,
+        ));
+
+        let mut previous_base = tactic.clone();
+        previous_base.base = tactic.base.previous();
+        let mut next_base = tactic.clone();
+        next_base.base = tactic.base.next();
+        rows.push((
+            "Base tactic".to_string(),
+            tactic.base.to_string(),
+            previous_base,
+            next_base,
+        ));
+
+        for (row_index, (label, value, decreased, increased)) in rows.into_iter().enumerate() {
+            let row_split = Layout::horizontal([
+                Constraint::Length(18),
+                Constraint::Length(3),
+                Constraint::Length(13),
+                Constraint::Length(3),
+                Constraint::Min(0),
+            ])
+            .split(split[row_index]);
+
+            frame.render_widget(Paragraph::new(label), row_split[0]);
+            frame.render_widget(Paragraph::new(value).centered(), row_split[2]);
+
+            for (button_text, updated, button_area) in [
+                ("-", decreased, row_split[1]),
+                ("+", increased, row_split[3]),
+            ] {
+                let mut button = Button::new(
+                    button_text,
+                    UiCallback::UpdateCustomTactic {
+                        index,
+                        tactic: updated.clone(),
+                    },
+                );
+                if let Some(text) = disabled_text.as_ref() {
+                    button.disable(Some(text.clone()));
+                } else if let Err(err) = updated.validate() {
+                    button.disable(Some(err.to_string()));
+                } else if updated == *tactic {
+                    button.disable(None::<String>);
+                }
+                frame.render_interactive_widget(button, button_area);
+            }
+        }
+
+        frame.render_widget(
+            Paragraph::new(tactic.description()).wrap(Wrap { trim: true }),
+            split[8],
+        );
+    }
+
     fn render_games(&mut self, frame: &mut UiFrame, world: &World, area: Rect) -> AppResult<()> {
         let split = Layout::horizontal([Constraint::Length(48), Constraint::Min(48)]).split(area);
         self.render_challenge_teams(frame, world, split[0])?;
@@ -864,8 +1183,8 @@ impl MyTeamPanel {
                 let text = format!(
                     " {:>12} {:>3}-{:<3} {:<}",
                     game.home_team_name,
-                    game.home_quarters_score.iter().sum::<u16>(),
-                    game.away_quarters_score.iter().sum::<u16>(),
+                    game.get_score().0,
+                    game.get_score().1,
                     game.away_team_name,
                 );
 
@@ -927,6 +1246,8 @@ impl MyTeamPanel {
 
         let summary = if let Ok(current_game) = world.games.get_or_err(&game_id) {
             let (home_quarters_score, away_quarters_score) = current_game.get_score_by_quarter();
+            let (home_overtime_score, away_overtime_score) = current_game.get_overtime_score();
+            let (home_score, away_score) = current_game.get_score();
 
             let lines = vec![
                 Line::from(format!(
@@ -947,8 +1268,8 @@ impl MyTeamPanel {
                 Line::default(),
                 Line::from(Span::styled(
                     format!(
-                        "{:12} {} {} {} {} {}",
-                        "Team", "Q1", "Q2", "Q3", "Q4", "Result"
+                        "{:12} {} {} {} {} {} {}",
+                        "Team", "Q1", "Q2", "Q3", "Q4", "OT", "Result"
                     ),
                     UiStyle::HEADER.bold(),
                 )),
@@ -964,7 +1285,7 @@ impl MyTeamPanel {
                         },
                     ),
                     Span::raw(format!(
-                        "{:02} {} {} {} {:^6}",
+                        "{:02} {} {} {} {} {:^6}",
                         home_quarters_score[0],
                         if current_game.timer.period() >= Period::Q2 {
                             format!("{:02}", home_quarters_score[1])
@@ -981,7 +1302,12 @@ impl MyTeamPanel {
                         } else {
                             "--".to_string()
                         },
-                        home_quarters_score.iter().sum::<u16>(),
+                        if current_game.timer.period() >= Period::OT1 {
+                            format!("{:02}", home_overtime_score)
+                        } else {
+                            "--".to_string()
+                        },
+                        home_score,
                     )),
                 ]),
                 Line::from(vec![
@@ -996,7 +1322,7 @@ impl MyTeamPanel {
                         },
                     ),
                     Span::raw(format!(
-                        "{:02} {} {} {} {:^6}",
+                        "{:02} {} {} {} {} {:^6}",
                         away_quarters_score[0],
                         if current_game.timer.period() >= Period::Q2 {
                             format!("{:02}", away_quarters_score[1])
@@ -1013,7 +1339,12 @@ impl MyTeamPanel {
                         } else {
                             "--".to_string()
                         },
-                        away_quarters_score.iter().sum::<u16>(),
+                        if current_game.timer.period() >= Period::OT1 {
+                            format!("{:02}", away_overtime_score)
+                        } else {
+                            "--".to_string()
+                        },
+                        away_score,
                     )),
                 ]),
             ];
@@ -1024,6 +1355,15 @@ impl MyTeamPanel {
                 .past_games
                 .get(&game_id)
                 .ok_or_else(|| anyhow!("Unable to get past game."))?;
+            let (home_score, away_score) = game_summary.get_score();
+            let went_to_overtime = game_summary.overtimes > 0;
+            let overtime_score = |score: u16| {
+                if went_to_overtime {
+                    format!("{score:02}")
+                } else {
+                    "--".to_string()
+                }
+            };
 
             let mut lines = vec![
                 Line::from(format!(
@@ -1047,8 +1387,8 @@ impl MyTeamPanel {
                 Line::default(),
                 Line::from(Span::styled(
                     format!(
-                        "{:12} {} {} {} {} {}",
-                        "Team", "Q1", "Q2", "Q3", "Q4", "Result"
+                        "{:12} {} {} {} {} {} {}",
+                        "Team", "Q1", "Q2", "Q3", "Q4", "OT", "Result"
                     ),
                     UiStyle::HEADER.bold(),
                 )),
@@ -1064,12 +1404,13 @@ impl MyTeamPanel {
                         },
                     ),
                     Span::raw(format!(
-                        "{:02} {:02} {:02} {:02} {:^6} {}",
+                        "{:02} {:02} {:02} {:02} {} {:^6} {}",
                         game_summary.home_quarters_score[0],
                         game_summary.home_quarters_score[1],
                         game_summary.home_quarters_score[2],
                         game_summary.home_quarters_score[3],
-                        game_summary.home_quarters_score.iter().sum::<u16>(),
+                        overtime_score(game_summary.home_overtime_score),
+                        home_score,
                         if game_summary.home_team_knocked_out {
                             "wasted"
                         } else {
@@ -1089,12 +1430,13 @@ impl MyTeamPanel {
                         },
                     ),
                     Span::raw(format!(
-                        "{:02} {:02} {:02} {:02} {:^6} {}",
+                        "{:02} {:02} {:02} {:02} {} {:^6} {}",
                         game_summary.away_quarters_score[0],
                         game_summary.away_quarters_score[1],
                         game_summary.away_quarters_score[2],
                         game_summary.away_quarters_score[3],
-                        game_summary.away_quarters_score.iter().sum::<u16>(),
+                        overtime_score(game_summary.away_overtime_score),
+                        away_score,
                         if game_summary.away_team_knocked_out {
                             "wasted"
                         } else {
@@ -1299,7 +1641,8 @@ impl MyTeamPanel {
         let available = available_upgrade_targets(&own_team.spaceship);
         let possible_upgrade_target = available[self.spaceship_upgrade_index % available.len()];
         let bonus = TeamBonus::Upgrades.current_team_bonus(world, &own_team.id)?;
-        let possible_upgrade = possible_upgrade_target.map(|target| Upgrade::new(target, bonus));
+        let possible_upgrade =
+            possible_upgrade_target.map(|target| Upgrade::new(target, bonus, world.clock.now()));
 
         let lines = if let Some(target) = possible_upgrade_target {
             let header = match target {
@@ -1594,17 +1937,29 @@ impl MyTeamPanel {
                 .contains(&AsteroidUpgradeTarget::TeleportationPad)
             {
                 let bonus = TeamBonus::Upgrades.current_team_bonus(world, &own_team.id)?;
-                Some(Upgrade::new(AsteroidUpgradeTarget::TeleportationPad, bonus))
+                Some(Upgrade::new(
+                    AsteroidUpgradeTarget::TeleportationPad,
+                    bonus,
+                    world.clock.now(),
+                ))
             } else if own_team.has_space_cove_on().is_none() {
                 // Build space cove button
                 let bonus = TeamBonus::Upgrades.current_team_bonus(world, &own_team.id)?;
-                Some(Upgrade::new(AsteroidUpgradeTarget::SpaceCove, bonus))
+                Some(Upgrade::new(
+                    AsteroidUpgradeTarget::SpaceCove,
+                    bonus,
+                    world.clock.now(),
+                ))
             } else if matches!(own_team.has_space_cove_on(), Some(id) if id == asteroid.id)
                 && !asteroid.upgrades.contains(&AsteroidUpgradeTarget::Market)
             {
                 // Build market button
                 let bonus = TeamBonus::Upgrades.current_team_bonus(world, &own_team.id)?;
-                Some(Upgrade::new(AsteroidUpgradeTarget::Market, bonus))
+                Some(Upgrade::new(
+                    AsteroidUpgradeTarget::Market,
+                    bonus,
+                    world.clock.now(),
+                ))
             } else {
                 None
             };
@@ -2441,6 +2796,17 @@ impl Screen for MyTeamPanel {
 
         self.max_player_index = own_team.player_ids.len();
 
+        self.max_tactic_index = own_team.custom_tactics.len();
+        self.tactic_index = if self.max_tactic_index > 0 {
+            if let Some(index) = self.tactic_index {
+                Some(index % self.max_tactic_index)
+            } else {
+                Some(0)
+            }
+        } else {
+            None
+        };
+
         if world.dirty_ui {
             let mut games = vec![];
             if let Some(current_game) = own_team.current_game {
@@ -2475,14 +2841,28 @@ impl Screen for MyTeamPanel {
                 })
                 .cloned()
                 .collect();
+            // Suggest first the opponents leading to the most uncertain games.
+            let now = world.last_tick_short_interval;
+            let match_quality = |team: &Team| {
+                if team.peer_id.is_some() {
+                    own_team
+                        .network_game_rating
+                        .match_quality(&team.network_game_rating, now)
+                } else {
+                    own_team
+                        .local_game_rating
+                        .match_quality(&team.local_game_rating, now)
+                }
+            };
             self.challenge_teams.sort_by(|a, b| {
                 let a = world.teams.get_or_err(a).unwrap();
                 let b = world.teams.get_or_err(b).unwrap();
-                world
-                    .team_rating(&b.id)
-                    .unwrap_or_default()
-                    .partial_cmp(&world.team_rating(&a.id).unwrap_or_default())
-                    .unwrap()
+                match_quality(b).total_cmp(&match_quality(a)).then(
+                    world
+                        .team_rating(&b.id)
+                        .unwrap_or_default()
+                        .total_cmp(&world.team_rating(&a.id).unwrap_or_default()),
+                )
             });
 
             let sorted_players = own_team
@@ -2539,6 +2919,7 @@ impl Screen for MyTeamPanel {
         match self.view {
             MyTeamView::Info => self.render_info(frame, world, bottom_split[1])?,
             MyTeamView::Team => self.render_team(frame, world, bottom_split[1])?,
+            MyTeamView::Tactics => self.render_tactics(frame, world, bottom_split[1])?,
             MyTeamView::Games => self.render_games(frame, world, bottom_split[1])?,
             MyTeamView::Market => self.render_market(frame, world, bottom_split[1])?,
             MyTeamView::Shipyard => self.render_shipyard(frame, world, bottom_split[1])?,
@@ -2623,7 +3004,7 @@ impl Screen for MyTeamPanel {
             vec![
                 Line::from(" Controls:"),
                 Line::from(format!(
-                    "   {}        Cycle view (Info/Team/Games/Market/Shipyard/Asteroids)",
+                    "   {}        Cycle view (Info/Team/Tactics/Games/Market/Shipyard/Asteroids)",
                     ui_key::CYCLE_VIEW
                 )),
                 Line::from("   ↑/↓        Move highlight in the active list"),
@@ -2645,6 +3026,17 @@ impl Screen for MyTeamPanel {
                     ui_key::team::TRAINING_FOCUS,
                     ui_key::team::SET_TACTIC
                 )),
+                Line::from(format!(
+                    "   {}          Cycle defensive scheme",
+                    ui_key::team::SET_DEFENSIVE_SCHEME
+                )),
+                Line::from(format!(
+                    "   {}/{}/{}/{}    New/delete/use/rename custom tactic",
+                    ui_key::team::NEW_CUSTOM_TACTIC,
+                    ui_key::team::DELETE_CUSTOM_TACTIC,
+                    ui_key::team::USE_CUSTOM_TACTIC,
+                    ui_key::team::RENAME_CUSTOM_TACTIC
+                )),
             ],
         );
         Ok(())
@@ -2661,6 +3053,8 @@ impl SplitPanel for MyTeamPanel {
             return Some(self.spaceship_upgrade_index);
         } else if self.active_list == PanelList::Bottom && self.view == MyTeamView::Asteroids {
             return self.asteroid_index;
+        } else if self.active_list == PanelList::Bottom && self.view == MyTeamView::Tactics {
+            return self.tactic_index;
         }
 
         // we should always have at least 1 player
@@ -2676,6 +3070,8 @@ impl SplitPanel for MyTeamPanel {
             return SpaceshipUpgradeTarget::iter().count();
         } else if self.active_list == PanelList::Bottom && self.view == MyTeamView::Asteroids {
             return self.asteroid_ids.len();
+        } else if self.active_list == PanelList::Bottom && self.view == MyTeamView::Tactics {
+            return self.max_tactic_index;
         }
         self.max_player_index
     }
@@ -2690,6 +3086,8 @@ impl SplitPanel for MyTeamPanel {
                 panic!("Max upgrade_index should be 3");
             } else if self.active_list == PanelList::Bottom && self.view == MyTeamView::Asteroids {
                 self.asteroid_index = None;
+            } else if self.active_list == PanelList::Bottom && self.view == MyTeamView::Tactics {
+                self.tactic_index = None;
             } else {
                 self.player_index = None;
             }
@@ -2701,6 +3099,8 @@ impl SplitPanel for MyTeamPanel {
             self.spaceship_upgrade_index = index % self.max_index();
         } else if self.active_list == PanelList::Bottom && self.view == MyTeamView::Asteroids {
             self.asteroid_index = Some(index % self.max_index());
+        } else if self.active_list == PanelList::Bottom && self.view == MyTeamView::Tactics {
+            self.tactic_index = Some(index % self.max_index());
         } else {
             self.player_index = Some(index % self.max_index());
         }
@@ -15,7 +15,7 @@ use super::{
 use ratatui::text::Line;
 
 use crate::network::trade::Trade;
-use crate::types::{AppResult, HashMapWithResult};
+use crate::types::{AppResult, HashMapWithResult, SystemTimeTick};
 use crate::ui::ui_key;
 use crate::{
     core::*,
@@ -29,7 +29,7 @@ use ratatui::style::Stylize;
 use ratatui::{
     layout::{Constraint, Layout},
     prelude::Rect,
-    widgets::Paragraph,
+    widgets::{Paragraph, Sparkline},
 };
 use std::fmt::Display;
 
@@ -312,11 +312,85 @@ impl PlayerListPanel {
                 h_split[1],
             );
             self.render_buttons(locked_player, open_trade, frame, world, button_split[1])?;
+        } else {
+            Self::render_career(player, frame, h_split[1]);
         }
 
         Ok(())
     }
 
+    fn render_career(player: &Player, frame: &mut UiFrame, area: Rect) {
+        let career = &player.career;
+        if career.is_empty() {
+            frame.render_widget(
+                Paragraph::new(" No games played yet.").block(default_block().title("Career")),
+                area,
+            );
+            return;
+        }
+
+        frame.render_widget(default_block().title("Career"), area);
+        let split = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .split(area.inner(Margin::new(1, 1)));
+
+        frame.render_widget(Paragraph::new(" Points per game"), split[0]);
+        let points_per_game = career
+            .entries
+            .iter()
+            .map(|entry| (10.0 * entry.per_game(entry.stats.points)).round() as u64)
+            .collect::<Vec<u64>>();
+        frame.render_widget(
+            Sparkline::default()
+                .data(&points_per_game)
+                .style(UiStyle::OWN_TEAM),
+            split[1].inner(Margin::new(1, 0)),
+        );
+
+        frame.render_widget(
+            Paragraph::new(format!(
+                " {:<10} {:<MAX_NAME_LENGTH$} {:>3} {:>5} {:>5} {:>5} {:>5}",
+                "From", "Crew", "G", "PTS", "REB", "AST", "+/-"
+            ))
+            .style(UiStyle::HEADER),
+            split[2],
+        );
+
+        let format_entry = |entry: &CareerEntry| {
+            let stats = &entry.stats;
+            format!(
+                " {:<10} {:<MAX_NAME_LENGTH$} {:>3} {:>5.1} {:>5.1} {:>5.1} {:>+5.1}",
+                entry.from.formatted_as_date(),
+                entry.team_name.chars().take(MAX_NAME_LENGTH).collect::<String>(),
+                entry.games(),
+                entry.per_game(stats.points),
+                entry.per_game(stats.offensive_rebounds + stats.defensive_rebounds),
+                entry.per_game(stats.assists),
+                stats.plus_minus as f32 / entry.games().max(1) as f32,
+            )
+        };
+
+        // Totals per crew first, then the most recent entries.
+        let mut lines = career
+            .team_splits()
+            .iter()
+            .map(|split| Line::from(format_entry(split)).bold())
+            .collect::<Vec<Line>>();
+        lines.extend(career.entries.iter().rev().map(|entry| {
+            let line = Line::from(format_entry(entry));
+            if entry.is_spell() {
+                line.italic()
+            } else {
+                line
+            }
+        }));
+        frame.render_widget(Paragraph::new(lines), split[3]);
+    }
+
     fn render_buttons(
         &self,
         player: &Player,
@@ -68,6 +68,10 @@ pub enum PopupMessage {
         timestamp: Tick,
         asteroid_type: usize,
     },
+    TacticNameDialog {
+        index: usize,
+        timestamp: Tick,
+    },
     BuildSpaceCove {
         asteroid_name: String,
         asteroid_id: PlanetId,
@@ -180,6 +184,25 @@ impl PopupMessage {
                 }
             }
 
+            Self::TacticNameDialog { index, .. } => {
+                if key_event.code == ui_key::YES_TO_DIALOG {
+                    let name = popup_input.lines()[0].trim().to_string();
+                    if validate_textarea_input(popup_input, "Tactic name") {
+                        return Some(UiCallback::RenameCustomTactic {
+                            index: *index,
+                            name,
+                        });
+                    }
+                } else if key_event.code == ui_key::NO_TO_DIALOG {
+                    if popup_input.lines()[0].is_empty() {
+                        return Some(UiCallback::CloseUiPopup);
+                    }
+                    popup_input.input(input_from_key_event(key_event));
+                } else {
+                    popup_input.input(input_from_key_event(key_event));
+                }
+            }
+
             Self::ReleasePlayer { player_id, .. } => {
                 if key_event.code == ui_key::YES_TO_DIALOG {
                     return Some(UiCallback::ReleasePlayer {
@@ -748,6 +771,78 @@ impl PopupMessage {
                 frame.render_interactive_widget(no_button, buttons_split[1]);
             }
 
+            Self::TacticNameDialog { index, .. } => {
+                frame.render_widget(
+                    Paragraph::new("Rename tactic")
+                        .bold()
+                        .block(default_block().border_style(UiStyle::HIGHLIGHT))
+                        .centered(),
+                    split[0],
+                );
+
+                let m_split = Layout::vertical([
+                    Constraint::Min(0),    //message
+                    Constraint::Length(3), //input
+                ])
+                .split(split[1]);
+
+                frame.render_widget(
+                    Paragraph::new("Choose a name for your custom tactic.")
+                        .centered()
+                        .wrap(Wrap { trim: true }),
+                    m_split[0].inner(Margin {
+                        horizontal: 1,
+                        vertical: 1,
+                    }),
+                );
+
+                popup_input.set_cursor_style(UiStyle::SELECTED);
+                popup_input.set_block(
+                    default_block()
+                        .border_style(UiStyle::DEFAULT)
+                        .title("Tactic name"),
+                );
+
+                frame.render_widget(
+                    &popup_input.clone(),
+                    m_split[1].inner(Margin {
+                        horizontal: 1,
+                        vertical: 0,
+                    }),
+                );
+
+                let buttons_split =
+                    Layout::horizontal([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+                        .split(split[2]);
+
+                let name = popup_input.lines()[0].trim().to_string();
+                let mut ok_button = Button::new(
+                    UiText::YES,
+                    UiCallback::RenameCustomTactic {
+                        index: *index,
+                        name,
+                    },
+                )
+                .set_hover_text("Rename the tactic")
+                .set_hotkey(ui_key::YES_TO_DIALOG)
+                .block(default_block().border_style(UiStyle::OK))
+                .set_layer(1);
+
+                if !validate_textarea_input(popup_input, "Tactic name") {
+                    ok_button.disable(Some("Invalid tactic name"));
+                }
+
+                frame.render_interactive_widget(ok_button, buttons_split[0]);
+
+                let no_button = Button::new(UiText::NO, UiCallback::CloseUiPopup)
+                    .set_hover_text("Keep the current name")
+                    .set_hotkey(ui_key::NO_TO_DIALOG)
+                    .block(default_block().border_style(UiStyle::ERROR))
+                    .set_layer(1);
+
+                frame.render_interactive_widget(no_button, buttons_split[1]);
+            }
+
             Self::PortalFound {
                 player_name,
                 portal_target,
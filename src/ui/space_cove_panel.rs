@@ -23,6 +23,7 @@ use ratatui::style::Stylize;
 use ratatui::widgets::Paragraph;
 use std::collections::HashSet;
 use std::fmt::{self, Display};
+use strum::IntoEnumIterator;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum SpaceCoveView {
@@ -311,6 +312,21 @@ impl SpaceCovePanel {
                 ui_key::ORGANIZE_BIG_TOURNAMENT,
                 "Registrations close in 1 hour, max 8 participants.",
             ),
+            TournamentType::League => (
+                "Organize league",
+                ui_key::ORGANIZE_LEAGUE,
+                "Registrations close in 1 hour, max 6 participants. Every crew plays every other crew once.",
+            ),
+            TournamentType::DoubleElimination => (
+                "Organize double elimination",
+                ui_key::ORGANIZE_DOUBLE_ELIMINATION,
+                "Registrations close in 45 minutes, max 8 participants. Crews are out after two losses.",
+            ),
+            TournamentType::Swiss => (
+                "Organize swiss tournament",
+                ui_key::ORGANIZE_SWISS,
+                "Registrations close in 45 minutes, max 8 participants. Crews are paired by record.",
+            ),
         };
 
         let hover = match asteroid {
@@ -482,14 +498,21 @@ impl Screen for SpaceCovePanel {
 
         let own_team = world.get_own_team()?;
 
-        let col = Layout::vertical([
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Fill(1),
-            Constraint::Fill(1),
-            Constraint::Length(3),
-            Constraint::Length(3),
-        ])
+        // The own cove view has one organize button per tournament type.
+        let bottom_rows = match self.view {
+            SpaceCoveView::OwnCove => TournamentType::iter().count(),
+            SpaceCoveView::AllCoves => 2,
+        };
+        let col = Layout::vertical(
+            [
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ]
+            .into_iter()
+            .chain([Constraint::Length(3)].repeat(bottom_rows)),
+        )
         .split(split[0]);
 
         self.render_view_buttons(frame, world, col[0], col[1])?;
@@ -502,20 +525,15 @@ impl Screen for SpaceCovePanel {
                 let asteroid = own_team
                     .has_space_cove_on()
                     .and_then(|id| world.planets.get(&id));
-                self.render_tournament_button(
-                    frame,
-                    own_team,
-                    asteroid,
-                    TournamentType::Cup,
-                    col[4],
-                );
-                self.render_tournament_button(
-                    frame,
-                    own_team,
-                    asteroid,
-                    TournamentType::Supercup,
-                    col[5],
-                );
+                for (idx, tournament_type) in TournamentType::iter().enumerate() {
+                    self.render_tournament_button(
+                        frame,
+                        own_team,
+                        asteroid,
+                        tournament_type,
+                        col[4 + idx],
+                    );
+                }
             }
             SpaceCoveView::AllCoves => {
                 self.render_cove_list(frame, world, col[2])?;
@@ -612,6 +630,18 @@ impl Screen for SpaceCovePanel {
                     "   {}          Organize a big tournament (own cove only)",
                     ui_key::ORGANIZE_BIG_TOURNAMENT
                 )),
+                Line::from(format!(
+                    "   {}          Organize a round-robin league (own cove only)",
+                    ui_key::ORGANIZE_LEAGUE
+                )),
+                Line::from(format!(
+                    "   {}          Organize a double elimination tournament (own cove only)",
+                    ui_key::ORGANIZE_DOUBLE_ELIMINATION
+                )),
+                Line::from(format!(
+                    "   {}          Organize a swiss tournament (own cove only)",
+                    ui_key::ORGANIZE_SWISS
+                )),
             ],
         );
         Ok(())
@@ -95,8 +95,7 @@ impl SplashScreen {
 
         if save_game_exists(store_prefix) {
             if let Ok(continue_data) = world_file_data(store_prefix) {
-                if let Ok(last_modified) = continue_data.modified() {
-                    let tick = Tick::from_system_time(last_modified);
+                if let Some(tick) = continue_data.modified_at {
                     continue_text = format!(
                         "Continue: {} {}",
                         tick.formatted_as_date(),
@@ -15,7 +15,7 @@ use super::{
 };
 use crate::core::constants::{MINUTES, MIN_PLAYERS_PER_GAME};
 use crate::core::{skill::Rated, world::World};
-use crate::network::types::{ChatHistoryEntry, PlayerRanking, TeamRanking};
+use crate::network::types::{ChatChannel, ChatHistoryEntry, PlayerRanking, TeamRanking};
 use crate::types::{AppResult, HashMapWithResult, PlayerId, SystemTimeTick, TeamId, Tick};
 use crate::ui::clickable_list::{ClickableList, ClickableListItem};
 use crate::ui::ui_key;
@@ -44,6 +44,7 @@ const PEER_DISCONNECTION_INTERVAL: Tick = 5 * MINUTES;
 pub enum SwarmView {
     #[default]
     Chat,
+    Messages,
     Requests,
     Log,
     Ranking,
@@ -52,7 +53,8 @@ pub enum SwarmView {
 impl SwarmView {
     const fn next(&self) -> Self {
         match self {
-            Self::Chat => Self::Requests,
+            Self::Chat => Self::Messages,
+            Self::Messages => Self::Requests,
             Self::Requests => Self::Log,
             Self::Log => Self::Ranking,
             Self::Ranking => Self::Chat,
@@ -91,26 +93,35 @@ struct ChatEvent {
     author: String,
     text: String,
     is_error: bool,
+    channel: ChatChannel,
 }
 
 impl ChatEvent {
-    pub const fn new(timestamp: Tick, peer_id: PeerId, author: String, text: String) -> Self {
+    pub const fn new(
+        timestamp: Tick,
+        peer_id: PeerId,
+        author: String,
+        text: String,
+        channel: ChatChannel,
+    ) -> Self {
         Self {
             timestamp,
             peer_id: Some(peer_id),
             author,
             text,
             is_error: false,
+            channel,
         }
     }
 
-    pub fn error(timestamp: Tick, text: String) -> Self {
+    pub fn error(timestamp: Tick, text: String, channel: ChatChannel) -> Self {
         Self {
             timestamp,
             peer_id: None,
             author: "".to_string(),
             text,
             is_error: true,
+            channel,
         }
     }
 }
@@ -171,6 +182,13 @@ pub struct SwarmPanel {
     should_update_message_list: Option<SwarmView>,
     emojies_substutions: Vec<(&'static str, &'static str)>,
     chat_history_received_len: usize,
+    // Peers we can message directly, with their display name, most recent conversation first.
+    conversations: Vec<(PeerId, String)>,
+    conversation_index: Option<usize>,
+    unread_direct_messages: HashMap<PeerId, usize>,
+    chat_channel: ChatChannel,
+    // Channels the own team takes part in: global, current planet and tournament.
+    chat_channels: Vec<ChatChannel>,
 }
 
 impl SwarmPanel {
@@ -178,6 +196,10 @@ impl SwarmPanel {
         self.unread_chat_messages
     }
 
+    pub fn unread_direct_messages(&self) -> usize {
+        self.unread_direct_messages.values().sum()
+    }
+
     pub fn remove_player_from_ranking(&mut self, player_id: PlayerId) {
         self.player_ranking.retain(|&(id, _)| id != player_id);
         if self.player_ranking.is_empty() {
@@ -217,14 +239,14 @@ impl SwarmPanel {
     }
 
     pub fn update_team_ranking(&mut self, team_ranking: &[(TeamId, TeamRanking)]) {
+        let now = Tick::now();
         self.team_ranking = team_ranking
             .iter()
             .sorted_by(|(_, a), (_, b)| {
                 b.team
                     .network_game_rating
-                    .rating
-                    .partial_cmp(&a.team.network_game_rating.rating)
-                    .expect("Netowrk rating should be a number")
+                    .conservative_rating(now)
+                    .total_cmp(&a.team.network_game_rating.conservative_rating(now))
             })
             .cloned()
             .collect_vec();
@@ -272,26 +294,54 @@ impl SwarmPanel {
         self.should_update_message_list = Some(SwarmView::Log);
     }
 
+    fn is_chat_channel_joined(&self, channel: &ChatChannel) -> bool {
+        *channel == ChatChannel::Global || self.chat_channels.contains(channel)
+    }
+
+    fn channel_chat_events(&self) -> impl Iterator<Item = &ChatEvent> {
+        self.chat_events
+            .iter()
+            .filter(|event| event.channel == self.chat_channel)
+    }
+
+    pub fn set_chat_channel(&mut self, channel: ChatChannel) {
+        self.chat_channel = channel;
+        self.chat_message_index = self.channel_chat_events().count().checked_sub(1);
+        self.should_update_message_list = Some(SwarmView::Chat);
+    }
+
     pub fn push_chat_event(
         &mut self,
         timestamp: Tick,
         peer_id: PeerId,
         author: String,
         message: String,
+        channel: ChatChannel,
     ) {
-        let event = ChatEvent::new(timestamp, peer_id, author, message);
+        let event = ChatEvent::new(timestamp, peer_id, author, message, channel);
 
         if self.chat_events.insert(event) {
-            self.unread_chat_messages += 1;
-            self.chat_message_index = self
-                .chat_message_index
-                .map_or(Some(0), |index| Some(index + 1));
+            if self.is_chat_channel_joined(&channel) {
+                self.unread_chat_messages += 1;
+            }
+            if channel == self.chat_channel {
+                self.chat_message_index = self
+                    .chat_message_index
+                    .map_or(Some(0), |index| Some(index + 1));
+            }
         }
         self.should_update_message_list = Some(SwarmView::Chat);
     }
 
+    pub fn remove_chat_events_from(&mut self, peer_id: &PeerId) {
+        self.chat_events
+            .retain(|event| event.peer_id.as_ref() != Some(peer_id));
+        self.chat_message_index = self.channel_chat_events().count().checked_sub(1);
+        self.should_update_message_list = Some(SwarmView::Chat);
+    }
+
     pub fn push_chat_error_event(&mut self, timestamp: Tick, error: Error) {
-        let event = ChatEvent::error(timestamp, error.to_string());
+        let event = ChatEvent::error(timestamp, error.to_string(), self.chat_channel);
 
         if self.chat_events.insert(event) {
             self.unread_chat_messages += 1;
@@ -314,10 +364,13 @@ impl SwarmPanel {
                 e.from_peer_id,
                 e.author.clone(),
                 e.message.clone(),
+                e.channel,
             );
             if self.chat_events.insert(event) {
                 any_new = true;
-                self.unread_chat_messages += 1;
+                if self.is_chat_channel_joined(&e.channel) {
+                    self.unread_chat_messages += 1;
+                }
             }
         }
 
@@ -332,6 +385,141 @@ impl SwarmPanel {
         self.chat_history_received_len = chat_history.len();
     }
 
+    pub fn push_direct_message(&mut self, peer_id: PeerId) {
+        *self.unread_direct_messages.entry(peer_id).or_default() += 1;
+    }
+
+    fn selected_conversation(&self) -> Option<PeerId> {
+        self.conversation_index
+            .and_then(|index| self.conversations.get(index))
+            .map(|(peer_id, _)| *peer_id)
+    }
+
+    fn update_chat_channels(&mut self, world: &World) {
+        let mut chat_channels = vec![ChatChannel::Global];
+        if let Ok(own_team) = world.get_own_team() {
+            if let Some(planet_id) = own_team.is_on_planet() {
+                chat_channels.push(ChatChannel::Planet { planet_id });
+            }
+            if let Some(tournament_id) = own_team
+                .committed_to_tournament()
+                .or(own_team.is_organizing_tournament)
+            {
+                chat_channels.push(ChatChannel::Tournament { tournament_id });
+            }
+        }
+
+        if chat_channels != self.chat_channels {
+            self.chat_channels = chat_channels;
+            if !self.chat_channels.contains(&self.chat_channel) {
+                self.set_chat_channel(ChatChannel::Global);
+            }
+            self.should_update_message_list = Some(SwarmView::Chat);
+        }
+    }
+
+    fn render_chat_channels(&self, frame: &mut UiFrame, world: &World, area: Rect) {
+        let mut constraints = [Constraint::Length(28)].repeat(self.chat_channels.len());
+        constraints.push(Constraint::Fill(1));
+        constraints.push(Constraint::Length(28));
+        let split = Layout::horizontal(constraints).split(area);
+
+        for (idx, channel) in self.chat_channels.iter().enumerate() {
+            let mut button = Button::new(
+                channel.name(world),
+                UiCallback::SetChatChannel { channel: *channel },
+            )
+            .set_hover_text(match channel {
+                ChatChannel::Global => "Talk to every crew in the network.",
+                ChatChannel::Planet { .. } => "Talk to the crews on your current planet.",
+                ChatChannel::Tournament { .. } => "Talk to the crews in your tournament.",
+            });
+            if *channel == self.chat_channel {
+                button.select();
+            }
+            frame.render_interactive_widget(button, split[idx]);
+        }
+
+        // Block the author of the selected message.
+        let selected_event = self
+            .chat_message_index
+            .and_then(|index| self.channel_chat_events().nth(index));
+        if let Some(event) = selected_event {
+            if let Some(peer_id) = event.peer_id {
+                if !matches!(self.peer_id_to_team_id.get(&peer_id), Some(&id) if id == world.own_team_id)
+                {
+                    let block_button = Button::new(
+                        format!("Block {}", event.author),
+                        UiCallback::ToggleBlockPeer { peer_id },
+                    )
+                    .block(default_block().border_style(UiStyle::ERROR))
+                    .set_hover_text(format!(
+                        "Hide chat and direct messages from {}. Unblock from the Messages view.",
+                        event.author
+                    ));
+                    frame.render_interactive_widget(block_button, split[split.len() - 1]);
+                }
+            }
+        }
+    }
+
+    fn update_conversations(&mut self, world: &World) {
+        let selected_peer_id = self.selected_conversation();
+        let own_peer_id = self.team_id_to_peer_id.get(&world.own_team_id).copied();
+
+        let conversations = &world.network_store_data.direct_messages;
+        self.conversations = conversations
+            .keys()
+            .chain(self.team_id_to_peer_id.values())
+            .unique()
+            .filter(|&&peer_id| Some(peer_id) != own_peer_id)
+            .map(|&peer_id| {
+                let last_message = conversations
+                    .get(&peer_id)
+                    .and_then(|conversation| conversation.iter().max_by_key(|e| e.timestamp));
+
+                let name = if let Some(team) = self
+                    .peer_id_to_team_id
+                    .get(&peer_id)
+                    .and_then(|team_id| world.teams.get(team_id))
+                {
+                    team.name.clone()
+                } else if let Some(entry) = conversations.get(&peer_id).and_then(|conversation| {
+                    conversation.iter().find(|e| e.from_peer_id == peer_id)
+                }) {
+                    entry.author.clone()
+                } else {
+                    peer_id.to_base58().chars().skip(8).take(8).collect()
+                };
+
+                (
+                    peer_id,
+                    name,
+                    last_message.map(|e| e.timestamp).unwrap_or_default(),
+                )
+            })
+            .sorted_by(|(_, a_name, a_timestamp), (_, b_name, b_timestamp)| {
+                b_timestamp.cmp(a_timestamp).then(a_name.cmp(b_name))
+            })
+            .map(|(peer_id, name, _)| (peer_id, name))
+            .collect();
+
+        // Keep the same conversation selected when the order changes.
+        self.conversation_index = selected_peer_id
+            .and_then(|peer_id| self.conversations.iter().position(|(id, _)| *id == peer_id))
+            .or(if self.conversations.is_empty() {
+                None
+            } else {
+                Some(0)
+            });
+
+        if self.view == SwarmView::Messages {
+            if let Some(peer_id) = self.selected_conversation() {
+                self.unread_direct_messages.remove(&peer_id);
+            }
+        }
+    }
+
     pub fn add_peer_id(&mut self, peer_id: PeerId, team_id: TeamId) {
         self.team_id_to_peer_id.insert(team_id, peer_id);
         self.peer_id_to_team_id.insert(peer_id, team_id);
@@ -360,6 +548,7 @@ impl SwarmPanel {
             Constraint::Length(3),
             Constraint::Length(3),
             Constraint::Length(3),
+            Constraint::Length(3),
             Constraint::Fill(1),
             Constraint::Length(3),
         ])
@@ -375,6 +564,23 @@ impl SwarmPanel {
         .set_hotkey(ui_key::CYCLE_VIEW)
         .set_hover_text("View the chat. Just type and press Enter to message the network.");
 
+        let unread_direct_messages = self.unread_direct_messages();
+        let mut messages_button = Button::new(
+            if unread_direct_messages > 0 {
+                format!("Messages ({unread_direct_messages})")
+            } else {
+                "Messages".to_string()
+            },
+            UiCallback::SetSwarmPanelView {
+                topic: SwarmView::Messages,
+            },
+        )
+        .bold()
+        .set_hotkey(ui_key::CYCLE_VIEW)
+        .set_hover_text(
+            "View private conversations with other crews. Messages are end-to-end encrypted.",
+        );
+
         let mut requests_button = Button::new(
             "Requests",
             UiCallback::SetSwarmPanelView {
@@ -407,15 +613,17 @@ impl SwarmPanel {
 
         match self.view {
             SwarmView::Chat => chat_button.select(),
+            SwarmView::Messages => messages_button.select(),
             SwarmView::Requests => requests_button.select(),
             SwarmView::Log => log_button.select(),
             SwarmView::Ranking => ranking_button.select(),
         }
 
         frame.render_interactive_widget(chat_button, split[0]);
-        frame.render_interactive_widget(requests_button, split[1]);
-        frame.render_interactive_widget(log_button, split[2]);
-        frame.render_interactive_widget(ranking_button, split[3]);
+        frame.render_interactive_widget(messages_button, split[1]);
+        frame.render_interactive_widget(requests_button, split[2]);
+        frame.render_interactive_widget(log_button, split[3]);
+        frame.render_interactive_widget(ranking_button, split[4]);
 
         let mut items: Vec<ListItem> = vec![];
 
@@ -452,12 +660,12 @@ impl SwarmPanel {
             .count();
         frame.render_widget(
             list.block(default_block().title(format!("Peers ({connected_peers_count})"))),
-            split[4],
+            split[5],
         );
 
         let dial_button = Button::new("Ping", UiCallback::Ping);
 
-        frame.render_interactive_widget(dial_button, split[5]);
+        frame.render_interactive_widget(dial_button, split[6]);
     }
 
     fn build_challenge_list(
@@ -655,7 +863,7 @@ impl SwarmPanel {
     }
 
     fn render_team_ranking(&mut self, frame: &mut UiFrame, world: &World, area: Rect) {
-        let block_title = "Top 10 Crews by Elo";
+        let block_title = "Top 10 Crews by Rating";
         let h_split = Layout::horizontal([Constraint::Fill(1), Constraint::Length(80)]).split(area);
         if self.team_ranking.is_empty() {
             frame.render_widget(default_block().title(block_title), h_split[0]);
@@ -698,10 +906,14 @@ impl SwarmPanel {
             .map(|(idx, (_, ranking))| {
                 let team_id = ranking.team.id;
                 let text = format!(
-                    "{:>2}. {:<MAX_NAME_LENGTH$} {:5.0}   {}",
+                    "{:>2}. {:<MAX_NAME_LENGTH$} {:5.0}±{:<3.0} {}",
                     idx + 1,
                     &ranking.team.name,
                     ranking.team.network_game_rating.rating,
+                    ranking
+                        .team
+                        .network_game_rating
+                        .deviation_at(world.last_tick_short_interval),
                     ranking.team.reputation.stars()
                 );
 
@@ -725,8 +937,8 @@ impl SwarmPanel {
         frame.render_widget(
             Span::styled(
                 format!(
-                    "{:>2}   {:<MAX_NAME_LENGTH$}  {}    {}",
-                    "", "Team", "Elo", "Reputation"
+                    "{:>2}   {:<MAX_NAME_LENGTH$} {:<8} {}",
+                    "", "Team", "Rating", "Reputation"
                 ),
                 UiStyle::HEADER,
             ),
@@ -869,7 +1081,13 @@ impl SwarmPanel {
                 self.render_team_ranking(frame, world, ranking_split[1]);
             }
 
-            SwarmView::Chat => self.render_event_messages(frame, SwarmView::Chat, split[0]),
+            SwarmView::Chat => {
+                let chat_split =
+                    Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).split(split[0]);
+                self.render_chat_channels(frame, world, chat_split[0]);
+                self.render_event_messages(frame, SwarmView::Chat, chat_split[1]);
+            }
+            SwarmView::Messages => self.render_conversations(frame, world, split[0]),
             SwarmView::Log => self.render_event_messages(frame, SwarmView::Log, split[0]),
         }
 
@@ -883,9 +1101,120 @@ impl SwarmPanel {
         Ok(())
     }
 
+    fn render_conversations(&self, frame: &mut UiFrame, world: &World, area: Rect) {
+        let h_split = Layout::horizontal([Constraint::Length(36), Constraint::Fill(1)]).split(area);
+
+        let options = self
+            .conversations
+            .iter()
+            .map(|(peer_id, name)| {
+                let unread = self
+                    .unread_direct_messages
+                    .get(peer_id)
+                    .copied()
+                    .unwrap_or_default();
+                let is_blocked = world.network_store_data.is_peer_blocked(peer_id);
+                let text = if is_blocked {
+                    format!("{name:MAX_NAME_LENGTH$} (blocked)")
+                } else if unread > 0 {
+                    format!("{name:MAX_NAME_LENGTH$} ({unread})")
+                } else {
+                    name.clone()
+                };
+                let style = if is_blocked {
+                    UiStyle::ERROR
+                } else if self.is_peer_connected(peer_id) {
+                    UiStyle::NETWORK
+                } else {
+                    UiStyle::DISCONNECTED
+                };
+                (text, style)
+            })
+            .collect_vec();
+
+        frame.render_stateful_interactive_widget(
+            selectable_list(options).block(default_block().title("Conversations")),
+            h_split[0],
+            &mut ClickableListState::default().with_selected(self.conversation_index),
+        );
+
+        let Some((peer_id, name)) = self
+            .conversation_index
+            .and_then(|index| self.conversations.get(index))
+        else {
+            frame.render_widget(default_block().title("Messages"), h_split[1]);
+            return;
+        };
+
+        let conversation_split =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(3)]).split(h_split[1]);
+        let button_split = Layout::horizontal([Constraint::Fill(1), Constraint::Length(28)])
+            .split(conversation_split[1]);
+        let block_button = if world.network_store_data.blocked_peers.contains(peer_id) {
+            Button::new(
+                format!("Unblock {name}"),
+                UiCallback::ToggleBlockPeer { peer_id: *peer_id },
+            )
+            .set_hover_text(format!(
+                "Receive chat and direct messages from {name} again."
+            ))
+        } else {
+            Button::new(
+                format!("Block {name}"),
+                UiCallback::ToggleBlockPeer { peer_id: *peer_id },
+            )
+            .block(default_block().border_style(UiStyle::ERROR))
+            .set_hover_text(format!("Hide chat and direct messages from {name}."))
+        };
+        frame.render_interactive_widget(block_button, button_split[1]);
+
+        let conversation = world.network_store_data.get_conversation(peer_id);
+        let mut items = vec![];
+        for entry in conversation.iter() {
+            let timestamp_span = Span::styled(
+                format!("[{}] ", entry.timestamp.formatted_as_time()),
+                UiStyle::HIGHLIGHT,
+            );
+            let author_span = if entry.from_peer_id == *peer_id {
+                Span::styled(format!("{}: ", entry.author), UiStyle::NETWORK)
+            } else {
+                Span::styled("You: ", UiStyle::OWN_TEAM)
+            };
+
+            let incipit_length = timestamp_span.content.len() + author_span.content.len();
+            let message_max_length =
+                (conversation_split[0].width as usize).saturating_sub(2 + incipit_length);
+
+            let text_lines = wrap_text(entry.message.as_str(), message_max_length);
+            let mut lines = vec![Line::from(vec![
+                timestamp_span,
+                author_span,
+                Span::raw(text_lines[0].clone()),
+            ])];
+            for text in text_lines.iter().skip(1) {
+                lines.push(Line::from(format!(
+                    "{}{}",
+                    " ".repeat(incipit_length),
+                    text
+                )));
+            }
+            items.push(ClickableListItem::new(lines));
+        }
+
+        // Always show the most recent messages.
+        let selected = items.len().checked_sub(1);
+        frame.render_stateful_widget(
+            ClickableList::new(items).block(
+                default_block().title(format!("Messages with {name} (end-to-end encrypted)")),
+            ),
+            conversation_split[0],
+            &mut ClickableListState::default().with_selected(selected),
+        );
+    }
+
     fn update_chat_event_list(&mut self, world: &World) {
         let mut items = vec![];
-        for event in self.chat_events.iter() {
+        for event in self.channel_chat_events() {
             let timestamp_span = Span::styled(
                 format!("[{}] ", event.timestamp.formatted_as_time()),
                 UiStyle::HIGHLIGHT,
@@ -941,7 +1270,8 @@ impl SwarmPanel {
             items.push(ClickableListItem::new(lines));
         }
 
-        self.chat_message_list = ClickableList::new(items).block(default_block().title("Chat"));
+        self.chat_message_list = ClickableList::new(items)
+            .block(default_block().title(format!("Chat - {}", self.chat_channel.name(world))));
     }
 
     fn update_log_event_list(&mut self) {
@@ -1017,6 +1347,7 @@ impl Screen for SwarmPanel {
         if self.max_index() == 0 {
             match self.view {
                 SwarmView::Chat => self.chat_message_index = None,
+                SwarmView::Messages => self.conversation_index = None,
                 SwarmView::Log => self.log_message_index = None,
                 SwarmView::Ranking => match self.active_list {
                     PanelList::Players => self.player_ranking_index = None,
@@ -1032,6 +1363,9 @@ impl Screen for SwarmPanel {
             self.unread_chat_messages = 0;
         }
 
+        self.update_chat_channels(world);
+        self.update_conversations(world);
+
         match self.should_update_message_list {
             Some(SwarmView::Chat) => {
                 self.update_chat_event_list(world);
@@ -1071,6 +1405,26 @@ impl Screen for SwarmPanel {
                     topic: self.view.next(),
                 });
             }
+            KeyCode::Enter if self.view == SwarmView::Messages => {
+                let to_peer_id = self.selected_conversation()?;
+                let mut message = self.textarea.lines().iter().join("\n");
+                if message.is_empty() {
+                    return None;
+                }
+
+                for (from, to) in self.emojies_substutions.iter() {
+                    message = message.replace(from, to);
+                }
+
+                self.textarea.move_cursor(CursorMove::End);
+                self.textarea.delete_line_by_head();
+
+                return Some(UiCallback::SendDirectMessage {
+                    timestamp: Tick::now(),
+                    to_peer_id,
+                    message,
+                });
+            }
             KeyCode::Enter => {
                 // FIXME: if a message is selected, render this as a reply to that message.
                 if self.max_index() > 0 {
@@ -1088,11 +1442,7 @@ impl Screen for SwarmPanel {
 
                 if message.is_empty() {
                     // If no message, go to last message
-                    self.chat_message_index = if self.chat_events.is_empty() {
-                        None
-                    } else {
-                        Some(self.chat_events.len() - 1)
-                    };
+                    self.chat_message_index = self.channel_chat_events().count().checked_sub(1);
                     return None;
                 }
 
@@ -1104,7 +1454,11 @@ impl Screen for SwarmPanel {
                 self.textarea.delete_line_by_head();
 
                 let timestamp = Tick::now();
-                return Some(UiCallback::SendMessage { timestamp, message });
+                return Some(UiCallback::SendMessage {
+                    timestamp,
+                    message,
+                    channel: self.chat_channel,
+                });
             }
             _ => {
                 self.textarea.input(input_from_key_event(key_event));
@@ -1121,7 +1475,7 @@ impl Screen for SwarmPanel {
     }
 
     fn is_capturing_text(&self) -> bool {
-        self.view == SwarmView::Chat
+        matches!(self.view, SwarmView::Chat | SwarmView::Messages)
     }
 
     fn render_help_widget(
@@ -1136,8 +1490,12 @@ impl Screen for SwarmPanel {
             area,
             vec![
                 Line::from(" Talk to other captains over the peer-to-peer swarm: chat,"),
-                Line::from(" review trade and challenge requests, browse the global player"),
-                Line::from(" and team rankings, and watch the network log."),
+                Line::from(" message crews privately, review trade and challenge requests,"),
+                Line::from(" browse the global player and team rankings, and watch the"),
+                Line::from(" network log. Private messages are end-to-end encrypted, and"),
+                Line::from(" relayers deliver them to crews that are offline. Chat has a"),
+                Line::from(" channel for your planet and tournament, and you can block"),
+                Line::from(" captains from the Chat and Messages views."),
             ],
             vec![
                 (
@@ -1162,11 +1520,14 @@ impl Screen for SwarmPanel {
             vec![
                 Line::from(" Controls:"),
                 Line::from(format!(
-                    "   {}        Cycle view (Chat / Requests / Log / Ranking)",
+                    "   {}        Cycle view (Chat / Messages / Requests / Log / Ranking)",
                     ui_key::CYCLE_VIEW
                 )),
                 Line::from("   ↑/↓        Scroll the active list"),
-                Line::from("   Enter      Send a chat message in Chat view"),
+                Line::from(
+                    "   Enter      Send a chat message to the selected channel in Chat view",
+                ),
+                Line::from("   Enter      Message the selected crew in Messages view"),
                 Line::from("   Type       Compose your chat message at the input bar"),
                 Line::from(format!(
                     "   {} / {}      Accept / decline highlighted trade",
@@ -1183,6 +1544,7 @@ impl SplitPanel for SwarmPanel {
     fn index(&self) -> Option<usize> {
         match self.view {
             SwarmView::Chat => self.chat_message_index,
+            SwarmView::Messages => self.conversation_index,
             SwarmView::Log => self.log_message_index,
             SwarmView::Ranking => match self.active_list {
                 PanelList::Players => self.player_ranking_index,
@@ -1194,7 +1556,8 @@ impl SplitPanel for SwarmPanel {
 
     fn max_index(&self) -> usize {
         match self.view {
-            SwarmView::Chat => self.chat_events.len(),
+            SwarmView::Chat => self.channel_chat_events().count(),
+            SwarmView::Messages => self.conversations.len(),
             SwarmView::Log => self.log_events.len(),
             SwarmView::Ranking => match self.active_list {
                 PanelList::Players => self.player_ranking.len(),
@@ -1213,6 +1576,7 @@ impl SplitPanel for SwarmPanel {
 
         match self.view {
             SwarmView::Chat => self.chat_message_index = index,
+            SwarmView::Messages => self.conversation_index = index,
             SwarmView::Log => self.log_message_index = index,
             SwarmView::Ranking => match self.active_list {
                 PanelList::Players => self.player_ranking_index = index,
@@ -3,12 +3,12 @@ use crate::{
         game::{Game, GameSummary},
         types::Possession,
     },
-    types::{SystemTimeTick, TeamId, Tick},
+    types::{GameId, GameMap, GameSummaryMap, SystemTimeTick, TeamId, Tick},
     ui::constants::UiStyle,
 };
 use itertools::Itertools;
 use ratatui::{
-    style::Style,
+    style::{Style, Stylize},
     text::{Line, Span},
 };
 
@@ -476,6 +476,63 @@ pub fn get_bracket_lines(
     lines
 }
 
+fn game_block_lines(description: TournamentDescription) -> Vec<Line<'static>> {
+    let marker = |possession: Possession| {
+        if description.winner == Some(possession) {
+            Span::styled("▸ ", UiStyle::OK)
+        } else {
+            Span::raw("  ")
+        }
+    };
+
+    vec![
+        Line::from(vec![
+            marker(Possession::Home),
+            Span::styled(
+                description.home_team_name.clone(),
+                description.home_team_style,
+            ),
+        ]),
+        Line::from(format!("  {}", description.result)),
+        Line::from(vec![
+            marker(Possession::Away),
+            Span::styled(
+                description.away_team_name.clone(),
+                description.away_team_style,
+            ),
+        ]),
+        Line::default(),
+    ]
+}
+
+// Games arranged in one column per round, for formats whose games do not form
+// a single elimination tree (double elimination brackets and swiss rounds).
+pub fn get_round_columns(
+    rounds: &[(String, Vec<GameId>)],
+    games: &GameMap,
+    past_games: &GameSummaryMap,
+    own_team_id: TeamId,
+    timestamp: Tick,
+) -> Vec<Vec<Line<'static>>> {
+    rounds
+        .iter()
+        .map(|(title, game_ids)| {
+            let mut lines = vec![Line::from(title.clone()).bold(), Line::default()];
+            for game_id in game_ids {
+                let description = if let Some(game) = games.get(game_id) {
+                    game.tournament_description(own_team_id, timestamp)
+                } else if let Some(game) = past_games.get(game_id) {
+                    game.tournament_description(own_team_id, timestamp)
+                } else {
+                    continue;
+                };
+                lines.append(&mut game_block_lines(description));
+            }
+            lines
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -10,8 +10,11 @@ use super::{
 };
 use crate::core::{skill::Rated, world::World};
 use crate::game_engine::game::GameSummary;
-use crate::game_engine::{Tournament, TournamentId, TournamentState, TournamentSummary};
-use crate::types::{AppResult, SystemTimeTick, Tick};
+use crate::game_engine::{
+    Tournament, TournamentBracket, TournamentId, TournamentStanding, TournamentState,
+    TournamentSummary, TournamentType,
+};
+use crate::types::{AppResult, GameId, SystemTimeTick};
 use crate::ui::tournament_brackets_lines::{current_round, number_of_rounds};
 use crate::ui::{tournament_brackets_lines, ui_key};
 use core::fmt::Debug;
@@ -28,6 +31,8 @@ use ratatui::{
 };
 use std::fmt::Display;
 
+const STANDINGS_WIDTH: u16 = 60;
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum TournamentView {
     #[default]
@@ -51,7 +56,7 @@ impl TournamentView {
             Self::Open => world
                 .tournaments
                 .get(tournament_id)
-                .map(|t| !t.has_started(Tick::now()))
+                .map(|t| !t.has_started(world.clock.now()))
                 .unwrap_or_default(),
             Self::Past => world
                 .past_tournaments
@@ -246,7 +251,7 @@ impl TournamentPanel {
             Button::new(tournament.name(), UiCallback::GoToPlanet { planet_id });
         frame.render_interactive_widget(tournament_main_button, split[0]);
 
-        match tournament.state(Tick::now()) {
+        match tournament.state(world.clock.now()) {
             TournamentState::Canceled => {}
             TournamentState::Registration => {
                 self.render_registration_tournament(tournament, frame, world, split[1])?
@@ -278,7 +283,7 @@ impl TournamentPanel {
         let t_split = Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).split(area);
         let countdown = (tournament
             .registrations_closing_at
-            .saturating_sub(Tick::now()))
+            .saturating_sub(world.clock.now()))
         .formatted();
         frame.render_widget(
             Paragraph::new(format!(
@@ -334,7 +339,7 @@ impl TournamentPanel {
             tournament.registrations_closing_at.formatted_as_time(),
         ));
 
-        if let Err(err) = own_team.can_register_to_tournament(tournament, Tick::now()) {
+        if let Err(err) = own_team.can_register_to_tournament(tournament, world.clock.now()) {
             register_button.disable(Some(err.to_string()));
             if tournament.is_team_registered(&world.own_team_id) {
                 register_button.set_text("Already registered");
@@ -460,6 +465,30 @@ impl TournamentPanel {
     ) -> AppResult<()> {
         let t_split = Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).split(area);
 
+        if tournament.tournament_type().is_round_based() {
+            let current_round = tournament.current_round();
+            let text = match tournament.number_of_rounds() {
+                Some(number_of_rounds) => {
+                    format!("Currently playing round {current_round}/{number_of_rounds}")
+                }
+                None => format!("Currently playing round {current_round}"),
+            };
+            frame.render_widget(
+                Paragraph::new(text).centered().block(default_block()),
+                t_split[0],
+            );
+            let standings = tournament.standings(&world.games, &world.past_games);
+            self.render_round_based_tournament(
+                tournament.tournament_type(),
+                &standings,
+                |bracket| tournament.rounds(bracket),
+                frame,
+                world,
+                t_split[1],
+            );
+            return Ok(());
+        }
+
         let active_games = tournament.active_games(&world.games);
         let past_game_summaries = tournament.past_game_summaries(&world.past_games);
 
@@ -493,7 +522,7 @@ impl TournamentPanel {
             &active_games,
             &past_game_summaries,
             world.own_team_id,
-            Tick::now(),
+            world.clock.now(),
         );
         for (round_idx, lines) in brackets.iter().enumerate() {
             frame.render_widget(Paragraph::new(lines.clone()), brackets_split[round_idx]);
@@ -524,6 +553,18 @@ impl TournamentPanel {
             t_split[0],
         );
 
+        if tournament_summary.tournament_type().is_round_based() {
+            self.render_round_based_tournament(
+                tournament_summary.tournament_type(),
+                &tournament_summary.standings,
+                |bracket| tournament_summary.rounds(bracket),
+                frame,
+                world,
+                t_split[1],
+            );
+            return Ok(());
+        }
+
         let games = vec![];
         let game_summaries = tournament_summary
             .game_ids
@@ -539,7 +580,7 @@ impl TournamentPanel {
             &games,
             &game_summaries,
             world.own_team_id,
-            Tick::now(),
+            world.clock.now(),
         );
 
         let brackets_split =
@@ -551,6 +592,121 @@ impl TournamentPanel {
         Ok(())
     }
 
+    fn render_round_based_tournament(
+        &self,
+        tournament_type: TournamentType,
+        standings: &[TournamentStanding],
+        rounds: impl Fn(TournamentBracket) -> Vec<(usize, Vec<GameId>)>,
+        frame: &mut UiFrame,
+        world: &World,
+        area: Rect,
+    ) {
+        match tournament_type {
+            TournamentType::Swiss => {
+                let split =
+                    Layout::horizontal([Constraint::Length(STANDINGS_WIDTH), Constraint::Fill(1)])
+                        .split(area);
+                self.render_standings(standings, frame, world, split[0]);
+                self.render_rounds(
+                    rounds(TournamentBracket::Main),
+                    None,
+                    frame,
+                    world,
+                    split[1],
+                );
+            }
+            TournamentType::DoubleElimination => {
+                let brackets = [
+                    TournamentBracket::Winners,
+                    TournamentBracket::Losers,
+                    TournamentBracket::GrandFinal,
+                ];
+                let split =
+                    Layout::vertical([Constraint::Fill(1)].repeat(brackets.len())).split(area);
+                for (idx, bracket) in brackets.into_iter().enumerate() {
+                    self.render_rounds(rounds(bracket), Some(bracket), frame, world, split[idx]);
+                }
+            }
+            _ => self.render_standings(standings, frame, world, area),
+        }
+    }
+
+    fn render_rounds(
+        &self,
+        rounds: Vec<(usize, Vec<GameId>)>,
+        bracket: Option<TournamentBracket>,
+        frame: &mut UiFrame,
+        world: &World,
+        area: Rect,
+    ) {
+        let title = match bracket {
+            Some(bracket) => format!("{bracket} bracket"),
+            None => "Rounds".to_string(),
+        };
+        frame.render_widget(default_block().title(title), area);
+
+        let rounds = rounds
+            .into_iter()
+            .map(|(round, game_ids)| (format!("Round {}", round + 1), game_ids))
+            .collect_vec();
+        let columns = tournament_brackets_lines::get_round_columns(
+            &rounds,
+            &world.games,
+            &world.past_games,
+            world.own_team_id,
+            world.clock.now(),
+        );
+
+        let split = Layout::horizontal([Constraint::Length(24)].repeat(columns.len()))
+            .split(area.inner(Margin::new(1, 1)));
+        for (idx, lines) in columns.into_iter().enumerate() {
+            frame.render_widget(Paragraph::new(lines), split[idx]);
+        }
+    }
+
+    fn render_standings(
+        &self,
+        standings: &[TournamentStanding],
+        frame: &mut UiFrame,
+        world: &World,
+        area: Rect,
+    ) {
+        let mut lines = vec![Line::from(format!(
+            "{:>3} {:<MAX_NAME_LENGTH$} {:>3} {:>3} {:>3} {:>3} {:>5} {:>5} {:>5} {:>4}",
+            "#", "Crew", "GP", "W", "D", "L", "PF", "PA", "Diff", "Pts"
+        ))
+        .bold()];
+
+        for (idx, standing) in standings.iter().enumerate() {
+            let style = if standing.team_id == world.own_team_id {
+                UiStyle::OWN_TEAM
+            } else {
+                UiStyle::DEFAULT
+            };
+            lines.push(
+                Line::from(format!(
+                    "{:>3} {:<MAX_NAME_LENGTH$} {:>3} {:>3} {:>3} {:>3} {:>5} {:>5} {:>+5} {:>4}",
+                    idx + 1,
+                    standing.team_name,
+                    standing.games_played(),
+                    standing.wins,
+                    standing.draws,
+                    standing.losses,
+                    standing.points_for,
+                    standing.points_against,
+                    standing.point_differential(),
+                    standing.points()
+                ))
+                .style(style),
+            );
+        }
+
+        frame.render_widget(
+            Paragraph::new(lines).block(default_block().title("Standings")),
+            area,
+        );
+    }
+
     pub const fn set_view(&mut self, filter: TournamentView) {
         self.view = filter;
         self.update_view = true;
@@ -684,9 +840,9 @@ impl Screen for TournamentPanel {
             frame,
             area,
             vec![
-                Line::from(" Track running tournaments, browse past brackets, register"),
-                Line::from(" your team, or organize a new quick or big tournament from"),
-                Line::from(" your home planet."),
+                Line::from(" Track running tournaments, browse past brackets and league"),
+                Line::from(" standings, register your team, or organize a new tournament"),
+                Line::from(" from your home planet."),
             ],
             vec![
                 (
@@ -720,9 +876,15 @@ impl Screen for TournamentPanel {
                     ui_key::REGISTER_TO_TOURNAMENT
                 )),
                 Line::from(format!(
-                    "   {} / {}      Organize a quick / big tournament",
+                    "   {} / {} / {}  Organize a quick / big tournament or a league",
                     ui_key::ORGANIZE_QUICK_TOURNAMENT,
-                    ui_key::ORGANIZE_BIG_TOURNAMENT
+                    ui_key::ORGANIZE_BIG_TOURNAMENT,
+                    ui_key::ORGANIZE_LEAGUE
+                )),
+                Line::from(format!(
+                    "   {} / {}      Organize a double elimination / swiss tournament",
+                    ui_key::ORGANIZE_DOUBLE_ELIMINATION,
+                    ui_key::ORGANIZE_SWISS
                 )),
             ],
         );
@@ -14,7 +14,7 @@ use crate::app_version;
 use crate::core::{AsteroidUpgradeTarget, UpgradeableElement};
 use crate::game_engine::game::Game;
 use crate::game_engine::{Tournament, TournamentId, TournamentType};
-use crate::network::types::TournamentRequestState;
+use crate::network::types::{ChatChannel, ChatHistoryEntry, TournamentRequestState};
 use crate::network::{challenge::Challenge, trade::Trade};
 use crate::types::{HashMapWithResult, PlayerMap};
 use crate::ui::tournament_panel::TournamentView;
@@ -22,12 +22,17 @@ use crate::ui::ui_key;
 use crate::{
     app::App,
     core::*,
-    game_engine::{tactic::Tactic, types::TeamInGame},
+    game_engine::{
+        adjustment::GameAdjustmentKind,
+        tactic::{CustomTactic, DefensiveScheme, Tactic, MAX_CUSTOM_TACTICS},
+        types::TeamInGame,
+    },
     image::color_map::{ColorMap, ColorPreset},
     space_adventure::PlayerInput,
     types::{AppCallback, AppResult, GameId, PlanetId, PlayerId, SystemTimeTick, TeamId, Tick},
 };
 use anyhow::anyhow;
+use libp2p::PeerId;
 use rand::{seq::IteratorRandom, RngExt, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 use ratatui::crossterm::event::{KeyCode, MouseEvent, MouseEventKind};
@@ -126,6 +131,28 @@ pub enum UiCallback {
         tactic: Tactic,
     },
     SetNextTeamTactic,
+    SetTeamDefensiveScheme {
+        scheme: DefensiveScheme,
+    },
+    CreateCustomTactic,
+    DeleteCustomTactic {
+        index: usize,
+    },
+    UpdateCustomTactic {
+        index: usize,
+        tactic: CustomTactic,
+    },
+    RenameCustomTactic {
+        index: usize,
+        name: String,
+    },
+    SetTeamCustomTactic {
+        index: Option<usize>,
+    },
+    AdjustGame {
+        game_id: GameId,
+        kind: GameAdjustmentKind,
+    },
     SetUiTab {
         ui_tab: UiTab,
     },
@@ -239,6 +266,18 @@ pub enum UiCallback {
     SendMessage {
         timestamp: Tick,
         message: String,
+        channel: ChatChannel,
+    },
+    SetChatChannel {
+        channel: ChatChannel,
+    },
+    ToggleBlockPeer {
+        peer_id: PeerId,
+    },
+    SendDirectMessage {
+        timestamp: Tick,
+        to_peer_id: PeerId,
+        message: String,
     },
     PushUiPopup {
         popup_message: PopupMessage,
@@ -680,6 +719,7 @@ impl UiCallback {
 
     fn organize_new_tournament(tournament_type: TournamentType) -> AppCallback {
         Box::new(move |app: &mut App| {
+            let now = app.world.clock.now();
             let own_team = app
                 .world
                 .teams
@@ -697,7 +737,7 @@ impl UiCallback {
                 .get(&planet_id)
                 .expect("Space cove planet should exist.");
 
-            let tournament = Tournament::new(own_team, tournament_type)?.on_planet(planet);
+            let tournament = Tournament::new(own_team, tournament_type, now)?.on_planet(planet);
 
             own_team.is_organizing_tournament = Some(tournament.id);
 
@@ -820,7 +860,7 @@ impl UiCallback {
             own_team.current_location = TeamLocation::Travelling {
                 from: current_planet.id,
                 to: planet_id,
-                started: Tick::now(),
+                started: app.world.clock.now(),
                 duration,
                 distance,
             };
@@ -891,7 +931,7 @@ impl UiCallback {
 
             own_team.current_location = TeamLocation::Exploring {
                 around: planet_id,
-                started: Tick::now(),
+                started: app.world.clock.now(),
                 duration,
             };
 
@@ -937,15 +977,18 @@ impl UiCallback {
         })
     }
 
-    fn send_message(timestamp: Tick, message: String) -> AppCallback {
+    fn send_message(timestamp: Tick, message: String, channel: ChatChannel) -> AppCallback {
         Box::new(move |app: &mut App| {
             let from_peer_id = *app.network_handler.own_peer_id();
             let own_team = app.world.get_own_team()?;
             let author = own_team.name.clone();
-            if let Err(err) =
-                app.network_handler
-                    .send_message(timestamp, from_peer_id, author, message.clone())
-            {
+            if let Err(err) = app.network_handler.send_message(
+                timestamp,
+                from_peer_id,
+                author,
+                message.clone(),
+                channel,
+            ) {
                 app.ui.push_chat_error_event(timestamp, err);
             } else {
                 app.ui.push_chat_event(
@@ -953,6 +996,7 @@ impl UiCallback {
                     from_peer_id,
                     own_team.name.clone(),
                     message.clone(),
+                    channel,
                 );
             }
 
@@ -960,6 +1004,47 @@ impl UiCallback {
         })
     }
 
+    fn toggle_block_peer(peer_id: PeerId) -> AppCallback {
+        Box::new(move |app: &mut App| {
+            if peer_id == *app.network_handler.own_peer_id() {
+                return Err(anyhow!("Cannot block yourself"));
+            }
+
+            let text = if app.world.network_store_data.toggle_blocked_peer(peer_id) {
+                app.ui.remove_chat_events_from(&peer_id);
+                format!("Blocked peer {peer_id}")
+            } else {
+                format!("Unblocked peer {peer_id}")
+            };
+            app.ui
+                .push_log_event(Tick::now(), Some(peer_id), text, log::Level::Info);
+
+            Ok(None)
+        })
+    }
+
+    fn send_direct_message(timestamp: Tick, to_peer_id: PeerId, message: String) -> AppCallback {
+        Box::new(move |app: &mut App| {
+            let from_peer_id = *app.network_handler.own_peer_id();
+            let author = app.world.get_own_team()?.name.clone();
+            app.network_handler
+                .send_direct_message(timestamp, to_peer_id, &author, &message)?;
+
+            app.world.network_store_data.add_direct_message(
+                to_peer_id,
+                ChatHistoryEntry {
+                    timestamp,
+                    from_peer_id,
+                    author,
+                    message: message.clone(),
+                    channel: ChatChannel::default(),
+                },
+            );
+
+            Ok(None)
+        })
+    }
+
     fn name_and_accept_asteroid(name: String, filename: String) -> AppCallback {
         Box::new(move |app: &mut App| {
             let mut own_team = app.world.get_own_team()?.clone();
@@ -1024,6 +1109,132 @@ impl UiCallback {
         })
     }
 
+    fn create_custom_tactic() -> AppCallback {
+        Box::new(move |app: &mut App| {
+            let mut team = app.world.get_own_team()?.clone();
+            if team.custom_tactics.len() >= MAX_CUSTOM_TACTICS {
+                return Err(anyhow!(
+                    "Cannot have more than {MAX_CUSTOM_TACTICS} custom tactics"
+                ));
+            }
+
+            let name = format!("Custom {}", team.custom_tactics.len() + 1);
+            team.custom_tactics
+                .push(CustomTactic::new(name, team.game_tactic));
+            app.world.teams.insert(team.id, team);
+
+            app.world.dirty = true;
+            app.world.dirty_network = true;
+            app.world.dirty_ui = true;
+
+            Ok(None)
+        })
+    }
+
+    fn delete_custom_tactic(index: usize) -> AppCallback {
+        Box::new(move |app: &mut App| {
+            let mut team = app.world.get_own_team()?.clone();
+            if index >= team.custom_tactics.len() {
+                return Err(anyhow!("Invalid custom tactic"));
+            }
+
+            if let Some(active) = team.active_custom_tactic {
+                if active == index {
+                    team.can_change_tactic()?;
+                    team.active_custom_tactic = None;
+                } else if active > index {
+                    team.active_custom_tactic = Some(active - 1);
+                }
+            }
+
+            team.custom_tactics.remove(index);
+            app.world.teams.insert(team.id, team);
+
+            app.world.dirty = true;
+            app.world.dirty_network = true;
+            app.world.dirty_ui = true;
+
+            Ok(None)
+        })
+    }
+
+    fn update_custom_tactic(index: usize, tactic: CustomTactic) -> AppCallback {
+        Box::new(move |app: &mut App| {
+            let mut team = app.world.get_own_team()?.clone();
+            if index >= team.custom_tactics.len() {
+                return Err(anyhow!("Invalid custom tactic"));
+            }
+
+            if team.active_custom_tactic == Some(index) {
+                team.can_change_tactic()?;
+            }
+
+            tactic.validate()?;
+            team.custom_tactics[index] = tactic.clone();
+            app.world.teams.insert(team.id, team);
+
+            app.world.dirty = true;
+            app.world.dirty_network = true;
+            app.world.dirty_ui = true;
+
+            Ok(None)
+        })
+    }
+
+    fn rename_custom_tactic(index: usize, name: String) -> AppCallback {
+        Box::new(move |app: &mut App| {
+            let mut tactic = app
+                .world
+                .get_own_team()?
+                .custom_tactics
+                .get(index)
+                .ok_or(anyhow!("Invalid custom tactic"))?
+                .clone();
+            tactic.name = name.clone();
+            Self::update_custom_tactic(index, tactic)(app)?;
+            app.ui.close_popup();
+
+            Ok(None)
+        })
+    }
+
+    fn set_team_custom_tactic(index: Option<usize>) -> AppCallback {
+        Box::new(move |app: &mut App| {
+            let mut team = app.world.get_own_team()?.clone();
+            team.can_change_tactic()?;
+
+            if let Some(index) = index {
+                let tactic = team
+                    .custom_tactics
+                    .get(index)
+                    .ok_or(anyhow!("Invalid custom tactic"))?;
+                tactic.validate()?;
+            }
+
+            team.active_custom_tactic = index;
+            app.world.teams.insert(team.id, team);
+
+            app.world.dirty = true;
+            app.world.dirty_network = true;
+            app.world.dirty_ui = true;
+
+            Ok(None)
+        })
+    }
+
+    fn adjust_game(game_id: GameId, kind: GameAdjustmentKind) -> AppCallback {
+        Box::new(move |app: &mut App| {
+            let adjustment = app.world.add_own_game_adjustment(game_id, kind.clone())?;
+
+            if app.world.games.get_or_err(&game_id)?.is_network() {
+                app.network_handler
+                    .send_game_adjustment(game_id, adjustment)?;
+            }
+
+            Ok(None)
+        })
+    }
+
     fn upgrade_spaceship(upgrade: Upgrade<SpaceshipUpgradeTarget>) -> AppCallback {
         Box::new(move |app: &mut App| {
             let team = app.world.get_own_team_mut()?;
@@ -1235,6 +1446,7 @@ impl UiCallback {
                 let own_team = app.world.get_own_team()?;
                 let mut team = own_team.clone();
                 team.game_tactic = *tactic;
+                team.active_custom_tactic = None;
                 app.world.teams.insert(team.id, team);
                 app.world.dirty = true;
                 app.world.dirty_ui = true;
@@ -1245,12 +1457,33 @@ impl UiCallback {
                 let own_team = app.world.get_own_team()?;
                 let mut team = own_team.clone();
                 team.game_tactic = team.game_tactic.next();
+                team.active_custom_tactic = None;
+                app.world.teams.insert(team.id, team);
+                app.world.dirty = true;
+                app.world.dirty_ui = true;
+                app.world.dirty_network = true;
+                Ok(None)
+            }
+            Self::SetTeamDefensiveScheme { scheme } => {
+                let own_team = app.world.get_own_team()?;
+                let mut team = own_team.clone();
+                team.defensive_scheme = *scheme;
                 app.world.teams.insert(team.id, team);
                 app.world.dirty = true;
                 app.world.dirty_ui = true;
                 app.world.dirty_network = true;
                 Ok(None)
             }
+            Self::CreateCustomTactic => Self::create_custom_tactic()(app),
+            Self::DeleteCustomTactic { index } => Self::delete_custom_tactic(*index)(app),
+            Self::UpdateCustomTactic { index, tactic } => {
+                Self::update_custom_tactic(*index, tactic.clone())(app)
+            }
+            Self::RenameCustomTactic { index, name } => {
+                Self::rename_custom_tactic(*index, name.clone())(app)
+            }
+            Self::SetTeamCustomTactic { index } => Self::set_team_custom_tactic(*index)(app),
+            Self::AdjustGame { game_id, kind } => Self::adjust_game(*game_id, kind.clone())(app),
             Self::TogglePitchView => {
                 app.ui.game_panel.toggle_pitch_view();
                 Ok(None)
@@ -1434,7 +1667,11 @@ impl UiCallback {
             Self::BuildSpaceCove { asteroid_id } => {
                 let own_team = app.world.get_own_team()?;
                 let bonus = TeamBonus::Upgrades.current_team_bonus(&app.world, &own_team.id)?;
-                let upgrade = Upgrade::new(AsteroidUpgradeTarget::SpaceCove, bonus);
+                let upgrade = Upgrade::new(
+                    AsteroidUpgradeTarget::SpaceCove,
+                    bonus,
+                    app.world.clock.now(),
+                );
                 Self::set_asteroid_pending_upgrade(*asteroid_id, upgrade)(app)?;
                 app.ui.close_popup();
                 Ok(None)
@@ -1547,7 +1784,7 @@ impl UiCallback {
                         team.current_location = TeamLocation::Travelling {
                             from,
                             to,
-                            started: Tick::now(),
+                            started: app.world.clock.now(),
                             duration: PORTAL_TRAVEL_DURATION,
                             distance,
                         };
@@ -1691,7 +1928,7 @@ impl UiCallback {
                     }
 
                     if tournament
-                        .confirm_team_registration(&team, players, Tick::now())
+                        .confirm_team_registration(&team, players, app.world.clock.now())
                         .is_ok()
                     {
                         own_team.tournament_registration_state =
@@ -1741,7 +1978,7 @@ impl UiCallback {
                 team.peer_id = Some(*app.network_handler.own_peer_id());
 
                 if let Some(tournament) = app.world.tournaments.get(tournament_id) {
-                    team.can_register_to_tournament(tournament, Tick::now())?;
+                    team.can_register_to_tournament(tournament, app.world.clock.now())?;
                 } else {
                     log::warn!("Invalid tournament id {tournament_id} for RegisterToTournament.");
                     return Ok(None);
@@ -1756,7 +1993,7 @@ impl UiCallback {
                     .expect("Tournament should exist here.");
                 // Organizer gets registered immediately.
                 if tournament.organizer_id == app.world.own_team_id {
-                    tournament.register_team(&team, players, Tick::now())?;
+                    tournament.register_team(&team, players, app.world.clock.now())?;
                     let own_team = app.world.get_own_team_mut()?;
                     own_team.tournament_registration_state =
                         TournamentRegistrationState::Registered {
@@ -1797,9 +2034,21 @@ impl UiCallback {
                 zoom_level,
             } => Self::zoom_to_planet(*planet_id, *zoom_level)(app),
             Self::Ping => Self::ping()(app),
-            Self::SendMessage { timestamp, message } => {
-                Self::send_message(*timestamp, message.clone())(app)
+            Self::SendMessage {
+                timestamp,
+                message,
+                channel,
+            } => Self::send_message(*timestamp, message.clone(), *channel)(app),
+            Self::SetChatChannel { channel } => {
+                app.ui.swarm_panel.set_chat_channel(*channel);
+                Ok(None)
             }
+            Self::ToggleBlockPeer { peer_id } => Self::toggle_block_peer(*peer_id)(app),
+            Self::SendDirectMessage {
+                timestamp,
+                to_peer_id,
+                message,
+            } => Self::send_direct_message(*timestamp, *to_peer_id, message.clone())(app),
             Self::PushUiPopup { popup_message } => {
                 app.ui.push_popup(popup_message.clone());
                 Ok(None)
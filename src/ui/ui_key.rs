@@ -29,6 +29,9 @@ pub const DECLINE_TRADE: KeyCode = KeyCode::Char('D');
 
 pub const ORGANIZE_QUICK_TOURNAMENT: KeyCode = KeyCode::Char('q');
 pub const ORGANIZE_BIG_TOURNAMENT: KeyCode = KeyCode::Char('b');
+pub const ORGANIZE_LEAGUE: KeyCode = KeyCode::Char('o');
+pub const ORGANIZE_DOUBLE_ELIMINATION: KeyCode = KeyCode::Char('k');
+pub const ORGANIZE_SWISS: KeyCode = KeyCode::Char('w');
 pub const REGISTER_TO_TOURNAMENT: KeyCode = KeyCode::Char('R');
 
 pub const GO_TO_TEAM: KeyCode = KeyCode::Backspace;
@@ -89,6 +92,9 @@ pub mod game {
     pub const PITCH_VIEW: KeyCode = KeyCode::Char('v');
     pub const PLAYER_STATUS_VIEW: KeyCode = KeyCode::Char('s');
     pub const CHALLENGE_TEAM: KeyCode = KeyCode::Char('C');
+    pub const TIMEOUT: KeyCode = KeyCode::Char('T');
+    pub const CHANGE_TACTIC: KeyCode = KeyCode::Char('t');
+    pub const SUBSTITUTION: KeyCode = KeyCode::Char('b');
 }
 
 pub mod player {
@@ -108,6 +114,11 @@ pub mod team {
     pub const TRAINING_FOCUS: KeyCode = KeyCode::Char('T');
     pub const AUTO_ASSIGN: KeyCode = KeyCode::Char('a');
     pub const SET_TACTIC: KeyCode = KeyCode::Char('t');
+    pub const SET_DEFENSIVE_SCHEME: KeyCode = KeyCode::Char('f');
+    pub const NEW_CUSTOM_TACTIC: KeyCode = KeyCode::Char('N');
+    pub const DELETE_CUSTOM_TACTIC: KeyCode = KeyCode::Char('X');
+    pub const USE_CUSTOM_TACTIC: KeyCode = KeyCode::Char('Y');
+    pub const RENAME_CUSTOM_TACTIC: KeyCode = KeyCode::Char('E');
 
     pub const TOGGLE_ACCEPT_LOCAL_CHALLENGES: KeyCode = KeyCode::Char('l');
     pub const TOGGLE_ACCEPT_NETWORK_CHALLENGES: KeyCode = KeyCode::Char('n');
@@ -18,7 +18,7 @@ use super::{
 #[cfg(feature = "audio")]
 use crate::audio::music_player::MusicPlayer;
 use crate::core::world::World;
-use crate::network::types::ChatHistoryEntry;
+use crate::network::types::{ChatChannel, ChatHistoryEntry};
 use crate::types::Tick;
 use crate::types::{AppResult, SystemTimeTick};
 use crate::ui::space_cove_panel::SpaceCovePanel;
@@ -228,15 +228,29 @@ impl UiScreen {
         peer_id: PeerId,
         author: impl Into<String>,
         message: impl Into<String>,
+        channel: ChatChannel,
     ) {
-        self.swarm_panel
-            .push_chat_event(timestamp, peer_id, author.into(), message.into());
+        self.swarm_panel.push_chat_event(
+            timestamp,
+            peer_id,
+            author.into(),
+            message.into(),
+            channel,
+        );
+    }
+
+    pub fn remove_chat_events_from(&mut self, peer_id: &PeerId) {
+        self.swarm_panel.remove_chat_events_from(peer_id);
     }
 
     pub fn push_chat_error_event(&mut self, timestamp: Tick, error: Error) {
         self.swarm_panel.push_chat_error_event(timestamp, error);
     }
 
+    pub fn push_direct_message(&mut self, peer_id: PeerId) {
+        self.swarm_panel.push_direct_message(peer_id);
+    }
+
     pub fn push_chat_history(&mut self, chat_history: &[ChatHistoryEntry]) {
         self.swarm_panel.push_chat_history(chat_history);
     }
@@ -602,13 +616,15 @@ impl UiScreen {
                             .name
                             .clone()
                     } else if tab == UiTab::Swarm {
+                        let unread_messages = self.swarm_panel.unread_chat_messages()
+                            + self.swarm_panel.unread_direct_messages();
                         format!(
                             "{}{}",
                             tab,
-                            if self.swarm_panel.unread_chat_messages() > 99 {
+                            if unread_messages > 99 {
                                 " (99+)".to_string()
-                            } else if self.swarm_panel.unread_chat_messages() > 0 {
-                                format!(" ({})", self.swarm_panel.unread_chat_messages())
+                            } else if unread_messages > 0 {
+                                format!(" ({unread_messages})")
                             } else {
                                 "".to_string()
                             }
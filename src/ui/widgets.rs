@@ -889,11 +889,17 @@ pub fn render_spaceship_description(
         );
         frame.render_interactive_widget(reputation_span, split[2]);
 
+        let now = world.last_tick_short_interval;
         let game_record = format!(
-            "Local Elo {:.0}{}",
+            "Local rating {:.0}±{:.0}{}",
             team.local_game_rating.rating,
+            team.local_game_rating.deviation_at(now),
             if team.peer_id.is_some() || team.id == world.own_team_id {
-                format!("  Network Elo {:.0}", team.network_game_rating.rating)
+                format!(
+                    "  Network rating {:.0}±{:.0}",
+                    team.network_game_rating.rating,
+                    team.network_game_rating.deviation_at(now)
+                )
             } else {
                 String::new()
             }
@@ -1500,7 +1506,14 @@ pub fn render_player_description(
         HoverTextSpan::new(
             trait_span,
             player.special_trait.map_or_else(String::new, |t| t.description(player)),
-        )
+        ),
+        HoverTextSpan::new(
+            player.injury.map_or_else(
+                || Span::raw(""),
+                |injury| Span::styled(format!("  Injured ({})", injury.severity), UiStyle::ERROR),
+            ),
+            player.injury.map_or_else(String::new, |injury| format!("Injured pirates sit out games until they recover. A good doctor speeds up the recovery. (recovery left {})", injury.recovery_left.formatted())),
+        ),
     ]);
     frame.render_interactive_widget(line, header_body_stats[1]);
 
@@ -1807,6 +1820,16 @@ fn format_player_stats(player: &'_ Player) -> Vec<Line<'_>> {
             stats.attempted_3pt as f32 / games_played
         ),
     )));
+    text.push(Line::from(format!(
+        "{:<12} {:>9} {:>9}",
+        "Free throws",
+        format!("{}/{}", stats.made_ft, stats.attempted_ft),
+        format!(
+            "{:3.1}/{:3.1}",
+            stats.made_ft as f32 / games_played,
+            stats.attempted_ft as f32 / games_played
+        ),
+    )));
     text.push(Line::from(format!(
         "{:<12} {:>9} {:>9.1}",
         "Points",
@@ -1850,6 +1873,12 @@ fn format_player_stats(player: &'_ Player) -> Vec<Line<'_>> {
         stats.turnovers,
         stats.turnovers as f32 / games_played
     )));
+    text.push(Line::from(format!(
+        "{:<12} {:>9} {:>9.1}",
+        "Fouls",
+        stats.fouls,
+        stats.fouls as f32 / games_played
+    )));
 
     text
 }
@@ -2129,12 +2158,13 @@ mod tests {
     use super::{AppResult, BARS_LENGTH};
     use crate::{
         core::{resources::Resource, spaceship::SpaceshipPrefab, team::Team},
+        types::{SystemTimeTick, Tick},
         ui::widgets::get_storage_lengths,
     };
 
     #[test]
     fn test_storage_spans() -> AppResult<()> {
-        let mut team = Team::random(None);
+        let mut team = Team::random(None, Tick::now());
         team.spaceship = SpaceshipPrefab::Bresci.spaceship();
 
         let bars_length = BARS_LENGTH;
@@ -0,0 +1,206 @@
+// Calibration harness for the game engine: a fixed seeded sample of games is simulated for every
+// tactic and the per-tactic averages are compared against the golden baselines, failing when a
+// statistic leaves its tolerance band. When a balance change is intended, regenerate them with
+// cargo test --test game_calibration regenerate_calibration_baselines -- --ignored
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use rand::{RngExt, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use rebels::core::{
+        GamePosition, Player, Team, TickInterval, DEFAULT_PLANET_ID, MAX_GAME_POSITION,
+        MAX_PLAYERS_PER_GAME,
+    };
+    use rebels::game_engine::game::Game;
+    use rebels::game_engine::tactic::Tactic;
+    use rebels::game_engine::types::TeamInGame;
+    use rebels::types::{AppResult, GameId, PlayerId, PlayerMap, TeamId, Tick};
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+    use strum::IntoEnumIterator;
+
+    const BASELINES_PATH: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/game_engine_data/calibration_baselines.json"
+    );
+    const REGENERATE_COMMAND: &str =
+        "cargo test --test game_calibration regenerate_calibration_baselines -- --ignored";
+    const SEED: u64 = 0;
+    const GAMES_PER_TACTIC: usize = 100;
+    // The game rng is seeded with the starting tick, so it must not depend on the current time.
+    const STARTING_AT: Tick = 1_700_000_000_000;
+
+    // Averages per team per game.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    struct TacticAverages {
+        points: f32,
+        attempted_3pt: f32,
+        rebounds: f32,
+        brawls: f32,
+    }
+
+    impl TacticAverages {
+        // Name, value and relative tolerance of every calibrated statistic.
+        fn bands(&self) -> [(&'static str, f32, f32); 4] {
+            [
+                ("points", self.points, 0.03),
+                ("3PA", self.attempted_3pt, 0.06),
+                ("rebounds", self.rebounds, 0.04),
+                ("brawls", self.brawls, 0.15),
+            ]
+        }
+    }
+
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    struct CalibrationBaselines {
+        seed: u64,
+        games_per_tactic: usize,
+        tactics: BTreeMap<String, TacticAverages>,
+    }
+
+    fn generate_team_in_game(tactic: Tactic, rng: &mut ChaCha8Rng) -> TeamInGame {
+        let team = Team {
+            id: TeamId::from_u128(rng.random()),
+            game_tactic: tactic,
+            ..Default::default()
+        };
+
+        let mut player_ids = vec![];
+        let mut players = PlayerMap::new();
+        for _ in 0..MAX_PLAYERS_PER_GAME {
+            let mut player = Player::default().randomize(Some(rng));
+            player.id = PlayerId::from_u128(rng.random());
+            player_ids.push(player.id);
+            players.insert(player.id, player);
+        }
+
+        let mut team_in_game = TeamInGame::new(&team, players);
+
+        // Positions would otherwise follow the hash map order, which changes at every run.
+        for (idx, player_id) in player_ids.iter().enumerate() {
+            let stats = team_in_game
+                .stats
+                .get_mut(player_id)
+                .expect("Player should have stats");
+            stats.position = (idx < MAX_GAME_POSITION as usize).then_some(idx as GamePosition);
+        }
+        team_in_game.initial_tiredness = player_ids
+            .iter()
+            .map(|id| team_in_game.players[id].tiredness)
+            .collect();
+        team_in_game.initial_morale = player_ids
+            .iter()
+            .map(|id| team_in_game.players[id].morale)
+            .collect();
+        team_in_game.initial_positions = player_ids;
+
+        team_in_game
+    }
+
+    fn simulate_tactic(tactic: Tactic) -> TacticAverages {
+        let rng = &mut ChaCha8Rng::seed_from_u64(SEED + tactic as u64);
+        let mut totals = TacticAverages::default();
+
+        for _ in 0..GAMES_PER_TACTIC {
+            let home_team_in_game = generate_team_in_game(tactic, rng);
+            let away_team_in_game = generate_team_in_game(tactic, rng);
+            let mut game = Game::new(
+                GameId::from_u128(rng.random()),
+                home_team_in_game,
+                away_team_in_game,
+                STARTING_AT,
+                *DEFAULT_PLANET_ID,
+                0,
+                "Calibration arena",
+                None,
+            );
+
+            let mut current_tick = STARTING_AT;
+            while !game.has_ended() {
+                if game.has_started(current_tick) {
+                    game.tick(current_tick);
+                }
+                current_tick += TickInterval::SHORT;
+            }
+
+            for team in [&game.home_team_in_game, &game.away_team_in_game] {
+                for stats in team.stats.values() {
+                    totals.points += stats.points as f32;
+                    totals.attempted_3pt += stats.attempted_3pt as f32;
+                    totals.rebounds += (stats.offensive_rebounds + stats.defensive_rebounds) as f32;
+                    totals.brawls += stats.brawls.iter().sum::<u16>() as f32;
+                }
+            }
+        }
+
+        let team_games = (2 * GAMES_PER_TACTIC) as f32;
+        TacticAverages {
+            points: totals.points / team_games,
+            attempted_3pt: totals.attempted_3pt / team_games,
+            rebounds: totals.rebounds / team_games,
+            brawls: totals.brawls / team_games,
+        }
+    }
+
+    fn simulate_baselines() -> CalibrationBaselines {
+        CalibrationBaselines {
+            seed: SEED,
+            games_per_tactic: GAMES_PER_TACTIC,
+            tactics: Tactic::iter()
+                .map(|tactic| (tactic.to_string(), simulate_tactic(tactic)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_engine_calibration() -> AppResult<()> {
+        let baselines: CalibrationBaselines =
+            serde_json::from_str(&std::fs::read_to_string(BASELINES_PATH)?)?;
+        if baselines.seed != SEED || baselines.games_per_tactic != GAMES_PER_TACTIC {
+            return Err(anyhow!(
+                "Baselines were generated with a different sample, regenerate them with `{REGENERATE_COMMAND}`"
+            ));
+        }
+
+        let current = simulate_baselines();
+        let mut regressions = vec![];
+        for (tactic, averages) in current.tactics.iter() {
+            let baseline = baselines
+                .tactics
+                .get(tactic)
+                .ok_or(anyhow!("Missing baseline for {tactic}"))?;
+
+            for ((name, value, tolerance), (_, expected, _)) in
+                averages.bands().into_iter().zip(baseline.bands())
+            {
+                println!("{tactic} {name}: {value:.2} (baseline {expected:.2})");
+                if (value - expected).abs() > tolerance * expected.abs() {
+                    regressions.push(format!(
+                        "{tactic} {name}: {value:.2} is outside {expected:.2} ± {:.0}%",
+                        100.0 * tolerance
+                    ));
+                }
+            }
+        }
+
+        assert!(
+            regressions.is_empty(),
+            "Game engine calibration regressed:\n{}\nIf the balance change is intended, regenerate the baselines with `{REGENERATE_COMMAND}`",
+            regressions.join("\n")
+        );
+
+        Ok(())
+    }
+
+    #[ignore]
+    #[test]
+    fn regenerate_calibration_baselines() -> AppResult<()> {
+        let baselines = simulate_baselines();
+        std::fs::write(
+            BASELINES_PATH,
+            serde_json::to_string_pretty(&baselines)? + "\n",
+        )?;
+
+        Ok(())
+    }
+}
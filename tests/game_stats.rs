@@ -5,7 +5,9 @@ mod tests {
     use rebels::core::{Player, Rated, Skill, Team, TickInterval, MAX_PLAYERS_PER_GAME};
     use rebels::game_engine::action::{ActionOutput, ActionSituation, Advantage};
     use rebels::game_engine::game::Game;
-    use rebels::game_engine::tactic::Tactic;
+    use rebels::game_engine::tactic::{
+        CustomTactic, DefensiveScheme, Tactic, MAX_TIREDNESS_BUDGET, MIN_TIREDNESS_BUDGET,
+    };
     use rebels::game_engine::types::{GameStats, GameStatsMap, Possession, TeamInGame};
     use rebels::types::{AppResult, PlayerId, PlayerMap, SystemTimeTick, TeamId, Tick};
     use std::collections::{BTreeMap, HashMap};
@@ -182,11 +184,49 @@ mod tests {
         )
     }
 
+    fn simulate_game_sample(
+        home_team_in_game: TeamInGame,
+        away_team_in_game: TeamInGame,
+        rating_diff: f32,
+    ) -> GameSample {
+        let mut current_tick = Tick::now();
+        let mut game = Game::test(home_team_in_game, away_team_in_game);
+
+        // Simulate until finished
+        while !game.has_ended() {
+            if game.has_started(current_tick) {
+                game.tick(current_tick);
+            }
+            current_tick += TickInterval::SHORT;
+        }
+
+        let winner = match game.winner {
+            Some(id) if id == game.home_team_in_game.team_id => Some(Possession::Home),
+            Some(id) if id == game.away_team_in_game.team_id => Some(Possession::Away),
+            None => None,
+            _ => unreachable!(),
+        };
+
+        let action_outputs = game.action_results;
+
+        GameSample {
+            rating_diff,
+            winner,
+            home_stats: game.home_team_in_game.stats,
+            away_stats: game.away_team_in_game.stats,
+            home_players: game.home_team_in_game.players,
+            away_players: game.away_team_in_game.players,
+            action_outputs,
+        }
+    }
+
     fn get_simulated_game_samples(
         n_games: usize,
         max_delta_rating: f32,
         home_tactic: Tactic,
         away_tactic: Tactic,
+        home_scheme: DefensiveScheme,
+        away_scheme: DefensiveScheme,
         with_fixed_stamina: Option<Skill>,
     ) -> Vec<GameSample> {
         let mut samples = Vec::with_capacity(n_games);
@@ -235,36 +275,14 @@ mod tests {
 
             home_team_in_game.tactic = home_tactic;
             away_team_in_game.tactic = away_tactic;
+            home_team_in_game.defensive_scheme = home_scheme;
+            away_team_in_game.defensive_scheme = away_scheme;
 
-            let mut current_tick = Tick::now();
-            let mut game = Game::test(home_team_in_game, away_team_in_game);
-
-            // Simulate until finished
-            while !game.has_ended() {
-                if game.has_started(current_tick) {
-                    game.tick(current_tick);
-                }
-                current_tick += TickInterval::SHORT;
-            }
-
-            let winner = match game.winner {
-                Some(id) if id == game.home_team_in_game.team_id => Some(Possession::Home),
-                Some(id) if id == game.away_team_in_game.team_id => Some(Possession::Away),
-                None => None,
-                _ => unreachable!(),
-            };
-
-            let action_outputs = game.action_results;
-
-            samples.push(GameSample {
+            samples.push(simulate_game_sample(
+                home_team_in_game,
+                away_team_in_game,
                 rating_diff,
-                winner,
-                home_stats: game.home_team_in_game.stats,
-                away_stats: game.away_team_in_game.stats,
-                home_players: game.home_team_in_game.players,
-                away_players: game.away_team_in_game.players,
-                action_outputs,
-            })
+            ));
         }
 
         samples
@@ -690,6 +708,8 @@ mod tests {
                     max_delta_rating,
                     home_tactic,
                     away_tactic,
+                    DefensiveScheme::default(),
+                    DefensiveScheme::default(),
                     with_fixed_stamina,
                 );
                 let bins = process_stats(&samples, BIN_SIZE);
@@ -708,6 +728,200 @@ mod tests {
 
         Ok(())
     }
+
+    // Home team averages over identical teams: win%, points, turnovers, steals
+    // suffered and the away team (defending) tiredness.
+    fn scheme_matchup_summary(samples: &[GameSample]) -> [f32; 5] {
+        let n = samples.len() as f32;
+        let wins = samples
+            .iter()
+            .filter(|sample| sample.winner == Some(Possession::Home))
+            .count() as f32;
+        let average =
+            |selector: &dyn Fn(&GameSample) -> f32| samples.iter().map(selector).sum::<f32>() / n;
+
+        [
+            100.0 * wins / n,
+            average(&|sample| {
+                team_stat_sum(&sample.home_stats, &sample.home_players, |s, _| {
+                    s.points as f32
+                })
+            }),
+            average(&|sample| {
+                team_stat_sum(&sample.home_stats, &sample.home_players, |s, _| {
+                    s.turnovers as f32
+                })
+            }),
+            average(&|sample| {
+                team_stat_sum(&sample.away_stats, &sample.away_players, |s, _| {
+                    s.steals as f32
+                })
+            }),
+            average(&|sample| {
+                team_stat_sum(&sample.away_stats, &sample.away_players, |_, p| p.tiredness)
+                    / sample.away_players.len() as f32
+            }),
+        ]
+    }
+
+    fn print_scheme_matrix<R: std::fmt::Display>(
+        title: &str,
+        rows: &[R],
+        results: &[Vec<[f32; 5]>],
+        stat_idx: usize,
+    ) {
+        println!("{title}");
+        print!("{:>20}", "");
+        for scheme in DefensiveScheme::iter() {
+            print!("{:>20}", scheme.to_string());
+        }
+        println!();
+        for (row, row_results) in rows.iter().zip(results.iter()) {
+            print!("{:>20}", row.to_string());
+            for summary in row_results {
+                print!("{:>20.1}", summary[stat_idx]);
+            }
+            println!();
+        }
+        println!();
+    }
+
+    #[ignore]
+    #[test]
+    fn test_defensive_scheme_matchups() -> AppResult<()> {
+        const N: usize = 2_000;
+        const STATS: [&str; 5] = [
+            "Home win%",
+            "Home points",
+            "Home turnovers",
+            "Away steals",
+            "Away tiredness",
+        ];
+
+        // Offensive tactics (home, man-to-man) against defensive schemes (away, balanced).
+        let tactics = Tactic::iter().collect_vec();
+        let tactic_results = tactics
+            .par_iter()
+            .map(|&tactic| {
+                DefensiveScheme::iter()
+                    .map(|scheme| {
+                        let samples = get_simulated_game_samples(
+                            N,
+                            0.0,
+                            tactic,
+                            Tactic::Balanced,
+                            DefensiveScheme::ManToMan,
+                            scheme,
+                            None,
+                        );
+                        scheme_matchup_summary(&samples)
+                    })
+                    .collect_vec()
+            })
+            .collect::<Vec<_>>();
+
+        // Defensive schemes against each other, both teams playing balanced.
+        let schemes = DefensiveScheme::iter().collect_vec();
+        let scheme_results = schemes
+            .par_iter()
+            .map(|&home_scheme| {
+                DefensiveScheme::iter()
+                    .map(|away_scheme| {
+                        let samples = get_simulated_game_samples(
+                            N,
+                            0.0,
+                            Tactic::Balanced,
+                            Tactic::Balanced,
+                            home_scheme,
+                            away_scheme,
+                            None,
+                        );
+                        scheme_matchup_summary(&samples)
+                    })
+                    .collect_vec()
+            })
+            .collect::<Vec<_>>();
+
+        println!("Offensive tactic (home) vs defensive scheme (away), {N} games per matchup\n");
+        for (stat_idx, stat) in STATS.iter().enumerate() {
+            print_scheme_matrix(stat, &tactics, &tactic_results, stat_idx);
+        }
+
+        println!("Defensive scheme (home) vs defensive scheme (away), {N} games per matchup\n");
+        print_scheme_matrix(STATS[0], &schemes, &scheme_results, 0);
+
+        Ok(())
+    }
+
+    #[ignore]
+    #[test]
+    fn test_custom_tactic_tiredness_budgets() -> AppResult<()> {
+        const N: usize = 4_000;
+        let neutral = CustomTactic::new("Neutral", Tactic::Balanced).tiredness_budget;
+        let budgets = [MIN_TIREDNESS_BUDGET, neutral, MAX_TIREDNESS_BUDGET];
+
+        // A balanced custom tactic (home) against the balanced tactic (away).
+        let results = budgets
+            .par_iter()
+            .map(|&budget| {
+                let mut custom_tactic = CustomTactic::new("Budget", Tactic::Balanced);
+                custom_tactic.tiredness_budget = budget;
+
+                let samples = (0..N)
+                    .map(|_| {
+                        let (mut home_team_in_game, away_team_in_game) =
+                            generate_identical_team_in_game(0.0, None);
+                        home_team_in_game.custom_tactic = Some(custom_tactic.clone());
+                        simulate_game_sample(home_team_in_game, away_team_in_game, 0.0)
+                    })
+                    .collect_vec();
+
+                let n = samples.len() as f32;
+                let wins = samples
+                    .iter()
+                    .filter(|sample| sample.winner == Some(Possession::Home))
+                    .count() as f32;
+                let average = |selector: &dyn Fn(&GameSample) -> f32| {
+                    samples.iter().map(selector).sum::<f32>() / n
+                };
+
+                [
+                    100.0 * wins / n,
+                    average(&|sample| {
+                        team_stat_sum(&sample.home_stats, &sample.home_players, |s, _| {
+                            s.points as f32
+                        })
+                    }),
+                    average(&|sample| {
+                        team_stat_sum(&sample.away_stats, &sample.away_players, |s, _| {
+                            s.points as f32
+                        })
+                    }),
+                    average(&|sample| {
+                        team_stat_sum(&sample.home_stats, &sample.home_players, |_, p| p.tiredness)
+                            / sample.home_players.len() as f32
+                    }),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        println!("Balanced custom tactic (home) vs balanced (away), {N} games per budget\n");
+        println!(
+            "{:>20}{:>20}{:>20}{:>20}{:>20}",
+            "Tiredness budget", "Home win%", "Home points", "Away points", "Home tiredness"
+        );
+        for (budget, summary) in budgets.iter().zip(results.iter()) {
+            print!("{:>20}", format!("{budget}/{MAX_TIREDNESS_BUDGET}"));
+            for value in summary {
+                print!("{:>20.1}", value);
+            }
+            println!();
+        }
+
+        Ok(())
+    }
 }
 
 //cargo test test_multiple_games -- --nocapture --ignored > tests/game_engine_data/game_stats_v1.5.x.data
+//cargo test test_defensive_scheme_matchups -- --nocapture --ignored > tests/game_engine_data/defensive_schemes_v1.6.x.data
+//cargo test test_custom_tactic_tiredness_budgets -- --nocapture --ignored > tests/game_engine_data/custom_tactic_budgets_v1.6.x.data
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use rebels::app::App;
+    use rebels::args::AppArgs;
+    use rebels::core::{
+        Clock, SpaceshipUpgradeTarget, TickInterval, Upgrade, UpgradeableElement, World, DAYS,
+        HOURS,
+    };
+    use rebels::game_engine::replay::GameReplay;
+    use rebels::types::{AppResult, SystemTimeTick, Tick};
+
+    fn app_with_manual_clock() -> AppResult<(App, Clock)> {
+        // Start on a day boundary so that all tick intervals are aligned.
+        let clock = Clock::manual(Tick::now() / DAYS * DAYS);
+        let mut app = App::new(AppArgs::test())?;
+        app.world = World::new(Some(0)).with_clock(clock.clone());
+        app.new_world();
+
+        let home_planet_id = *app
+            .world
+            .planets
+            .keys()
+            .next()
+            .expect("There should be at least one planet");
+        app.world.own_team_id = app.world.generate_random_team(
+            &mut ChaCha8Rng::seed_from_u64(0),
+            home_planet_id,
+            "own team".into(),
+            "ship_name".into(),
+            None,
+        )?;
+
+        Ok((app, clock))
+    }
+
+    #[test]
+    fn test_manual_clock_drives_world() -> AppResult<()> {
+        let (mut app, clock) = app_with_manual_clock()?;
+        let start = clock.now();
+        assert!(!app.world.is_simulating());
+
+        app.fast_forward(HOURS)?;
+
+        assert!(!app.world.is_simulating());
+        assert!(app.world.last_tick_short_interval + TickInterval::SHORT >= start + HOURS);
+        assert!(app.world.last_tick_medium_interval + TickInterval::MEDIUM > start + HOURS);
+        assert!(
+            !app.world.games.is_empty() || !app.world.past_games.is_empty(),
+            "Local teams should have started playing games"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_days_of_league_life() -> AppResult<()> {
+        let (mut app, clock) = app_with_manual_clock()?;
+        let start = clock.now();
+
+        let own_player_id = app.world.get_own_team()?.player_ids[0];
+        let age_before = app.world.players[&own_player_id].info.age;
+
+        let days = 3;
+        app.fast_forward(days * DAYS)?;
+
+        assert_eq!(
+            app.world.last_tick_long_interval,
+            start + days * TickInterval::LONG
+        );
+        assert!(!app.world.past_games.is_empty());
+
+        // Unless the pirate retired, it aged once per long tick.
+        let player = &app.world.players[&own_player_id];
+        if player.team.is_some() {
+            assert!(player.info.age > age_before);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_spaceship_upgrade_completes_in_simulation() -> AppResult<()> {
+        let (mut app, clock) = app_with_manual_clock()?;
+
+        let target = SpaceshipUpgradeTarget::Repairs { amount: 10 };
+        let upgrade = Upgrade::new(target, 1.0, clock.now());
+        let duration = target.upgrade_duration();
+        app.world.get_own_team_mut()?.spaceship.pending_upgrade = Some(upgrade);
+
+        app.fast_forward(duration / 2)?;
+        assert!(app
+            .world
+            .get_own_team()?
+            .spaceship
+            .pending_upgrade
+            .is_some());
+
+        app.fast_forward(duration / 2 + 2 * TickInterval::SHORT)?;
+        assert!(app
+            .world
+            .get_own_team()?
+            .spaceship
+            .pending_upgrade
+            .is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_world_games_can_be_replayed() -> AppResult<()> {
+        let (mut app, _) = app_with_manual_clock()?;
+        app.fast_forward(3 * HOURS)?;
+
+        assert!(!app.world.recently_finished_games.is_empty());
+        for game in app.world.recently_finished_games.values() {
+            let replay = GameReplay::from_game(game)?;
+            assert_eq!(replay.verify()?, game.action_results.len());
+        }
+        Ok(())
+    }
+}